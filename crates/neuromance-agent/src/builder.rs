@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
 
+use thiserror::Error;
 use uuid::Uuid;
 
 use neuromance::Core;
@@ -22,6 +24,55 @@ struct BuilderSkills {
     body_budget: usize,
 }
 
+/// Errors from rendering a template passed to
+/// [`AgentBuilder::with_system_template`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TemplateError {
+    /// A `{{name}}` placeholder has no matching variable.
+    #[error("system prompt template references undefined variable '{0}'")]
+    MissingVariable(String),
+
+    /// A `{{` opens a placeholder that is never closed.
+    #[error("system prompt template has an unterminated placeholder at byte {0}")]
+    Unterminated(usize),
+}
+
+/// Substitutes `{{name}}` placeholders in `template` from `vars`.
+///
+/// Whitespace inside the braces is ignored. In strict mode an undefined
+/// variable or unterminated placeholder is an error; otherwise it is left in
+/// the output verbatim.
+fn render_template(
+    template: &str,
+    vars: &HashMap<String, String>,
+    strict: bool,
+) -> Result<String, TemplateError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 2..];
+        let Some(close) = after.find("}}") else {
+            if strict {
+                return Err(TemplateError::Unterminated(
+                    template.len() - rest.len() + open,
+                ));
+            }
+            out.push_str(&rest[open..]);
+            return Ok(out);
+        };
+        let name = after[..close].trim();
+        match vars.get(name) {
+            Some(value) => out.push_str(value),
+            None if strict => return Err(TemplateError::MissingVariable(name.to_string())),
+            None => out.push_str(&rest[open..open + 2 + close + 2]),
+        }
+        rest = &after[close + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
 /// Simple builder for creating agents with common configuration
 ///
 /// # Example
@@ -50,6 +101,7 @@ pub struct AgentBuilder<C: LLMClient> {
     user_prompt: Option<String>,
     tool_choice: ToolChoice,
     skills: Option<BuilderSkills>,
+    strict_templates: bool,
 }
 
 impl<C: LLMClient> AgentBuilder<C> {
@@ -66,6 +118,7 @@ impl<C: LLMClient> AgentBuilder<C> {
             user_prompt: None,
             tool_choice: ToolChoice::Auto,
             skills: None,
+            strict_templates: true,
         }
     }
 
@@ -79,6 +132,38 @@ impl<C: LLMClient> AgentBuilder<C> {
         self
     }
 
+    /// Set the system prompt from a template, substituting `{{name}}`
+    /// placeholders from `vars`
+    ///
+    /// # Arguments
+    /// * `template` - System prompt text with `{{name}}` placeholders
+    /// * `vars` - Values for the placeholders
+    ///
+    /// # Errors
+    /// In strict mode (the default, see [`Self::strict_templates`]), returns a
+    /// [`TemplateError`] if a placeholder names a variable missing from `vars`
+    /// or is never closed.
+    pub fn with_system_template(
+        mut self,
+        template: &str,
+        vars: &HashMap<String, String>,
+    ) -> Result<Self, TemplateError> {
+        let prompt = render_template(template, vars, self.strict_templates)?;
+        self.system_prompt = Some(prompt);
+        Ok(self)
+    }
+
+    /// Choose whether [`Self::with_system_template`] rejects undefined or
+    /// unterminated placeholders (`true`, the default) or leaves them verbatim
+    ///
+    /// # Arguments
+    /// * `strict` - Whether template errors are reported
+    #[must_use]
+    pub const fn strict_templates(mut self, strict: bool) -> Self {
+        self.strict_templates = strict;
+        self
+    }
+
     /// Set the user prompt for the agent
     ///
     /// # Arguments
@@ -109,6 +194,19 @@ impl<C: LLMClient> AgentBuilder<C> {
         self
     }
 
+    /// Stop the tool loop as soon as an assistant message contains `marker`
+    ///
+    /// The marker is stripped from the final response, and any tool calls in
+    /// the same message are not run.
+    ///
+    /// # Arguments
+    /// * `marker` - Text the model emits when it considers the task done
+    #[must_use]
+    pub fn with_completion_marker(mut self, marker: &str) -> Self {
+        self.core = self.core.with_completion_marker(marker);
+        self
+    }
+
     /// Enable automatic approval of all tools
     ///
     /// # Arguments
@@ -43,13 +43,19 @@ use neuromance_common::chat::{Message, MessageRole};
 use neuromance_common::client::ToolChoice;
 
 pub mod builder;
+pub mod stream;
 pub mod subagent;
+pub mod verdict;
 
 // --- Agent core ---
-pub use builder::AgentBuilder;
+pub use builder::{AgentBuilder, TemplateError};
+pub use stream::AgentEvent;
 
 // --- Subagents ---
-pub use subagent::{FanoutVote, LocalSubagent, Subagent, SubagentError, SubagentTool};
+pub use subagent::{FanoutVote, LocalSubagent, Subagent, SubagentError, SubagentTool, VerifyRetry};
+
+// --- Verification ---
+pub use verdict::{VERDICT_INSTRUCTIONS, Verdict};
 
 use neuromance_common::delegation::{self, DelegationContext};
 
@@ -186,9 +192,15 @@ impl<C: LLMClient + Send + Sync> Agent<C> {
             self.state.stats.tokens_used += tokens as usize;
             self.state.stats.successful_tool_calls += run_stats.successful_tool_calls as usize;
             self.state.stats.failed_tool_calls += run_stats.failed_tool_calls as usize;
+            self.state.stats.tool_call_count +=
+                (run_stats.successful_tool_calls + run_stats.failed_tool_calls) as usize;
         }
 
-        let duration_ms = u64::try_from(exec_start.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let elapsed = exec_start.elapsed();
+        self.state.stats.total_duration += elapsed;
+        self.state.stats.turn_count = self.state.stats.turn_count.saturating_add(1);
+
+        let duration_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
         info!(
             duration_ms,
             turns = metrics.total_requests,
@@ -222,22 +234,33 @@ impl<C: LLMClient + Send + Sync> Agent<C> {
     ) -> Result<(AgentResponse, Vec<Message>), CoreError> {
         let exec_start = Instant::now();
         info!("agent executing");
-        self.core.tool_choice = self.tool_choice.clone();
+        let messages = self.prepare_messages(messages)?;
 
-        // Read the enclosing delegation context (set by a parent agent's scope,
-        // or the runtime's `scope_task`). A root run sees no parent.
-        let enclosing = delegation::current();
-        let parent_conversation_id = enclosing.conversation_id;
-        let task_id = enclosing.task_id;
+        // Record the enclosing delegation lineage; a root run sees no parent.
+        let child_ctx = self.delegation_scope();
         {
             let span = tracing::Span::current();
-            if let Some(parent) = parent_conversation_id {
+            if let Some(parent) = delegation::current().conversation_id {
                 span.record("parent_conversation_id", tracing::field::display(parent));
             }
-            if let Some(task) = task_id {
+            if let Some(task) = child_ctx.task_id {
                 span.record("task_id", tracing::field::display(task));
             }
         }
+        let (messages, run_stats) =
+            delegation::scope(child_ctx, self.core.chat_with_tool_loop(messages, cancel)).await?;
+
+        let response = self.finish_run(&messages, &run_stats, exec_start)?;
+        Ok((response, messages))
+    }
+
+    /// Validate the history a run starts from (or the agent's own messages
+    /// when `None`) and fold the agent's context into its system prompt.
+    fn prepare_messages(
+        &mut self,
+        messages: Option<Vec<Message>>,
+    ) -> Result<Vec<Message>, CoreError> {
+        self.core.tool_choice = self.tool_choice.clone();
         let mut messages = messages.unwrap_or_else(|| self.messages.clone());
 
         if messages.len() < 2 {
@@ -267,21 +290,33 @@ impl<C: LLMClient + Send + Sync> Agent<C> {
             messages[0].content.push_str(&ctx);
         }
 
-        // Scope this agent's conversation as the parent for any subagent it
-        // delegates to during the run, inheriting the same runtime task id.
-        let child_ctx = DelegationContext {
+        Ok(messages)
+    }
+
+    /// Delegation scope for a run: this agent's conversation becomes the parent
+    /// of any subagent it delegates to, inheriting the enclosing task id (set by
+    /// a parent agent's scope, or the runtime's `scope_task`).
+    fn delegation_scope(&self) -> DelegationContext {
+        DelegationContext {
             conversation_id: Some(self.conversation_id),
-            task_id,
+            task_id: delegation::current().task_id,
             // Message-level provenance is filled in per tool call by Core when
             // this agent delegates; a fresh scope starts without it.
             parent_message_id: None,
             parent_tool_call_id: None,
-        };
-        let (messages, run_stats) =
-            delegation::scope(child_ctx, self.core.chat_with_tool_loop(messages, cancel)).await?;
+        }
+    }
 
+    /// Fold a finished run into the agent's state and build its response from
+    /// the last assistant message.
+    fn finish_run(
+        &mut self,
+        messages: &[Message],
+        run_stats: &RunStats,
+        exec_start: Instant,
+    ) -> Result<AgentResponse, CoreError> {
         self.state.stats.total_messages += messages.len();
-        self.record_run_stats(&run_stats, exec_start);
+        self.record_run_stats(run_stats, exec_start);
 
         let content = messages
             .iter()
@@ -299,7 +334,7 @@ impl<C: LLMClient + Send + Sync> Agent<C> {
 
         let response = AgentResponse {
             content,
-            reasoning: None,
+            reasoning: run_reasoning(messages),
             tool_responses,
         };
 
@@ -312,9 +347,27 @@ impl<C: LLMClient + Send + Sync> Agent<C> {
             .conversation_history
             .push((AgentMessage::UserInput(user_content), response.clone()));
 
-        Ok((response, messages))
+        Ok(response)
     }
 }
 
+/// Reasoning from the assistant turns of the latest run (those after the last
+/// user message), joined in order so interleaved thinking between tool calls
+/// reads as one trace.
+fn run_reasoning(messages: &[Message]) -> Option<String> {
+    let run_start = messages
+        .iter()
+        .rposition(|m| m.role == MessageRole::User)
+        .map_or(0, |i| i + 1);
+    let parts: Vec<&str> = messages[run_start..]
+        .iter()
+        .filter(|m| m.role == MessageRole::Assistant)
+        .filter_map(|m| m.reasoning.as_ref())
+        .map(|r| r.content.as_str())
+        .filter(|c| !c.is_empty())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("\n\n"))
+}
+
 #[cfg(test)]
 mod tests;
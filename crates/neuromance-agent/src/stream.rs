@@ -0,0 +1,109 @@
+//! Streaming agent execution.
+//!
+//! [`Agent::execute_stream`] runs the same tool loop as [`Agent::execute`] but
+//! yields [`AgentEvent`]s as they happen, so a UI can render tokens and tool
+//! activity live instead of waiting for the whole run.
+
+use std::time::Instant;
+
+use async_stream::try_stream;
+use futures::{Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
+
+use neuromance::error::CoreError;
+use neuromance::{CoreEvent, RunStats};
+use neuromance_client::LLMClient;
+use neuromance_common::agents::AgentResponse;
+use neuromance_common::chat::Message;
+use neuromance_common::delegation;
+use neuromance_common::tools::{ToolApproval, ToolCall};
+
+use crate::Agent;
+
+/// Events yielded by [`Agent::execute_stream`].
+///
+/// Terminal event is always [`AgentEvent::Finished`].
+#[derive(Debug, Clone)]
+pub enum AgentEvent {
+    /// Streaming content chunk from the model.
+    ContentDelta(String),
+    /// Streaming reasoning chunk from a thinking model.
+    ReasoningDelta(String),
+    /// The model requested a tool call.
+    ToolCallStarted(ToolCall),
+    /// A tool finished executing.
+    ToolResult {
+        /// Name of the tool that was executed.
+        name: String,
+        /// Stringified result or error message.
+        output: String,
+        /// Whether execution succeeded.
+        success: bool,
+    },
+    /// The run finished; carries the response [`Agent::execute`] would return.
+    Finished(Box<AgentResponse>),
+}
+
+impl<C: LLMClient + Send + Sync> Agent<C> {
+    /// Like [`execute`](Self::execute), but streams [`AgentEvent`]s as the run
+    /// progresses.
+    ///
+    /// Content and reasoning deltas are only produced when the core is in
+    /// streaming mode; otherwise each turn's text arrives with its tool calls.
+    /// Agent state is updated just before [`AgentEvent::Finished`] is yielded,
+    /// so dropping the stream early leaves the stats untouched.
+    ///
+    /// # Errors
+    /// Returns [`CoreError::InvalidInput`] up front under the same conditions
+    /// as [`execute`](Self::execute). Errors from the run itself are yielded
+    /// by the stream.
+    pub fn execute_stream(
+        &mut self,
+        messages: Option<Vec<Message>>,
+        cancel: CancellationToken,
+    ) -> Result<impl Stream<Item = Result<AgentEvent, CoreError>> + Send + '_, CoreError> {
+        let exec_start = Instant::now();
+        let messages = self.prepare_messages(messages)?;
+        let child_ctx = self.delegation_scope();
+
+        Ok(try_stream! {
+            let mut run_stats = RunStats::default();
+            let mut history = None;
+            {
+                let mut events = Box::pin(self.core.run(messages, cancel));
+                // Each poll runs inside the agent's delegation scope, so
+                // subagents spawned by tool calls see this run as their parent.
+                while let Some(event) = delegation::scope(child_ctx.clone(), events.next()).await {
+                    let event = event?;
+                    run_stats.observe(&event);
+                    match event {
+                        CoreEvent::Delta(text) => yield AgentEvent::ContentDelta(text),
+                        CoreEvent::ReasoningDelta(text) => yield AgentEvent::ReasoningDelta(text),
+                        CoreEvent::ToolCallRequested(call) => {
+                            yield AgentEvent::ToolCallStarted(call);
+                        }
+                        CoreEvent::ToolResult { name, result, success } => {
+                            yield AgentEvent::ToolResult { name, output: result, success };
+                        }
+                        CoreEvent::ApprovalRequest { responder, .. } => {
+                            let _ = responder.send(ToolApproval::Denied(
+                                "No approval mechanism configured".into(),
+                            ));
+                        }
+                        CoreEvent::Completed(msgs) => {
+                            history = Some(msgs);
+                            break;
+                        }
+                        CoreEvent::Usage(_) | CoreEvent::Compaction { .. } => {}
+                    }
+                }
+            }
+
+            let messages = history.ok_or_else(|| {
+                CoreError::NoResponse("Stream ended without Completed event".to_string())
+            })?;
+            let response = self.finish_run(&messages, &run_stats, exec_start)?;
+            yield AgentEvent::Finished(Box::new(response));
+        })
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! [`FanoutVote`] is the worked example that sets the combinator style — a
 //! combinator is itself a [`Subagent`], so combinators nest and compose with
-//! leaves uniformly.
+//! leaves uniformly. [`VerifyRetry`] pairs a worker with a verifier and
+//! retries until the work passes.
 
 use std::fmt::Write as _;
 use std::sync::Arc;
@@ -10,11 +11,12 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use futures::future::join_all;
 use tokio_util::sync::CancellationToken;
-use tracing::warn;
+use tracing::{debug, warn};
 
 use neuromance_common::task::{Outcome, Task};
 
 use super::{Subagent, SubagentError};
+use crate::verdict::{VERDICT_INSTRUCTIONS, Verdict};
 
 /// Runs a task across several member subagents in parallel, then asks a judge
 /// subagent to pick or synthesize the best answer.
@@ -113,6 +115,115 @@ impl Subagent for FanoutVote {
     }
 }
 
+/// Runs a task through a worker subagent, then asks a verifier subagent for a
+/// [`Verdict`] on the answer, re-running the worker until the answer passes.
+///
+/// Each retry keeps the original task and adds the unmet criteria from the
+/// last verdict, so the worker knows what to fix. If no attempt passes within
+/// `max_attempts`, [`VerifyRetry::run`] returns [`SubagentError::Unverified`].
+pub struct VerifyRetry {
+    id: String,
+    worker: Arc<dyn Subagent>,
+    verifier: Arc<dyn Subagent>,
+    max_attempts: usize,
+    threshold: f32,
+}
+
+impl VerifyRetry {
+    /// Default confidence a passing verdict must reach.
+    pub const DEFAULT_THRESHOLD: f32 = 0.5;
+
+    /// Build a verify-retry loop running `worker` up to `max_attempts` times
+    /// (at least once), judged by `verifier`.
+    pub fn new(
+        id: impl Into<String>,
+        worker: Arc<dyn Subagent>,
+        verifier: Arc<dyn Subagent>,
+        max_attempts: usize,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            worker,
+            verifier,
+            max_attempts: max_attempts.max(1),
+            threshold: Self::DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// Set the confidence a passing verdict must reach (see [`Verdict::meets`]).
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+/// Build the worker's task for a retry: the original task plus what the
+/// verifier found missing last time.
+fn build_retry_task(original: &Task, unmet_criteria: &[String]) -> Task {
+    let mut task = original.clone();
+    task.instructions.push_str(
+        "\n\nA reviewer rejected your previous answer. Address these unmet requirements:\n",
+    );
+    for criterion in unmet_criteria {
+        let _ = writeln!(task.instructions, "- {criterion}");
+    }
+    task
+}
+
+/// Build the verifier's task: the original instructions and the answer to judge.
+fn build_verify_task(original: &Task, answer: &Outcome) -> Task {
+    let prompt = format!(
+        "Check whether this answer fully completes the task.\n\nTask:\n{}\n\nAnswer:\n{}\n\n{VERDICT_INSTRUCTIONS}",
+        original.instructions, answer.content
+    );
+    let task = Task::new(prompt);
+    match &original.context {
+        Some(ctx) => task.with_context(ctx.clone()),
+        None => task,
+    }
+}
+
+#[async_trait]
+impl Subagent for VerifyRetry {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn run(&self, task: Task, cancel: CancellationToken) -> Result<Outcome, SubagentError> {
+        let mut unmet_criteria = Vec::new();
+        for attempt in 1..=self.max_attempts {
+            let attempt_task = if attempt == 1 {
+                task.clone()
+            } else {
+                build_retry_task(&task, &unmet_criteria)
+            };
+            let outcome = self.worker.run(attempt_task, cancel.clone()).await?;
+
+            let reply = self
+                .verifier
+                .run(build_verify_task(&task, &outcome), cancel.clone())
+                .await?;
+            let verdict = Verdict::parse(&reply.content);
+            if verdict.meets(self.threshold) {
+                return Ok(outcome);
+            }
+            debug!(
+                id = %self.id,
+                attempt,
+                confidence = verdict.confidence,
+                unmet = ?verdict.unmet_criteria,
+                "verifier rejected attempt"
+            );
+            unmet_criteria = verdict.unmet_criteria;
+        }
+        Err(SubagentError::Unverified {
+            attempts: self.max_attempts,
+            unmet_criteria,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
@@ -156,6 +267,47 @@ mod tests {
         }
     }
 
+    /// Replies with each scripted answer in turn; records every task it ran.
+    struct ScriptedSubagent {
+        id: String,
+        answers: Mutex<Vec<String>>,
+        instructions: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedSubagent {
+        fn new(id: &str, answers: &[&str]) -> Arc<Self> {
+            Arc::new(Self {
+                id: id.to_string(),
+                answers: Mutex::new(answers.iter().rev().map(ToString::to_string).collect()),
+                instructions: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn runs(&self) -> Vec<String> {
+            self.instructions.lock().expect("lock").clone()
+        }
+    }
+
+    #[async_trait]
+    impl Subagent for ScriptedSubagent {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        async fn run(
+            &self,
+            task: Task,
+            _cancel: CancellationToken,
+        ) -> Result<Outcome, SubagentError> {
+            self.instructions
+                .lock()
+                .expect("lock")
+                .push(task.instructions.clone());
+            let answer = self.answers.lock().expect("lock").pop().expect("scripted");
+            Ok(Outcome::new(task.id, answer))
+        }
+    }
+
     /// Always fails — used to exercise the all-members-fail path.
     struct FailingSubagent;
 
@@ -282,4 +434,67 @@ mod tests {
         assert!(judge_task.instructions.contains("Candidate 2"));
         assert!(judge_task.instructions.contains("second"));
     }
+
+    #[tokio::test]
+    async fn test_verify_retry_feeds_unmet_criteria_back_until_verified() {
+        let worker = ScriptedSubagent::new("worker", &["draft", "draft with tests"]);
+        let verifier = ScriptedSubagent::new(
+            "verifier",
+            &[
+                r#"{"passed": false, "confidence": 0.9, "unmet_criteria": ["add tests"]}"#,
+                r#"{"passed": true, "confidence": 0.8, "unmet_criteria": []}"#,
+            ],
+        );
+        let retry = VerifyRetry::new(
+            "verified",
+            Arc::clone(&worker) as Arc<dyn Subagent>,
+            Arc::clone(&verifier) as Arc<dyn Subagent>,
+            3,
+        );
+
+        let task = Task::new("write the parser");
+        let outcome = retry
+            .run(task.clone(), CancellationToken::new())
+            .await
+            .expect("second attempt passes");
+
+        assert_eq!(outcome.content, "draft with tests");
+        assert_eq!(outcome.task_id, task.id);
+        let attempts = worker.runs();
+        assert_eq!(attempts.len(), 2, "two action phases ran");
+        assert_eq!(attempts[0], "write the parser");
+        assert!(attempts[1].starts_with("write the parser"));
+        assert!(attempts[1].contains("- add tests"));
+        let checks = verifier.runs();
+        assert_eq!(checks.len(), 2);
+        assert!(checks[1].contains("draft with tests"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_retry_gives_up_after_max_attempts() {
+        let worker = ScriptedSubagent::new("worker", &["a", "b"]);
+        let verifier = ScriptedSubagent::new(
+            "verifier",
+            &[
+                r#"{"passed": false, "unmet_criteria": ["x"]}"#,
+                r#"{"passed": true, "confidence": 0.4}"#,
+            ],
+        );
+        let retry = VerifyRetry::new(
+            "verified",
+            Arc::clone(&worker) as Arc<dyn Subagent>,
+            verifier,
+            2,
+        );
+
+        let err = retry
+            .run(Task::new("q"), CancellationToken::new())
+            .await
+            .expect_err("low confidence never passes");
+        assert!(matches!(
+            err,
+            SubagentError::Unverified { attempts: 2, ref unmet_criteria } if unmet_criteria.is_empty()
+        ));
+        assert_eq!(worker.runs().len(), 2);
+    }
 }
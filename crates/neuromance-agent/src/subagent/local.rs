@@ -134,6 +134,7 @@ mod tests {
             created_at: chrono::Utc::now(),
             response_id: None,
             metadata: HashMap::new(),
+            additional_choices: Vec::new(),
         }
     }
 
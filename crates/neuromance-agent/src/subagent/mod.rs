@@ -2,7 +2,7 @@
 //!
 //! A [`Subagent`] turns a [`Task`] into an [`Outcome`]. The trait is object-safe
 //! so subagents can be held as `Arc<dyn Subagent>` and composed. Combinators such
-//! as [`FanoutVote`] are themselves subagents, which lets them nest and lets
+//! as [`FanoutVote`] and [`VerifyRetry`] are themselves subagents, which lets them nest and lets
 //! [`SubagentTool`] expose any subagent — leaf or combinator — uniformly.
 //!
 //! [`LocalSubagent`] runs an in-process [`Agent`](crate::Agent). A remote variant
@@ -21,6 +21,6 @@ mod combinators;
 mod local;
 mod tool;
 
-pub use combinators::FanoutVote;
+pub use combinators::{FanoutVote, VerifyRetry};
 pub use local::LocalSubagent;
 pub use tool::SubagentTool;
@@ -2,7 +2,7 @@
 #![allow(clippy::expect_used)]
 #![allow(clippy::panic)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -16,9 +16,9 @@ use uuid::Uuid;
 use neuromance::Core;
 use neuromance_client::{ClientError, LLMClient};
 use neuromance_common::agents::{AgentMessage, AgentState, ContextUpdate};
-use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::chat::{Message, MessageRole, ReasoningContent};
 use neuromance_common::client::{ChatChunk, ChatRequest, ChatResponse, Config, ToolChoice, Usage};
-use neuromance_common::tools::{Function, FunctionCall, Tool, ToolCall};
+use neuromance_common::tools::{Function, Tool, ToolCall};
 use neuromance_tools::{ToolError, ToolImplementation};
 
 use crate::Agent;
@@ -64,6 +64,7 @@ impl LLMClient for MockLLMClient {
             created_at: chrono::Utc::now(),
             response_id: Some("test-response".to_string()),
             metadata: HashMap::new(),
+            additional_choices: Vec::new(),
         })
     }
 
@@ -214,6 +215,9 @@ async fn execute_tracks_stats() {
     assert_eq!(stats.tokens_used, 80);
     assert_eq!(stats.successful_tool_calls, 0);
     assert_eq!(stats.failed_tool_calls, 0);
+    assert_eq!(stats.tool_call_count, 0);
+    assert_eq!(stats.turn_count, 1);
+    assert!(stats.total_duration > std::time::Duration::ZERO);
 }
 
 #[tokio::test]
@@ -235,6 +239,7 @@ async fn stats_accumulate_across_executions() {
         .unwrap();
     assert_eq!(agent.state.stats.tokens_used, first_tokens * 2);
     assert_eq!(agent.state.stats.total_messages, first_messages * 2);
+    assert_eq!(agent.state.stats.turn_count, 2);
 }
 
 // -- conversation_history tests --
@@ -448,6 +453,60 @@ fn builder_sets_id_and_prompts() {
     assert_eq!(agent.messages[1].content, "Hi there");
 }
 
+#[test]
+fn builder_renders_system_template() {
+    let vars = HashMap::from([
+        ("role".to_string(), "reviewer".to_string()),
+        ("lang".to_string(), "Rust".to_string()),
+    ]);
+    let agent = Agent::builder("agent", MockLLMClient::new())
+        .with_system_template(
+            "You are a {{role}} for {{ lang }} code. {{role}}s are terse.",
+            &vars,
+        )
+        .unwrap()
+        .build();
+
+    assert_eq!(
+        agent.system_prompt.as_deref(),
+        Some("You are a reviewer for Rust code. reviewers are terse.")
+    );
+    assert_eq!(
+        agent.messages[0].content,
+        "You are a reviewer for Rust code. reviewers are terse."
+    );
+}
+
+#[test]
+fn builder_system_template_rejects_missing_variable() {
+    let vars = HashMap::from([("role".to_string(), "reviewer".to_string())]);
+    let err = Agent::builder("agent", MockLLMClient::new())
+        .with_system_template("You are a {{role}} for {{lang}} code.", &vars)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        crate::TemplateError::MissingVariable("lang".to_string())
+    );
+
+    let err = Agent::builder("agent", MockLLMClient::new())
+        .with_system_template("You are a {{role", &vars)
+        .err()
+        .unwrap();
+    assert_eq!(err, crate::TemplateError::Unterminated(10));
+
+    // Lenient mode leaves unknown placeholders in place.
+    let agent = Agent::builder("agent", MockLLMClient::new())
+        .strict_templates(false)
+        .with_system_template("You are a {{role}} for {{lang}} code.", &vars)
+        .unwrap()
+        .build();
+    assert_eq!(
+        agent.system_prompt.as_deref(),
+        Some("You are a reviewer for {{lang}} code.")
+    );
+}
+
 #[test]
 fn builder_sets_max_turns() {
     let client = MockLLMClient::new();
@@ -550,48 +609,51 @@ fn agent_state_accessors() {
 
 // -- Delegation context propagation --
 
-/// A client that calls `ctx_probe` on its first turn, then finishes. Drives the
-/// tool loop exactly once so a tool runs inside the agent's delegation scope.
-struct ToolCallingMock {
+/// Replays one scripted assistant turn per call: `chat` answers with the next
+/// queued message and `chat_stream` with the next queued chunk script, each
+/// stamped with the request's conversation id. `calls` counts both.
+struct ScriptedMock {
     config: Config,
+    replies: Mutex<VecDeque<Message>>,
+    streams: Mutex<VecDeque<Vec<ChatChunk>>>,
     calls: AtomicUsize,
 }
 
-impl ToolCallingMock {
-    fn new() -> Self {
+impl ScriptedMock {
+    fn new(replies: impl IntoIterator<Item = Message>) -> Self {
         Self {
             config: Config::new("mock", "mock-model"),
+            replies: Mutex::new(replies.into_iter().collect()),
+            streams: Mutex::default(),
             calls: AtomicUsize::new(0),
         }
     }
+
+    /// A client whose `n`th stream yields the `n`th chunk script.
+    fn streaming(streams: impl IntoIterator<Item = Vec<ChatChunk>>) -> Self {
+        let client = Self::new([]);
+        *client.streams.lock().unwrap() = streams.into_iter().collect();
+        client
+    }
 }
 
 #[async_trait]
-impl LLMClient for ToolCallingMock {
+impl LLMClient for ScriptedMock {
     fn config(&self) -> &Config {
         &self.config
     }
 
     async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
-        let conv_id = request
-            .messages
-            .first()
-            .map_or_else(Uuid::new_v4, |m| m.conversation_id);
-        let message = if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
-            Message::assistant(conv_id, "")
-                .with_tool_calls(vec![ToolCall {
-                    id: "call_1".to_string(),
-                    function: FunctionCall {
-                        name: "ctx_probe".to_string(),
-                        arguments: "{}".to_string(),
-                    },
-                    call_type: "function".to_string(),
-                    index: None,
-                }])
-                .expect("assistant message accepts tool calls")
-        } else {
-            Message::assistant(conv_id, "done")
-        };
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let mut message = self
+            .replies
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| ClientError::ConfigurationError("no scripted reply".to_string()))?;
+        if let Some(first) = request.messages.first() {
+            message.conversation_id = first.conversation_id;
+        }
         Ok(ChatResponse {
             message,
             model: "mock-model".to_string(),
@@ -600,6 +662,7 @@ impl LLMClient for ToolCallingMock {
             created_at: chrono::Utc::now(),
             response_id: None,
             metadata: HashMap::new(),
+            additional_choices: Vec::new(),
         })
     }
 
@@ -608,7 +671,14 @@ impl LLMClient for ToolCallingMock {
         _request: &ChatRequest,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
     {
-        panic!("ToolCallingMock does not stream")
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let chunks = self
+            .streams
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| ClientError::ConfigurationError("no scripted stream".to_string()))?;
+        Ok(Box::pin(futures::stream::iter(chunks.into_iter().map(Ok))))
     }
 
     fn supports_tools(&self) -> bool {
@@ -616,10 +686,45 @@ impl LLMClient for ToolCallingMock {
     }
 
     fn supports_streaming(&self) -> bool {
-        false
+        true
+    }
+}
+
+/// An assistant turn with `content` that calls `ctx_probe`.
+fn probe_turn(content: &str) -> Message {
+    Message::assistant(Uuid::nil(), content)
+        .with_tool_calls(vec![ToolCall::new("ctx_probe", "{}")])
+        .expect("assistant message accepts tool calls")
+}
+
+/// An assistant turn carrying `thinking` as its reasoning.
+fn with_thinking(mut message: Message, thinking: ReasoningContent) -> Message {
+    message.reasoning = Some(thinking);
+    message
+}
+
+/// A streamed assistant delta.
+fn chunk(content: Option<&str>, tool_calls: Option<Vec<ToolCall>>) -> ChatChunk {
+    ChatChunk {
+        model: "mock-model".to_string(),
+        delta_content: content.map(str::to_string),
+        delta_reasoning_content: None,
+        delta_role: Some(MessageRole::Assistant),
+        delta_tool_calls: tool_calls,
+        finish_reason: None,
+        usage: None,
+        response_id: None,
+        created_at: chrono::Utc::now(),
+        metadata: HashMap::new(),
     }
 }
 
+/// Calls `ctx_probe` on its first turn, then finishes. Drives the tool loop
+/// exactly once so a tool runs inside the agent's delegation scope.
+fn probe_once_mock() -> ScriptedMock {
+    ScriptedMock::new([probe_turn(""), Message::assistant(Uuid::nil(), "done")])
+}
+
 /// Records the delegation parent it observes from the task-local context when
 /// run. A subagent spawned during the parent's run would read the same value.
 struct CtxProbe {
@@ -658,7 +763,7 @@ impl ToolImplementation for CtxProbe {
 #[tokio::test]
 async fn execute_publishes_conversation_id_as_delegation_parent() {
     let seen = Arc::new(Mutex::new(None));
-    let mut agent = Agent::new("parent".into(), Core::new(ToolCallingMock::new()));
+    let mut agent = Agent::new("parent".into(), Core::new(probe_once_mock()));
     agent.core.auto_approve_tools = true;
     agent.core.tool_executor.add_tool(CtxProbe {
         seen: Arc::clone(&seen),
@@ -677,6 +782,27 @@ async fn execute_publishes_conversation_id_as_delegation_parent() {
     );
 }
 
+#[tokio::test]
+async fn execute_counts_tool_calls_from_the_tool_loop() {
+    let mut agent = Agent::new("test".into(), Core::new(probe_once_mock()));
+    agent.core.auto_approve_tools = true;
+    agent.core.tool_executor.add_tool(CtxProbe {
+        seen: Arc::new(Mutex::new(None)),
+    });
+    let conv_id = agent.conversation_id;
+
+    agent
+        .execute(Some(make_messages(conv_id)), CancellationToken::new())
+        .await
+        .unwrap();
+
+    let stats = &agent.state.stats;
+    assert_eq!(stats.tool_call_count, 1);
+    assert_eq!(stats.successful_tool_calls, 1);
+    assert_eq!(stats.turn_count, 1);
+    assert!(stats.total_duration > std::time::Duration::ZERO);
+}
+
 /// `scope_task` seeds only the runtime task id; the root conversation it wraps
 /// has no parent conversation of its own.
 #[tokio::test]
@@ -691,6 +817,131 @@ async fn scope_task_seeds_task_id_without_parent_conversation() {
     assert_eq!(observed, (None, Some(task_id)));
 }
 
+#[tokio::test]
+async fn execute_collects_reasoning_across_assistant_turns() {
+    // Thinks before the probe call, then again before answering, like a
+    // model with interleaved thinking.
+    let client = ScriptedMock::new([
+        with_thinking(probe_turn(""), ReasoningContent::new("Probe first.")),
+        with_thinking(
+            Message::assistant(Uuid::nil(), "done"),
+            ReasoningContent::with_signature("Probe said ok.", "sig"),
+        ),
+    ]);
+    let mut agent = Agent::new("thinker".into(), Core::new(client));
+    agent.core.auto_approve_tools = true;
+    agent.core.tool_executor.add_tool(CtxProbe {
+        seen: Arc::new(Mutex::new(None)),
+    });
+    let conv_id = agent.conversation_id;
+
+    let response = agent
+        .execute(Some(make_messages(conv_id)), CancellationToken::new())
+        .await
+        .unwrap();
+
+    assert_eq!(response.content.content, "done");
+    assert_eq!(
+        response.reasoning.as_deref(),
+        Some("Probe first.\n\nProbe said ok.")
+    );
+
+    // Without any reasoning content the field stays empty.
+    let mut plain = Agent::new("plain".into(), Core::new(MockLLMClient::new()));
+    let conv_id = plain.conversation_id;
+    let response = plain
+        .execute(Some(make_messages(conv_id)), CancellationToken::new())
+        .await
+        .unwrap();
+    assert!(response.reasoning.is_none());
+}
+
+/// The model would keep calling tools forever; the completion marker ends the
+/// loop on the turn it appears, without running that turn's tool call.
+#[tokio::test]
+async fn completion_marker_stops_tool_loop_mid_run() {
+    // Calls `ctx_probe` on every turn, announcing `TASK_COMPLETE` on the
+    // second while still asking for another call.
+    let client = ScriptedMock::new([
+        probe_turn("Looking."),
+        probe_turn("The probe answered. TASK_COMPLETE"),
+    ]);
+    let mut agent = Agent::builder("finisher", client)
+        .system_prompt("You are a test agent.")
+        .user_prompt("Probe until done.")
+        .add_tool(CtxProbe {
+            seen: Arc::new(Mutex::new(None)),
+        })
+        .auto_approve_tools(true)
+        .max_turns(5)
+        .with_completion_marker("TASK_COMPLETE")
+        .build();
+
+    let (response, messages) = agent
+        .execute_with_history(None, CancellationToken::new())
+        .await
+        .unwrap();
+
+    assert_eq!(response.content.content, "The probe answered.");
+    assert!(response.content.tool_calls.is_empty());
+    assert_eq!(agent.core.client.calls.load(Ordering::SeqCst), 2);
+    assert_eq!(agent.state.stats.tool_call_count, 1);
+    assert_eq!(messages.last().unwrap().role, MessageRole::Assistant);
+}
+
+// -- Streaming execution --
+
+#[tokio::test]
+async fn execute_stream_yields_deltas_tool_events_and_response() {
+    use futures::StreamExt;
+
+    use crate::AgentEvent;
+
+    // Streams two content chunks and then a `ctx_probe` call on its first
+    // turn, and a final answer on the next.
+    let mut core = Core::new(ScriptedMock::streaming([
+        vec![
+            chunk(Some("Let me "), None),
+            chunk(Some("check."), None),
+            chunk(None, Some(vec![ToolCall::new("ctx_probe", "{}")])),
+        ],
+        vec![chunk(Some("done"), None)],
+    ]));
+    core.streaming = true;
+    core.tool_executor.add_tool(CtxProbe {
+        seen: Arc::new(Mutex::new(None)),
+    });
+    let mut agent = Agent::new("streamer".into(), core);
+    let conv_id = agent.conversation_id;
+
+    let events: Vec<AgentEvent> = agent
+        .execute_stream(Some(make_messages(conv_id)), CancellationToken::new())
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 6, "unexpected events: {events:?}");
+    assert!(matches!(&events[0], AgentEvent::ContentDelta(t) if t == "Let me "));
+    assert!(matches!(&events[1], AgentEvent::ContentDelta(t) if t == "check."));
+    assert!(
+        matches!(&events[2], AgentEvent::ToolCallStarted(call) if call.function.name == "ctx_probe")
+    );
+    assert!(matches!(
+        &events[3],
+        AgentEvent::ToolResult { name, output, success: true } if name == "ctx_probe" && output == "ok"
+    ));
+    assert!(matches!(&events[4], AgentEvent::ContentDelta(t) if t == "done"));
+    let AgentEvent::Finished(response) = &events[5] else {
+        panic!("expected Finished, got {:?}", events[5]);
+    };
+    assert_eq!(response.content.content, "done");
+    assert_eq!(response.tool_responses.len(), 1);
+
+    assert_eq!(agent.state.stats.successful_tool_calls, 1);
+    assert_eq!(agent.state.conversation_history.len(), 1);
+}
+
 // -- CacheMetrics total_output_tokens --
 
 #[test]
@@ -0,0 +1,141 @@
+//! Structured verdicts from a verifying model.
+//!
+//! A bare pass/fail loses what a verifier knows about nuanced work: how sure
+//! it is and what is still missing. Append [`VERDICT_INSTRUCTIONS`] to a
+//! verifier's prompt and read its reply with [`Verdict::parse`].
+
+/// Instructions asking a model to answer with a JSON verdict that
+/// [`Verdict::parse`] understands.
+pub const VERDICT_INSTRUCTIONS: &str = "Respond with only a JSON object of the form \
+{\"passed\": true, \"confidence\": 0.9, \"unmet_criteria\": [\"...\"]}. \
+`confidence` is between 0.0 and 1.0 and `unmet_criteria` lists every requirement \
+the work does not meet.";
+
+/// A verifier's judgement of a piece of work.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verdict {
+    /// Whether the verifier judged the work acceptable.
+    pub passed: bool,
+    /// How sure the verifier is, from 0.0 to 1.0.
+    pub confidence: f32,
+    /// Requirements the verifier found unmet.
+    pub unmet_criteria: Vec<String>,
+    /// Whether the reply held no usable JSON and the verdict was guessed
+    /// from its wording instead.
+    pub fallback: bool,
+}
+
+impl Verdict {
+    /// Read a verdict from a verifier's reply.
+    ///
+    /// The first `{` to the last `}` is parsed as JSON, so fenced or
+    /// surrounded objects are fine. Missing fields are inferred: `passed`
+    /// from `confidence` (at least 0.5) or else from an empty
+    /// `unmet_criteria`, and `confidence` as 1.0 or 0.0 from `passed`.
+    /// Confidence is clamped to 0.0–1.0.
+    ///
+    /// A reply with no usable JSON falls back to its wording: it passes if it
+    /// mentions "pass" and never "fail", with a confidence of 0.5 for a pass
+    /// and 0.0 otherwise, and [`fallback`](Self::fallback) is set.
+    #[must_use]
+    pub fn parse(reply: &str) -> Self {
+        Self::parse_json(reply).unwrap_or_else(|| Self::from_wording(reply))
+    }
+
+    /// Whether the work passed with at least `threshold` confidence.
+    #[must_use]
+    pub fn meets(&self, threshold: f32) -> bool {
+        self.passed && self.confidence >= threshold
+    }
+
+    fn parse_json(reply: &str) -> Option<Self> {
+        let start = reply.find('{')?;
+        let end = reply.rfind('}')?;
+        let value: serde_json::Value = serde_json::from_str(reply.get(start..=end)?).ok()?;
+        let object = value.as_object()?;
+
+        let unmet_criteria: Vec<String> = object
+            .get("unmet_criteria")
+            .and_then(serde_json::Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        #[allow(clippy::cast_possible_truncation)]
+        let confidence = object
+            .get("confidence")
+            .and_then(serde_json::Value::as_f64)
+            .map(|c| (c as f32).clamp(0.0, 1.0));
+        let passed = object
+            .get("passed")
+            .and_then(serde_json::Value::as_bool)
+            .or_else(|| confidence.map(|c| c >= 0.5));
+        if passed.is_none() && !object.contains_key("unmet_criteria") {
+            return None;
+        }
+        let passed = passed.unwrap_or(unmet_criteria.is_empty());
+
+        Some(Self {
+            passed,
+            confidence: confidence.unwrap_or(if passed { 1.0 } else { 0.0 }),
+            unmet_criteria,
+            fallback: false,
+        })
+    }
+
+    fn from_wording(reply: &str) -> Self {
+        let lower = reply.to_lowercase();
+        let passed = lower.contains("pass") && !lower.contains("fail");
+        Self {
+            passed,
+            confidence: if passed { 0.5 } else { 0.0 },
+            unmet_criteria: Vec::new(),
+            fallback: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_well_formed_verdict() {
+        let reply = "Here is my assessment:\n```json\n{\"passed\": false, \"confidence\": 0.8, \
+                     \"unmet_criteria\": [\"no tests\", \"missing docs\"]}\n```";
+        let verdict = Verdict::parse(reply);
+
+        assert_eq!(
+            verdict,
+            Verdict {
+                passed: false,
+                confidence: 0.8,
+                unmet_criteria: vec!["no tests".to_string(), "missing docs".to_string()],
+                fallback: false,
+            }
+        );
+        assert!(!verdict.meets(0.5));
+
+        let inferred = Verdict::parse(r#"{"confidence": 1.7}"#);
+        assert!(inferred.passed);
+        assert!((inferred.confidence - 1.0).abs() < f32::EPSILON);
+        assert!(inferred.meets(0.9));
+    }
+
+    #[test]
+    fn test_parse_malformed_verdict_falls_back_to_wording() {
+        let verdict = Verdict::parse("{passed: yes} The change looks good, it should pass.");
+        assert!(verdict.fallback);
+        assert!(verdict.passed);
+        assert!(verdict.meets(0.5));
+        assert!(!verdict.meets(0.7));
+
+        let verdict = Verdict::parse("This fails to pass review: the tests are missing.");
+        assert!(verdict.fallback);
+        assert!(!verdict.passed);
+        assert!(verdict.unmet_criteria.is_empty());
+    }
+}
@@ -53,24 +53,41 @@ use secrecy::{ExposeSecret, SecretString};
 use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::{error, warn};
 
 use neuromance_common::chat::{Message, MessageRole};
-use neuromance_common::client::{ChatChunk, ChatRequest, ChatResponse, Config, ProxyConfig, Usage};
+use neuromance_common::client::{
+    Capabilities, ChatChunk, ChatRequest, ChatResponse, Config, FinishReason, ProxyConfig,
+    ResponseFormat, Usage,
+};
 use neuromance_common::tools::{FunctionCall, ToolCall};
 
+#[cfg(feature = "cassette")]
+use crate::cassette::RecordReplay;
 use crate::error::ClientError;
-use crate::message::MessageBuilder;
-use crate::streaming::{StreamingProvider, run_sse_stream};
-use crate::transport::{add_proxy_headers, send_json};
-use crate::{LLMClient, build_client_resources};
+use crate::interceptor::{Interceptor, intercept_request, intercept_response};
+use crate::message::{MessageBuilder, record_response_metadata};
+use crate::request_tracing::{traced_chat, traced_stream};
+use crate::streaming::{ChatChunkStream, StreamingProvider, run_sse_stream};
+use crate::transport::{add_extra_headers, add_proxy_headers, send_json};
+use crate::{LLMClient, base_capabilities, build_client_resources};
 
 use super::{
     ANTHROPIC_VERSION, ContentBlockStart, CreateMessageRequest, DEFAULT_BASE_URL, Delta,
     INTERLEAVED_THINKING_BETA, MessageResponse, ResponseContentBlock, StreamEvent,
-    StreamingToolCall,
+    StreamingToolCall, take_structured_output,
 };
 
+/// Headers the client sets itself, which `Config::extra_headers` cannot
+/// override.
+const RESERVED_HEADERS: [&str; 4] = [
+    "x-api-key",
+    "anthropic-version",
+    "anthropic-beta",
+    "Content-Type",
+];
+
 /// Client for Anthropic's Messages API.
 ///
 /// Supports Claude models with tool/function calling and streaming.
@@ -93,6 +110,9 @@ pub struct AnthropicClient {
     base_url: String,
     config: Arc<Config>,
     proxy_config: Option<ProxyConfig>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    #[cfg(feature = "cassette")]
+    cassette: Option<Arc<RecordReplay>>,
 }
 
 impl std::fmt::Debug for AnthropicClient {
@@ -102,6 +122,7 @@ impl std::fmt::Debug for AnthropicClient {
             .field("base_url", &self.base_url)
             .field("config", &self.config)
             .field("proxy_config", &self.proxy_config)
+            .field("interceptors", &self.interceptors.len())
             .finish_non_exhaustive()
     }
 }
@@ -160,6 +181,9 @@ impl AnthropicClient {
             base_url: r.base_url,
             config: r.config,
             proxy_config: r.proxy_config,
+            interceptors: Vec::new(),
+            #[cfg(feature = "cassette")]
+            cassette: None,
         })
     }
 
@@ -179,6 +203,25 @@ impl AnthropicClient {
         self
     }
 
+    /// Add an [`Interceptor`] to run around every request.
+    ///
+    /// Interceptors run in the order they were added.
+    #[must_use]
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Record non-streaming exchanges to, or replay them from, `cassette`.
+    ///
+    /// See [`cassette`](crate::cassette) for how requests are matched.
+    #[cfg(feature = "cassette")]
+    #[must_use]
+    pub fn with_cassette(mut self, cassette: Arc<RecordReplay>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
     /// Make a non-streaming request to the Messages API.
     ///
     /// # Arguments
@@ -202,6 +245,12 @@ impl AnthropicClient {
             .header("x-api-key", self.api_key.expose_secret())
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("Content-Type", "application/json");
+        request_builder = add_extra_headers(
+            request_builder,
+            &self.config.extra_headers,
+            &RESERVED_HEADERS,
+            self.proxy_config.as_ref(),
+        );
 
         // Add beta header if specified
         if let Some(beta) = beta_features {
@@ -215,11 +264,17 @@ impl AnthropicClient {
         let request_builder = request_builder
             .body(serde_json::to_string(body).map_err(ClientError::SerializationError)?);
 
+        #[cfg(feature = "cassette")]
+        if let Some(cassette) = &self.cassette {
+            return cassette
+                .exchange("messages", body, send_json(request_builder))
+                .await;
+        }
         send_json(request_builder).await
     }
 
     /// Convert an Anthropic response to our internal Message format.
-    fn convert_response_to_message(
+    pub(crate) fn convert_response_to_message(
         response: &MessageResponse,
         conversation_id: uuid::Uuid,
     ) -> Message {
@@ -433,21 +488,9 @@ pub fn convert_event_to_chat_chunk(
     }
 }
 
-#[async_trait]
-impl LLMClient for AnthropicClient {
-    fn config(&self) -> &Config {
-        &self.config
-    }
-
-    fn supports_tools(&self) -> bool {
-        true
-    }
-
-    fn supports_streaming(&self) -> bool {
-        true
-    }
-
-    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+impl AnthropicClient {
+    /// [`LLMClient::chat`] without its request span.
+    async fn chat_inner(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
         self.validate_request(request)?;
 
         let mut anthropic_request = CreateMessageRequest::from((request, self.config.as_ref()));
@@ -460,6 +503,7 @@ impl LLMClient for AnthropicClient {
             None
         };
 
+        let started = Instant::now();
         let response = self.make_request(&anthropic_request, beta_features).await?;
 
         // Get conversation_id from first message
@@ -472,26 +516,36 @@ impl LLMClient for AnthropicClient {
             })?
             .conversation_id;
 
-        let message = Self::convert_response_to_message(&response, conversation_id);
+        let mut message = Self::convert_response_to_message(&response, conversation_id);
 
-        let finish_reason = response.stop_reason.map(std::convert::Into::into);
+        let finish_reason = if let Some(ResponseFormat::JsonSchema { name, .. }) =
+            &request.response_format
+            && take_structured_output(&mut message, name)
+        {
+            Some(FinishReason::Stop)
+        } else {
+            response.stop_reason.map(std::convert::Into::into)
+        };
+
+        let usage = Usage::from(response.usage);
+        record_response_metadata(&mut message, &self.config.provider, started, Some(&usage));
 
-        Ok(ChatResponse {
+        let response = ChatResponse {
             message,
             model: response.model,
-            usage: Some(Usage::from(response.usage)),
+            usage: Some(usage),
             finish_reason,
             created_at: Utc::now(),
             response_id: Some(response.id),
             metadata: HashMap::new(),
-        })
+            additional_choices: Vec::new(),
+        };
+        intercept_response(&self.interceptors, &response);
+        Ok(response)
     }
 
-    async fn chat_stream(
-        &self,
-        request: &ChatRequest,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
-    {
+    /// [`LLMClient::chat_stream`] without its request span.
+    fn chat_stream_inner(&self, request: &ChatRequest) -> Result<ChatChunkStream, ClientError> {
         self.validate_request(request)?;
 
         let mut anthropic_request = CreateMessageRequest::from((request, self.config.as_ref()));
@@ -507,6 +561,12 @@ impl LLMClient for AnthropicClient {
             .header("x-api-key", self.api_key.expose_secret())
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("Content-Type", "application/json");
+        request_builder = add_extra_headers(
+            request_builder,
+            &self.config.extra_headers,
+            &RESERVED_HEADERS,
+            self.proxy_config.as_ref(),
+        );
 
         if request.thinking.is_interleaved() {
             request_builder = request_builder.header("anthropic-beta", INTERLEAVED_THINKING_BETA);
@@ -521,15 +581,77 @@ impl LLMClient for AnthropicClient {
     }
 }
 
+#[async_trait]
+impl LLMClient for AnthropicClient {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            parallel_tool_calls: true,
+            vision: true,
+            json_schema: true,
+            reasoning: true,
+            prompt_caching: true,
+            ..base_capabilities(self)
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        let mut anthropic_request = CreateMessageRequest::from((request, self.config.as_ref()));
+        anthropic_request.stream = Some(false);
+        serde_json::to_value(&anthropic_request).ok()
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        let request = &*intercept_request(&self.interceptors, request);
+        traced_chat(self.config(), request, self.chat_inner(request)).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        let request = &*intercept_request(&self.interceptors, request);
+        traced_stream(self.config(), request, async {
+            self.chat_stream_inner(request)
+        })
+        .await
+    }
+}
+
 /// Per-stream accumulator for the Anthropic streaming protocol.
 ///
 /// `model` and `response_id` are seeded from config and overwritten by the
 /// `MessageStart` event; `streaming_tool_calls` accumulates partial tool-use
-/// JSON across `ContentBlockStart` / `ContentBlockDelta` / `ContentBlockStop`.
+/// JSON across `ContentBlockStart` / `ContentBlockDelta` / `ContentBlockStop`,
+/// repairing truncated JSON when `repair_tool_arguments` is set.
 pub struct AnthropicStreamState {
     model: String,
     response_id: String,
     streaming_tool_calls: HashMap<u32, StreamingToolCall>,
+    repair_tool_arguments: bool,
+}
+
+impl AnthropicStreamState {
+    pub(crate) fn new(model: String, repair_tool_arguments: bool) -> Self {
+        Self {
+            model,
+            response_id: String::new(),
+            streaming_tool_calls: HashMap::new(),
+            repair_tool_arguments,
+        }
+    }
 }
 
 impl StreamingProvider for AnthropicClient {
@@ -537,11 +659,7 @@ impl StreamingProvider for AnthropicClient {
     type State = AnthropicStreamState;
 
     fn initial_state(&self) -> Self::State {
-        AnthropicStreamState {
-            model: self.config.model.clone(),
-            response_id: String::new(),
-            streaming_tool_calls: HashMap::new(),
-        }
+        AnthropicStreamState::new(self.config.model.clone(), self.config.repair_tool_arguments)
     }
 
     fn process_event(
@@ -552,13 +670,19 @@ impl StreamingProvider for AnthropicClient {
             state.model.clone_from(&msg.model);
             state.response_id.clone_from(&msg.id);
         }
-        convert_event_to_chat_chunk(
+        let chunk = convert_event_to_chat_chunk(
             &event,
             &state.model,
             &state.response_id,
             Some(&mut state.streaming_tool_calls),
-        )
-        .map(Ok)
+        );
+        if let StreamEvent::ContentBlockStart { index, .. } = event
+            && state.repair_tool_arguments
+            && let Some(tool_call) = state.streaming_tool_calls.get_mut(&index)
+        {
+            tool_call.repair = true;
+        }
+        chunk.map(Ok)
     }
 }
 
@@ -571,7 +695,9 @@ mod tests {
 
     use super::*;
     use futures::StreamExt;
-    use neuromance_common::chat::{Message, MessageRole};
+    use neuromance_common::chat::{
+        CACHED_METADATA_KEY, LATENCY_MS_METADATA_KEY, Message, MessageRole, PROVIDER_METADATA_KEY,
+    };
     use neuromance_common::client::FinishReason;
     use smallvec::SmallVec;
     use wiremock::matchers::{header, method, path};
@@ -598,6 +724,8 @@ mod tests {
             model: None,
             provider: None,
             usage: None,
+            parent_id: None,
+            content_parts: Vec::new(),
         }
     }
 
@@ -640,6 +768,23 @@ mod tests {
         assert_eq!(response.message.content, "Hello! How can I help you today?");
         assert_eq!(response.message.role, MessageRole::Assistant);
         assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(
+            response
+                .message
+                .get_meta::<String>(PROVIDER_METADATA_KEY)
+                .as_deref(),
+            Some("anthropic")
+        );
+        assert_eq!(
+            response.message.get_meta::<bool>(CACHED_METADATA_KEY),
+            Some(false)
+        );
+        assert!(
+            response
+                .message
+                .get_meta::<u64>(LATENCY_MS_METADATA_KEY)
+                .is_some()
+        );
 
         let usage = response.usage.unwrap();
         assert_eq!(usage.prompt_tokens, 10);
@@ -689,6 +834,58 @@ mod tests {
         assert_eq!(tool_call.function.name, "get_weather");
     }
 
+    #[tokio::test]
+    async fn test_json_schema_response_format_forces_tool_and_unwraps_output() {
+        use wiremock::matchers::body_partial_json;
+
+        let mock_server = MockServer::start().await;
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {"temp_c": {"type": "number"}},
+            "required": ["temp_c"]
+        });
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .and(body_partial_json(serde_json::json!({
+                "tools": [{"name": "weather", "input_schema": schema}],
+                "tool_choice": {"type": "tool", "name": "weather"}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "msg_structured",
+                "type": "message",
+                "role": "assistant",
+                "content": [{
+                    "type": "tool_use",
+                    "id": "toolu_structured",
+                    "name": "weather",
+                    "input": {"temp_c": 21.5}
+                }],
+                "model": "claude-sonnet-4-5-20250929",
+                "stop_reason": "tool_use",
+                "usage": {"input_tokens": 15, "output_tokens": 10}
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = AnthropicClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]).with_response_format(
+            ResponseFormat::JsonSchema {
+                name: "weather".to_string(),
+                schema,
+                strict: true,
+            },
+        );
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        assert!(response.message.tool_calls.is_empty());
+        let output: serde_json::Value = serde_json::from_str(&response.message.content).unwrap();
+        assert_eq!(output, serde_json::json!({"temp_c": 21.5}));
+    }
+
     #[tokio::test]
     async fn test_authentication_error() {
         let mock_server = MockServer::start().await;
@@ -1077,6 +1274,46 @@ mod tests {
         assert_eq!(args["location"], "San Francisco");
     }
 
+    #[test]
+    fn test_stream_repairs_truncated_tool_arguments_when_enabled() {
+        use crate::anthropic::{ContentBlockStart, Delta, StreamEvent};
+
+        let tool_calls = |repair: bool| {
+            let mut state = AnthropicStreamState::new("claude".to_string(), repair);
+            let events = [
+                StreamEvent::ContentBlockStart {
+                    index: 0,
+                    content_block: ContentBlockStart::ToolUse {
+                        id: "toolu_1".to_string(),
+                        name: "read_file".to_string(),
+                        input: serde_json::json!({}),
+                    },
+                },
+                StreamEvent::ContentBlockDelta {
+                    index: 0,
+                    delta: Delta::InputJsonDelta {
+                        partial_json: r#"{"path": "src/lib.rs", "lines": [1, 2,"#.to_string(),
+                    },
+                },
+                StreamEvent::ContentBlockStop { index: 0 },
+            ];
+            events
+                .into_iter()
+                .filter_map(|event| AnthropicClient::process_event(&mut state, event))
+                .filter_map(|chunk| chunk.unwrap().delta_tool_calls)
+                .flatten()
+                .collect::<Vec<_>>()
+        };
+
+        assert!(tool_calls(false).is_empty());
+        let repaired = tool_calls(true);
+        assert_eq!(repaired.len(), 1);
+        assert_eq!(
+            repaired[0].function.arguments_json(),
+            r#"{"lines":[1,2],"path":"src/lib.rs"}"#
+        );
+    }
+
     #[test]
     fn test_streaming_message_start_captures_metadata() {
         use crate::anthropic::{AnthropicUsage, MessageResponse, StreamEvent};
@@ -1252,6 +1489,8 @@ mod tests {
             model: None,
             provider: None,
             usage: None,
+            parent_id: None,
+            content_parts: Vec::new(),
         };
 
         let anthropic_msg = AnthropicMessage::from(&tool_message);
@@ -1268,6 +1507,7 @@ mod tests {
                         tool_use_id,
                         content,
                         is_error,
+                        ..
                     } => {
                         assert_eq!(tool_use_id, "toolu_weather_123");
                         assert!(is_error.is_none());
@@ -1317,6 +1557,8 @@ mod tests {
             model: None,
             provider: None,
             usage: None,
+            parent_id: None,
+            content_parts: Vec::new(),
         };
 
         let anthropic_msg = AnthropicMessage::from(&assistant_message);
@@ -1338,7 +1580,9 @@ mod tests {
 
                 // Second block should be tool use
                 match &blocks[1] {
-                    RequestContentBlock::ToolUse { id, name, input } => {
+                    RequestContentBlock::ToolUse {
+                        id, name, input, ..
+                    } => {
                         assert_eq!(id, "toolu_calc_1");
                         assert_eq!(name, "calculate");
                         assert_eq!(input["expression"], "2+2");
@@ -1398,6 +1642,8 @@ mod tests {
                 model: None,
                 provider: None,
                 usage: None,
+                parent_id: None,
+                content_parts: Vec::new(),
             },
             Message {
                 id: uuid::Uuid::new_v4(),
@@ -1413,6 +1659,8 @@ mod tests {
                 model: None,
                 provider: None,
                 usage: None,
+                parent_id: None,
+                content_parts: Vec::new(),
             },
         ];
         messages.push(create_test_message()); // Add a user message
@@ -1453,6 +1701,173 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cache_policy_sets_last_system_block_cache_control() {
+        use crate::anthropic::{CreateMessageRequest, SystemContentBlock, SystemPrompt};
+        use neuromance_common::features::CachePolicy;
+
+        let config =
+            Config::new("anthropic", "claude-sonnet-4-5-20250929").with_api_key("test-key");
+        let conversation_id = uuid::Uuid::new_v4();
+        let messages = vec![
+            Message::system(conversation_id, "Be helpful"),
+            Message::user(conversation_id, "Hello"),
+        ];
+
+        for (policy, expected) in [
+            (None, Some(("ephemeral", None))),
+            (Some(CachePolicy::Ephemeral), Some(("ephemeral", None))),
+            (Some(CachePolicy::OneHour), Some(("ephemeral", Some("1h")))),
+            (Some(CachePolicy::None), None),
+        ] {
+            let mut request = ChatRequest::new(messages.clone());
+            request.cache_control = policy;
+            let anthropic_request = CreateMessageRequest::from((&request, &config));
+
+            let Some(SystemPrompt::Blocks(blocks)) = anthropic_request.system else {
+                panic!("Expected Blocks variant");
+            };
+            let SystemContentBlock::Text { cache_control, .. } = &blocks[0];
+            let actual = cache_control
+                .as_ref()
+                .map(|c| (c.cache_type.as_str(), c.ttl.as_deref()));
+            assert_eq!(actual, expected, "policy {policy:?}");
+        }
+    }
+
+    #[test]
+    fn test_cache_boundary_marks_message_before_newest() {
+        use crate::anthropic::CreateMessageRequest;
+        use neuromance_common::features::CachePolicy;
+
+        let config = create_test_config("http://localhost");
+        let conversation_id = uuid::Uuid::new_v4();
+        let call = ToolCall::new("get_weather", r#"{"city":"Oslo"}"#);
+        let messages = vec![
+            Message::system(conversation_id, "Be helpful"),
+            Message::user(conversation_id, "Weather in Oslo?"),
+            Message::assistant(conversation_id, "Checking.")
+                .with_tool_calls(vec![call.clone()])
+                .unwrap(),
+            Message::tool(conversation_id, "4C", call.id, "get_weather".to_string()).unwrap(),
+        ];
+        let body = |request: ChatRequest| {
+            serde_json::to_value(CreateMessageRequest::from((&request, &config))).unwrap()
+        };
+
+        let cached = body(ChatRequest::new(messages.clone()).with_cache_boundary(true));
+        let history = cached["messages"].as_array().unwrap();
+        assert!(history[0]["content"][0].get("cache_control").is_none());
+        assert_eq!(
+            history[1]["content"][1]["type"], "tool_use",
+            "marker goes on the last block of the boundary message"
+        );
+        assert_eq!(
+            history[1]["content"][1]["cache_control"]["type"],
+            "ephemeral"
+        );
+        assert!(history[2]["content"][0].get("cache_control").is_none());
+
+        let off = body(ChatRequest::new(messages.clone()));
+        assert!(!off["messages"].to_string().contains("cache_control"));
+
+        let disabled = body(
+            ChatRequest::new(messages)
+                .with_cache_boundary(true)
+                .with_cache_policy(CachePolicy::None),
+        );
+        assert!(!disabled["messages"].to_string().contains("cache_control"));
+    }
+
+    #[test]
+    fn test_tool_choice_any_carries_disable_parallel_tool_use() {
+        use crate::anthropic::CreateMessageRequest;
+        use neuromance_common::client::ToolChoice;
+
+        let config = create_test_config("http://localhost");
+        let serialize = |choice: ToolChoice| {
+            let request = ChatRequest::new(vec![create_test_message()]).with_tool_choice(choice);
+            serde_json::to_value(CreateMessageRequest::from((&request, &config))).unwrap()
+                ["tool_choice"]
+                .clone()
+        };
+
+        assert_eq!(
+            serialize(ToolChoice::Any {
+                disable_parallel: true
+            }),
+            serde_json::json!({"type": "any", "disable_parallel_tool_use": true})
+        );
+        assert_eq!(
+            serialize(ToolChoice::Any {
+                disable_parallel: false
+            }),
+            serde_json::json!({"type": "any"})
+        );
+        assert_eq!(
+            serialize(ToolChoice::Required),
+            serde_json::json!({"type": "any"})
+        );
+    }
+
+    #[test]
+    fn test_developer_messages_fold_into_system_blocks() {
+        use crate::anthropic::{CreateMessageRequest, SystemContentBlock, SystemPrompt};
+
+        let conversation_id = uuid::Uuid::new_v4();
+        let messages = vec![
+            Message::system(conversation_id, "Be helpful."),
+            Message::developer(conversation_id, "Answer in French."),
+            Message::user(conversation_id, "Hi"),
+        ];
+        let config =
+            Config::new("anthropic", "claude-sonnet-4-5-20250929").with_api_key("test-key");
+        let request = ChatRequest::new(messages);
+
+        let anthropic_request = CreateMessageRequest::from((&request, &config));
+
+        assert_eq!(anthropic_request.messages.len(), 1);
+        match anthropic_request.system.expect("Should have system prompt") {
+            SystemPrompt::Blocks(blocks) => {
+                let texts: Vec<&str> = blocks
+                    .iter()
+                    .map(|SystemContentBlock::Text { text, .. }| text.as_str())
+                    .collect();
+                assert_eq!(texts, vec!["Be helpful.", "Answer in French."]);
+            }
+            _ => panic!("Expected Blocks variant"),
+        }
+    }
+
+    #[test]
+    fn test_image_parts_become_base64_image_blocks() {
+        use crate::anthropic::{
+            AnthropicMessage, ImageSource, MessageContent, RequestContentBlock,
+        };
+
+        let message = Message::user(uuid::Uuid::new_v4(), "What is this?")
+            .with_image_base64("image/png", "iVBORw0KGgo=")
+            .unwrap();
+
+        let MessageContent::Blocks(blocks) = AnthropicMessage::from(&message).content else {
+            panic!("Expected Blocks content");
+        };
+        assert_eq!(blocks.len(), 2);
+        match &blocks[0] {
+            RequestContentBlock::Image {
+                source: ImageSource::Base64 { media_type, data },
+                ..
+            } => {
+                assert_eq!(media_type, "image/png");
+                assert_eq!(data, "iVBORw0KGgo=");
+            }
+            other => panic!("Expected base64 image block, got {other:?}"),
+        }
+        assert!(
+            matches!(&blocks[1], RequestContentBlock::Text { text, .. } if text == "What is this?")
+        );
+    }
+
     #[test]
     fn test_tools_last_gets_cache_control() {
         use crate::anthropic::CreateMessageRequest;
@@ -1529,6 +1944,38 @@ mod tests {
         })
     }
 
+    #[tokio::test]
+    async fn test_extra_headers_sent_without_overriding_client_headers() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .and(header("x-api-key", "test-key"))
+            .and(header("X-Org-Id", "org-42"))
+            .and(header("X-Project", "neuromance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(create_successful_response()))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let config = create_test_config(&mock_server.uri())
+            .with_extra_header("X-Org-Id", "org-42")
+            .with_extra_header("X-Project", "neuromance")
+            .with_extra_header("X-API-Key", "gateway-key");
+        let client = AnthropicClient::new(config).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]).with_max_tokens(1024);
+
+        client.chat(&request).await.unwrap();
+        // The streaming path attaches them too; the mock answers with JSON,
+        // so only the request matters here.
+        let _ = client.chat_stream(&request).await.unwrap().next().await;
+
+        for received in mock_server.received_requests().await.unwrap() {
+            let keys: Vec<_> = received.headers.get_all("x-api-key").iter().collect();
+            assert_eq!(keys, ["test-key"]);
+        }
+    }
+
     #[tokio::test]
     async fn test_proxy_headers_sent() {
         let mock_server = MockServer::start().await;
@@ -6,10 +6,13 @@
 use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
-use neuromance_common::chat::{Message, MessageRole};
-use neuromance_common::client::{ChatRequest, Config, InputTokensDetails, Usage};
+use neuromance_common::chat::{ContentPart, Message, MessageRole};
+use neuromance_common::client::{ChatRequest, Config, InputTokensDetails, ResponseFormat, Usage};
+use neuromance_common::features::CachePolicy;
 use neuromance_common::tools::{FunctionCall, Tool, ToolCall};
 
+use crate::json_repair::parse_repaired;
+
 pub mod client;
 pub use client::AnthropicClient;
 
@@ -58,6 +61,17 @@ impl CacheControl {
             ttl: Some("1h".to_string()),
         }
     }
+
+    /// Cache control for a request's [`CachePolicy`], or `None` to add no
+    /// markers. Requests without a policy are cached ephemerally.
+    #[must_use]
+    pub fn for_policy(policy: Option<CachePolicy>) -> Option<Self> {
+        match policy {
+            Some(CachePolicy::None) => None,
+            Some(CachePolicy::OneHour) => Some(Self::one_hour()),
+            _ => Some(Self::ephemeral()),
+        }
+    }
 }
 
 /// Image source for multimodal content.
@@ -137,6 +151,9 @@ pub enum RequestContentBlock {
         /// Whether the tool execution resulted in an error.
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
+        /// Optional cache control.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
     /// Tool use content block (for assistant messages with tool calls).
     ToolUse {
@@ -146,6 +163,9 @@ pub enum RequestContentBlock {
         name: String,
         /// Input arguments as JSON.
         input: serde_json::Value,
+        /// Optional cache control.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
     /// Thinking content block (for extended thinking responses).
     /// Must be included in subsequent requests when thinking was enabled.
@@ -157,6 +177,20 @@ pub enum RequestContentBlock {
     },
 }
 
+impl RequestContentBlock {
+    /// The block's cache marker slot; thinking blocks cannot be cached.
+    const fn cache_control_mut(&mut self) -> Option<&mut Option<CacheControl>> {
+        match self {
+            Self::Text { cache_control, .. }
+            | Self::Image { cache_control, .. }
+            | Self::Document { cache_control, .. }
+            | Self::ToolResult { cache_control, .. }
+            | Self::ToolUse { cache_control, .. } => Some(cache_control),
+            Self::Thinking { .. } => None,
+        }
+    }
+}
+
 /// Content for tool results - can be a string or array of content blocks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -302,6 +336,9 @@ impl From<&neuromance_common::client::ToolChoice> for AnthropicToolChoice {
             neuromance_common::client::ToolChoice::Required => Self::Any {
                 disable_parallel_tool_use: None,
             },
+            neuromance_common::client::ToolChoice::Any { disable_parallel } => Self::Any {
+                disable_parallel_tool_use: disable_parallel.then_some(true),
+            },
             neuromance_common::client::ToolChoice::Function { name } => Self::Tool {
                 name: name.clone(),
                 disable_parallel_tool_use: None,
@@ -668,6 +705,21 @@ pub struct ApiError {
 // Conversion Helpers
 // ============================================================================
 
+/// Convert a non-text message part to an Anthropic content block.
+impl From<&ContentPart> for RequestContentBlock {
+    fn from(part: &ContentPart) -> Self {
+        match part {
+            ContentPart::Image { media_type, data } => Self::Image {
+                source: ImageSource::Base64 {
+                    media_type: media_type.clone(),
+                    data: data.clone(),
+                },
+                cache_control: None,
+            },
+        }
+    }
+}
+
 /// Convert our common Message to Anthropic format.
 impl From<&Message> for AnthropicMessage {
     fn from(message: &Message) -> Self {
@@ -682,6 +734,7 @@ impl From<&Message> for AnthropicMessage {
                     tool_use_id,
                     content: Some(ToolResultContent::Text(message.content.clone())),
                     is_error: None,
+                    cache_control: None,
                 }])
             }
             MessageRole::Assistant if !message.tool_calls.is_empty() => {
@@ -716,6 +769,7 @@ impl From<&Message> for AnthropicMessage {
                         id: tool_call.id.clone(),
                         name: tool_call.function.name.clone(),
                         input,
+                        cache_control: None,
                     });
                 }
 
@@ -748,6 +802,21 @@ impl From<&Message> for AnthropicMessage {
 
                 MessageContent::Blocks(blocks)
             }
+            _ if !message.content_parts.is_empty() => {
+                // Images first, then the text they refer to
+                let mut blocks: Vec<RequestContentBlock> = message
+                    .content_parts
+                    .iter()
+                    .map(RequestContentBlock::from)
+                    .collect();
+                if !message.content.is_empty() {
+                    blocks.push(RequestContentBlock::Text {
+                        text: message.content.clone(),
+                        cache_control: None,
+                    });
+                }
+                MessageContent::Blocks(blocks)
+            }
             _ => {
                 // Regular text content
                 if message.content.is_empty() {
@@ -762,17 +831,38 @@ impl From<&Message> for AnthropicMessage {
     }
 }
 
-/// Converts tools to Anthropic format with cache control on the last tool.
+impl AnthropicMessage {
+    /// Marks the last cacheable block with `cache`, turning plain text
+    /// content into a block so it can carry the marker.
+    fn set_cache_control(&mut self, cache: CacheControl) {
+        if let MessageContent::Text(text) = &mut self.content {
+            self.content = MessageContent::Blocks(vec![RequestContentBlock::Text {
+                text: std::mem::take(text),
+                cache_control: None,
+            }]);
+        }
+        if let MessageContent::Blocks(blocks) = &mut self.content
+            && let Some(slot) = blocks
+                .iter_mut()
+                .rev()
+                .find_map(RequestContentBlock::cache_control_mut)
+        {
+            *slot = Some(cache);
+        }
+    }
+}
+
+/// Converts tools to Anthropic format with `cache` on the last tool.
 ///
 /// Anthropic's prompt caching caches everything up to and including the
 /// content block marked with `cache_control`. By marking the last tool,
 /// we ensure all tool definitions are cached together.
-fn convert_tools_with_caching(tools: &[Tool]) -> Vec<AnthropicTool> {
+fn convert_tools_with_caching(tools: &[Tool], cache: Option<&CacheControl>) -> Vec<AnthropicTool> {
     let mut anthropic_tools: Vec<AnthropicTool> = tools.iter().map(AnthropicTool::from).collect();
 
     // Apply cache control to the last tool for prompt caching
     if let Some(last) = anthropic_tools.last_mut() {
-        last.cache_control = Some(CacheControl::ephemeral());
+        last.cache_control = cache.cloned();
     }
 
     anthropic_tools
@@ -781,13 +871,13 @@ fn convert_tools_with_caching(tools: &[Tool]) -> Vec<AnthropicTool> {
 /// Conversion from `ChatRequest` to Anthropic request format.
 impl From<(&ChatRequest, &Config)> for CreateMessageRequest {
     fn from((request, config): (&ChatRequest, &Config)) -> Self {
-        // Collect all system messages into blocks
+        // Collect all system and developer messages into blocks
         let mut system_blocks: Vec<SystemContentBlock> = Vec::new();
         let mut anthropic_messages: Vec<AnthropicMessage> = Vec::new();
 
         for message in request.messages.iter() {
             match message.role {
-                MessageRole::System => {
+                MessageRole::System | MessageRole::Developer => {
                     system_blocks.push(SystemContentBlock::Text {
                         text: message.content.clone(),
                         cache_control: None,
@@ -799,9 +889,20 @@ impl From<(&ChatRequest, &Config)> for CreateMessageRequest {
             }
         }
 
+        let cache = CacheControl::for_policy(request.cache_control);
+
+        // Cache the history up to the message before the newest one; the
+        // next request in an agent loop extends exactly this prefix.
+        if request.cache_boundary
+            && let Some(cache) = &cache
+            && let Some(boundary) = anthropic_messages.len().checked_sub(2)
+        {
+            anthropic_messages[boundary].set_cache_control(cache.clone());
+        }
+
         // Apply cache control to the last system block for prompt caching
         if let Some(SystemContentBlock::Text { cache_control, .. }) = system_blocks.last_mut() {
-            *cache_control = Some(CacheControl::ephemeral());
+            cache_control.clone_from(&cache);
         }
 
         // Convert collected system blocks to SystemPrompt
@@ -815,12 +916,32 @@ impl From<(&ChatRequest, &Config)> for CreateMessageRequest {
         let tools: Option<Vec<AnthropicTool>> = request
             .tools
             .as_ref()
-            .map(|t| convert_tools_with_caching(t));
+            .map(|t| convert_tools_with_caching(t, cache.as_ref()));
 
         // Convert tool choice if present
         let tool_choice: Option<AnthropicToolChoice> =
             request.tool_choice.as_ref().map(AnthropicToolChoice::from);
 
+        // Anthropic has no response format; a JSON schema is enforced by
+        // forcing a call to a tool that takes the schema as its input.
+        let (tools, tool_choice) = match &request.response_format {
+            Some(ResponseFormat::JsonSchema { name, schema, .. }) => {
+                let mut tools = tools.unwrap_or_default();
+                tools.push(AnthropicTool {
+                    name: name.clone(),
+                    description: format!("Respond with the {name} object."),
+                    input_schema: schema.clone(),
+                    cache_control: None,
+                });
+                let choice = AnthropicToolChoice::Tool {
+                    name: name.clone(),
+                    disable_parallel_tool_use: Some(true),
+                };
+                (Some(tools), Some(choice))
+            }
+            _ => (tools, tool_choice),
+        };
+
         // Create thinking config from ThinkingMode
         let thinking = request.thinking.budget().map(ThinkingConfig::new);
 
@@ -864,6 +985,24 @@ impl From<(&ChatRequest, &Config)> for CreateMessageRequest {
     }
 }
 
+/// Replace the forced structured-output tool call named `name` with its input
+/// as the message text, returning whether one was found.
+///
+/// Undoes the tool-forcing used for [`ResponseFormat::JsonSchema`] so callers
+/// read the JSON from `content` as with other providers.
+pub fn take_structured_output(message: &mut Message, name: &str) -> bool {
+    let Some(position) = message
+        .tool_calls
+        .iter()
+        .position(|call| call.function.name == name)
+    else {
+        return false;
+    };
+    let call = message.tool_calls.remove(position);
+    message.content = call.function.arguments;
+    true
+}
+
 /// Accumulator for building tool calls from streaming `input_json_delta` events.
 #[derive(Debug, Clone)]
 pub struct StreamingToolCall {
@@ -873,6 +1012,8 @@ pub struct StreamingToolCall {
     pub name: String,
     /// Accumulated JSON string.
     pub accumulated_json: String,
+    /// Whether [`finalize`](Self::finalize) repairs truncated JSON.
+    pub repair: bool,
 }
 
 impl StreamingToolCall {
@@ -883,9 +1024,18 @@ impl StreamingToolCall {
             id,
             name,
             accumulated_json: String::new(),
+            repair: false,
         }
     }
 
+    /// Sets whether [`finalize`](Self::finalize) repairs truncated JSON with
+    /// [`parse_repaired`] before giving up.
+    #[must_use]
+    pub const fn with_repair(mut self, enabled: bool) -> Self {
+        self.repair = enabled;
+        self
+    }
+
     /// Appends a delta to the accumulated JSON.
     pub fn append_delta(&mut self, partial_json: &str) {
         self.accumulated_json.push_str(partial_json);
@@ -895,11 +1045,14 @@ impl StreamingToolCall {
     ///
     /// # Errors
     ///
-    /// Returns an error if the accumulated JSON cannot be parsed.
+    /// Returns an error if the accumulated JSON cannot be parsed, or with
+    /// repair enabled, if it cannot be repaired either.
     pub fn finalize(self) -> Result<ToolCall, serde_json::Error> {
         // Parse the accumulated JSON
         let input: serde_json::Value = if self.accumulated_json.is_empty() {
             serde_json::Value::Object(serde_json::Map::new())
+        } else if self.repair {
+            parse_repaired(&self.accumulated_json)?
         } else {
             serde_json::from_str(&self.accumulated_json)?
         };
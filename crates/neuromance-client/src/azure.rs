@@ -0,0 +1,176 @@
+//! Azure `OpenAI` client: the Chat Completions protocol addressed to a deployment.
+//!
+//! Azure serves the same request and response bodies as `OpenAI`, but routes
+//! by deployment rather than model and authenticates with an `api-key`
+//! header instead of a bearer token. [`AzureOpenAIClient`] wraps
+//! [`ChatCompletionsClient`] and requires [`Config::azure`], so every request
+//! goes to
+//! `{base_url}/openai/deployments/{deployment}/chat/completions?api-version={api_version}`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use neuromance_client::AzureOpenAIClient;
+//! use neuromance_common::client::{AzureDeployment, Config};
+//!
+//! let config = Config::new("azure", "gpt-4o")
+//!     .with_api_key("...")
+//!     .with_base_url("https://my-resource.openai.azure.com")
+//!     .with_azure_deployment(AzureDeployment::new("gpt-4o-prod", "2024-10-21"));
+//! let client = AzureOpenAIClient::new(config)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::Stream;
+
+use neuromance_common::client::{Capabilities, ChatChunk, ChatRequest, ChatResponse, Config};
+
+use crate::{ChatCompletionsClient, ClientError, Interceptor, LLMClient};
+
+/// Client for an Azure `OpenAI` chat deployment.
+#[derive(Debug)]
+pub struct AzureOpenAIClient(ChatCompletionsClient);
+
+impl AzureOpenAIClient {
+    /// Create a new Azure `OpenAI` client.
+    ///
+    /// `config.base_url` is the resource endpoint and `config.azure` names the
+    /// deployment and API version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deployment or base URL is missing, the API key
+    /// is missing, or HTTP client creation fails.
+    pub fn new(config: Config) -> Result<Self, ClientError> {
+        if config.azure.is_none() {
+            return Err(ClientError::ConfigurationError(
+                "Azure OpenAI requires a deployment and api-version (Config::azure)".to_string(),
+            ));
+        }
+        if config.base_url.is_none() {
+            return Err(ClientError::ConfigurationError(
+                "Azure OpenAI requires the resource endpoint as base_url".to_string(),
+            ));
+        }
+        Ok(Self(ChatCompletionsClient::new(config)?))
+    }
+
+    /// Set the resource endpoint (e.g., `https://my-resource.openai.azure.com`).
+    #[must_use]
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Self(self.0.with_base_url(base_url))
+    }
+
+    /// Add an [`Interceptor`] to run around every request.
+    #[must_use]
+    pub fn with_interceptor(self, interceptor: Arc<dyn Interceptor>) -> Self {
+        Self(self.0.with_interceptor(interceptor))
+    }
+}
+
+#[async_trait]
+impl LLMClient for AzureOpenAIClient {
+    fn config(&self) -> &Config {
+        self.0.config()
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.0.capabilities()
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        self.0.request_payload(request)
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        self.0.chat(request).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        self.0.chat_stream(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use neuromance_common::chat::Message;
+    use neuromance_common::client::AzureDeployment;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn create_test_config(base_url: &str) -> Config {
+        Config::new("azure", "gpt-4o")
+            .with_api_key("azure-test-key")
+            .with_base_url(base_url)
+            .with_azure_deployment(AzureDeployment::new("gpt-4o-prod", "2024-10-21"))
+    }
+
+    #[tokio::test]
+    async fn test_requests_use_deployment_url_and_api_key_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/openai/deployments/gpt-4o-prod/chat/completions"))
+            .and(query_param("api-version", "2024-10-21"))
+            .and(header("api-key", "azure-test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "chatcmpl-123",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "gpt-4o",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hello from Azure"},
+                    "finish_reason": "stop"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client =
+            AzureOpenAIClient::new(create_test_config(&format!("{}/", server.uri()))).unwrap();
+
+        let request = ChatRequest::new(vec![Message::user(uuid::Uuid::new_v4(), "Hi")]);
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.message.content, "Hello from Azure");
+        let received = server.received_requests().await.unwrap();
+        assert!(received[0].headers.get("authorization").is_none());
+    }
+
+    #[test]
+    fn test_missing_deployment_or_endpoint_is_rejected() {
+        let mut config = create_test_config("https://my-resource.openai.azure.com");
+        config.azure = None;
+        assert!(matches!(
+            AzureOpenAIClient::new(config),
+            Err(ClientError::ConfigurationError(_))
+        ));
+
+        let mut config = create_test_config("https://my-resource.openai.azure.com");
+        config.base_url = None;
+        assert!(matches!(
+            AzureOpenAIClient::new(config),
+            Err(ClientError::ConfigurationError(_))
+        ));
+    }
+}
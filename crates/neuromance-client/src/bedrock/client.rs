@@ -0,0 +1,648 @@
+//! AWS Bedrock client implementation.
+//!
+//! Calls `InvokeModel` and `InvokeModelWithResponseStream` with `SigV4`-signed
+//! requests. Claude responses and stream events are decoded with the
+//! [`anthropic`](crate::anthropic) types; Titan Text uses its own.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use neuromance_client::{BedrockClient, LLMClient};
+//! use neuromance_common::chat::Message;
+//! use neuromance_common::client::{AwsCredentials, ChatRequest, Config};
+//! use uuid::Uuid;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let config = Config::new("bedrock", "anthropic.claude-3-5-sonnet-20240620-v1:0")
+//!     .with_aws_credentials(AwsCredentials::new("AKIA...", "secret", "us-east-1"));
+//! let client = BedrockClient::new(config)?;
+//!
+//! let request = ChatRequest::new(vec![Message::user(Uuid::new_v4(), "Hello!")]);
+//! let response = client.chat(&request).await?;
+//! println!("{}", response.message.content);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::Stream;
+use reqwest_middleware::ClientWithMiddleware;
+use tracing::error;
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{
+    AwsCredentials, Capabilities, ChatChunk, ChatRequest, ChatResponse, Config, FinishReason,
+    ResponseFormat, Usage,
+};
+
+use crate::anthropic::{
+    AnthropicClient, MessageResponse, StreamEvent, client::AnthropicStreamState,
+    take_structured_output,
+};
+use crate::error::ClientError;
+use crate::request_tracing::{traced_chat, traced_stream};
+use crate::streaming::{ChatChunkStream, StreamingProvider};
+use crate::transport::{WithHeader, send_json};
+use crate::{LLMClient, base_capabilities, build_http_clients};
+
+use super::eventstream::run_event_stream;
+use super::sigv4::{sign, uri_encode};
+use super::{
+    ModelFamily, SIGNING_SERVICE, TitanRequest, TitanResponse, TitanStreamChunk, anthropic_body,
+    default_base_url, titan_finish_reason, titan_usage,
+};
+
+/// Client for the AWS Bedrock runtime API.
+///
+/// Supports Anthropic Claude models (with tools, thinking, and streaming) and
+/// Amazon Titan Text models (plain text only). Requests are signed with the
+/// [`AwsCredentials`] from [`Config::aws`]; no API key is used.
+///
+/// # Security
+///
+/// The secret access key and session token are stored using the `secrecy`
+/// crate and never appear in debug output.
+#[derive(Clone)]
+pub struct BedrockClient {
+    client: ClientWithMiddleware,
+    streaming_client: reqwest::Client,
+    credentials: Arc<AwsCredentials>,
+    base_url: String,
+    config: Arc<Config>,
+}
+
+impl std::fmt::Debug for BedrockClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BedrockClient")
+            .field("credentials", &self.credentials)
+            .field("base_url", &self.base_url)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BedrockClient {
+    /// Create a new Bedrock client from a configuration.
+    ///
+    /// The endpoint defaults to the Bedrock runtime in the credentials'
+    /// region; `config.base_url` overrides it (e.g. for a VPC endpoint).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::ConfigurationError`] if `config.aws` is unset,
+    /// or an error if HTTP client creation fails.
+    pub fn new(config: Config) -> Result<Self, ClientError> {
+        let credentials = config.aws.clone().ok_or_else(|| {
+            ClientError::ConfigurationError("AWS credentials are required".to_string())
+        })?;
+        let base_url = config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| default_base_url(&credentials.region));
+        let (client, streaming_client) = build_http_clients(&config, None)?;
+
+        Ok(Self {
+            client,
+            streaming_client,
+            credentials: Arc::new(credentials),
+            base_url,
+            config: Arc::new(config),
+        })
+    }
+
+    /// Set a custom base URL for the API endpoint.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        Arc::make_mut(&mut self.config).base_url = Some(base_url.clone());
+        self.base_url = base_url;
+        self
+    }
+
+    /// Set the Bedrock model id (or inference profile id) to invoke.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.config).model = model.into();
+        self
+    }
+
+    fn model<'a>(&'a self, request: &'a ChatRequest) -> &'a str {
+        request.model.as_deref().unwrap_or(&self.config.model)
+    }
+
+    /// Serialize the family-specific request body.
+    fn request_body(
+        &self,
+        request: &ChatRequest,
+        family: ModelFamily,
+    ) -> Result<serde_json::Value, ClientError> {
+        match family {
+            ModelFamily::Anthropic => anthropic_body(request, &self.config),
+            ModelFamily::Titan => Ok(serde_json::to_value(TitanRequest::from((
+                request,
+                self.config.as_ref(),
+            )))?),
+        }
+    }
+
+    /// Endpoint URL for `action` (`invoke` or `invoke-with-response-stream`).
+    fn endpoint(&self, model: &str, action: &str) -> Result<reqwest::Url, ClientError> {
+        let url = format!("{}/model/{}/{action}", self.base_url, uri_encode(model));
+        reqwest::Url::parse(&url)
+            .map_err(|e| ClientError::ConfigurationError(format!("Invalid URL '{url}': {e}")))
+    }
+
+    /// Attach the content headers and the `SigV4` signature over them, `url`,
+    /// and `body`, which must be sent unchanged.
+    fn sign_request<B: WithHeader>(
+        &self,
+        builder: B,
+        url: &reqwest::Url,
+        accept: &str,
+        body: &[u8],
+    ) -> B {
+        let signed = sign(
+            &self.credentials,
+            SIGNING_SERVICE,
+            "POST",
+            url,
+            &[("accept", accept), ("content-type", "application/json")],
+            body,
+            Utc::now(),
+        );
+        let builder = builder
+            .header("accept", accept)
+            .header("content-type", "application/json")
+            .header("x-amz-date", &signed.amz_date)
+            .header("authorization", &signed.authorization);
+        match &signed.security_token {
+            Some(token) => builder.header("x-amz-security-token", token),
+            None => builder,
+        }
+    }
+
+    fn claude_response(
+        request: &ChatRequest,
+        response: MessageResponse,
+        conversation_id: uuid::Uuid,
+    ) -> ChatResponse {
+        let mut message = AnthropicClient::convert_response_to_message(&response, conversation_id);
+
+        let finish_reason = if let Some(ResponseFormat::JsonSchema { name, .. }) =
+            &request.response_format
+            && take_structured_output(&mut message, name)
+        {
+            Some(FinishReason::Stop)
+        } else {
+            response.stop_reason.map(std::convert::Into::into)
+        };
+
+        ChatResponse {
+            message,
+            model: response.model,
+            usage: Some(Usage::from(response.usage)),
+            finish_reason,
+            created_at: Utc::now(),
+            response_id: Some(response.id),
+            metadata: HashMap::new(),
+            additional_choices: Vec::new(),
+        }
+    }
+
+    fn titan_response(
+        model: &str,
+        response: TitanResponse,
+        conversation_id: uuid::Uuid,
+    ) -> Result<ChatResponse, ClientError> {
+        let result = response.results.into_iter().next().ok_or_else(|| {
+            ClientError::InvalidResponse("Titan response contained no results".to_string())
+        })?;
+
+        Ok(ChatResponse {
+            message: Message::new(conversation_id, MessageRole::Assistant, result.output_text),
+            model: model.to_string(),
+            usage: Some(titan_usage(
+                response.input_text_token_count,
+                result.token_count,
+            )),
+            finish_reason: result.completion_reason.as_deref().map(titan_finish_reason),
+            created_at: Utc::now(),
+            response_id: None,
+            metadata: HashMap::new(),
+            additional_choices: Vec::new(),
+        })
+    }
+}
+
+impl BedrockClient {
+    /// [`LLMClient::chat`] without its request span.
+    async fn chat_inner(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        self.validate_request(request)?;
+
+        let model = self.model(request);
+        let family = ModelFamily::from_model_id(model)?;
+        if family == ModelFamily::Titan && request.has_tools() {
+            return Err(ClientError::ToolsNotSupported);
+        }
+
+        let conversation_id = request
+            .messages
+            .first()
+            .ok_or_else(|| {
+                error!("Request has no messages despite passing validation");
+                ClientError::InvalidRequest("Request must contain at least one message".to_string())
+            })?
+            .conversation_id;
+
+        let url = self.endpoint(model, "invoke")?;
+        let body = serde_json::to_vec(&self.request_body(request, family)?)?;
+        let request_builder = self
+            .sign_request(
+                self.client.post(url.clone()),
+                &url,
+                "application/json",
+                &body,
+            )
+            .body(body);
+
+        match family {
+            ModelFamily::Anthropic => Ok(Self::claude_response(
+                request,
+                send_json(request_builder).await?,
+                conversation_id,
+            )),
+            ModelFamily::Titan => {
+                Self::titan_response(model, send_json(request_builder).await?, conversation_id)
+            }
+        }
+    }
+
+    /// [`LLMClient::chat_stream`] without its request span.
+    async fn chat_stream_inner(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<ChatChunkStream, ClientError> {
+        self.validate_request(request)?;
+
+        let model = self.model(request);
+        let family = ModelFamily::from_model_id(model)?;
+        if family == ModelFamily::Titan && request.has_tools() {
+            return Err(ClientError::ToolsNotSupported);
+        }
+
+        let url = self.endpoint(model, "invoke-with-response-stream")?;
+        let body = serde_json::to_vec(&self.request_body(request, family)?)?;
+        let request_builder = self
+            .sign_request(
+                self.streaming_client.post(url.clone()),
+                &url,
+                "application/vnd.amazon.eventstream",
+                &body,
+            )
+            .body(body);
+
+        match family {
+            ModelFamily::Anthropic => {
+                let stream = ClaudeStream {
+                    model: model.to_string(),
+                    repair_tool_arguments: self.config.repair_tool_arguments,
+                };
+                run_event_stream(&stream, request_builder).await
+            }
+            ModelFamily::Titan => {
+                run_event_stream(&TitanStream(model.to_string()), request_builder).await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for BedrockClient {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn supports_tools(&self) -> bool {
+        ModelFamily::from_model_id(&self.config.model).ok() == Some(ModelFamily::Anthropic)
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        let claude =
+            ModelFamily::from_model_id(&self.config.model).ok() == Some(ModelFamily::Anthropic);
+        Capabilities {
+            parallel_tool_calls: claude,
+            json_schema: claude,
+            reasoning: claude,
+            ..base_capabilities(self)
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        let family = ModelFamily::from_model_id(self.model(request)).ok()?;
+        self.request_body(request, family).ok()
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        traced_chat(self.config(), request, self.chat_inner(request)).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        traced_stream(self.config(), request, self.chat_stream_inner(request)).await
+    }
+}
+
+/// Streams Claude's Messages events, which Bedrock wraps unchanged.
+struct ClaudeStream {
+    model: String,
+    repair_tool_arguments: bool,
+}
+
+impl StreamingProvider for ClaudeStream {
+    type Event = StreamEvent;
+    type State = AnthropicStreamState;
+
+    fn initial_state(&self) -> Self::State {
+        AnthropicStreamState::new(self.model.clone(), self.repair_tool_arguments)
+    }
+
+    fn process_event(
+        state: &mut Self::State,
+        event: Self::Event,
+    ) -> Option<Result<ChatChunk, ClientError>> {
+        AnthropicClient::process_event(state, event)
+    }
+}
+
+/// Streams Titan Text chunks.
+struct TitanStream(String);
+
+/// Model id, and the prompt token count from the first chunk.
+struct TitanStreamState {
+    model: String,
+    input_tokens: u32,
+}
+
+impl StreamingProvider for TitanStream {
+    type Event = TitanStreamChunk;
+    type State = TitanStreamState;
+
+    fn initial_state(&self) -> Self::State {
+        TitanStreamState {
+            model: self.0.clone(),
+            input_tokens: 0,
+        }
+    }
+
+    fn process_event(
+        state: &mut Self::State,
+        event: Self::Event,
+    ) -> Option<Result<ChatChunk, ClientError>> {
+        if let Some(tokens) = event.input_text_token_count {
+            state.input_tokens = tokens;
+        }
+        let finish_reason = event.completion_reason.as_deref().map(titan_finish_reason);
+        let usage = event
+            .total_output_text_token_count
+            .filter(|_| finish_reason.is_some())
+            .map(|output| titan_usage(state.input_tokens, output));
+
+        Some(Ok(ChatChunk {
+            model: state.model.clone(),
+            delta_content: (!event.output_text.is_empty()).then_some(event.output_text),
+            delta_reasoning_content: None,
+            delta_role: Some(MessageRole::Assistant),
+            delta_tool_calls: None,
+            finish_reason,
+            usage,
+            response_id: None,
+            created_at: Utc::now(),
+            metadata: HashMap::new(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use futures::StreamExt;
+    use neuromance_common::chat::Message;
+    use neuromance_common::tools::{Function, Tool};
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    use super::super::eventstream::encode_chunk;
+    use super::*;
+
+    const CLAUDE: &str = "anthropic.claude-3-5-sonnet-20240620-v1:0";
+    const TITAN: &str = "amazon.titan-text-express-v1";
+
+    fn client(server: &MockServer, model: &str) -> BedrockClient {
+        let config = Config::new("bedrock", model).with_aws_credentials(AwsCredentials::new(
+            "AKIDEXAMPLE",
+            "secret",
+            "us-east-1",
+        ));
+        BedrockClient::new(config)
+            .unwrap()
+            .with_base_url(server.uri())
+    }
+
+    fn request(text: &str) -> ChatRequest {
+        ChatRequest::new(vec![Message::user(uuid::Uuid::new_v4(), text)])
+    }
+
+    #[test]
+    fn test_new_requires_aws_credentials() {
+        let result = BedrockClient::new(Config::new("bedrock", CLAUDE));
+        assert!(matches!(result, Err(ClientError::ConfigurationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_claude_chat_is_signed_and_sends_model_in_path() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/model/anthropic.claude-3-5-sonnet-20240620-v1%3A0/invoke",
+            ))
+            .and(header_exists("x-amz-date"))
+            .and(header("content-type", "application/json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "model": CLAUDE,
+                "content": [{ "type": "text", "text": "Hello!" }],
+                "stop_reason": "end_turn",
+                "usage": { "input_tokens": 5, "output_tokens": 2 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let response = client(&server, CLAUDE).chat(&request("Hi")).await.unwrap();
+
+        assert_eq!(response.message.content, "Hello!");
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(response.usage.unwrap().total_tokens, 7);
+
+        let sent: Vec<Request> = server.received_requests().await.unwrap();
+        let authorization = sent[0].headers["authorization"].to_str().unwrap();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+        assert!(authorization.contains("/us-east-1/bedrock/aws4_request"));
+        let body: serde_json::Value = serde_json::from_slice(&sent[0].body).unwrap();
+        assert!(body.get("model").is_none());
+        assert_eq!(body["anthropic_version"], "bedrock-2023-05-31");
+    }
+
+    #[tokio::test]
+    async fn test_titan_chat_parses_results() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/model/amazon.titan-text-express-v1/invoke"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "inputTextTokenCount": 4,
+                "results": [{
+                    "tokenCount": 3,
+                    "outputText": " Hello there",
+                    "completionReason": "LENGTH"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let response = client(&server, TITAN).chat(&request("Hi")).await.unwrap();
+
+        assert_eq!(response.message.content, " Hello there");
+        assert_eq!(response.finish_reason, Some(FinishReason::Length));
+        assert_eq!(response.usage.unwrap().prompt_tokens, 4);
+    }
+
+    #[tokio::test]
+    async fn test_titan_rejects_tools() {
+        let server = MockServer::start().await;
+        let tool = Tool::builder()
+            .function(Function {
+                name: "lookup".to_string(),
+                description: "Look something up".to_string(),
+                parameters: serde_json::json!({ "type": "object" }),
+            })
+            .build();
+        let request = request("Hi").with_tools(vec![tool]);
+
+        let result = client(&server, TITAN).chat(&request).await;
+        assert!(matches!(result, Err(ClientError::ToolsNotSupported)));
+    }
+
+    #[tokio::test]
+    async fn test_claude_stream_decodes_event_stream_frames() {
+        let server = MockServer::start().await;
+        let events = [
+            serde_json::json!({
+                "type": "message_start",
+                "message": {
+                    "id": "msg_1", "type": "message", "role": "assistant",
+                    "model": CLAUDE, "content": [],
+                    "usage": { "input_tokens": 5, "output_tokens": 0 }
+                }
+            }),
+            serde_json::json!({
+                "type": "content_block_start", "index": 0,
+                "content_block": { "type": "text", "text": "" }
+            }),
+            serde_json::json!({
+                "type": "content_block_delta", "index": 0,
+                "delta": { "type": "text_delta", "text": "Hel" }
+            }),
+            serde_json::json!({
+                "type": "content_block_delta", "index": 0,
+                "delta": { "type": "text_delta", "text": "lo" }
+            }),
+            serde_json::json!({
+                "type": "message_delta",
+                "delta": { "stop_reason": "end_turn" },
+                "usage": { "output_tokens": 2 }
+            }),
+            serde_json::json!({ "type": "message_stop" }),
+        ];
+        let body: Vec<u8> = events.iter().flat_map(encode_chunk).collect();
+        Mock::given(method("POST"))
+            .and(path(
+                "/model/anthropic.claude-3-5-sonnet-20240620-v1%3A0/invoke-with-response-stream",
+            ))
+            .and(header("accept", "application/vnd.amazon.eventstream"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(body, "application/vnd.amazon.eventstream"),
+            )
+            .mount(&server)
+            .await;
+
+        let chunks: Vec<ChatChunk> = client(&server, CLAUDE)
+            .chat_stream(&request("Hi"))
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = chunks
+            .iter()
+            .filter_map(|c| c.delta_content.as_deref())
+            .collect();
+        assert_eq!(text, "Hello");
+        assert!(
+            chunks
+                .iter()
+                .any(|c| c.finish_reason == Some(FinishReason::Stop))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_titan_stream_reports_usage_on_final_chunk() {
+        let server = MockServer::start().await;
+        let body: Vec<u8> = [
+            serde_json::json!({ "outputText": "Hi", "index": 0, "inputTextTokenCount": 3 }),
+            serde_json::json!({
+                "outputText": " there", "index": 0,
+                "totalOutputTextTokenCount": 2, "completionReason": "FINISH"
+            }),
+        ]
+        .iter()
+        .flat_map(encode_chunk)
+        .collect();
+        Mock::given(method("POST"))
+            .and(path(
+                "/model/amazon.titan-text-express-v1/invoke-with-response-stream",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(body, "application/vnd.amazon.eventstream"),
+            )
+            .mount(&server)
+            .await;
+
+        let chunks: Vec<ChatChunk> = client(&server, TITAN)
+            .chat_stream(&request("Hi"))
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].finish_reason, Some(FinishReason::Stop));
+        assert_eq!(chunks[1].usage.as_ref().unwrap().total_tokens, 5);
+    }
+}
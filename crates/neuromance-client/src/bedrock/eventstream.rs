@@ -0,0 +1,364 @@
+//! Bedrock's `application/vnd.amazon.eventstream` response driver.
+//!
+//! `invoke-model-with-response-stream` answers with binary event-stream
+//! frames rather than SSE. Each frame is
+//!
+//! ```text
+//! total length (u32) | headers length (u32) | prelude CRC32 (u32)
+//! headers | payload | message CRC32 (u32)
+//! ```
+//!
+//! with big-endian integers. Model output arrives as `chunk` events whose
+//! payload is `{"bytes": "<base64>"}`, the base64 wrapping the model's own
+//! streaming JSON; failures arrive as `exception` messages.
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use futures::StreamExt;
+use serde::Deserialize;
+use tracing::{debug, error, warn};
+
+use crate::error::ClientError;
+use crate::streaming::{ChatChunkStream, StreamingProvider};
+use crate::transport::map_http_error;
+
+/// Prelude (lengths and their CRC) plus the trailing message CRC.
+const FRAME_OVERHEAD: usize = 16;
+
+/// One decoded event-stream message. Only string-valued headers are kept;
+/// Bedrock sends no others that matter here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub headers: HashMap<String, String>,
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+}
+
+/// Removes and decodes the first complete frame in `buffer`.
+///
+/// Returns `Ok(None)` if `buffer` does not yet hold a whole frame.
+///
+/// # Errors
+///
+/// Returns [`ClientError::InvalidResponse`] if the frame is malformed or a
+/// checksum does not match.
+pub fn decode_frame(buffer: &mut Vec<u8>) -> Result<Option<Frame>, ClientError> {
+    if buffer.len() < 12 {
+        return Ok(None);
+    }
+    let total_len = read_u32(buffer, 0) as usize;
+    let headers_len = read_u32(buffer, 4) as usize;
+    if total_len < FRAME_OVERHEAD + headers_len {
+        return Err(invalid("frame length is shorter than its headers"));
+    }
+    if read_u32(buffer, 8) != crc32fast::hash(&buffer[..8]) {
+        return Err(invalid("prelude checksum mismatch"));
+    }
+    if buffer.len() < total_len {
+        return Ok(None);
+    }
+
+    let frame: Vec<u8> = buffer.drain(..total_len).collect();
+    if read_u32(&frame, total_len - 4) != crc32fast::hash(&frame[..total_len - 4]) {
+        return Err(invalid("message checksum mismatch"));
+    }
+    let headers = decode_headers(&frame[12..12 + headers_len])?;
+    let payload = frame[12 + headers_len..total_len - 4].to_vec();
+    Ok(Some(Frame { headers, payload }))
+}
+
+fn decode_headers(mut bytes: &[u8]) -> Result<HashMap<String, String>, ClientError> {
+    let mut headers = HashMap::new();
+    while let Some((&name_len, rest)) = bytes.split_first() {
+        let (name, rest) = split(rest, usize::from(name_len))?;
+        let (&value_type, rest) = rest
+            .split_first()
+            .ok_or_else(|| invalid("truncated header"))?;
+        let value_len = match value_type {
+            // bool true / bool false carry no value bytes.
+            0 | 1 => 0,
+            2 => 1,
+            3 => 2,
+            4 => 4,
+            5 | 8 => 8,
+            9 => 16,
+            6 | 7 => {
+                let (len, _) = split(rest, 2)?;
+                2 + usize::from(u16::from_be_bytes([len[0], len[1]]))
+            }
+            other => return Err(invalid(&format!("unknown header value type {other}"))),
+        };
+        let (value, rest) = split(rest, value_len)?;
+        if value_type == 7 {
+            headers.insert(
+                String::from_utf8_lossy(name).into_owned(),
+                String::from_utf8_lossy(&value[2..]).into_owned(),
+            );
+        }
+        bytes = rest;
+    }
+    Ok(headers)
+}
+
+fn split(bytes: &[u8], at: usize) -> Result<(&[u8], &[u8]), ClientError> {
+    if bytes.len() < at {
+        return Err(invalid("truncated header"));
+    }
+    Ok(bytes.split_at(at))
+}
+
+const fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+fn invalid(reason: &str) -> ClientError {
+    ClientError::InvalidResponse(format!("malformed event stream: {reason}"))
+}
+
+#[derive(Deserialize)]
+struct ChunkPayload {
+    bytes: String,
+}
+
+#[derive(Deserialize)]
+struct ExceptionPayload {
+    #[serde(default)]
+    message: String,
+}
+
+/// Maps a Bedrock `exception` message to the closest [`ClientError`].
+fn exception_error(frame: &Frame) -> ClientError {
+    let kind = frame.header(":exception-type").unwrap_or("exception");
+    let message = serde_json::from_slice::<ExceptionPayload>(&frame.payload)
+        .map(|p| p.message)
+        .unwrap_or_default();
+    let message = format!("{kind}: {message}");
+    match kind {
+        "throttlingException" => ClientError::RateLimitError { retry_after: None },
+        "internalServerException" | "serviceUnavailableException" | "modelStreamErrorException" => {
+            ClientError::ServiceUnavailable(message)
+        }
+        _ => ClientError::RequestError(message),
+    }
+}
+
+/// Unwraps a `chunk` event into the model's own streaming event.
+fn chunk_event<E: serde::de::DeserializeOwned>(frame: &Frame) -> Result<E, ClientError> {
+    let chunk: ChunkPayload = serde_json::from_slice(&frame.payload)?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(chunk.bytes)
+        .map_err(|e| ClientError::InvalidResponse(format!("invalid chunk encoding: {e}")))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Send `request` and stream its event-stream body through a
+/// [`StreamingProvider`], yielding a stream of chunks.
+///
+/// Behaviour mirrors [`run_ndjson_stream`](crate::streaming::run_ndjson_stream):
+/// - Frames may be split across network reads; they are buffered until whole.
+/// - A `chunk` whose inner JSON fails to parse yields an error but does not
+///   terminate the stream.
+/// - An `exception` message, a corrupt frame, or a body read error yields an
+///   error and terminates the stream.
+///
+/// # Errors
+///
+/// The request is sent before the stream is returned, so transport failures
+/// surface as [`ClientError::NetworkError`] and HTTP error statuses as the
+/// variant chosen by [`map_http_error`].
+pub async fn run_event_stream<P: StreamingProvider + Sync>(
+    provider: &P,
+    request: reqwest::RequestBuilder,
+) -> Result<ChatChunkStream, ClientError> {
+    let response = request.send().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let error_text = response.text().await.unwrap_or_default();
+        let error = map_http_error(status, &headers, &error_text);
+        error!("API error: {error}");
+        return Err(error);
+    }
+
+    let stream = futures::stream::unfold(
+        FrameState {
+            bytes: Box::pin(response.bytes_stream().fuse()),
+            buffer: Vec::new(),
+            provider_state: provider.initial_state(),
+            terminated: false,
+        },
+        |mut s| async move {
+            loop {
+                if s.terminated {
+                    return None;
+                }
+                let frame = match decode_frame(&mut s.buffer) {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => {
+                        match s.bytes.next().await {
+                            Some(Ok(bytes)) => s.buffer.extend_from_slice(&bytes),
+                            Some(Err(e)) => {
+                                let error = ClientError::NetworkError(e);
+                                error!("Stream error: {error}");
+                                s.terminated = true;
+                                return Some((Err(error), s));
+                            }
+                            None => {
+                                if !s.buffer.is_empty() {
+                                    warn!(bytes = s.buffer.len(), "Event stream ended mid-frame");
+                                }
+                                debug!("Event stream completed");
+                                return None;
+                            }
+                        }
+                        continue;
+                    }
+                    Err(error) => {
+                        s.terminated = true;
+                        return Some((Err(error), s));
+                    }
+                };
+
+                match frame.header(":message-type") {
+                    Some("exception" | "error") => {
+                        let error = exception_error(&frame);
+                        error!("Stream error from API: {error}");
+                        s.terminated = true;
+                        return Some((Err(error), s));
+                    }
+                    Some("event") if frame.header(":event-type") == Some("chunk") => {
+                        match chunk_event::<P::Event>(&frame) {
+                            Ok(event) => {
+                                if let Some(item) = P::process_event(&mut s.provider_state, event) {
+                                    return Some((item, s));
+                                }
+                            }
+                            Err(e) => {
+                                warn!("Failed to parse streaming chunk: {e}");
+                                return Some((Err(e), s));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        },
+    );
+
+    Ok(Box::pin(stream))
+}
+
+/// Internal carrier for state threaded through the unfold closure.
+struct FrameState<B, S> {
+    bytes: B,
+    buffer: Vec<u8>,
+    provider_state: S,
+    terminated: bool,
+}
+
+/// Encodes one frame with string headers, as Bedrock would send it.
+#[cfg(test)]
+pub fn encode_frame(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+    let mut header_bytes = Vec::new();
+    for (name, value) in headers {
+        header_bytes.push(u8::try_from(name.len()).unwrap_or(u8::MAX));
+        header_bytes.extend_from_slice(name.as_bytes());
+        header_bytes.push(7);
+        header_bytes
+            .extend_from_slice(&u16::try_from(value.len()).unwrap_or(u16::MAX).to_be_bytes());
+        header_bytes.extend_from_slice(value.as_bytes());
+    }
+    let total_len = FRAME_OVERHEAD + header_bytes.len() + payload.len();
+    let mut frame = Vec::with_capacity(total_len);
+    frame.extend_from_slice(&u32::try_from(total_len).unwrap_or(u32::MAX).to_be_bytes());
+    frame.extend_from_slice(
+        &u32::try_from(header_bytes.len())
+            .unwrap_or(u32::MAX)
+            .to_be_bytes(),
+    );
+    frame.extend_from_slice(&crc32fast::hash(&frame).to_be_bytes());
+    frame.extend_from_slice(&header_bytes);
+    frame.extend_from_slice(payload);
+    frame.extend_from_slice(&crc32fast::hash(&frame).to_be_bytes());
+    frame
+}
+
+/// A `chunk` event wrapping `inner` the way Bedrock does.
+#[cfg(test)]
+pub fn encode_chunk(inner: &serde_json::Value) -> Vec<u8> {
+    let bytes = base64::engine::general_purpose::STANDARD.encode(inner.to_string());
+    encode_frame(
+        &[
+            (":message-type", "event"),
+            (":event-type", "chunk"),
+            (":content-type", "application/json"),
+        ],
+        serde_json::json!({ "bytes": bytes }).to_string().as_bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_decode_frame_waits_for_whole_frame() {
+        let frame = encode_frame(&[(":event-type", "chunk")], b"payload");
+        let mut buffer = frame[..frame.len() - 1].to_vec();
+        assert_eq!(decode_frame(&mut buffer).unwrap(), None);
+
+        buffer.push(frame[frame.len() - 1]);
+        buffer.extend_from_slice(&encode_frame(&[], b"next"));
+        let decoded = decode_frame(&mut buffer).unwrap().unwrap();
+        assert_eq!(decoded.header(":event-type"), Some("chunk"));
+        assert_eq!(decoded.payload, b"payload");
+
+        let next = decode_frame(&mut buffer).unwrap().unwrap();
+        assert_eq!(next.payload, b"next");
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_decode_frame_rejects_corrupt_checksum() {
+        let mut frame = encode_frame(&[(":event-type", "chunk")], b"payload");
+        let last = frame.len() - 5;
+        frame[last] ^= 0xff;
+        assert!(matches!(
+            decode_frame(&mut frame),
+            Err(ClientError::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_chunk_event_unwraps_base64_payload() {
+        let mut buffer = encode_chunk(&serde_json::json!({ "text": "hi" }));
+        let frame = decode_frame(&mut buffer).unwrap().unwrap();
+        let event: serde_json::Value = chunk_event(&frame).unwrap();
+        assert_eq!(event["text"], "hi");
+    }
+
+    #[test]
+    fn test_exception_maps_throttling_to_rate_limit() {
+        let mut buffer = encode_frame(
+            &[
+                (":message-type", "exception"),
+                (":exception-type", "throttlingException"),
+            ],
+            br#"{"message":"Too many requests"}"#,
+        );
+        let frame = decode_frame(&mut buffer).unwrap().unwrap();
+        assert!(matches!(
+            exception_error(&frame),
+            ClientError::RateLimitError { .. }
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+//! AWS Bedrock `InvokeModel` API types and client implementation.
+//!
+//! Bedrock hosts several model families behind one `SigV4`-signed endpoint,
+//! each keeping its vendor's request and response bodies. This module shapes
+//! those bodies for the families [`BedrockClient`] supports:
+//!
+//! - **Anthropic Claude** (`anthropic.*` and cross-region `us.anthropic.*`
+//!   ids) reuse the [`anthropic`](crate::anthropic) Messages types, with the
+//!   model moving into the URL and the API version into the body.
+//! - **Amazon Titan Text** (`amazon.titan-text-*`) takes a flat prompt and
+//!   supports neither tools nor system prompts, so the conversation is
+//!   rendered as a `User:`/`Bot:` transcript.
+
+use serde::{Deserialize, Serialize};
+
+use neuromance_common::chat::MessageRole;
+use neuromance_common::client::{ChatRequest, Config, FinishReason, Usage};
+
+use crate::anthropic::{CreateMessageRequest, INTERLEAVED_THINKING_BETA};
+use crate::error::ClientError;
+
+pub mod client;
+mod eventstream;
+mod sigv4;
+
+pub use client::BedrockClient;
+
+/// `anthropic_version` Bedrock expects in Claude request bodies.
+pub const BEDROCK_ANTHROPIC_VERSION: &str = "bedrock-2023-05-31";
+
+/// Service name Bedrock runtime requests are signed for.
+pub const SIGNING_SERVICE: &str = "bedrock";
+
+/// Default Bedrock runtime endpoint for `region`.
+#[must_use]
+pub fn default_base_url(region: &str) -> String {
+    format!("https://bedrock-runtime.{region}.amazonaws.com")
+}
+
+/// Model family, which decides the request and response body shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelFamily {
+    /// Anthropic Claude models.
+    Anthropic,
+    /// Amazon Titan Text models.
+    Titan,
+}
+
+impl ModelFamily {
+    /// Detects the family from a Bedrock model id or inference profile id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::ConfigurationError`] for model ids from other
+    /// families.
+    pub fn from_model_id(model: &str) -> Result<Self, ClientError> {
+        // Inference profiles prefix the model id with a region group
+        // (e.g. `us.anthropic.claude-...`).
+        let id = model
+            .split_once('.')
+            .filter(|(prefix, _)| prefix.len() <= 4 && !prefix.contains('-'))
+            .filter(|(prefix, _)| *prefix != "amazon" && *prefix != "anthropic")
+            .map_or(model, |(_, rest)| rest);
+        if id.starts_with("anthropic.") {
+            Ok(Self::Anthropic)
+        } else if id.starts_with("amazon.titan-text") {
+            Ok(Self::Titan)
+        } else {
+            Err(ClientError::ConfigurationError(format!(
+                "unsupported Bedrock model '{model}'; expected an anthropic.* or \
+                 amazon.titan-text-* model"
+            )))
+        }
+    }
+}
+
+/// Builds the Claude request body: the Anthropic Messages request without
+/// `model` and `stream`, which Bedrock takes from the URL, plus the Bedrock
+/// API version and any beta flags.
+///
+/// # Errors
+///
+/// Returns [`ClientError::SerializationError`] if the request cannot be
+/// serialized.
+pub fn anthropic_body(
+    request: &ChatRequest,
+    config: &Config,
+) -> Result<serde_json::Value, ClientError> {
+    let mut body = serde_json::to_value(CreateMessageRequest::from((request, config)))?;
+    if let Some(fields) = body.as_object_mut() {
+        fields.remove("model");
+        fields.remove("stream");
+        fields.insert(
+            "anthropic_version".to_string(),
+            BEDROCK_ANTHROPIC_VERSION.into(),
+        );
+        if request.thinking.is_interleaved() {
+            fields.insert(
+                "anthropic_beta".to_string(),
+                serde_json::json!([INTERLEAVED_THINKING_BETA]),
+            );
+        }
+    }
+    Ok(body)
+}
+
+// ============================================================================
+// Titan Text
+// ============================================================================
+
+/// Generation parameters for a Titan Text request.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextGenerationConfig {
+    /// Maximum tokens to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_token_count: Option<u32>,
+    /// Sampling temperature (0.0-1.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling threshold (0.0-1.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Stop sequences.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+}
+
+/// Request body for Titan Text models.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TitanRequest {
+    /// The prompt, with the conversation rendered as a transcript.
+    pub input_text: String,
+    /// Generation parameters.
+    pub text_generation_config: TextGenerationConfig,
+}
+
+impl From<(&ChatRequest, &Config)> for TitanRequest {
+    fn from((request, config): (&ChatRequest, &Config)) -> Self {
+        let mut input_text = String::new();
+        for message in request.messages.iter() {
+            let speaker = match message.role {
+                MessageRole::System | MessageRole::Developer => None,
+                MessageRole::Assistant => Some("Bot"),
+                _ => Some("User"),
+            };
+            if !input_text.is_empty() {
+                input_text.push('\n');
+            }
+            if let Some(speaker) = speaker {
+                input_text.push_str(speaker);
+                input_text.push_str(": ");
+            }
+            input_text.push_str(&message.content);
+        }
+        input_text.push_str("\nBot:");
+
+        Self {
+            input_text,
+            text_generation_config: TextGenerationConfig {
+                max_token_count: request.max_tokens.or(config.max_tokens),
+                temperature: request.temperature.or(config.temperature),
+                top_p: request.top_p.or(config.top_p),
+                stop_sequences: request.stop.clone(),
+            },
+        }
+    }
+}
+
+/// One generated result in a Titan Text response.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TitanResult {
+    /// Tokens generated for this result.
+    pub token_count: u32,
+    /// The generated text.
+    pub output_text: String,
+    /// Why generation stopped (e.g. `"FINISH"`, `"LENGTH"`).
+    pub completion_reason: Option<String>,
+}
+
+/// Response body from Titan Text models.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TitanResponse {
+    /// Tokens in the prompt.
+    pub input_text_token_count: u32,
+    /// Generated results; Bedrock returns one.
+    pub results: Vec<TitanResult>,
+}
+
+/// One streamed chunk from a Titan Text model.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TitanStreamChunk {
+    /// Text generated since the previous chunk.
+    #[serde(default)]
+    pub output_text: String,
+    /// Tokens in the prompt.
+    pub input_text_token_count: Option<u32>,
+    /// Tokens generated so far.
+    pub total_output_text_token_count: Option<u32>,
+    /// Set on the final chunk.
+    pub completion_reason: Option<String>,
+}
+
+/// Maps a Titan `completionReason` to a [`FinishReason`].
+#[must_use]
+pub fn titan_finish_reason(reason: &str) -> FinishReason {
+    match reason {
+        "LENGTH" => FinishReason::Length,
+        "CONTENT_FILTERED" => FinishReason::ContentFilter,
+        _ => FinishReason::Stop,
+    }
+}
+
+/// Token usage from Titan's prompt and output counts.
+#[must_use]
+pub const fn titan_usage(input_tokens: u32, output_tokens: u32) -> Usage {
+    Usage {
+        prompt_tokens: input_tokens,
+        completion_tokens: output_tokens,
+        total_tokens: input_tokens.saturating_add(output_tokens),
+        cost: None,
+        input_tokens_details: None,
+        output_tokens_details: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use neuromance_common::chat::Message;
+    use neuromance_common::features::ThinkingMode;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn config() -> Config {
+        Config::new("bedrock", "anthropic.claude-3-5-sonnet-20240620-v1:0")
+    }
+
+    #[test]
+    fn test_model_family_detection() {
+        for id in [
+            "anthropic.claude-3-5-sonnet-20240620-v1:0",
+            "us.anthropic.claude-3-7-sonnet-20250219-v1:0",
+        ] {
+            assert_eq!(
+                ModelFamily::from_model_id(id).unwrap(),
+                ModelFamily::Anthropic
+            );
+        }
+        assert_eq!(
+            ModelFamily::from_model_id("amazon.titan-text-express-v1").unwrap(),
+            ModelFamily::Titan
+        );
+        assert!(ModelFamily::from_model_id("meta.llama3-70b-instruct-v1:0").is_err());
+    }
+
+    #[test]
+    fn test_anthropic_body_moves_model_to_url_and_adds_version() {
+        let conversation_id = Uuid::new_v4();
+        let request = ChatRequest::new(vec![
+            Message::system(conversation_id, "be brief"),
+            Message::user(conversation_id, "hi"),
+        ])
+        .with_max_tokens(256)
+        .with_thinking_mode(ThinkingMode::Interleaved {
+            budget_tokens: 1024,
+        });
+
+        let body = anthropic_body(&request, &config()).unwrap();
+
+        assert!(body.get("model").is_none());
+        assert!(body.get("stream").is_none());
+        assert_eq!(body["anthropic_version"], BEDROCK_ANTHROPIC_VERSION);
+        assert_eq!(body["anthropic_beta"][0], INTERLEAVED_THINKING_BETA);
+        assert_eq!(body["messages"][0]["role"], "user");
+        assert_eq!(body["system"][0]["text"], "be brief");
+        assert!(body["max_tokens"].as_u64().unwrap() > 1024);
+    }
+
+    #[test]
+    fn test_titan_request_renders_transcript() {
+        let conversation_id = Uuid::new_v4();
+        let request = ChatRequest::new(vec![
+            Message::system(conversation_id, "You are terse."),
+            Message::user(conversation_id, "Hi"),
+            Message::assistant(conversation_id, "Hello."),
+            Message::user(conversation_id, "Bye"),
+        ])
+        .with_max_tokens(64)
+        .with_stop_sequences(["User:"]);
+
+        let body = serde_json::to_value(TitanRequest::from((&request, &config()))).unwrap();
+
+        assert_eq!(
+            body["inputText"],
+            "You are terse.\nUser: Hi\nBot: Hello.\nUser: Bye\nBot:"
+        );
+        assert_eq!(body["textGenerationConfig"]["maxTokenCount"], 64);
+        assert_eq!(body["textGenerationConfig"]["stopSequences"][0], "User:");
+        assert!(body["textGenerationConfig"].get("temperature").is_none());
+    }
+}
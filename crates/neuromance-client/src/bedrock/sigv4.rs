@@ -0,0 +1,235 @@
+//! AWS Signature Version 4 request signing.
+//!
+//! Implements the header-based variant of
+//! [`SigV4`](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_sigv-create-signed-request.html)
+//! for the non-S3 services Bedrock runs on: path segments are encoded twice,
+//! and the payload hash is part of the canonical request but not sent as a
+//! header.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use secrecy::ExposeSecret;
+use sha2::{Digest, Sha256};
+
+use neuromance_common::client::AwsCredentials;
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Headers to attach to a request so AWS accepts it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedHeaders {
+    /// Value of `x-amz-date`, which is part of the signature.
+    pub amz_date: String,
+    /// Value of `x-amz-security-token`, for temporary credentials.
+    pub security_token: Option<String>,
+    /// Value of `Authorization`.
+    pub authorization: String,
+}
+
+/// Signs a request to `service` in the credentials' region.
+///
+/// `headers` are the extra headers to cover with the signature, as lowercase
+/// name and value pairs; `host` and `x-amz-date` are always signed, as is
+/// `x-amz-security-token` when the credentials carry a session token. The
+/// request must then be sent with exactly `url`, `headers`, and `body`.
+pub fn sign(
+    credentials: &AwsCredentials,
+    service: &str,
+    method: &str,
+    url: &url::Url,
+    headers: &[(&str, &str)],
+    body: &[u8],
+    now: DateTime<Utc>,
+) -> SignedHeaders {
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let security_token = credentials
+        .session_token
+        .as_ref()
+        .map(|token| token.expose_secret().to_owned());
+
+    let host = url.host_str().unwrap_or_default();
+    let host = url
+        .port()
+        .map_or_else(|| host.to_owned(), |port| format!("{host}:{port}"));
+    let mut signed: Vec<(String, String)> = headers
+        .iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), value.trim().to_owned()))
+        .collect();
+    signed.push(("host".to_owned(), host));
+    signed.push(("x-amz-date".to_owned(), amz_date.clone()));
+    if let Some(token) = &security_token {
+        signed.push(("x-amz-security-token".to_owned(), token.clone()));
+    }
+    signed.sort();
+
+    let canonical_headers = signed.iter().fold(String::new(), |mut out, (name, value)| {
+        let _ = writeln!(out, "{name}:{value}");
+        out
+    });
+    let signed_header_names = signed
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "{method}\n{}\n{}\n{canonical_headers}\n{signed_header_names}\n{}",
+        canonical_uri(url),
+        canonical_query(url),
+        hex::encode(Sha256::digest(body)),
+    );
+
+    let scope = format!("{date}/{}/{service}/aws4_request", credentials.region);
+    let string_to_sign = format!(
+        "{ALGORITHM}\n{amz_date}\n{scope}\n{}",
+        hex::encode(Sha256::digest(canonical_request.as_bytes())),
+    );
+
+    let secret = format!("AWS4{}", credentials.secret_access_key.expose_secret());
+    let key = [
+        date.as_str(),
+        credentials.region.as_str(),
+        service,
+        "aws4_request",
+    ]
+    .iter()
+    .fold(secret.into_bytes(), |key, part| hmac(&key, part.as_bytes()));
+    let signature = hex::encode(hmac(&key, string_to_sign.as_bytes()));
+
+    SignedHeaders {
+        authorization: format!(
+            "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_header_names}, \
+             Signature={signature}",
+            credentials.access_key_id,
+        ),
+        amz_date,
+        security_token,
+    }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key)
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encodes everything except RFC 3986 unreserved characters.
+pub fn uri_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// The URL path with each (already encoded) segment encoded again.
+fn canonical_uri(url: &url::Url) -> String {
+    let path = url.path();
+    if path.is_empty() {
+        return "/".to_owned();
+    }
+    path.split('/')
+        .map(uri_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn canonical_query(url: &url::Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| (uri_encode(&name), uri_encode(&value)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn example_credentials() -> AwsCredentials {
+        AwsCredentials::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "us-east-1",
+        )
+    }
+
+    fn example_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap()
+    }
+
+    /// The `get-vanilla` case from the AWS `SigV4` test suite.
+    #[test]
+    fn test_sign_matches_aws_test_suite() {
+        let url = url::Url::parse("https://example.amazonaws.com/").unwrap();
+        let signed = sign(
+            &example_credentials(),
+            "service",
+            "GET",
+            &url,
+            &[],
+            b"",
+            example_time(),
+        );
+
+        assert_eq!(signed.amz_date, "20150830T123600Z");
+        assert_eq!(signed.security_token, None);
+        assert_eq!(
+            signed.authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_sign_covers_extra_headers_and_session_token() {
+        let credentials = example_credentials().with_session_token("session");
+        let url = url::Url::parse(
+            "https://bedrock-runtime.us-east-1.amazonaws.com/model/a.b-v1%3A0/invoke",
+        )
+        .unwrap();
+        let signed = sign(
+            &credentials,
+            "bedrock",
+            "POST",
+            &url,
+            &[("content-type", "application/json")],
+            b"{}",
+            example_time(),
+        );
+
+        assert_eq!(signed.security_token.as_deref(), Some("session"));
+        assert!(signed.authorization.contains(
+            "SignedHeaders=content-type;host;x-amz-date;x-amz-security-token, Signature="
+        ));
+        assert!(
+            signed
+                .authorization
+                .contains("Credential=AKIDEXAMPLE/20150830/us-east-1/bedrock/aws4_request")
+        );
+    }
+
+    #[test]
+    fn test_canonical_uri_encodes_segments_twice() {
+        let url = url::Url::parse("https://host/model/a.b-v1%3A0/invoke").unwrap();
+        assert_eq!(canonical_uri(&url), "/model/a.b-v1%253A0/invoke");
+        assert_eq!(uri_encode("a.b-v1:0"), "a.b-v1%3A0");
+    }
+}
@@ -0,0 +1,296 @@
+//! Record and replay provider responses for reproducible tests and demos.
+//!
+//! A [`RecordReplay`] attached to a [`ChatCompletionsClient`] or
+//! [`AnthropicClient`] sits between the client and the network. In record
+//! mode every non-streaming request goes out as usual and the exchange is
+//! saved to a JSON cassette; in replay mode the cassette answers instead and
+//! nothing is sent. Requests are matched by a SHA-256 hash of the endpoint and
+//! the provider request body, so a replayed run must build the same requests
+//! as the recorded one. Streaming calls always use the network.
+//!
+//! [`ChatCompletionsClient`]: crate::ChatCompletionsClient
+//! [`AnthropicClient`]: crate::AnthropicClient
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use neuromance_client::ChatCompletionsClient;
+//! use neuromance_client::cassette::RecordReplay;
+//! use neuromance_common::client::Config;
+//!
+//! let config = Config::new("openai", "gpt-4o").with_api_key("sk-...");
+//! let cassette = Arc::new(RecordReplay::replay("tests/fixtures/demo.json")?);
+//! let client = ChatCompletionsClient::new(config)?.with_cassette(cassette);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::ClientError;
+
+/// Whether a [`RecordReplay`] saves exchanges or answers from them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send each request and save the exchange.
+    Record,
+    /// Answer each request from the cassette without sending it.
+    Replay,
+}
+
+/// One saved request and the provider's response to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request_hash: String,
+    request: Value,
+    response: Value,
+}
+
+/// A cassette of recorded provider exchanges. See the [module docs](self).
+#[derive(Debug)]
+pub struct RecordReplay {
+    mode: CassetteMode,
+    path: PathBuf,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl RecordReplay {
+    /// Record exchanges to `path`, replacing any cassette already there.
+    ///
+    /// The file is rewritten after every exchange, so a run that stops
+    /// early keeps what it recorded.
+    #[must_use]
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            path: path.into(),
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Replay the exchanges recorded at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::ConfigurationError`] if the cassette cannot be
+    /// read or parsed.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, ClientError> {
+        let path = path.into();
+        let cassette_error = |e: &dyn std::fmt::Display| {
+            ClientError::ConfigurationError(format!(
+                "Cannot load cassette '{}': {e}",
+                path.display()
+            ))
+        };
+        let text = std::fs::read_to_string(&path).map_err(|e| cassette_error(&e))?;
+        let interactions = serde_json::from_str(&text).map_err(|e| cassette_error(&e))?;
+        Ok(Self {
+            mode: CassetteMode::Replay,
+            path,
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    /// Whether this cassette records or replays.
+    #[must_use]
+    pub const fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// The cassette file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of exchanges recorded or loaded.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.interactions().len()
+    }
+
+    /// Whether the cassette holds no exchanges.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn interactions(&self) -> std::sync::MutexGuard<'_, Vec<Interaction>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Answer the request `body` sent to `endpoint`: from the cassette when
+    /// replaying, otherwise by awaiting `send` and recording its response.
+    ///
+    /// `send` is only polled in record mode.
+    pub(crate) async fn exchange<B, T>(
+        &self,
+        endpoint: &str,
+        body: &B,
+        send: impl Future<Output = Result<Value, ClientError>>,
+    ) -> Result<T, ClientError>
+    where
+        B: Serialize + Sync,
+        T: DeserializeOwned,
+    {
+        let request = serde_json::to_value(body)?;
+        let request_hash = request_hash(endpoint, &request);
+
+        let response = match self.mode {
+            CassetteMode::Replay => self
+                .interactions()
+                .iter()
+                .find(|interaction| interaction.request_hash == request_hash)
+                .map(|interaction| interaction.response.clone())
+                .ok_or_else(|| {
+                    ClientError::InvalidRequest(format!(
+                        "No recorded response for request {request_hash} in cassette '{}'",
+                        self.path.display()
+                    ))
+                })?,
+            CassetteMode::Record => {
+                let response = send.await?;
+                let snapshot = {
+                    let mut interactions = self.interactions();
+                    interactions.push(Interaction {
+                        request_hash,
+                        request,
+                        response: response.clone(),
+                    });
+                    serde_json::to_string_pretty(&*interactions)?
+                };
+                tokio::fs::write(&self.path, snapshot).await.map_err(|e| {
+                    ClientError::ConfigurationError(format!(
+                        "Cannot write cassette '{}': {e}",
+                        self.path.display()
+                    ))
+                })?;
+                response
+            }
+        };
+        Ok(serde_json::from_value(response)?)
+    }
+}
+
+/// Hex SHA-256 of `endpoint` and the canonical JSON of `request`.
+fn request_hash(endpoint: &str, request: &Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(endpoint.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(request.to_string().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::Arc;
+
+    use neuromance_common::chat::Message;
+    use neuromance_common::client::{ChatRequest, Config};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::{AnthropicClient, ChatCompletionsClient, LLMClient};
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("neuromance-{name}-{}.json", uuid::Uuid::new_v4()))
+    }
+
+    fn request(prompt: &str) -> ChatRequest {
+        ChatRequest::new(vec![Message::user(uuid::Uuid::new_v4(), prompt)])
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_replays_recorded_response_offline() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "gpt-4o",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "recorded answer"},
+                    "finish_reason": "stop"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config = Config::new("openai", "gpt-4o").with_api_key("test-key");
+        let path = cassette_path("openai");
+
+        let recorder = Arc::new(RecordReplay::record(&path));
+        let client = ChatCompletionsClient::new(config.clone().with_base_url(server.uri()))
+            .unwrap()
+            .with_cassette(Arc::clone(&recorder));
+        let live = client.chat(&request("hello")).await.unwrap();
+        assert_eq!(live.message.content, "recorded answer");
+        assert_eq!(recorder.len(), 1);
+        drop(server);
+
+        // Nothing listens here, so only the cassette can answer.
+        let player = Arc::new(RecordReplay::replay(&path).unwrap());
+        let client = ChatCompletionsClient::new(config.with_base_url("http://127.0.0.1:9"))
+            .unwrap()
+            .with_cassette(player);
+        let replayed = client.chat(&request("hello")).await.unwrap();
+        assert_eq!(replayed.message.content, "recorded answer");
+        assert_eq!(replayed.response_id.as_deref(), Some("chatcmpl-1"));
+
+        let missing = client.chat(&request("something else")).await.unwrap_err();
+        assert!(matches!(missing, ClientError::InvalidRequest(_)));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_replays_recorded_response_offline() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "model": "claude-sonnet-4-5-20250929",
+                "content": [{"type": "text", "text": "recorded answer"}],
+                "stop_reason": "end_turn",
+                "usage": {"input_tokens": 3, "output_tokens": 2}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config =
+            Config::new("anthropic", "claude-sonnet-4-5-20250929").with_api_key("test-key");
+        let path = cassette_path("anthropic");
+
+        let client = AnthropicClient::new(config.clone().with_base_url(server.uri()))
+            .unwrap()
+            .with_cassette(Arc::new(RecordReplay::record(&path)));
+        let request = request("hello").with_max_tokens(64);
+        client.chat(&request).await.unwrap();
+        drop(server);
+
+        let client = AnthropicClient::new(config.with_base_url("http://127.0.0.1:9"))
+            .unwrap()
+            .with_cassette(Arc::new(RecordReplay::replay(&path).unwrap()));
+        let replayed = client.chat(&request).await.unwrap();
+        assert_eq!(replayed.message.content, "recorded answer");
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -61,6 +61,7 @@
 //!     max_delay: Duration::from_secs(60),
 //!     backoff_multiplier: 2.0,
 //!     jitter: true,
+//!     ..RetryConfig::default()
 //! };
 //!
 //! let config = Config::new("openai", "gpt-4")
@@ -82,7 +83,7 @@
 //!
 //! let message = ChatCompletionsMessage::builder()
 //!     .role(MessageRole::User)
-//!     .content(Some("Hello!".to_string()))
+//!     .content(Some("Hello!".into()))
 //!     .build();
 //! ```
 //!
@@ -113,20 +114,32 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::{error, warn};
 
 use neuromance_common::chat::Message;
-use neuromance_common::client::{ChatChunk, ChatRequest, ChatResponse, Config, ProxyConfig, Usage};
+use neuromance_common::client::{
+    Capabilities, ChatChunk, ChatRequest, ChatResponse, Config, FinishReason, ProxyConfig,
+    ResponseChoice, Usage,
+};
 use neuromance_common::tools::{FunctionCall, ToolCall};
 
+#[cfg(feature = "cassette")]
+use crate::cassette::RecordReplay;
 use crate::chat_completions::{
-    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatCompletionsMessage,
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatCompletionsContent,
+    ChatCompletionsMessage, SYSTEM_FINGERPRINT_METADATA_KEY,
 };
 use crate::error::ClientError;
-use crate::message::MessageBuilder;
-use crate::streaming::{StreamingProvider, run_sse_stream};
-use crate::transport::{add_proxy_headers, send_json};
-use crate::{LLMClient, build_client_resources};
+use crate::interceptor::{Interceptor, intercept_request, intercept_response};
+use crate::message::{MessageBuilder, record_response_metadata};
+use crate::request_tracing::{traced_chat, traced_stream};
+use crate::streaming::{
+    ChatChunkStream, StreamingProvider, collect_stream, enforce_stop_sequences, run_sse_stream,
+    truncate_at_stop,
+};
+use crate::transport::{add_extra_headers, add_proxy_headers, send_json};
+use crate::{LLMClient, base_capabilities, build_client_resources};
 
 /// Type-state marker types for compile-time validation.
 ///
@@ -154,7 +167,7 @@ mod builder_states {
 /// // Valid: role is set
 /// let message = ChatCompletionsMessage::builder()
 ///     .role(MessageRole::User)
-///     .content(Some("Hello".to_string()))
+///     .content(Some("Hello".into()))
 ///     .build();
 /// ```
 pub struct ChatCompletionsMessageBuilder<State> {
@@ -272,7 +285,7 @@ impl ChatCompletionsMessageBuilder<builder_states::HasRole> {
             role: self
                 .role
                 .unwrap_or_else(|| unreachable!("Role must be set in HasRole state")),
-            content: self.content,
+            content: self.content.map(ChatCompletionsContent::Text),
             name: self.name,
             tool_calls: self.tool_calls,
             tool_call_id: self.tool_call_id,
@@ -312,6 +325,11 @@ pub struct ChatCompletionsClient {
     base_url: String,
     config: Arc<Config>,
     proxy_config: Option<ProxyConfig>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    #[cfg(feature = "cassette")]
+    cassette: Option<Arc<RecordReplay>>,
+    /// Provider-specific fix-ups applied to every outgoing request body.
+    request_adapter: Option<fn(&mut ChatCompletionRequest)>,
 }
 
 // Custom Debug implementation to avoid exposing API key
@@ -322,6 +340,7 @@ impl std::fmt::Debug for ChatCompletionsClient {
             .field("base_url", &self.base_url)
             .field("config", &self.config)
             .field("proxy_config", &self.proxy_config)
+            .field("interceptors", &self.interceptors.len())
             .finish_non_exhaustive()
     }
 }
@@ -386,14 +405,19 @@ pub fn convert_chunk_to_chat_chunk(chunk: &ChatCompletionChunk) -> ChatChunk {
         delta_role,
         delta_tool_calls,
         finish_reason,
-        usage: chunk.usage.clone().map(|u| Usage {
-            prompt_tokens: u.prompt_tokens,
-            completion_tokens: u.completion_tokens,
-            total_tokens: u.total_tokens,
-            cost: None,
-            input_tokens_details: u.input_tokens_details,
-            output_tokens_details: u.output_tokens_details,
-        }),
+        usage: chunk
+            .usage
+            .as_ref()
+            .or_else(|| chunk.x_groq.as_ref()?.usage.as_ref())
+            .cloned()
+            .map(|u| Usage {
+                prompt_tokens: u.prompt_tokens,
+                completion_tokens: u.completion_tokens,
+                total_tokens: u.total_tokens,
+                cost: None,
+                input_tokens_details: u.input_tokens_details,
+                output_tokens_details: u.output_tokens_details,
+            }),
         response_id: Some(chunk.id.clone()),
         created_at: DateTime::from_timestamp(i64::try_from(chunk.created).unwrap_or(0), 0)
             .unwrap_or_else(Utc::now),
@@ -449,6 +473,8 @@ impl ChatCompletionsClient {
     /// Returns an error if the API key is missing or HTTP client creation fails.
     pub fn new(config: Config) -> Result<Self, ClientError> {
         let r = build_client_resources(config, "https://api.openai.com/v1")?;
+        let request_adapter = (r.config.provider == "deepseek")
+            .then_some(adapt_for_deepseek as fn(&mut ChatCompletionRequest));
 
         Ok(Self {
             client: r.client,
@@ -457,6 +483,10 @@ impl ChatCompletionsClient {
             base_url: r.base_url,
             config: r.config,
             proxy_config: r.proxy_config,
+            interceptors: Vec::new(),
+            #[cfg(feature = "cassette")]
+            cassette: None,
+            request_adapter,
         })
     }
 
@@ -487,25 +517,122 @@ impl ChatCompletionsClient {
         self
     }
 
-    async fn make_request<T: for<'de> Deserialize<'de>, B: Serialize + Sync>(
+    /// Add an [`Interceptor`] to run around every request.
+    ///
+    /// Interceptors run in the order they were added.
+    #[must_use]
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Record non-streaming exchanges to, or replay them from, `cassette`.
+    ///
+    /// See [`cassette`](crate::cassette) for how requests are matched.
+    #[cfg(feature = "cassette")]
+    #[must_use]
+    pub fn with_cassette(mut self, cassette: Arc<RecordReplay>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Rewrite every outgoing request body with `adapter`, for servers that
+    /// speak the Chat Completions protocol with quirks (see
+    /// [`GroqClient`](crate::GroqClient)).
+    #[must_use]
+    pub(crate) fn with_request_adapter(mut self, adapter: fn(&mut ChatCompletionRequest)) -> Self {
+        self.request_adapter = Some(adapter);
+        self
+    }
+
+    /// Convert `request` to the wire format, applying the request adapter.
+    fn build_request(&self, request: &ChatRequest) -> ChatCompletionRequest {
+        let mut chat_request = ChatCompletionRequest::from((request, self.config.as_ref()));
+        if let Some(adapter) = self.request_adapter {
+            adapter(&mut chat_request);
+        }
+        chat_request
+    }
+
+    /// Send a chat request over the streaming endpoint and collect the
+    /// chunks into a single [`ChatResponse`].
+    ///
+    /// Returns the same shape as [`LLMClient::chat`], but takes the
+    /// streaming path so servers that only report token usage mid-stream
+    /// (vLLM, some proxies) still populate [`ChatResponse::usage`]. Tool-call
+    /// deltas are stitched with [`ToolCall::merge_deltas`] and the finish
+    /// reason comes from the last chunk that carried one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails validation, the stream cannot
+    /// be opened, any chunk fails, or the stream ends without any chunks.
+    pub async fn chat_via_stream(
         &self,
-        endpoint: &str,
-        body: &B,
-    ) -> Result<T, ClientError> {
-        let url = format!("{}/{}", self.base_url, endpoint);
+        request: &ChatRequest,
+    ) -> Result<ChatResponse, ClientError> {
+        let conversation_id = request
+            .messages
+            .first()
+            .ok_or_else(|| {
+                ClientError::InvalidRequest("Request must contain at least one message".to_string())
+            })?
+            .conversation_id;
+
+        let stream = self.chat_stream(request).await?;
+        collect_stream(stream, conversation_id).await
+    }
+
+    /// The URL of `endpoint`, routed through the Azure deployment when
+    /// [`Config::azure`] is set.
+    fn endpoint_url(&self, endpoint: &str) -> Result<String, ClientError> {
+        let url = self.config.azure.as_ref().map_or_else(
+            || format!("{}/{}", self.base_url, endpoint),
+            |azure| {
+                format!(
+                    "{}/openai/deployments/{}/{endpoint}?api-version={}",
+                    self.base_url.trim_end_matches('/'),
+                    azure.deployment,
+                    azure.api_version
+                )
+            },
+        );
 
         // Validate URL construction
         reqwest::Url::parse(&url)
             .map_err(|e| ClientError::ConfigurationError(format!("Invalid URL '{url}': {e}")))?;
+        Ok(url)
+    }
+
+    /// The authentication header: Azure's `api-key`, otherwise a bearer token.
+    fn auth_header(&self) -> (&'static str, String) {
+        let key = self.api_key.expose_secret();
+        if self.config.azure.is_some() {
+            ("api-key", key.to_string())
+        } else {
+            ("Authorization", format!("Bearer {key}"))
+        }
+    }
+
+    async fn make_request<T: for<'de> Deserialize<'de>, B: Serialize + Sync>(
+        &self,
+        endpoint: &str,
+        body: &B,
+    ) -> Result<T, ClientError> {
+        let url = self.endpoint_url(endpoint)?;
+        let (auth_name, auth_value) = self.auth_header();
 
         let mut request_builder = self
             .client
             .post(&url)
-            .header(
-                "Authorization",
-                format!("Bearer {}", self.api_key.expose_secret()),
-            )
+            .header(auth_name, auth_value)
             .header("Content-Type", "application/json");
+        request_builder = add_extra_headers(
+            request_builder,
+            &self.config.extra_headers,
+            &[auth_name, "Content-Type"],
+            self.proxy_config.as_ref(),
+        );
 
         // Add proxy headers if configured
         request_builder =
@@ -514,6 +641,12 @@ impl ChatCompletionsClient {
         let request_builder = request_builder
             .body(serde_json::to_string(body).map_err(ClientError::SerializationError)?);
 
+        #[cfg(feature = "cassette")]
+        if let Some(cassette) = &self.cassette {
+            return cassette
+                .exchange(endpoint, body, send_json(request_builder))
+                .await;
+        }
         send_json(request_builder).await
     }
 
@@ -541,8 +674,8 @@ impl ChatCompletionsClient {
     /// ```
     fn convert_message(msg: &ChatCompletionsMessage, conversation_id: uuid::Uuid) -> Message {
         let mut builder = MessageBuilder::new(conversation_id, msg.role);
-        if let Some(content) = msg.content.as_deref() {
-            builder.set_content(content.to_string());
+        if let Some(content) = msg.content.as_ref() {
+            builder.set_content(content.text().into_owned());
         }
         if let Some(tcs) = msg.tool_calls.as_ref() {
             for tc in tcs {
@@ -568,28 +701,43 @@ impl ChatCompletionsClient {
         }
         builder.build()
     }
-}
 
-#[async_trait]
-impl LLMClient for ChatCompletionsClient {
-    fn config(&self) -> &Config {
-        &self.config
+    /// Stop sequences to apply to the output ourselves, empty unless
+    /// [`Config::enforce_stop_client_side`] is set.
+    fn client_side_stops<'a>(&self, request: &'a ChatCompletionRequest) -> &'a [String] {
+        if self.config.enforce_stop_client_side {
+            request.stop.as_deref().unwrap_or_default()
+        } else {
+            &[]
+        }
     }
+}
 
-    fn supports_tools(&self) -> bool {
-        true
+/// Compatibility mode applied when the config's provider is `"deepseek"`.
+///
+/// The `deepseek` API rejects input messages that carry `reasoning_content`,
+/// so the reasoning recorded on earlier replies is not sent back; replies
+/// still map it into [`Message::reasoning`]. Its reasoner counts the chain of
+/// thought against `max_tokens` and ignores `max_completion_tokens`, so a
+/// thinking budget is sent as `max_tokens` when no explicit limit is set.
+fn adapt_for_deepseek(request: &mut ChatCompletionRequest) {
+    for message in &mut request.messages {
+        message.reasoning_content = None;
     }
-
-    fn supports_streaming(&self) -> bool {
-        true
+    if let Some(max) = request.max_completion_tokens.take() {
+        request.max_tokens.get_or_insert(max);
     }
+}
 
-    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+impl ChatCompletionsClient {
+    /// [`LLMClient::chat`] without its request span.
+    async fn chat_inner(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
         self.validate_request(request)?;
 
-        let mut chat_request = ChatCompletionRequest::from((request, self.config.as_ref()));
+        let mut chat_request = self.build_request(request);
         chat_request.stream = Some(false);
 
+        let started = Instant::now();
         let response: ChatCompletionResponse =
             self.make_request("chat/completions", &chat_request).await?;
 
@@ -612,12 +760,37 @@ impl LLMClient for ChatCompletionsClient {
             })?
             .conversation_id;
 
-        let message = Self::convert_message(&choice.message, conversation_id);
-
-        let finish_reason = choice
-            .finish_reason
-            .as_ref()
-            .and_then(|reason| reason.parse().ok());
+        let stops = self.client_side_stops(&chat_request);
+
+        let mut message = Self::convert_message(&choice.message, conversation_id);
+
+        let finish_reason = if truncate_at_stop(&mut message.content, stops) {
+            Some(FinishReason::Stop)
+        } else {
+            choice
+                .finish_reason
+                .as_ref()
+                .and_then(|reason| reason.parse().ok())
+        };
+
+        let additional_choices = response
+            .choices
+            .iter()
+            .skip(1)
+            .map(|choice| {
+                let mut extra = ResponseChoice {
+                    message: Self::convert_message(&choice.message, conversation_id),
+                    finish_reason: choice
+                        .finish_reason
+                        .as_ref()
+                        .and_then(|reason| reason.parse().ok()),
+                };
+                if truncate_at_stop(&mut extra.message.content, stops) {
+                    extra.finish_reason = Some(FinishReason::Stop);
+                }
+                extra
+            })
+            .collect();
 
         let usage = response.usage.map(|u| Usage {
             prompt_tokens: u.prompt_tokens,
@@ -627,8 +800,20 @@ impl LLMClient for ChatCompletionsClient {
             input_tokens_details: u.input_tokens_details,
             output_tokens_details: u.output_tokens_details,
         });
+        record_response_metadata(&mut message, &self.config.provider, started, usage.as_ref());
+
+        let metadata = response
+            .system_fingerprint
+            .map(|fingerprint| {
+                (
+                    SYSTEM_FINGERPRINT_METADATA_KEY.to_string(),
+                    serde_json::Value::String(fingerprint),
+                )
+            })
+            .into_iter()
+            .collect();
 
-        Ok(ChatResponse {
+        let response = ChatResponse {
             message,
             model: response.model,
             usage,
@@ -636,42 +821,99 @@ impl LLMClient for ChatCompletionsClient {
             created_at: DateTime::from_timestamp(i64::try_from(response.created).unwrap_or(0), 0)
                 .unwrap_or_else(Utc::now),
             response_id: Some(response.id),
-            metadata: HashMap::new(),
-        })
+            metadata,
+            additional_choices,
+        };
+        intercept_response(&self.interceptors, &response);
+        Ok(response)
     }
 
-    async fn chat_stream(
-        &self,
-        request: &ChatRequest,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
-    {
+    /// [`LLMClient::chat_stream`] without its request span.
+    fn chat_stream_inner(&self, request: &ChatRequest) -> Result<ChatChunkStream, ClientError> {
         self.validate_request(request)?;
 
-        let mut chat_request = ChatCompletionRequest::from((request, self.config.as_ref()));
+        let mut chat_request = self.build_request(request);
         chat_request.stream = Some(true);
         chat_request.stream_options = Some(serde_json::json!({
             "include_usage": true
         }));
 
-        let url = format!("{}/{}", self.base_url, "chat/completions");
-        reqwest::Url::parse(&url)
-            .map_err(|e| ClientError::ConfigurationError(format!("Invalid URL '{url}': {e}")))?;
+        let url = self.endpoint_url("chat/completions")?;
+        let (auth_name, auth_value) = self.auth_header();
 
         let mut request_builder = self
             .streaming_client
             .post(&url)
-            .header(
-                "Authorization",
-                format!("Bearer {}", self.api_key.expose_secret()),
-            )
+            .header(auth_name, auth_value)
             .header("Content-Type", "application/json");
+        request_builder = add_extra_headers(
+            request_builder,
+            &self.config.extra_headers,
+            &[auth_name, "Content-Type"],
+            self.proxy_config.as_ref(),
+        );
 
         request_builder =
             add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
 
         let request_builder = request_builder.json(&chat_request);
 
-        run_sse_stream(self, request_builder)
+        let stream = run_sse_stream(self, request_builder)?;
+        let stops = self.client_side_stops(&chat_request);
+        if stops.is_empty() {
+            Ok(stream)
+        } else {
+            Ok(enforce_stop_sequences(stream, stops.to_vec()))
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for ChatCompletionsClient {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            parallel_tool_calls: true,
+            vision: true,
+            json_schema: true,
+            reasoning: true,
+            prompt_caching: true,
+            ..base_capabilities(self)
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        let mut chat_request = self.build_request(request);
+        chat_request.stream = Some(false);
+        serde_json::to_value(&chat_request).ok()
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        let request = &*intercept_request(&self.interceptors, request);
+        traced_chat(self.config(), request, self.chat_inner(request)).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        let request = &*intercept_request(&self.interceptors, request);
+        traced_stream(self.config(), request, async {
+            self.chat_stream_inner(request)
+        })
+        .await
     }
 }
 
@@ -685,6 +927,14 @@ impl StreamingProvider for ChatCompletionsClient {
         data == "[DONE]"
     }
 
+    /// SSE comment lines never reach the provider, but some compatible
+    /// servers send empty `data:` frames or put the comment in the data
+    /// (`data: : keep-alive`) to hold the connection open.
+    fn is_keepalive(data: &str) -> bool {
+        let data = data.trim();
+        data.is_empty() || data.starts_with(':')
+    }
+
     fn process_event(
         _state: &mut Self::State,
         event: Self::Event,
@@ -700,7 +950,9 @@ mod tests {
 
     use super::*;
     use futures::StreamExt;
-    use neuromance_common::chat::{Message, MessageRole};
+    use neuromance_common::chat::{
+        CACHED_METADATA_KEY, LATENCY_MS_METADATA_KEY, Message, MessageRole, PROVIDER_METADATA_KEY,
+    };
     use neuromance_common::client::FinishReason;
     use wiremock::matchers::{header, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -726,6 +978,8 @@ mod tests {
             model: None,
             provider: None,
             usage: None,
+            parent_id: None,
+            content_parts: Vec::new(),
         }
     }
 
@@ -778,6 +1032,155 @@ mod tests {
         assert_eq!(usage.total_tokens, 30);
     }
 
+    #[tokio::test]
+    async fn test_chat_completion_with_multiple_choices() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(wiremock::matchers::body_partial_json(
+                serde_json::json!({"n": 2}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "chatcmpl-123",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "gpt-4",
+                "choices": [
+                    {
+                        "index": 0,
+                        "message": {"role": "assistant", "content": "First take"},
+                        "finish_reason": "stop"
+                    },
+                    {
+                        "index": 1,
+                        "message": {"role": "assistant", "content": "Second take, cut"},
+                        "finish_reason": "length"
+                    }
+                ]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = create_test_config(&mock_server.uri());
+        let client = ChatCompletionsClient::new(config).unwrap();
+        let message = create_test_message();
+        let conversation_id = message.conversation_id;
+        let request = ChatRequest::new(vec![message]).with_n(2);
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.message.content, "First take");
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(response.additional_choices.len(), 1);
+        let second = &response.additional_choices[0];
+        assert_eq!(second.message.content, "Second take, cut");
+        assert_eq!(second.message.conversation_id, conversation_id);
+        assert_eq!(second.finish_reason, Some(FinishReason::Length));
+    }
+
+    /// Tags requests with a `user` and records the ids of the responses.
+    #[derive(Default)]
+    struct RecordingInterceptor {
+        response_ids: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl Interceptor for RecordingInterceptor {
+        fn before_request(&self, request: &mut ChatRequest) {
+            request.user = Some("trace-42".to_string());
+        }
+
+        fn after_response(&self, response: &ChatResponse) {
+            self.response_ids
+                .lock()
+                .unwrap()
+                .extend(response.response_id.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_mutates_request_and_sees_response() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(wiremock::matchers::body_partial_json(
+                serde_json::json!({"user": "trace-42"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "chatcmpl-123",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hi"},
+                    "finish_reason": "stop"
+                }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let interceptor = Arc::new(RecordingInterceptor::default());
+        let client = ChatCompletionsClient::new(create_test_config(&mock_server.uri()))
+            .unwrap()
+            .with_interceptor(interceptor.clone());
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.message.content, "Hi");
+        assert_eq!(request.user, None);
+        assert_eq!(
+            *interceptor.response_ids.lock().unwrap(),
+            ["chatcmpl-123".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_seed_sent_and_system_fingerprint_recorded() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(wiremock::matchers::body_partial_json(
+                serde_json::json!({"seed": 42}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "chatcmpl-123",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "gpt-4",
+                "system_fingerprint": "fp_44709d6fcb",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hi"},
+                    "finish_reason": "stop"
+                }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = create_test_config(&mock_server.uri());
+        let client = ChatCompletionsClient::new(config.clone()).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]).with_seed(42);
+
+        let body = serde_json::to_value(ChatCompletionRequest::from((&request, &config))).unwrap();
+        assert_eq!(body["seed"], 42);
+        let unseeded = ChatRequest::new(vec![create_test_message()]);
+        let body = serde_json::to_value(ChatCompletionRequest::from((&unseeded, &config))).unwrap();
+        assert!(body.get("seed").is_none());
+
+        let response = client.chat(&request).await.unwrap();
+        assert_eq!(
+            response.metadata[SYSTEM_FINGERPRINT_METADATA_KEY],
+            "fp_44709d6fcb"
+        );
+    }
+
     #[tokio::test]
     async fn test_chat_completion_with_different_finish_reasons() {
         let test_cases = vec![
@@ -1074,6 +1477,14 @@ mod tests {
 
         let output_details = usage.output_tokens_details.unwrap();
         assert_eq!(output_details.reasoning_tokens, 3);
+
+        let message = &response.message;
+        assert_eq!(
+            message.get_meta::<String>(PROVIDER_METADATA_KEY).as_deref(),
+            Some("openai")
+        );
+        assert_eq!(message.get_meta::<bool>(CACHED_METADATA_KEY), Some(true));
+        assert!(message.get_meta::<u64>(LATENCY_MS_METADATA_KEY).is_some());
     }
 
     // ==================== Proxy Header Tests ====================
@@ -1238,6 +1649,291 @@ mod tests {
         );
     }
 
+    fn deepseek_config(base_url: &str) -> Config {
+        Config::new("deepseek", "deepseek-reasoner")
+            .with_api_key("test-key")
+            .with_base_url(base_url)
+    }
+
+    #[tokio::test]
+    async fn test_deepseek_reasoning_content_maps_to_message_reasoning() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(wiremock::matchers::body_partial_json(serde_json::json!({
+                "stream": false
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "ds-1",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "deepseek-reasoner",
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "reasoning_content": "9.11 has fewer tenths than 9.9.",
+                        "content": "9.9 is larger."
+                    },
+                    "finish_reason": "stop"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let chunk = |delta: serde_json::Value| {
+            format!(
+                "data: {}\n\n",
+                serde_json::json!({
+                    "id": "ds-2",
+                    "object": "chat.completion.chunk",
+                    "created": 1_677_652_288,
+                    "model": "deepseek-reasoner",
+                    "choices": [{ "index": 0, "delta": delta, "finish_reason": null }]
+                })
+            )
+        };
+        let sse_body = [
+            chunk(serde_json::json!({ "role": "assistant", "reasoning_content": "9.11 has " })),
+            chunk(serde_json::json!({ "reasoning_content": "fewer tenths." })),
+            chunk(serde_json::json!({ "content": "9.9 is larger." })),
+            "data: [DONE]\n\n".to_string(),
+        ]
+        .concat();
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(wiremock::matchers::body_partial_json(serde_json::json!({
+                "stream": true
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+            .mount(&mock_server)
+            .await;
+
+        let client = ChatCompletionsClient::new(deepseek_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let response = client.chat(&request).await.unwrap();
+        assert_eq!(response.message.content, "9.9 is larger.");
+        assert_eq!(
+            response.message.reasoning.unwrap().content,
+            "9.11 has fewer tenths than 9.9."
+        );
+
+        let streamed = client.chat_via_stream(&request).await.unwrap();
+        assert_eq!(streamed.message.content, "9.9 is larger.");
+        assert_eq!(
+            streamed.message.reasoning.unwrap().content,
+            "9.11 has fewer tenths."
+        );
+    }
+
+    #[test]
+    fn test_deepseek_mode_drops_reasoning_from_request() {
+        let mut previous = Message::assistant(uuid::Uuid::new_v4(), "9.9 is larger.");
+        previous.reasoning = Some(neuromance_common::chat::ReasoningContent::new("tenths"));
+        let mut request = ChatRequest::new(vec![create_test_message(), previous]);
+        request.max_completion_tokens = Some(4096);
+
+        let body = ChatCompletionsClient::new(deepseek_config("http://localhost"))
+            .unwrap()
+            .request_payload(&request)
+            .unwrap();
+        assert!(body["messages"][1].get("reasoning_content").is_none());
+        assert_eq!(body["max_tokens"], 4096);
+        assert!(body.get("max_completion_tokens").is_none());
+
+        let body = ChatCompletionsClient::new(create_test_config("http://localhost"))
+            .unwrap()
+            .request_payload(&request)
+            .unwrap();
+        assert_eq!(body["messages"][1]["reasoning_content"], "tenths");
+    }
+
+    #[tokio::test]
+    async fn test_extra_headers_sent_without_overriding_client_headers() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(header("Authorization", "Bearer test-key"))
+            .and(header("X-Org-Id", "org-42"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw("data: [DONE]\n\n", "text/event-stream"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = create_test_config(&mock_server.uri())
+            .with_extra_header("X-Org-Id", "org-42")
+            .with_extra_header("authorization", "Bearer gateway-key")
+            .with_extra_header("content-type", "text/plain");
+        let client = ChatCompletionsClient::new(config).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let chunks: Vec<_> = client.chat_stream(&request).await.unwrap().collect().await;
+        assert!(chunks.is_empty());
+
+        let received = mock_server.received_requests().await.unwrap();
+        let auth: Vec<_> = received[0]
+            .headers
+            .get_all("authorization")
+            .iter()
+            .collect();
+        assert_eq!(auth, ["Bearer test-key"]);
+        let content_type: Vec<_> = received[0].headers.get_all("content-type").iter().collect();
+        assert_eq!(content_type, ["application/json"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_skips_keepalive_frames() {
+        let mock_server = MockServer::start().await;
+
+        let chunk = |content: &str| {
+            serde_json::json!({
+                "id": "chatcmpl-keepalive",
+                "object": "chat.completion.chunk",
+                "created": 1_677_652_288,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "delta": { "content": content },
+                    "finish_reason": null
+                }]
+            })
+        };
+        let sse_body = [
+            ": keep-alive".to_string(),
+            String::new(),
+            format!("data: {}", chunk("Hello")),
+            String::new(),
+            "data: ".to_string(),
+            String::new(),
+            ": OPENROUTER PROCESSING".to_string(),
+            String::new(),
+            "data: : ping".to_string(),
+            String::new(),
+            format!("data: {}", chunk(" world")),
+            String::new(),
+            "data: [DONE]".to_string(),
+            String::new(),
+        ]
+        .join("\n");
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+            .mount(&mock_server)
+            .await;
+
+        let client = ChatCompletionsClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+        let results: Vec<_> = client.chat_stream(&request).await.unwrap().collect().await;
+
+        assert_eq!(results.len(), 2);
+        let content: String = results
+            .into_iter()
+            .map(|chunk| chunk.unwrap().delta_content.unwrap_or_default())
+            .collect();
+        assert_eq!(content, "Hello world");
+    }
+
+    #[tokio::test]
+    async fn test_enforce_stop_client_side_truncates_response() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "chatcmpl-stop",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": "Answer: 42\nQuestion: what next?"
+                    },
+                    "finish_reason": "length"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let request =
+            ChatRequest::new(vec![create_test_message()]).with_stop_sequences(["\nQuestion:"]);
+
+        // Without the flag the server's output is passed through untouched.
+        let client = ChatCompletionsClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let response = client.chat(&request).await.unwrap();
+        assert_eq!(response.message.content, "Answer: 42\nQuestion: what next?");
+        assert_eq!(response.finish_reason, Some(FinishReason::Length));
+
+        let config = create_test_config(&mock_server.uri()).with_enforce_stop_client_side(true);
+        let client = ChatCompletionsClient::new(config).unwrap();
+        let response = client.chat(&request).await.unwrap();
+        assert_eq!(response.message.content, "Answer: 42");
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+    }
+
+    #[tokio::test]
+    async fn test_enforce_stop_client_side_ends_stream() {
+        let mock_server = MockServer::start().await;
+
+        let chunk = |content: &str| {
+            format!(
+                "data: {}\n",
+                serde_json::json!({
+                    "id": "chatcmpl-stop-stream",
+                    "object": "chat.completion.chunk",
+                    "created": 1_677_652_288,
+                    "model": "gpt-4",
+                    "choices": [{
+                        "index": 0,
+                        "delta": { "content": content },
+                        "finish_reason": null
+                    }]
+                })
+            )
+        };
+        // The stop sequence is split across two deltas.
+        let sse_body = [
+            chunk("Answer: 42"),
+            chunk("\nQues"),
+            chunk("tion: what next?"),
+            "data: [DONE]\n".to_string(),
+        ]
+        .join("\n");
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+            .mount(&mock_server)
+            .await;
+
+        let config = create_test_config(&mock_server.uri()).with_enforce_stop_client_side(true);
+        let client = ChatCompletionsClient::new(config).unwrap();
+        let request =
+            ChatRequest::new(vec![create_test_message()]).with_stop_sequences(["\nQuestion:"]);
+
+        let mut stream = client.chat_stream(&request).await.unwrap();
+        let mut content = String::new();
+        let mut finish_reason = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            assert!(
+                finish_reason.is_none(),
+                "content emitted after the stop sequence"
+            );
+            content.push_str(chunk.delta_content.as_deref().unwrap_or_default());
+            finish_reason = chunk.finish_reason;
+        }
+        assert_eq!(content, "Answer: 42");
+        assert_eq!(finish_reason, Some(FinishReason::Stop));
+    }
+
     #[tokio::test]
     async fn test_proxy_preserves_custom_base_url_path() {
         let mock_server = MockServer::start().await;
@@ -1269,6 +1965,231 @@ mod tests {
         assert_eq!(response.message.content, "Response via proxy");
     }
 
+    #[tokio::test]
+    async fn test_chat_via_stream_accumulates_tool_calls_and_usage() {
+        let mock_server = MockServer::start().await;
+
+        let frames = [
+            serde_json::json!({
+                "id": "chatcmpl-collect",
+                "object": "chat.completion.chunk",
+                "created": 1_677_652_288,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "delta": {
+                        "role": "assistant",
+                        "tool_calls": [{
+                            "index": 0,
+                            "id": "call_weather",
+                            "type": "function",
+                            "function": {"name": "get_weather", "arguments": "{\"loc"}
+                        }]
+                    },
+                    "finish_reason": null
+                }]
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-collect",
+                "object": "chat.completion.chunk",
+                "created": 1_677_652_288,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "delta": {
+                        "tool_calls": [{
+                            "index": 0,
+                            "function": {"arguments": "ation\": \"Paris\"}"}
+                        }]
+                    },
+                    "finish_reason": "tool_calls"
+                }]
+            }),
+            // Usage arrives on a trailing chunk with no choices.
+            serde_json::json!({
+                "id": "chatcmpl-collect",
+                "object": "chat.completion.chunk",
+                "created": 1_677_652_288,
+                "model": "gpt-4",
+                "choices": [],
+                "usage": {
+                    "prompt_tokens": 12,
+                    "completion_tokens": 8,
+                    "total_tokens": 20
+                }
+            }),
+        ];
+        let mut events: Vec<String> = frames.iter().map(|f| format!("data: {f}")).collect();
+        events.push("data: [DONE]".to_string());
+        let sse_body = events.join("\n\n") + "\n\n";
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ChatCompletionsClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let message = create_test_message();
+        let conversation_id = message.conversation_id;
+        let request = ChatRequest::new(vec![message]);
+
+        let response = client.chat_via_stream(&request).await.unwrap();
+
+        assert_eq!(response.model, "gpt-4");
+        assert_eq!(response.response_id.as_deref(), Some("chatcmpl-collect"));
+        assert_eq!(response.finish_reason, Some(FinishReason::ToolCalls));
+        assert_eq!(response.message.role, MessageRole::Assistant);
+        assert_eq!(response.message.conversation_id, conversation_id);
+        assert_eq!(response.message.tool_calls.len(), 1);
+        let call = &response.message.tool_calls[0];
+        assert_eq!(call.id, "call_weather");
+        assert_eq!(call.function.name, "get_weather");
+        assert_eq!(call.function.arguments, r#"{"location": "Paris"}"#);
+        assert_eq!(call.index, None);
+
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 12);
+        assert_eq!(usage.completion_tokens, 8);
+        assert_eq!(usage.total_tokens, 20);
+    }
+
+    #[test]
+    fn test_response_format_serialization() {
+        use neuromance_common::client::ResponseFormat;
+
+        let config = create_test_config("http://localhost");
+        let serialize = |format: Option<ResponseFormat>| {
+            let mut request = ChatRequest::new(vec![create_test_message()]);
+            request.response_format = format;
+            serde_json::to_value(ChatCompletionRequest::from((&request, &config))).unwrap()
+        };
+
+        assert!(serialize(None).get("response_format").is_none());
+        assert_eq!(
+            serialize(Some(ResponseFormat::JsonObject))["response_format"],
+            serde_json::json!({"type": "json_object"})
+        );
+        assert_eq!(
+            serialize(Some(ResponseFormat::JsonSchema {
+                name: "weather".to_string(),
+                schema: serde_json::json!({"type": "object"}),
+                strict: true,
+            }))["response_format"],
+            serde_json::json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "weather",
+                    "schema": {"type": "object"},
+                    "strict": true
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_common_request_fields_survive_conversion() {
+        let config = create_test_config("http://localhost");
+        let request = ChatRequest::new(vec![create_test_message()])
+            .with_logit_bias(HashMap::from([("50256".to_string(), -100.0)]))
+            .with_user("user-1234")
+            .with_seed(7);
+
+        let wire = ChatCompletionRequest::from((&request, &config));
+        assert_eq!(
+            wire.logit_bias,
+            Some(HashMap::from([("50256".to_string(), -100.0)]))
+        );
+        assert_eq!(wire.user.as_deref(), Some("user-1234"));
+        assert_eq!(wire.seed, Some(7));
+
+        let body = serde_json::to_value(&wire).unwrap();
+        assert_eq!(body["logit_bias"], serde_json::json!({"50256": -100.0}));
+        assert_eq!(body["user"], "user-1234");
+        assert_eq!(body["seed"], 7);
+    }
+
+    #[test]
+    fn test_reasoning_model_gets_effort_without_sampling_params() {
+        use neuromance_common::features::ReasoningLevel;
+
+        let config = create_test_config("http://localhost");
+        let serialize = |model: &str| {
+            let mut request = ChatRequest::new(vec![create_test_message()])
+                .with_model(model)
+                .with_temperature(0.7);
+            request.top_p = Some(0.9);
+            request.reasoning_level = ReasoningLevel::High;
+            serde_json::to_value(ChatCompletionRequest::from((&request, &config))).unwrap()
+        };
+
+        let body = serialize("o3-mini");
+        assert_eq!(body["reasoning_effort"], "high");
+        assert!(body.get("temperature").is_none());
+        assert!(body.get("top_p").is_none());
+
+        let body = serialize("gpt-4o");
+        assert_eq!(body["reasoning_effort"], "high");
+        assert!(body.get("temperature").is_some());
+        assert!(body.get("top_p").is_some());
+    }
+
+    #[test]
+    fn test_tool_choice_serialization() {
+        use neuromance_common::client::ToolChoice;
+
+        let config = create_test_config("http://localhost");
+        let serialize = |choice: ToolChoice| {
+            let request = ChatRequest::new(vec![create_test_message()]).with_tool_choice(choice);
+            serde_json::to_value(ChatCompletionRequest::from((&request, &config))).unwrap()
+                ["tool_choice"]
+                .clone()
+        };
+
+        assert_eq!(serialize(ToolChoice::None), serde_json::json!("none"));
+        assert_eq!(serialize(ToolChoice::Auto), serde_json::json!("auto"));
+        assert_eq!(
+            serialize(ToolChoice::Required),
+            serde_json::json!("required")
+        );
+        assert_eq!(
+            serialize(ToolChoice::Any {
+                disable_parallel: true
+            }),
+            serde_json::json!("required")
+        );
+        assert_eq!(
+            serialize(ToolChoice::Function {
+                name: "get_weather".to_string(),
+            }),
+            serde_json::json!({
+                "type": "function",
+                "function": {"name": "get_weather"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_image_parts_serialize_as_data_urls() {
+        let message = Message::user(uuid::Uuid::new_v4(), "What is this?")
+            .with_image_base64("image/jpeg", "/9j/4AAQ")
+            .unwrap();
+
+        let json = serde_json::to_value(ChatCompletionsMessage::from(&message)).unwrap();
+        assert_eq!(
+            json["content"],
+            serde_json::json!([
+                {"type": "image_url", "image_url": {"url": "data:image/jpeg;base64,/9j/4AAQ"}},
+                {"type": "text", "text": "What is this?"},
+            ])
+        );
+
+        // Text-only messages keep the plain string form.
+        let plain = serde_json::to_value(ChatCompletionsMessage::from(&create_test_message()));
+        assert_eq!(plain.unwrap()["content"], "Hello");
+    }
+
     /// Reproduces the canonical `OpenAI` streaming shape where `id`, `type`, and
     /// `function.name` are sent only in the first chunk for a given tool call,
     /// and subsequent chunks carry just `index` plus a fragment of `arguments`.
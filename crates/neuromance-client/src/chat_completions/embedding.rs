@@ -31,7 +31,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use base64::prelude::*;
 use reqwest_middleware::ClientWithMiddleware;
-use reqwest_retry::{RetryTransientMiddleware, policies::ExponentialBackoff};
+use reqwest_retry::RetryTransientMiddleware;
 use reqwest_retry_after::RetryAfterMiddleware;
 use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
@@ -42,6 +42,7 @@ use crate::embedding::{
     EmbeddingResponse, EmbeddingUsage, EncodingFormat, models,
 };
 use crate::error::{ClientError, ErrorResponse};
+use crate::retry_policy::StrategyPolicy;
 
 /// Maximum number of inputs allowed in a single batch request.
 ///
@@ -217,12 +218,7 @@ impl OpenAIEmbedding {
         })?;
 
         // Build retry policy from config
-        let retry_policy = ExponentialBackoff::builder()
-            .retry_bounds(
-                config.retry_config.initial_delay,
-                config.retry_config.max_delay,
-            )
-            .build_with_max_retries(config.retry_config.max_retries);
+        let retry_policy = StrategyPolicy::new(config.retry_config.backoff());
 
         // Create reqwest client with optional timeout
         let reqwest_client = match config.timeout_seconds {
@@ -438,6 +434,27 @@ impl EmbeddingClient for OpenAIEmbedding {
     fn supports_dimensions(&self) -> bool {
         Self::model_supports_dimensions(&self.config.model)
     }
+
+    fn max_inputs_per_request(&self) -> Option<usize> {
+        Some(
+            self.config
+                .max_inputs_per_request
+                .map_or(MAX_BATCH_SIZE, |cap| cap.min(MAX_BATCH_SIZE)),
+        )
+    }
+}
+
+#[async_trait]
+impl neuromance_common::Embedder for OpenAIEmbedding {
+    type Error = ClientError;
+
+    fn embedding_model(&self) -> &str {
+        &self.config.model
+    }
+
+    async fn embed_texts(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, ClientError> {
+        self.embed_batch(texts).await
+    }
 }
 
 #[cfg(test)]
@@ -1121,6 +1138,62 @@ mod tests {
         assert!(OpenAIEmbedding::validate_input(&input).is_ok());
     }
 
+    /// A batch over the per-request cap is split into several requests and
+    /// the vectors come back in input order.
+    #[tokio::test]
+    async fn test_batch_split_by_max_inputs_per_request() {
+        let mock_server = MockServer::start().await;
+
+        // Each input "tN" embeds to [N]; data is returned in reverse order.
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(|request: &wiremock::Request| {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                let inputs = body["input"].as_array().unwrap();
+                let data: Vec<_> = inputs
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .map(|(index, input)| {
+                        let n: f32 = input.as_str().unwrap()[1..].parse().unwrap();
+                        serde_json::json!({"object": "embedding", "index": index, "embedding": [n]})
+                    })
+                    .collect();
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "object": "list",
+                    "data": data,
+                    "model": "text-embedding-3-small",
+                    "usage": {"prompt_tokens": 1, "total_tokens": 1}
+                }))
+            })
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let config = create_test_config(&mock_server.uri()).with_max_inputs_per_request(2);
+        let client = OpenAIEmbedding::new(config).unwrap();
+
+        let embeddings = client
+            .embed_batch(&["t0", "t1", "t2", "t3", "t4"])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            embeddings,
+            vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0], vec![4.0]]
+        );
+    }
+
+    #[test]
+    fn test_max_inputs_per_request_capped_at_provider_limit() {
+        let client = OpenAIEmbedding::new(EmbeddingConfig::openai_small("test-key")).unwrap();
+        assert_eq!(client.max_inputs_per_request(), Some(MAX_BATCH_SIZE));
+
+        let config = EmbeddingConfig::openai_small("test-key").with_max_inputs_per_request(10_000);
+        let client = OpenAIEmbedding::new(config).unwrap();
+        assert_eq!(client.max_inputs_per_request(), Some(MAX_BATCH_SIZE));
+    }
+
     #[tokio::test]
     async fn test_empty_input_rejected_before_request() {
         // Empty input should be rejected client-side before making a request
@@ -10,13 +10,17 @@ use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use typed_builder::TypedBuilder;
 
-use neuromance_common::chat::{Message, MessageRole};
-use neuromance_common::client::{ChatRequest, Config, Usage};
+use neuromance_common::chat::{ContentPart, Message, MessageRole};
+use neuromance_common::client::{ChatRequest, Config, ToolChoice, Usage};
 use neuromance_common::features::ReasoningLevel;
 use neuromance_common::tools::{FunctionCall, Tool, ToolCall};
 
 pub use neuromance_common::client::ReasoningEffort;
 
+/// [`ChatResponse::metadata`](neuromance_common::client::ChatResponse::metadata)
+/// key holding the backend's `system_fingerprint`, when it sends one.
+pub const SYSTEM_FINGERPRINT_METADATA_KEY: &str = "system_fingerprint";
+
 /// Convert from the abstract `ReasoningLevel` to the Chat Completions API's `ReasoningEffort`.
 const fn reasoning_level_to_effort(level: ReasoningLevel) -> Option<ReasoningEffort> {
     match level {
@@ -30,6 +34,14 @@ const fn reasoning_level_to_effort(level: ReasoningLevel) -> Option<ReasoningEff
     }
 }
 
+/// Whether `model` is an o-series reasoning model (`o1`, `o3-mini`,
+/// `o4-mini`, ...), which rejects `temperature` and `top_p`.
+fn is_reasoning_model(model: &str) -> bool {
+    let model = model.rsplit('/').next().unwrap_or(model);
+    let mut chars = model.chars();
+    chars.next() == Some('o') && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
 pub mod client;
 pub mod embedding;
 
@@ -59,10 +71,10 @@ pub struct ChatChoice {
 pub struct ChatCompletionsMessage {
     /// The role of the message author (user, assistant, system, or tool).
     pub role: MessageRole,
-    /// The text content of the message (optional for tool calls).
+    /// The content of the message (optional for tool calls).
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub content: Option<String>,
+    pub content: Option<ChatCompletionsContent>,
     /// Optional name of the message author.
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -99,11 +111,24 @@ impl From<&Message> for ChatCompletionsMessage {
             )
         };
 
-        // Only include content if it's non-empty
-        let content = if message.content.is_empty() {
+        // Images are sent as parts ahead of the text; otherwise only include
+        // content if it's non-empty
+        let content = if !message.content_parts.is_empty() {
+            let mut parts: Vec<ChatCompletionsContentPart> = message
+                .content_parts
+                .iter()
+                .map(ChatCompletionsContentPart::from)
+                .collect();
+            if !message.content.is_empty() {
+                parts.push(ChatCompletionsContentPart::Text {
+                    text: message.content.clone(),
+                });
+            }
+            Some(ChatCompletionsContent::Parts(parts))
+        } else if message.content.is_empty() {
             None
         } else {
-            Some(message.content.clone())
+            Some(ChatCompletionsContent::Text(message.content.clone()))
         };
 
         Self::builder()
@@ -117,6 +142,87 @@ impl From<&Message> for ChatCompletionsMessage {
     }
 }
 
+/// Content of a Chat Completions message: plain text or an array of parts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ChatCompletionsContent {
+    /// Plain text content.
+    Text(String),
+    /// Multimodal content parts.
+    Parts(Vec<ChatCompletionsContentPart>),
+}
+
+impl ChatCompletionsContent {
+    /// Returns the text content, joining the text parts of multimodal content.
+    #[must_use]
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            Self::Text(text) => Cow::Borrowed(text),
+            Self::Parts(parts) => Cow::Owned(
+                parts
+                    .iter()
+                    .filter_map(|part| match part {
+                        ChatCompletionsContentPart::Text { text } => Some(text.as_str()),
+                        ChatCompletionsContentPart::ImageUrl { .. } => None,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<String> for ChatCompletionsContent {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for ChatCompletionsContent {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+/// A part of multimodal Chat Completions content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatCompletionsContentPart {
+    /// Text content part.
+    Text {
+        /// The text content.
+        text: String,
+    },
+    /// Image content part.
+    ImageUrl {
+        /// The image, by URL or data URL.
+        image_url: ImageUrl,
+    },
+}
+
+/// Image reference in a Chat Completions content part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageUrl {
+    /// HTTP(S) URL or `data:` URL of the image.
+    pub url: String,
+    /// Optional detail level (`low`, `high`, or `auto`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Inline images become `data:` URLs.
+impl From<&ContentPart> for ChatCompletionsContentPart {
+    fn from(part: &ContentPart) -> Self {
+        match part {
+            ContentPart::Image { media_type, data } => Self::ImageUrl {
+                image_url: ImageUrl {
+                    url: format!("data:{media_type};base64,{data}"),
+                    detail: None,
+                },
+            },
+        }
+    }
+}
+
 /// Chat Completions tool call format.
 ///
 /// Represents a request from the model to call a function/tool.
@@ -170,6 +276,48 @@ impl From<&FunctionCall> for ChatCompletionsFunction {
     }
 }
 
+/// Chat Completions `tool_choice` value.
+///
+/// Modes serialize as bare strings (`"auto"`, `"none"`, `"required"`); forcing
+/// a function serializes as `{"type": "function", "function": {"name": ...}}`.
+/// Some OpenAI-compatible servers reject any other shape.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ChatCompletionsToolChoice {
+    /// String-based choice (auto, none, required).
+    Mode(Cow<'static, str>),
+    /// Force a specific function.
+    Function {
+        /// Type, always "function".
+        #[serde(rename = "type")]
+        r#type: Cow<'static, str>,
+        /// The function to call.
+        function: ChatCompletionsFunctionName,
+    },
+}
+
+/// Names the function forced by [`ChatCompletionsToolChoice::Function`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatCompletionsFunctionName {
+    /// Name of the function to call.
+    pub name: String,
+}
+
+impl From<&ToolChoice> for ChatCompletionsToolChoice {
+    fn from(choice: &ToolChoice) -> Self {
+        match choice {
+            ToolChoice::None => Self::Mode(Cow::Borrowed("none")),
+            ToolChoice::Required | ToolChoice::Any { .. } => Self::Mode(Cow::Borrowed("required")),
+            ToolChoice::Function { name } => Self::Function {
+                r#type: Cow::Borrowed("function"),
+                function: ChatCompletionsFunctionName { name: name.clone() },
+            },
+            // Auto and any future variants default to auto
+            _ => Self::Mode(Cow::Borrowed("auto")),
+        }
+    }
+}
+
 /// Request for a chat completion.
 ///
 /// Contains all parameters for the Chat Completions API.
@@ -223,6 +371,10 @@ pub struct ChatCompletionRequest {
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<u32>,
+    /// Seed for best-effort deterministic sampling (optional).
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
     /// Stop sequences (optional).
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -254,7 +406,7 @@ pub struct ChatCompletionRequest {
     /// Tool selection strategy (optional).
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_choice: Option<serde_json::Value>,
+    pub tool_choice: Option<ChatCompletionsToolChoice>,
     /// Whether to enable thinking mode (vendor-specific, optional).
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -263,6 +415,10 @@ pub struct ChatCompletionRequest {
     #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream_options: Option<serde_json::Value>,
+    /// Structured output format, e.g. `{"type": "json_schema", ...}` (optional).
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<serde_json::Value>,
 }
 
 /// Conversion from a generic `ChatRequest` to the Chat Completions format.
@@ -278,10 +434,21 @@ impl From<(&ChatRequest, &Config)> for ChatCompletionRequest {
             .collect();
 
         let tools: Option<Vec<Tool>> = request.tools.clone();
+        let model = request
+            .model
+            .clone()
+            .unwrap_or_else(|| config.model.clone());
 
         // Map ReasoningLevel to ReasoningEffort
         let reasoning_effort = reasoning_level_to_effort(request.reasoning_level);
 
+        // Reasoning models reject sampling parameters outright
+        let (temperature, top_p) = if is_reasoning_model(&model) {
+            (None, None)
+        } else {
+            (request.temperature, request.top_p)
+        };
+
         // Map ThinkingMode - thinking budget maps to max_completion_tokens
         // and enable_thinking is set if any thinking mode is enabled
         let max_completion_tokens = request
@@ -294,26 +461,30 @@ impl From<(&ChatRequest, &Config)> for ChatCompletionRequest {
         };
 
         Self::builder()
-            .model(
-                request
-                    .model
-                    .clone()
-                    .unwrap_or_else(|| config.model.clone()),
-            )
+            .model(model)
             .messages(messages)
             .max_tokens(request.max_tokens)
             .max_completion_tokens(max_completion_tokens)
             .reasoning_effort(reasoning_effort)
-            .temperature(request.temperature)
-            .top_p(request.top_p)
+            .temperature(temperature)
+            .top_p(top_p)
+            .n(request.n)
+            .seed(request.seed)
             .stop(request.stop.clone())
             .presence_penalty(request.presence_penalty)
             .frequency_penalty(request.frequency_penalty)
+            .logit_bias(request.logit_bias.clone())
             .user(request.user.clone())
             .stream(Some(request.stream))
             .tools(tools)
-            .tool_choice(request.tool_choice.as_ref().map(|tc| tc.clone().into()))
+            .tool_choice(
+                request
+                    .tool_choice
+                    .as_ref()
+                    .map(ChatCompletionsToolChoice::from),
+            )
             .enable_thinking(enable_thinking)
+            .response_format(request.response_format.clone().map(Into::into))
             .build()
     }
 }
@@ -363,6 +534,10 @@ pub struct ChatCompletionResponse {
     /// Token usage statistics (if available).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<Usage>,
+    /// Backend configuration fingerprint; changes when results may differ
+    /// for the same seed.
+    #[serde(default)]
+    pub system_fingerprint: Option<String>,
 }
 
 /// A single choice from a streaming chat completion chunk.
@@ -457,4 +632,16 @@ pub struct ChatCompletionChunk {
     /// Token usage (only present in final chunk for some providers).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<Usage>,
+    /// Groq's extension block, which carries the final chunk's usage in
+    /// place of `usage`.
+    #[serde(default)]
+    pub x_groq: Option<GroqChunkExtension>,
+}
+
+/// The `x_groq` block on a Groq streaming chunk.
+#[derive(Debug, Deserialize)]
+pub struct GroqChunkExtension {
+    /// Token usage for the whole stream (final chunk only).
+    #[serde(default)]
+    pub usage: Option<Usage>,
 }
@@ -0,0 +1,672 @@
+//! Cohere native client implementation.
+//!
+//! This module provides a client for Cohere's `/v1/chat` endpoint, used by the
+//! Command model family.
+//!
+//! # Features
+//!
+//! - **Conversation mapping**: messages are split into `preamble`,
+//!   `chat_history`, and the current `message`
+//! - **Tool/Function Calling**: tool schemas become `parameter_definitions`,
+//!   and tool messages become `tool_results` paired with their originating call
+//! - **RAG**: the `documents` request metadata key is passed through as
+//!   Cohere's `documents`
+//! - **Streaming**: newline-delimited events via [`run_ndjson_stream`]
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use neuromance_client::{CohereClient, LLMClient};
+//! use neuromance_common::client::{Config, ChatRequest};
+//! use neuromance_common::chat::Conversation;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let config = Config::new("cohere", "command-r-plus")
+//!     .with_api_key("your-api-key");
+//! let client = CohereClient::new(config)?;
+//!
+//! let mut conversation = Conversation::new();
+//! conversation.add_message(conversation.user_message("Where do emperor penguins live?"))?;
+//!
+//! let documents = serde_json::json!([
+//!     {"title": "Penguins", "snippet": "Emperor penguins live in Antarctica."}
+//! ]);
+//! let request = ChatRequest::new(conversation.get_messages().to_vec())
+//!     .with_metadata([("documents".to_string(), documents)].into());
+//! let response = client.chat(&request).await?;
+//!
+//! println!("Response: {}", response.message.content);
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::stream::Stream;
+use reqwest_middleware::ClientWithMiddleware;
+use secrecy::{ExposeSecret, SecretString};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::error;
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{
+    Capabilities, ChatChunk, ChatRequest, ChatResponse, Config, FinishReason, ProxyConfig,
+};
+
+use crate::error::ClientError;
+use crate::message::MessageBuilder;
+use crate::request_tracing::{traced_chat, traced_stream};
+use crate::streaming::{ChatChunkStream, StreamingProvider, run_ndjson_stream};
+use crate::transport::{add_proxy_headers, send_json};
+use crate::{LLMClient, base_capabilities, build_client_resources};
+
+use super::{
+    CohereChatRequest, CohereChatResponse, CohereStreamEvent, DEFAULT_BASE_URL, convert_tool_call,
+    parse_finish_reason,
+};
+
+/// Client for Cohere's native chat endpoint.
+///
+/// # Security
+///
+/// The API key is stored using the `secrecy` crate to prevent accidental
+/// exposure through debug logs or memory dumps.
+///
+/// # Proxy Support
+///
+/// When a [`ProxyConfig`] is provided in the [`Config`], requests are routed
+/// through a tokenizer proxy. The proxy intercepts requests and injects real
+/// credentials, allowing agents to use sealed tokens instead of raw API keys.
+#[derive(Clone)]
+pub struct CohereClient {
+    client: ClientWithMiddleware,
+    streaming_client: reqwest::Client,
+    api_key: Arc<SecretString>,
+    base_url: String,
+    config: Arc<Config>,
+    proxy_config: Option<ProxyConfig>,
+}
+
+impl std::fmt::Debug for CohereClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CohereClient")
+            .field("api_key", &"[REDACTED]")
+            .field("base_url", &self.base_url)
+            .field("config", &self.config)
+            .field("proxy_config", &self.proxy_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CohereClient {
+    /// Create a new Cohere client from a configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neuromance_client::CohereClient;
+    /// use neuromance_common::client::Config;
+    ///
+    /// let config = Config::new("cohere", "command-r").with_api_key("your-api-key");
+    /// let client = CohereClient::new(config)?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key is missing or HTTP client creation fails.
+    pub fn new(config: Config) -> Result<Self, ClientError> {
+        let r = build_client_resources(config, DEFAULT_BASE_URL)?;
+
+        Ok(Self {
+            client: r.client,
+            streaming_client: r.streaming_client,
+            api_key: r.api_key,
+            base_url: r.base_url,
+            config: r.config,
+            proxy_config: r.proxy_config,
+        })
+    }
+
+    /// Set a custom base URL for the API endpoint.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        Arc::make_mut(&mut self.config).base_url = Some(base_url.clone());
+        self.base_url = base_url;
+        self
+    }
+
+    /// Set the model to use for chat.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.config).model = model.into();
+        self
+    }
+
+    /// Build and validate the `/chat` URL.
+    fn chat_url(&self) -> Result<String, ClientError> {
+        let url = format!("{}/chat", self.base_url);
+        reqwest::Url::parse(&url)
+            .map_err(|e| ClientError::ConfigurationError(format!("Invalid URL '{url}': {e}")))?;
+        Ok(url)
+    }
+
+    /// Convert a Cohere response to our internal Message format.
+    fn convert_message(response: &CohereChatResponse, conversation_id: uuid::Uuid) -> Message {
+        let mut builder = MessageBuilder::new(conversation_id, MessageRole::Assistant);
+        builder.set_content(response.text.clone());
+        for call in &response.tool_calls {
+            builder.push_tool_call(convert_tool_call(call));
+        }
+        builder.build()
+    }
+}
+
+/// Report a turn that called tools as [`FinishReason::ToolCalls`], since
+/// Cohere often finishes such turns with `COMPLETE`.
+fn finish_reason(reason: Option<&str>, called_tools: bool) -> Option<FinishReason> {
+    match reason.and_then(parse_finish_reason) {
+        Some(FinishReason::Stop) if called_tools => Some(FinishReason::ToolCalls),
+        reason => reason,
+    }
+}
+
+/// Per-stream state for [`CohereClient`]'s event stream.
+#[derive(Debug, Default)]
+pub struct CohereStreamState {
+    /// Number of tool calls seen so far; the next call's index.
+    tool_calls_seen: u32,
+}
+
+/// Convert one streamed Cohere event to our common `ChatChunk` format.
+///
+/// Returns `None` for events that carry nothing we surface. Tool calls arrive
+/// whole in `tool-calls-generation` and are indexed in arrival order.
+#[must_use]
+pub fn convert_event_to_chat_chunk(
+    event: CohereStreamEvent,
+    state: &mut CohereStreamState,
+    model: &str,
+) -> Option<ChatChunk> {
+    let mut chunk = ChatChunk {
+        model: model.to_string(),
+        delta_content: None,
+        delta_reasoning_content: None,
+        delta_role: None,
+        delta_tool_calls: None,
+        finish_reason: None,
+        usage: None,
+        response_id: None,
+        created_at: Utc::now(),
+        metadata: HashMap::new(),
+    };
+    match event {
+        CohereStreamEvent::StreamStart { .. } => chunk.delta_role = Some(MessageRole::Assistant),
+        CohereStreamEvent::TextGeneration { text } => chunk.delta_content = Some(text),
+        CohereStreamEvent::ToolCallsGeneration { tool_calls } if !tool_calls.is_empty() => {
+            let calls = tool_calls
+                .iter()
+                .map(|call| {
+                    let mut tool_call = convert_tool_call(call);
+                    tool_call.index = Some(state.tool_calls_seen);
+                    state.tool_calls_seen += 1;
+                    tool_call
+                })
+                .collect();
+            chunk.delta_tool_calls = Some(calls);
+        }
+        CohereStreamEvent::StreamEnd {
+            finish_reason: reason,
+            response,
+        } => {
+            chunk.finish_reason = finish_reason(reason.as_deref(), state.tool_calls_seen > 0);
+            if let Some(response) = response {
+                chunk.usage = response.meta.as_ref().and_then(super::CohereMeta::usage);
+                chunk.response_id =
+                    (!response.response_id.is_empty()).then_some(response.response_id);
+            }
+        }
+        CohereStreamEvent::ToolCallsGeneration { .. } | CohereStreamEvent::Other => return None,
+    }
+    Some(chunk)
+}
+
+impl CohereClient {
+    /// [`LLMClient::chat`] without its request span.
+    async fn chat_inner(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        self.validate_request(request)?;
+
+        let mut cohere_request = CohereChatRequest::from((request, self.config.as_ref()));
+        cohere_request.stream = false;
+        let url = self.chat_url()?;
+
+        let mut request_builder = self
+            .client
+            .post(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.api_key.expose_secret()),
+            )
+            .header("Content-Type", "application/json");
+
+        request_builder =
+            add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
+
+        let request_builder = request_builder
+            .body(serde_json::to_string(&cohere_request).map_err(ClientError::SerializationError)?);
+
+        let response: CohereChatResponse = send_json(request_builder).await?;
+
+        let conversation_id = request
+            .messages
+            .first()
+            .ok_or_else(|| {
+                error!("Request has no messages despite passing validation");
+                ClientError::InvalidRequest("Request must contain at least one message".to_string())
+            })?
+            .conversation_id;
+
+        let message = Self::convert_message(&response, conversation_id);
+        let finish_reason = finish_reason(
+            response.finish_reason.as_deref(),
+            !response.tool_calls.is_empty(),
+        );
+
+        Ok(ChatResponse {
+            message,
+            usage: response.meta.as_ref().and_then(super::CohereMeta::usage),
+            model: cohere_request.model,
+            finish_reason,
+            created_at: Utc::now(),
+            response_id: (!response.response_id.is_empty()).then_some(response.response_id),
+            metadata: HashMap::new(),
+            additional_choices: Vec::new(),
+        })
+    }
+
+    /// [`LLMClient::chat_stream`] without its request span.
+    async fn chat_stream_inner(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<ChatChunkStream, ClientError> {
+        self.validate_request(request)?;
+
+        let mut cohere_request = CohereChatRequest::from((request, self.config.as_ref()));
+        cohere_request.stream = true;
+        let url = self.chat_url()?;
+
+        let mut request_builder = self
+            .streaming_client
+            .post(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.api_key.expose_secret()),
+            )
+            .header("Content-Type", "application/json");
+
+        request_builder =
+            add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
+
+        let request_builder = request_builder.json(&cohere_request);
+
+        let stream = CohereStream {
+            model: cohere_request.model,
+        };
+        run_ndjson_stream(&stream, request_builder).await
+    }
+}
+
+#[async_trait]
+impl LLMClient for CohereClient {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            parallel_tool_calls: true,
+            ..base_capabilities(self)
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        let mut cohere_request = CohereChatRequest::from((request, self.config.as_ref()));
+        cohere_request.stream = false;
+        serde_json::to_value(&cohere_request).ok()
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        traced_chat(self.config(), request, self.chat_inner(request)).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        traced_stream(self.config(), request, self.chat_stream_inner(request)).await
+    }
+}
+
+/// Streaming provider for one Cohere response. Cohere's events do not name
+/// the model, so the requested model is carried into each chunk.
+struct CohereStream {
+    model: String,
+}
+
+impl StreamingProvider for CohereStream {
+    type Event = CohereStreamEvent;
+    type State = (CohereStreamState, String);
+
+    fn initial_state(&self) -> Self::State {
+        (CohereStreamState::default(), self.model.clone())
+    }
+
+    fn process_event(
+        (state, model): &mut Self::State,
+        event: Self::Event,
+    ) -> Option<Result<ChatChunk, ClientError>> {
+        convert_event_to_chat_chunk(event, state, model).map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::streaming::collect_stream;
+    use futures::StreamExt;
+    use neuromance_common::tools::{Function, Tool, ToolCall};
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_test_config(base_url: &str) -> Config {
+        Config::new("cohere", "command-r-plus")
+            .with_api_key("test-key")
+            .with_base_url(base_url)
+    }
+
+    fn weather_tool() -> Tool {
+        Tool {
+            r#type: "function".to_string(),
+            function: Function {
+                name: "get_weather".to_string(),
+                description: "Get the weather".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "location": {"type": "string", "description": "City name"},
+                        "days": {"type": "array", "items": {"type": "integer"}}
+                    },
+                    "required": ["location"]
+                }),
+            },
+        }
+    }
+
+    fn ndjson(events: &[serde_json::Value]) -> String {
+        events.iter().fold(String::new(), |mut body, event| {
+            body.push_str(&event.to_string());
+            body.push('\n');
+            body
+        })
+    }
+
+    #[tokio::test]
+    async fn test_basic_chat_maps_history_preamble_and_documents() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat"))
+            .and(header("authorization", "Bearer test-key"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "command-r-plus",
+                "message": "And in winter?",
+                "preamble": "Be brief.",
+                "chat_history": [
+                    {"role": "USER", "message": "Where do emperor penguins live?"},
+                    {"role": "CHATBOT", "message": "Antarctica."}
+                ],
+                "documents": [{"title": "Penguins", "snippet": "They huddle."}],
+                "stream": false
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "response_id": "resp-1",
+                "generation_id": "gen-1",
+                "text": "They huddle for warmth.",
+                "finish_reason": "COMPLETE",
+                "meta": {
+                    "billed_units": {"input_tokens": 20, "output_tokens": 5},
+                    "tokens": {"input_tokens": 90, "output_tokens": 5}
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = CohereClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let conversation_id = uuid::Uuid::new_v4();
+        let documents = serde_json::json!([{"title": "Penguins", "snippet": "They huddle."}]);
+        let request = ChatRequest::new(vec![
+            Message::system(conversation_id, "Be brief."),
+            Message::user(conversation_id, "Where do emperor penguins live?"),
+            Message::assistant(conversation_id, "Antarctica."),
+            Message::user(conversation_id, "And in winter?"),
+        ])
+        .with_metadata([("documents".to_string(), documents)].into());
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.model, "command-r-plus");
+        assert_eq!(response.message.content, "They huddle for warmth.");
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(response.response_id.as_deref(), Some("resp-1"));
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 90);
+        assert_eq!(usage.total_tokens, 95);
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_turn_and_tool_results() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat"))
+            .and(body_partial_json(serde_json::json!({
+                "message": "Weather in Paris?",
+                "tools": [{
+                    "name": "get_weather",
+                    "parameter_definitions": {
+                        "location": {"type": "str", "description": "City name", "required": true},
+                        "days": {"type": "List[int]", "required": false}
+                    }
+                }]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "response_id": "resp-2",
+                "text": "I will look that up.",
+                "tool_calls": [{"name": "get_weather", "parameters": {"location": "Paris"}}],
+                "finish_reason": "COMPLETE"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = CohereClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let conversation_id = uuid::Uuid::new_v4();
+        let request = ChatRequest::new(vec![Message::user(conversation_id, "Weather in Paris?")])
+            .with_tools(vec![weather_tool()]);
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.finish_reason, Some(FinishReason::ToolCalls));
+        let call = &response.message.tool_calls[0];
+        assert!(!call.id.is_empty());
+        assert_eq!(call.function.name, "get_weather");
+        let args: serde_json::Value = serde_json::from_str(&call.function.arguments).unwrap();
+        assert_eq!(args["location"], "Paris");
+
+        // Sending the result back: the call is recovered by id and the plain
+        // text output is wrapped in an object.
+        let mut messages = request.messages.to_vec();
+        messages.push(response.message.clone());
+        messages.push(
+            Message::tool(
+                conversation_id,
+                "18C",
+                call.id.clone(),
+                "get_weather".into(),
+            )
+            .unwrap(),
+        );
+        let follow_up = ChatRequest::new(messages);
+        let body =
+            serde_json::to_value(CohereChatRequest::from((&follow_up, client.config()))).unwrap();
+
+        assert_eq!(body["message"], "");
+        assert_eq!(
+            body["tool_results"],
+            serde_json::json!([{
+                "call": {"name": "get_weather", "parameters": {"location": "Paris"}},
+                "outputs": [{"result": "18C"}]
+            }])
+        );
+        let history = body["chat_history"].as_array().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1]["role"], "CHATBOT");
+        assert_eq!(history[1]["tool_calls"][0]["name"], "get_weather");
+    }
+
+    #[test]
+    fn test_earlier_tool_results_are_grouped_in_history() {
+        let conversation_id = uuid::Uuid::new_v4();
+        let first = ToolCall::new("get_weather", r#"{"location":"Oslo"}"#);
+        let second = ToolCall::new("get_weather", r#"{"location":"Rome"}"#);
+        let mut assistant = Message::assistant(conversation_id, "");
+        assistant.tool_calls.push(first.clone());
+        assistant.tool_calls.push(second.clone());
+        let messages = vec![
+            Message::user(conversation_id, "Compare Oslo and Rome."),
+            assistant,
+            Message::tool(
+                conversation_id,
+                r#"{"temp":3}"#,
+                first.id,
+                "get_weather".into(),
+            )
+            .unwrap(),
+            Message::tool(conversation_id, "21C", second.id, "get_weather".into()).unwrap(),
+            Message::assistant(conversation_id, "Rome is warmer."),
+            Message::developer(conversation_id, "Answer in French."),
+            Message::user(conversation_id, "Why?"),
+        ];
+        let request = ChatRequest::new(messages);
+        let config = Config::new("cohere", "command-r");
+
+        let body = serde_json::to_value(CohereChatRequest::from((&request, &config))).unwrap();
+
+        assert!(body.get("preamble").is_none());
+        assert_eq!(body["message"], "Why?");
+        let history = body["chat_history"].as_array().unwrap();
+        assert_eq!(history.len(), 5);
+        assert_eq!(history[2]["role"], "TOOL");
+        assert_eq!(
+            history[2]["tool_results"][0]["outputs"],
+            serde_json::json!([{"temp": 3}])
+        );
+        assert_eq!(
+            history[2]["tool_results"][1]["call"]["parameters"]["location"],
+            "Rome"
+        );
+        assert_eq!(history[4]["role"], "SYSTEM");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_text_and_tool_calls() {
+        let mock_server = MockServer::start().await;
+
+        let body = ndjson(&[
+            serde_json::json!({"is_finished": false, "event_type": "stream-start", "generation_id": "gen-3"}),
+            serde_json::json!({"is_finished": false, "event_type": "text-generation", "text": "Checking "}),
+            serde_json::json!({"is_finished": false, "event_type": "text-generation", "text": "both."}),
+            serde_json::json!({"is_finished": false, "event_type": "tool-calls-chunk",
+                "tool_call_delta": {"index": 0, "name": "get_weather"}}),
+            serde_json::json!({"is_finished": false, "event_type": "tool-calls-generation",
+            "tool_calls": [
+                {"name": "get_weather", "parameters": {"location": "Oslo"}},
+                {"name": "get_weather", "parameters": {"location": "Rome"}}
+            ]}),
+            serde_json::json!({"is_finished": true, "event_type": "stream-end",
+            "finish_reason": "COMPLETE",
+            "response": {
+                "response_id": "resp-3",
+                "text": "Checking both.",
+                "meta": {"billed_units": {"input_tokens": 12, "output_tokens": 9}}
+            }}),
+        ]);
+
+        Mock::given(method("POST"))
+            .and(path("/chat"))
+            .and(body_partial_json(serde_json::json!({"stream": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/stream+json"))
+            .mount(&mock_server)
+            .await;
+
+        let client = CohereClient::new(create_test_config(&mock_server.uri()))
+            .unwrap()
+            .with_model("command-r");
+        let request = ChatRequest::new(vec![Message::user(uuid::Uuid::new_v4(), "Oslo or Rome?")])
+            .with_tools(vec![weather_tool()]);
+
+        let chunks: Vec<ChatChunk> = client
+            .chat_stream(&request)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 5);
+        assert!(chunks.iter().all(|c| c.model == "command-r"));
+
+        let stream = client.chat_stream(&request).await.unwrap();
+        let response = collect_stream(stream, uuid::Uuid::new_v4()).await.unwrap();
+
+        assert_eq!(response.message.content, "Checking both.");
+        assert_eq!(response.finish_reason, Some(FinishReason::ToolCalls));
+        assert_eq!(response.response_id.as_deref(), Some("resp-3"));
+        assert_eq!(response.usage.unwrap().total_tokens, 21);
+        let calls = &response.message.tool_calls;
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].function.arguments, r#"{"location":"Oslo"}"#);
+        assert_eq!(calls[1].function.arguments, r#"{"location":"Rome"}"#);
+    }
+
+    #[test]
+    fn test_finish_reason_mapping() {
+        assert_eq!(parse_finish_reason("COMPLETE"), Some(FinishReason::Stop));
+        assert_eq!(
+            parse_finish_reason("MAX_TOKENS"),
+            Some(FinishReason::Length)
+        );
+        assert_eq!(
+            parse_finish_reason("TOOL_CALL"),
+            Some(FinishReason::ToolCalls)
+        );
+        assert_eq!(parse_finish_reason("UNKNOWN"), None);
+        assert_eq!(
+            finish_reason(Some("MAX_TOKENS"), true),
+            Some(FinishReason::Length)
+        );
+    }
+}
@@ -0,0 +1,454 @@
+//! Cohere chat API types and client implementation.
+//!
+//! Cohere's `/v1/chat` differs from `OpenAI`-style APIs: the latest user turn
+//! is sent as `message` with earlier turns in `chat_history`, system prompts
+//! go in `preamble`, tools declare flat `parameter_definitions` instead of a
+//! JSON schema, and tool calls carry no ids, so results are matched back to
+//! their call by name and parameters in `tool_results`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{ChatRequest, Config, FinishReason, Usage};
+use neuromance_common::tools::{Tool, ToolCall};
+
+pub mod client;
+pub use client::CohereClient;
+
+/// Default base URL for the Cohere API.
+pub const DEFAULT_BASE_URL: &str = "https://api.cohere.com/v1";
+
+/// Request metadata key read into `documents`; an array of objects (e.g.
+/// `{"title": ..., "snippet": ...}`) that grounds the reply for RAG.
+pub const DOCUMENTS_METADATA_KEY: &str = "documents";
+
+// ============================================================================
+// Request Types
+// ============================================================================
+
+/// An earlier turn in `chat_history`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "role", rename_all = "UPPERCASE")]
+pub enum CohereChatMessage {
+    /// A user turn.
+    User {
+        /// The user's text.
+        message: String,
+    },
+    /// A model turn, optionally calling tools.
+    Chatbot {
+        /// The model's text.
+        #[serde(default)]
+        message: String,
+        /// Tools the model called in this turn.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tool_calls: Vec<CohereToolCall>,
+    },
+    /// A system message after the conversation started.
+    System {
+        /// The instruction text.
+        message: String,
+    },
+    /// Results of the tool calls in the preceding model turn.
+    Tool {
+        /// One result per call.
+        tool_results: Vec<CohereToolResult>,
+    },
+}
+
+/// A tool call in the Cohere format; calls have no id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohereToolCall {
+    /// Name of the tool to call.
+    pub name: String,
+    /// Arguments as a JSON object.
+    #[serde(default)]
+    pub parameters: serde_json::Value,
+}
+
+/// The outputs of one tool call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohereToolResult {
+    /// The call these outputs answer.
+    pub call: CohereToolCall,
+    /// Output objects; plain-text results are wrapped as `{"result": ...}`.
+    pub outputs: Vec<serde_json::Value>,
+}
+
+/// A tool definition in the Cohere format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohereTool {
+    /// Name of the tool.
+    pub name: String,
+    /// What the tool does.
+    pub description: String,
+    /// Parameters keyed by name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameter_definitions: HashMap<String, CohereParameterDefinition>,
+}
+
+/// One tool parameter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohereParameterDefinition {
+    /// What the parameter means.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Python-style type name, e.g. `str`, `int`, or `List[str]`.
+    pub r#type: String,
+    /// Whether the model must supply the parameter.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Request body for `/chat`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohereChatRequest {
+    /// Model name, e.g. `command-r-plus`.
+    pub model: String,
+    /// The latest user message; empty when sending `tool_results`.
+    pub message: String,
+    /// System instructions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preamble: Option<String>,
+    /// Earlier turns, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chat_history: Vec<CohereChatMessage>,
+    /// Tools the model may call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<CohereTool>>,
+    /// Results for the tool calls of the last model turn.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_results: Option<Vec<CohereToolResult>>,
+    /// Documents to ground the reply in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documents: Option<Vec<serde_json::Value>>,
+    /// Sampling temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling threshold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p: Option<f32>,
+    /// Maximum number of tokens to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Stop sequences.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    /// Presence penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    /// Frequency penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Whether to stream newline-delimited events.
+    pub stream: bool,
+}
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Response from `/chat`; also the `response` of a `stream-end` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohereChatResponse {
+    /// Response id.
+    #[serde(default)]
+    pub response_id: String,
+    /// Generated text.
+    #[serde(default)]
+    pub text: String,
+    /// Tools the model wants to call.
+    #[serde(default)]
+    pub tool_calls: Vec<CohereToolCall>,
+    /// Why generation stopped.
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+    /// Billing and token counts.
+    #[serde(default)]
+    pub meta: Option<CohereMeta>,
+}
+
+/// The `meta` block of a response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohereMeta {
+    /// Tokens billed for the request.
+    #[serde(default)]
+    pub billed_units: Option<CohereTokens>,
+    /// Tokens processed, including any added by Cohere's prompt template.
+    #[serde(default)]
+    pub tokens: Option<CohereTokens>,
+}
+
+/// Input and output token counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohereTokens {
+    /// Prompt tokens.
+    #[serde(default)]
+    pub input_tokens: u32,
+    /// Generated tokens.
+    #[serde(default)]
+    pub output_tokens: u32,
+}
+
+impl CohereMeta {
+    /// Token usage, preferring processed tokens over billed units.
+    #[must_use]
+    pub fn usage(&self) -> Option<Usage> {
+        let tokens = self.tokens.as_ref().or(self.billed_units.as_ref())?;
+        Some(Usage {
+            prompt_tokens: tokens.input_tokens,
+            completion_tokens: tokens.output_tokens,
+            total_tokens: tokens.input_tokens.saturating_add(tokens.output_tokens),
+            cost: None,
+            input_tokens_details: None,
+            output_tokens_details: None,
+        })
+    }
+}
+
+/// One line of a streamed chat response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event_type", rename_all = "kebab-case")]
+pub enum CohereStreamEvent {
+    /// First event of the stream.
+    StreamStart {
+        /// Id of the generation.
+        #[serde(default)]
+        generation_id: Option<String>,
+    },
+    /// A fragment of generated text.
+    TextGeneration {
+        /// The text fragment.
+        text: String,
+    },
+    /// Complete tool calls, sent once the model has finished planning them.
+    ToolCallsGeneration {
+        /// The tool calls.
+        #[serde(default)]
+        tool_calls: Vec<CohereToolCall>,
+    },
+    /// Last event of the stream, carrying the full response.
+    StreamEnd {
+        /// Why generation stopped.
+        #[serde(default)]
+        finish_reason: Option<String>,
+        /// The aggregated response, including token usage.
+        #[serde(default)]
+        response: Option<CohereChatResponse>,
+    },
+    /// Events this client does not interpret (citations, search results,
+    /// partial tool call chunks, ...).
+    #[serde(other)]
+    Other,
+}
+
+/// Map a Cohere `finish_reason` to our [`FinishReason`].
+#[must_use]
+pub fn parse_finish_reason(reason: &str) -> Option<FinishReason> {
+    match reason {
+        "COMPLETE" | "STOP_SEQUENCE" => Some(FinishReason::Stop),
+        "MAX_TOKENS" => Some(FinishReason::Length),
+        "TOOL_CALL" => Some(FinishReason::ToolCalls),
+        "ERROR_TOXIC" => Some(FinishReason::ContentFilter),
+        "ERROR" | "ERROR_LIMIT" => Some(FinishReason::ModelError),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Conversion Helpers
+// ============================================================================
+
+/// Map a JSON schema property to Cohere's Python-style type name.
+fn parameter_type(schema: &serde_json::Value) -> String {
+    match schema.get("type").and_then(serde_json::Value::as_str) {
+        Some("string") | None => "str".to_string(),
+        Some("integer") => "int".to_string(),
+        Some("number") => "float".to_string(),
+        Some("boolean") => "bool".to_string(),
+        Some("object") => "Dict".to_string(),
+        Some("array") => schema.get("items").map_or_else(
+            || "List".to_string(),
+            |items| format!("List[{}]", parameter_type(items)),
+        ),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Convert a tool's JSON schema parameters to flat parameter definitions.
+impl From<&Tool> for CohereTool {
+    fn from(tool: &Tool) -> Self {
+        let schema = &tool.function.parameters;
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(serde_json::Value::as_array)
+            .map(|r| r.iter().filter_map(serde_json::Value::as_str).collect())
+            .unwrap_or_default();
+        let parameter_definitions = schema
+            .get("properties")
+            .and_then(serde_json::Value::as_object)
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, property)| {
+                        let definition = CohereParameterDefinition {
+                            description: property
+                                .get("description")
+                                .and_then(serde_json::Value::as_str)
+                                .map(ToString::to_string),
+                            r#type: parameter_type(property),
+                            required: required.contains(&name.as_str()),
+                        };
+                        (name.clone(), definition)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            name: tool.function.name.clone(),
+            description: tool.function.description.clone(),
+            parameter_definitions,
+        }
+    }
+}
+
+impl From<&ToolCall> for CohereToolCall {
+    fn from(call: &ToolCall) -> Self {
+        Self {
+            name: call.function.name.clone(),
+            parameters: serde_json::from_str(call.function.arguments_json())
+                .unwrap_or_else(|_| serde_json::json!({})),
+        }
+    }
+}
+
+/// Convert a Cohere tool call into our common `ToolCall`, with a fresh id.
+#[must_use]
+pub fn convert_tool_call(call: &CohereToolCall) -> ToolCall {
+    let arguments = match &call.parameters {
+        serde_json::Value::Null => "{}".to_string(),
+        parameters => parameters.to_string(),
+    };
+    ToolCall::new(call.name.clone(), arguments)
+}
+
+/// Build the result for one tool message, recovering its call by id.
+fn tool_result(message: &Message, calls: &HashMap<&str, CohereToolCall>) -> CohereToolResult {
+    let call = message
+        .tool_call_id
+        .as_deref()
+        .and_then(|id| calls.get(id))
+        .cloned()
+        .unwrap_or_else(|| CohereToolCall {
+            name: message.name.clone().unwrap_or_default(),
+            parameters: serde_json::json!({}),
+        });
+    let outputs = match serde_json::from_str::<serde_json::Value>(&message.content) {
+        Ok(output @ serde_json::Value::Object(_)) => vec![output],
+        Ok(serde_json::Value::Array(outputs))
+            if outputs.iter().all(serde_json::Value::is_object) =>
+        {
+            outputs
+        }
+        _ => vec![serde_json::json!({ "result": message.content })],
+    };
+    CohereToolResult { call, outputs }
+}
+
+/// Conversion from `ChatRequest` to the Cohere request format.
+///
+/// Leading system and developer messages become the `preamble`. A trailing
+/// user message becomes `message`; trailing tool messages become
+/// `tool_results` with an empty `message`. Everything else goes, in order,
+/// into `chat_history`.
+impl From<(&ChatRequest, &Config)> for CohereChatRequest {
+    fn from((request, config): (&ChatRequest, &Config)) -> Self {
+        let messages: &[Message] = &request.messages;
+        let calls: HashMap<&str, CohereToolCall> = messages
+            .iter()
+            .flat_map(|m| &m.tool_calls)
+            .map(|call| (call.id.as_str(), CohereToolCall::from(call)))
+            .collect();
+
+        let trailing_tools = messages
+            .iter()
+            .rev()
+            .take_while(|m| m.role == MessageRole::Tool)
+            .count();
+        let (history, message, tool_results) = match messages.split_last() {
+            _ if trailing_tools > 0 => {
+                let (history, results) = messages.split_at(messages.len() - trailing_tools);
+                let results = results.iter().map(|m| tool_result(m, &calls)).collect();
+                (history, String::new(), Some(results))
+            }
+            Some((last, history)) if last.role == MessageRole::User => {
+                (history, last.content.clone(), None)
+            }
+            _ => (messages, String::new(), None),
+        };
+
+        let mut preamble = Vec::new();
+        let mut chat_history: Vec<CohereChatMessage> = Vec::new();
+        for message in history {
+            match message.role {
+                MessageRole::System | MessageRole::Developer if chat_history.is_empty() => {
+                    preamble.push(message.content.as_str());
+                }
+                MessageRole::System | MessageRole::Developer => {
+                    chat_history.push(CohereChatMessage::System {
+                        message: message.content.clone(),
+                    });
+                }
+                MessageRole::Assistant => chat_history.push(CohereChatMessage::Chatbot {
+                    message: message.content.clone(),
+                    tool_calls: message.tool_calls.iter().map(Into::into).collect(),
+                }),
+                MessageRole::Tool => {
+                    let result = tool_result(message, &calls);
+                    if let Some(CohereChatMessage::Tool { tool_results }) = chat_history.last_mut()
+                    {
+                        tool_results.push(result);
+                    } else {
+                        chat_history.push(CohereChatMessage::Tool {
+                            tool_results: vec![result],
+                        });
+                    }
+                }
+                _ => chat_history.push(CohereChatMessage::User {
+                    message: message.content.clone(),
+                }),
+            }
+        }
+
+        Self {
+            model: request
+                .model
+                .clone()
+                .unwrap_or_else(|| config.model.clone()),
+            message,
+            preamble: (!preamble.is_empty()).then(|| preamble.join("\n\n")),
+            chat_history,
+            tools: request
+                .tools
+                .as_ref()
+                .filter(|t| !t.is_empty())
+                .map(|tools| tools.iter().map(CohereTool::from).collect()),
+            tool_results,
+            documents: request
+                .metadata
+                .get(DOCUMENTS_METADATA_KEY)
+                .and_then(serde_json::Value::as_array)
+                .cloned(),
+            temperature: request.temperature,
+            p: request.top_p,
+            max_tokens: request.max_tokens.or(request.max_completion_tokens),
+            stop_sequences: request.stop.clone(),
+            presence_penalty: request.presence_penalty,
+            frequency_penalty: request.frequency_penalty,
+            stream: request.stream,
+        }
+    }
+}
@@ -92,6 +92,9 @@ pub struct EmbeddingConfig {
     pub retry_config: RetryConfig,
     /// Optional request timeout in seconds.
     pub timeout_seconds: Option<u64>,
+    /// Optional cap on inputs sent in one request. Larger batches are split
+    /// into several requests.
+    pub max_inputs_per_request: Option<usize>,
 }
 
 // Custom Debug to avoid exposing API key
@@ -104,6 +107,7 @@ impl std::fmt::Debug for EmbeddingConfig {
             .field("dimensions", &self.dimensions)
             .field("retry_config", &self.retry_config)
             .field("timeout_seconds", &self.timeout_seconds)
+            .field("max_inputs_per_request", &self.max_inputs_per_request)
             .finish()
     }
 }
@@ -123,6 +127,7 @@ impl EmbeddingConfig {
             dimensions: None,
             retry_config: RetryConfig::default(),
             timeout_seconds: None,
+            max_inputs_per_request: None,
         }
     }
 
@@ -195,7 +200,7 @@ impl EmbeddingConfig {
     ///
     /// * `retry_config` - The retry configuration
     #[must_use]
-    pub const fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
         self.retry_config = retry_config;
         self
     }
@@ -210,6 +215,20 @@ impl EmbeddingConfig {
         self.timeout_seconds = Some(timeout_seconds);
         self
     }
+
+    /// Cap the number of inputs sent in a single request.
+    ///
+    /// Batches larger than this are split into consecutive requests; the
+    /// results are concatenated in input order.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_inputs` - The maximum inputs per request
+    #[must_use]
+    pub const fn with_max_inputs_per_request(mut self, max_inputs: usize) -> Self {
+        self.max_inputs_per_request = Some(max_inputs);
+        self
+    }
 }
 
 impl From<&Config> for EmbeddingConfig {
@@ -249,6 +268,7 @@ impl From<&Config> for EmbeddingConfig {
             dimensions: None,
             retry_config: config.retry_config.clone(),
             timeout_seconds: config.timeout_seconds,
+            max_inputs_per_request: None,
         }
     }
 }
@@ -449,6 +469,43 @@ fn extract_batch_embeddings(response: EmbeddingResponse) -> Vec<Vec<f32>> {
     embeddings.into_iter().map(|e| e.embedding).collect()
 }
 
+/// Embed `texts` in requests of at most `client.max_inputs_per_request()`
+/// inputs each, returning one vector per input in input order.
+///
+/// Used by trait default implementations.
+async fn embed_in_batches<C: EmbeddingClient + ?Sized>(
+    client: &C,
+    texts: &[&str],
+    user: Option<&str>,
+) -> Result<Vec<Vec<f32>>, ClientError> {
+    let cap = client.max_inputs_per_request().unwrap_or(usize::MAX).max(1);
+    // An empty batch still goes out as one request so the provider's
+    // validation reports it.
+    let chunks: Vec<&[&str]> = if texts.is_empty() {
+        vec![texts]
+    } else {
+        texts.chunks(cap).collect()
+    };
+
+    let mut vectors = Vec::with_capacity(texts.len());
+    for chunk in chunks {
+        let mut request = EmbeddingRequest::new(EmbeddingInput::from(chunk));
+        if let Some(user) = user {
+            request = request.with_user(user);
+        }
+        let batch = extract_batch_embeddings(client.embed_request(&request).await?);
+        if batch.len() != chunk.len() {
+            return Err(ClientError::EmbeddingError(format!(
+                "API returned {} embeddings for {} inputs",
+                batch.len(),
+                chunk.len()
+            )));
+        }
+        vectors.extend(batch);
+    }
+    Ok(vectors)
+}
+
 /// Trait for embedding client implementations.
 ///
 /// Provides a unified interface for generating embeddings from text
@@ -475,9 +532,11 @@ pub trait EmbeddingClient: Send + Sync {
         extract_single_embedding(&response)
     }
 
-    /// Generate embeddings for multiple text inputs in a single request.
+    /// Generate embeddings for multiple text inputs.
     ///
-    /// This is more efficient than calling `embed` multiple times.
+    /// This is more efficient than calling `embed` multiple times. Batches
+    /// larger than [`max_inputs_per_request`](Self::max_inputs_per_request)
+    /// are split across several requests.
     ///
     /// # Arguments
     ///
@@ -487,10 +546,7 @@ pub trait EmbeddingClient: Send + Sync {
     ///
     /// A vector of embedding vectors, in the same order as the inputs.
     async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, ClientError> {
-        let input: EmbeddingInput = texts.into();
-        let request = EmbeddingRequest::new(input);
-        let response = self.embed_request(&request).await?;
-        Ok(extract_batch_embeddings(response))
+        embed_in_batches(self, texts, None).await
     }
 
     /// Generate embeddings with full control over request parameters.
@@ -534,10 +590,15 @@ pub trait EmbeddingClient: Send + Sync {
         texts: &[&str],
         user: &str,
     ) -> Result<Vec<Vec<f32>>, ClientError> {
-        let input: EmbeddingInput = texts.into();
-        let request = EmbeddingRequest::new(input).with_user(user);
-        let response = self.embed_request(&request).await?;
-        Ok(extract_batch_embeddings(response))
+        embed_in_batches(self, texts, Some(user)).await
+    }
+
+    /// The most inputs [`embed_batch`](Self::embed_batch) sends in one
+    /// request, or `None` for no limit.
+    ///
+    /// Defaults to [`EmbeddingConfig::max_inputs_per_request`].
+    fn max_inputs_per_request(&self) -> Option<usize> {
+        self.config().max_inputs_per_request
     }
 
     /// Get the default dimensions for the configured model.
@@ -553,6 +614,21 @@ pub trait EmbeddingClient: Send + Sync {
     }
 }
 
+/// Lets any embedding client drive code written against the
+/// [`Embedder`](neuromance_common::Embedder) seam, such as tool selection.
+#[async_trait]
+impl neuromance_common::Embedder for dyn EmbeddingClient {
+    type Error = ClientError;
+
+    fn embedding_model(&self) -> &str {
+        &self.config().model
+    }
+
+    async fn embed_texts(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, ClientError> {
+        self.embed_batch(texts).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
@@ -577,7 +653,8 @@ mod tests {
         let config = EmbeddingConfig::openai_small("test-key")
             .with_base_url("https://custom.api.com/v1")
             .with_dimensions(512)
-            .with_timeout(30);
+            .with_timeout(30)
+            .with_max_inputs_per_request(100);
 
         assert_eq!(
             config.base_url,
@@ -585,6 +662,7 @@ mod tests {
         );
         assert_eq!(config.dimensions, Some(512));
         assert_eq!(config.timeout_seconds, Some(30));
+        assert_eq!(config.max_inputs_per_request, Some(100));
     }
 
     #[test]
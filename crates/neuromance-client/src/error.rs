@@ -100,6 +100,20 @@ pub enum ClientError {
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
 
+    /// The request names a model outside [`Config::allowed_models`].
+    ///
+    /// [`Config::allowed_models`]: neuromance_common::client::Config::allowed_models
+    #[error(
+        "Model '{model}' is not in the allowed model list{}",
+        .suggestion.as_ref().map(|s| format!("; did you mean '{s}'?")).unwrap_or_default()
+    )]
+    ModelNotAllowed {
+        /// The model the request named.
+        model: String,
+        /// The closest allowed model, if any is a plausible typo fix.
+        suggestion: Option<String>,
+    },
+
     /// Tools requested but not supported by this model.
     ///
     /// The model or provider doesn't support function calling.
@@ -172,16 +186,29 @@ pub enum ClientError {
 impl ClientError {
     /// Check if this error is potentially retryable.
     ///
-    /// Returns `true` for network errors, timeouts, rate limits, and service unavailable errors.
-    pub const fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            Self::NetworkError(_)
-                | Self::MiddlewareError(_)
-                | Self::TimeoutError
-                | Self::RateLimitError { .. }
-                | Self::ServiceUnavailable(_)
-        )
+    /// Returns `true` for rate limits, service unavailable errors, timeouts,
+    /// and transient transport failures (connection, send, or body errors, and
+    /// HTTP 429/5xx statuses). Authentication, serialization, invalid-request,
+    /// and response-decoding errors are not retryable.
+    ///
+    /// Retry loops should consult this rather than inspecting status codes,
+    /// and honor [`Self::retry_after`] when it is set.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::NetworkError(e)
+            | Self::MiddlewareError(reqwest_middleware::Error::Reqwest(e))
+            | Self::EventSourceError(reqwest_eventsource::Error::Transport(e)) => {
+                is_transient_reqwest_error(e)
+            }
+            Self::EventSourceError(reqwest_eventsource::Error::InvalidStatusCode(status, _)) => {
+                is_retryable_status(*status)
+            }
+            Self::MiddlewareError(reqwest_middleware::Error::Middleware(_))
+            | Self::TimeoutError
+            | Self::RateLimitError { .. }
+            | Self::ServiceUnavailable(_) => true,
+            _ => false,
+        }
     }
 
     /// Check if this is an authentication error.
@@ -204,3 +231,132 @@ impl ClientError {
         }
     }
 }
+
+/// Whether an HTTP status signals a transient condition worth retrying.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Whether a `reqwest` error came from a transient transport failure rather
+/// than a malformed request or an undecodable response.
+fn is_transient_reqwest_error(err: &reqwest::Error) -> bool {
+    if let Some(status) = err.status() {
+        return is_retryable_status(status);
+    }
+    err.is_timeout() || err.is_connect() || err.is_request() || err.is_body()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn serde_error() -> serde_json::Error {
+        serde_json::from_str::<serde_json::Value>("{").unwrap_err()
+    }
+
+    #[test]
+    fn retryable_variants() {
+        for err in [
+            ClientError::RateLimitError { retry_after: None },
+            ClientError::ServiceUnavailable("overloaded".to_string()),
+            ClientError::TimeoutError,
+        ] {
+            assert!(err.is_retryable(), "{err} should be retryable");
+        }
+    }
+
+    #[test]
+    fn non_retryable_variants() {
+        for err in [
+            ClientError::AuthenticationError("bad key".to_string()),
+            ClientError::SerializationError(serde_error()),
+            ClientError::InvalidRequest("no messages".to_string()),
+            ClientError::RequestError("400".to_string()),
+            ClientError::ConfigurationError("bad url".to_string()),
+            ClientError::InvalidResponse("no choices".to_string()),
+            ClientError::ContextLengthExceeded {
+                current_tokens: 2,
+                max_tokens: 1,
+            },
+            ClientError::ContentFiltered {
+                reason: "policy".to_string(),
+            },
+            ClientError::InvalidTemperature,
+            ClientError::ToolsNotSupported,
+            ClientError::EventSourceError(reqwest_eventsource::Error::StreamEnded),
+        ] {
+            assert!(!err.is_retryable(), "{err} should not be retryable");
+        }
+    }
+
+    #[tokio::test]
+    async fn connection_failure_is_retryable() {
+        // Port 1 is reserved and refuses connections.
+        let err = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+        assert!(err.is_connect());
+        assert!(ClientError::NetworkError(err).is_retryable());
+    }
+
+    #[test]
+    fn request_builder_failure_is_not_retryable() {
+        let err = reqwest::Client::new().get("not a url").build().unwrap_err();
+        assert!(!ClientError::NetworkError(err).is_retryable());
+    }
+
+    #[tokio::test]
+    async fn network_error_status_decides_retryability() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let err = reqwest::get(server.uri())
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap_err();
+        assert!(ClientError::NetworkError(err).is_retryable());
+    }
+
+    #[tokio::test]
+    async fn undecodable_response_is_not_retryable() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+            .mount(&server)
+            .await;
+
+        let err = reqwest::get(server.uri())
+            .await
+            .unwrap()
+            .json::<serde_json::Value>()
+            .await
+            .unwrap_err();
+        assert!(err.is_decode());
+        assert!(!ClientError::NetworkError(err).is_retryable());
+    }
+
+    #[test]
+    fn retry_after_only_for_rate_limits() {
+        let hint = Duration::from_secs(7);
+        let err = ClientError::RateLimitError {
+            retry_after: Some(hint),
+        };
+        assert!(err.is_rate_limit_error());
+        assert_eq!(err.retry_after(), Some(hint));
+        assert_eq!(
+            ClientError::RateLimitError { retry_after: None }.retry_after(),
+            None
+        );
+        assert_eq!(
+            ClientError::ServiceUnavailable("down".to_string()).retry_after(),
+            None
+        );
+    }
+}
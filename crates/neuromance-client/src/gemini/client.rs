@@ -0,0 +1,711 @@
+//! Gemini client implementation.
+//!
+//! This module provides a client for the Google Gemini `generateContent` API.
+//!
+//! # Features
+//!
+//! - **generateContent**: Implementation of the Gemini content generation API
+//! - **Tool/Function Calling**: `functionDeclarations` in, `functionCall` parts out
+//! - **Streaming**: `streamGenerateContent` with `alt=sse`
+//! - **Secure API Keys**: Uses the `secrecy` crate to prevent accidental exposure
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use neuromance_client::{GeminiClient, LLMClient};
+//! use neuromance_common::client::{Config, ChatRequest};
+//! use neuromance_common::chat::Conversation;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let config = Config::new("gemini", "gemini-2.5-flash")
+//!     .with_api_key("AIza...");
+//!
+//! let client = GeminiClient::new(config)?;
+//!
+//! let mut conversation = Conversation::new();
+//! conversation.add_message(conversation.user_message("Hello!"))?;
+//!
+//! let request = ChatRequest::new(conversation.get_messages().to_vec());
+//! let response = client.chat(&request).await?;
+//!
+//! println!("Response: {}", response.message.content);
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::stream::Stream;
+use reqwest_middleware::ClientWithMiddleware;
+use secrecy::{ExposeSecret, SecretString};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::error;
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{
+    Capabilities, ChatChunk, ChatRequest, ChatResponse, Config, ProxyConfig, Usage,
+};
+
+use crate::error::ClientError;
+use crate::message::MessageBuilder;
+use crate::request_tracing::{traced_chat, traced_stream};
+use crate::streaming::{ChatChunkStream, StreamingProvider, run_sse_stream};
+use crate::transport::{add_proxy_headers, send_json};
+use crate::{LLMClient, base_capabilities, build_client_resources};
+
+use super::{
+    Candidate, DEFAULT_BASE_URL, GenerateContentRequest, GenerateContentResponse,
+    candidate_finish_reason, convert_function_call,
+};
+
+/// Header carrying the Gemini API key.
+const API_KEY_HEADER: &str = "x-goog-api-key";
+
+/// Client for the Google Gemini `generateContent` API.
+///
+/// # Security
+///
+/// The API key is stored using the `secrecy` crate to prevent accidental
+/// exposure through debug logs or memory dumps.
+///
+/// # Proxy Support
+///
+/// When a [`ProxyConfig`] is provided in the [`Config`], requests are routed
+/// through a tokenizer proxy. The proxy intercepts requests and injects real
+/// credentials, allowing agents to use sealed tokens instead of raw API keys.
+#[derive(Clone)]
+pub struct GeminiClient {
+    client: ClientWithMiddleware,
+    streaming_client: reqwest::Client,
+    api_key: Arc<SecretString>,
+    base_url: String,
+    config: Arc<Config>,
+    proxy_config: Option<ProxyConfig>,
+}
+
+impl std::fmt::Debug for GeminiClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeminiClient")
+            .field("api_key", &"[REDACTED]")
+            .field("base_url", &self.base_url)
+            .field("config", &self.config)
+            .field("proxy_config", &self.proxy_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GeminiClient {
+    /// Create a new Gemini client from a configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neuromance_client::GeminiClient;
+    /// use neuromance_common::client::Config;
+    ///
+    /// let config = Config::new("gemini", "gemini-2.5-flash")
+    ///     .with_api_key("AIza...");
+    ///
+    /// let client = GeminiClient::new(config)?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key is missing or HTTP client creation fails.
+    pub fn new(config: Config) -> Result<Self, ClientError> {
+        let r = build_client_resources(config, DEFAULT_BASE_URL)?;
+
+        Ok(Self {
+            client: r.client,
+            streaming_client: r.streaming_client,
+            api_key: r.api_key,
+            base_url: r.base_url,
+            config: r.config,
+            proxy_config: r.proxy_config,
+        })
+    }
+
+    /// Set a custom base URL for the API endpoint.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        Arc::make_mut(&mut self.config).base_url = Some(base_url.clone());
+        self.base_url = base_url;
+        self
+    }
+
+    /// Set the model to use for content generation.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.config).model = model.into();
+        self
+    }
+
+    /// Build and validate the URL for a model method such as `generateContent`.
+    fn method_url(&self, request: &ChatRequest, method: &str) -> Result<String, ClientError> {
+        let model = request.model.as_deref().unwrap_or(&self.config.model);
+        let url = format!("{}/models/{model}:{method}", self.base_url);
+        reqwest::Url::parse(&url)
+            .map_err(|e| ClientError::ConfigurationError(format!("Invalid URL '{url}': {e}")))?;
+        Ok(url)
+    }
+
+    /// Convert a Gemini candidate to our internal Message format.
+    fn convert_candidate(candidate: &Candidate, conversation_id: uuid::Uuid) -> Message {
+        let mut builder = MessageBuilder::new(conversation_id, MessageRole::Assistant);
+        let mut content = String::new();
+        for part in candidate.parts() {
+            if let Some(text) = &part.text {
+                if part.thought == Some(true) {
+                    builder.append_reasoning(text, "\n\n");
+                } else {
+                    content.push_str(text);
+                }
+            }
+            if let Some(call) = &part.function_call {
+                builder.push_tool_call(convert_function_call(call));
+            }
+        }
+        builder.set_content(content);
+        builder.build()
+    }
+}
+
+/// Convert one streamed Gemini event to our common `ChatChunk` format.
+///
+/// Gemini streams whole response objects: text arrives as incremental
+/// fragments, function calls arrive complete, and `usageMetadata` carries
+/// running totals.
+#[must_use]
+pub fn convert_response_to_chat_chunk(
+    response: &GenerateContentResponse,
+    model: &str,
+) -> ChatChunk {
+    let candidate = response.candidates.first();
+
+    let mut delta_content: Option<String> = None;
+    let mut delta_reasoning_content: Option<String> = None;
+    let mut tool_calls = Vec::new();
+    for part in candidate.into_iter().flat_map(Candidate::parts) {
+        if let Some(text) = &part.text {
+            let target = if part.thought == Some(true) {
+                &mut delta_reasoning_content
+            } else {
+                &mut delta_content
+            };
+            target.get_or_insert_with(String::new).push_str(text);
+        }
+        if let Some(call) = &part.function_call {
+            tool_calls.push(convert_function_call(call));
+        }
+    }
+
+    ChatChunk {
+        model: response
+            .model_version
+            .clone()
+            .unwrap_or_else(|| model.to_string()),
+        delta_content,
+        delta_reasoning_content,
+        delta_role: candidate.map(|_| MessageRole::Assistant),
+        delta_tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+        finish_reason: candidate.and_then(candidate_finish_reason),
+        usage: response.usage_metadata.clone().map(Usage::from),
+        response_id: response.response_id.clone(),
+        created_at: Utc::now(),
+        metadata: HashMap::new(),
+    }
+}
+
+impl GeminiClient {
+    /// [`LLMClient::chat`] without its request span.
+    async fn chat_inner(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        self.validate_request(request)?;
+
+        let gemini_request = GenerateContentRequest::from((request, self.config.as_ref()));
+        let url = self.method_url(request, "generateContent")?;
+
+        let mut request_builder = self
+            .client
+            .post(&url)
+            .header(API_KEY_HEADER, self.api_key.expose_secret())
+            .header("Content-Type", "application/json");
+
+        request_builder =
+            add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
+
+        let request_builder = request_builder
+            .body(serde_json::to_string(&gemini_request).map_err(ClientError::SerializationError)?);
+
+        let response: GenerateContentResponse = send_json(request_builder).await?;
+
+        let candidate = response.candidates.first().ok_or_else(|| {
+            ClientError::InvalidResponse("API returned no candidates in response".to_string())
+        })?;
+
+        let conversation_id = request
+            .messages
+            .first()
+            .ok_or_else(|| {
+                error!("Request has no messages despite passing validation");
+                ClientError::InvalidRequest("Request must contain at least one message".to_string())
+            })?
+            .conversation_id;
+
+        let message = Self::convert_candidate(candidate, conversation_id);
+        let finish_reason = candidate_finish_reason(candidate);
+
+        Ok(ChatResponse {
+            message,
+            model: response
+                .model_version
+                .unwrap_or_else(|| self.config.model.clone()),
+            usage: response.usage_metadata.map(Usage::from),
+            finish_reason,
+            created_at: Utc::now(),
+            response_id: response.response_id,
+            metadata: HashMap::new(),
+            additional_choices: Vec::new(),
+        })
+    }
+
+    /// [`LLMClient::chat_stream`] without its request span.
+    fn chat_stream_inner(&self, request: &ChatRequest) -> Result<ChatChunkStream, ClientError> {
+        self.validate_request(request)?;
+
+        let gemini_request = GenerateContentRequest::from((request, self.config.as_ref()));
+        let url = format!(
+            "{}?alt=sse",
+            self.method_url(request, "streamGenerateContent")?
+        );
+
+        let mut request_builder = self
+            .streaming_client
+            .post(&url)
+            .header(API_KEY_HEADER, self.api_key.expose_secret())
+            .header("Content-Type", "application/json");
+
+        request_builder =
+            add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
+
+        let request_builder = request_builder.json(&gemini_request);
+
+        run_sse_stream(self, request_builder)
+    }
+}
+
+#[async_trait]
+impl LLMClient for GeminiClient {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            parallel_tool_calls: true,
+            reasoning: true,
+            prompt_caching: true,
+            ..base_capabilities(self)
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        serde_json::to_value(GenerateContentRequest::from((
+            request,
+            self.config.as_ref(),
+        )))
+        .ok()
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        traced_chat(self.config(), request, self.chat_inner(request)).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        traced_stream(self.config(), request, async {
+            self.chat_stream_inner(request)
+        })
+        .await
+    }
+}
+
+impl StreamingProvider for GeminiClient {
+    type Event = GenerateContentResponse;
+    /// Configured model id, used until the server reports `modelVersion`.
+    type State = String;
+
+    fn initial_state(&self) -> Self::State {
+        self.config.model.clone()
+    }
+
+    fn process_event(
+        state: &mut Self::State,
+        event: Self::Event,
+    ) -> Option<Result<ChatChunk, ClientError>> {
+        if let Some(version) = &event.model_version {
+            state.clone_from(version);
+        }
+        Some(Ok(convert_response_to_chat_chunk(&event, state)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use futures::StreamExt;
+    use neuromance_common::client::FinishReason;
+    use neuromance_common::tools::{Function, Tool, ToolCall};
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_test_config(base_url: &str) -> Config {
+        Config::new("gemini", "gemini-2.5-flash")
+            .with_api_key("test-key")
+            .with_base_url(base_url)
+    }
+
+    fn create_test_message() -> Message {
+        Message::new(uuid::Uuid::new_v4(), MessageRole::User, "Hello")
+    }
+
+    fn weather_tool() -> Tool {
+        Tool {
+            r#type: "function".to_string(),
+            function: Function {
+                name: "get_weather".to_string(),
+                description: "Get the weather".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {"location": {"type": "string"}},
+                    "required": ["location"]
+                }),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_successful_chat_completion() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.5-flash:generateContent"))
+            .and(header("x-goog-api-key", "test-key"))
+            .and(header("content-type", "application/json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": [{
+                    "content": {
+                        "role": "model",
+                        "parts": [{"text": "Hello! How can I help you today?"}]
+                    },
+                    "finishReason": "STOP"
+                }],
+                "usageMetadata": {
+                    "promptTokenCount": 10,
+                    "candidatesTokenCount": 20,
+                    "totalTokenCount": 30
+                },
+                "modelVersion": "gemini-2.5-flash",
+                "responseId": "resp-123"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = GeminiClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.model, "gemini-2.5-flash");
+        assert_eq!(response.response_id.as_deref(), Some("resp-123"));
+        assert_eq!(response.message.content, "Hello! How can I help you today?");
+        assert_eq!(response.message.role, MessageRole::Assistant);
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 10);
+        assert_eq!(usage.completion_tokens, 20);
+        assert_eq!(usage.total_tokens, 30);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_with_function_call() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.5-flash:generateContent"))
+            .and(body_partial_json(serde_json::json!({
+                "tools": [{"functionDeclarations": [{"name": "get_weather"}]}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": [{
+                    "content": {
+                        "role": "model",
+                        "parts": [{
+                            "functionCall": {
+                                "name": "get_weather",
+                                "args": {"location": "Paris"}
+                            }
+                        }]
+                    },
+                    "finishReason": "STOP"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = GeminiClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request =
+            ChatRequest::new(vec![create_test_message()]).with_tools(vec![weather_tool()]);
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.finish_reason, Some(FinishReason::ToolCalls));
+        assert_eq!(response.message.tool_calls.len(), 1);
+        let call = &response.message.tool_calls[0];
+        assert_eq!(call.function.name, "get_weather");
+        assert!(call.id.starts_with("call_"));
+        let args: serde_json::Value = serde_json::from_str(&call.function.arguments).unwrap();
+        assert_eq!(args["location"], "Paris");
+    }
+
+    #[tokio::test]
+    async fn test_finish_reason_mapping() {
+        let test_cases = [
+            ("STOP", FinishReason::Stop),
+            ("MAX_TOKENS", FinishReason::Length),
+            ("SAFETY", FinishReason::ContentFilter),
+            ("RECITATION", FinishReason::ContentFilter),
+            ("SOMETHING_NEW", FinishReason::ModelError),
+        ];
+
+        for (reason, expected) in test_cases {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("POST"))
+                .and(path("/models/gemini-2.5-flash:generateContent"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "candidates": [{
+                        "content": {"role": "model", "parts": [{"text": "x"}]},
+                        "finishReason": reason
+                    }]
+                })))
+                .mount(&mock_server)
+                .await;
+
+            let client = GeminiClient::new(create_test_config(&mock_server.uri())).unwrap();
+            let request = ChatRequest::new(vec![create_test_message()]);
+
+            let response = client.chat(&request).await.unwrap();
+            assert_eq!(response.finish_reason, Some(expected), "reason {reason}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_candidates_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.5-flash:generateContent"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = GeminiClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let result = client.chat(&request).await;
+        assert!(matches!(result, Err(ClientError::InvalidResponse(_))));
+    }
+
+    #[tokio::test]
+    async fn test_authentication_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.5-flash:generateContent"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "error": {
+                    "code": 401,
+                    "message": "API key not valid",
+                    "status": "UNAUTHENTICATED"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = GeminiClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let result = client.chat(&request).await;
+        assert!(
+            matches!(result, Err(ClientError::AuthenticationError(m)) if m == "API key not valid")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_streaming_uses_alt_sse_and_accumulates() {
+        let mock_server = MockServer::start().await;
+
+        let events = [
+            serde_json::json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "Hel"}]}
+                }],
+                "modelVersion": "gemini-2.5-flash"
+            }),
+            serde_json::json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "lo"}]},
+                    "finishReason": "STOP"
+                }],
+                "usageMetadata": {
+                    "promptTokenCount": 4,
+                    "candidatesTokenCount": 2,
+                    "totalTokenCount": 6
+                },
+                "modelVersion": "gemini-2.5-flash"
+            }),
+        ];
+        let sse_body = events
+            .iter()
+            .map(|e| format!("data: {e}"))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            + "\n\n";
+
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.5-flash:streamGenerateContent"))
+            .and(query_param("alt", "sse"))
+            .and(header("x-goog-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = GeminiClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let mut stream = client.chat_stream(&request).await.unwrap();
+        let mut content = String::new();
+        let mut finish_reason = None;
+        let mut usage = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            if let Some(delta) = chunk.delta_content {
+                content.push_str(&delta);
+            }
+            finish_reason = chunk.finish_reason.or(finish_reason);
+            usage = chunk.usage.or(usage);
+        }
+
+        assert_eq!(content, "Hello");
+        assert_eq!(finish_reason, Some(FinishReason::Stop));
+        assert_eq!(usage.unwrap().total_tokens, 6);
+    }
+
+    #[test]
+    fn test_streaming_function_call_and_thought_conversion() {
+        let event: GenerateContentResponse = serde_json::from_value(serde_json::json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        {"text": "considering", "thought": true},
+                        {"functionCall": {"id": "fc-1", "name": "get_weather", "args": {"location": "Oslo"}}}
+                    ]
+                },
+                "finishReason": "STOP"
+            }]
+        }))
+        .unwrap();
+
+        let chunk = convert_response_to_chat_chunk(&event, "gemini-2.5-pro");
+
+        assert_eq!(chunk.model, "gemini-2.5-pro");
+        assert_eq!(chunk.delta_content, None);
+        assert_eq!(
+            chunk.delta_reasoning_content.as_deref(),
+            Some("considering")
+        );
+        assert_eq!(chunk.finish_reason, Some(FinishReason::ToolCalls));
+        let calls = chunk.delta_tool_calls.unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, "fc-1");
+        assert_eq!(calls[0].function.arguments, r#"{"location":"Oslo"}"#);
+    }
+
+    #[test]
+    fn test_request_conversion_maps_system_tools_and_function_responses() {
+        let conversation_id = uuid::Uuid::new_v4();
+        let mut assistant = Message::new(conversation_id, MessageRole::Assistant, "");
+        assistant
+            .tool_calls
+            .push(ToolCall::new("get_weather", r#"{"location":"Paris"}"#));
+        assistant
+            .tool_calls
+            .push(ToolCall::new("get_weather", r#"{"location":"Rome"}"#));
+        let messages = vec![
+            Message::new(conversation_id, MessageRole::System, "Be brief."),
+            Message::new(conversation_id, MessageRole::User, "Weather?"),
+            assistant,
+            Message::tool(conversation_id, "sunny", "a".into(), "get_weather".into()).unwrap(),
+            Message::tool(
+                conversation_id,
+                r#"{"temp": 20}"#,
+                "b".into(),
+                "get_weather".into(),
+            )
+            .unwrap(),
+        ];
+        let request = ChatRequest::new(messages)
+            .with_tools(vec![weather_tool()])
+            .with_tool_choice(neuromance_common::client::ToolChoice::Required);
+        let config = Config::new("gemini", "gemini-2.5-flash");
+
+        let body = serde_json::to_value(GenerateContentRequest::from((&request, &config))).unwrap();
+
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Be brief.");
+        assert!(body["systemInstruction"].get("role").is_none());
+        assert_eq!(
+            body["tools"][0]["functionDeclarations"][0]["name"],
+            "get_weather"
+        );
+        assert_eq!(body["toolConfig"]["functionCallingConfig"]["mode"], "ANY");
+
+        let contents = body["contents"].as_array().unwrap();
+        assert_eq!(contents.len(), 3, "tool results fold into one user turn");
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(
+            contents[1]["parts"][0]["functionCall"]["args"]["location"],
+            "Paris"
+        );
+        assert_eq!(contents[2]["role"], "user");
+        let responses = contents[2]["parts"].as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(
+            responses[0]["functionResponse"]["response"]["content"],
+            "sunny"
+        );
+        assert_eq!(responses[1]["functionResponse"]["response"]["temp"], 20);
+    }
+}
@@ -0,0 +1,521 @@
+//! Google Gemini `generateContent` API types and client implementation.
+//!
+//! This module provides types for the Gemini API (`generateContent` and
+//! `streamGenerateContent`) and a client implementation for Gemini models.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{ChatRequest, Config, FinishReason, InputTokensDetails, Usage};
+use neuromance_common::client::{OutputTokensDetails, ToolChoice};
+use neuromance_common::tools::{FunctionCall, Tool, ToolCall};
+
+pub mod client;
+pub use client::GeminiClient;
+
+/// Default base URL for the Gemini API.
+pub const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+// ============================================================================
+// Request Types
+// ============================================================================
+
+/// Role of a content entry in the Gemini API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeminiRole {
+    /// User turn (also carries function responses).
+    User,
+    /// Model turn.
+    Model,
+}
+
+impl From<MessageRole> for GeminiRole {
+    fn from(role: MessageRole) -> Self {
+        match role {
+            MessageRole::Assistant => Self::Model,
+            // Tool results travel as `functionResponse` parts on a user turn
+            _ => Self::User,
+        }
+    }
+}
+
+/// A single part of a content entry.
+///
+/// Gemini parts are objects with exactly one payload field set; the optional
+/// `thought` flag marks a part as a thinking summary.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Part {
+    /// Text payload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Function call requested by the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_call: Option<GeminiFunctionCall>,
+    /// Function result supplied by the caller.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_response: Option<GeminiFunctionResponse>,
+    /// True when this part is a thought summary rather than answer text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thought: Option<bool>,
+}
+
+impl Part {
+    /// Creates a text part.
+    #[must_use]
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            ..Self::default()
+        }
+    }
+}
+
+/// A function call emitted by the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiFunctionCall {
+    /// Call identifier (only returned by some models).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Name of the function to call.
+    pub name: String,
+    /// Arguments as a JSON object.
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
+
+/// The result of a function call, sent back to the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiFunctionResponse {
+    /// Call identifier this response answers, when the call carried one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Name of the function that was called.
+    pub name: String,
+    /// Function output; must be a JSON object.
+    pub response: serde_json::Value,
+}
+
+/// A content entry: one turn in the conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Content {
+    /// Author of this turn. Absent on `systemInstruction`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<GeminiRole>,
+    /// Parts making up this turn.
+    #[serde(default)]
+    pub parts: Vec<Part>,
+}
+
+/// A function the model may call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionDeclaration {
+    /// Function name.
+    pub name: String,
+    /// Description of what the function does.
+    pub description: String,
+    /// JSON Schema for the function's parameters.
+    pub parameters: serde_json::Value,
+}
+
+impl From<&Tool> for FunctionDeclaration {
+    fn from(tool: &Tool) -> Self {
+        Self {
+            name: tool.function.name.clone(),
+            description: tool.function.description.clone(),
+            parameters: tool.function.parameters.clone(),
+        }
+    }
+}
+
+/// Tool wrapper holding the function declarations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiTool {
+    /// Functions available to the model.
+    pub function_declarations: Vec<FunctionDeclaration>,
+}
+
+/// Function-calling mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FunctionCallingMode {
+    /// The model decides whether to call a function.
+    Auto,
+    /// The model must call a function.
+    Any,
+    /// Function calling is disabled.
+    None,
+}
+
+/// Function-calling configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionCallingConfig {
+    /// Calling mode.
+    pub mode: FunctionCallingMode,
+    /// Restricts `Any` mode to these functions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_function_names: Option<Vec<String>>,
+}
+
+/// Tool configuration for a request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolConfig {
+    /// Function-calling configuration.
+    pub function_calling_config: FunctionCallingConfig,
+}
+
+impl From<&ToolChoice> for ToolConfig {
+    fn from(choice: &ToolChoice) -> Self {
+        let (mode, allowed_function_names) = match choice {
+            ToolChoice::None => (FunctionCallingMode::None, None),
+            ToolChoice::Required | ToolChoice::Any { .. } => (FunctionCallingMode::Any, None),
+            ToolChoice::Function { name } => (FunctionCallingMode::Any, Some(vec![name.clone()])),
+            // Auto and any future variants default to Auto
+            _ => (FunctionCallingMode::Auto, None),
+        };
+        Self {
+            function_calling_config: FunctionCallingConfig {
+                mode,
+                allowed_function_names,
+            },
+        }
+    }
+}
+
+/// Thinking configuration for Gemini 2.5+ models.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThinkingConfig {
+    /// Token budget for thinking.
+    pub thinking_budget: u32,
+    /// Return thought summaries as `thought` parts.
+    pub include_thoughts: bool,
+}
+
+/// Sampling and output configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationConfig {
+    /// Sampling temperature (0.0-2.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling threshold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Maximum tokens to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    /// Stop sequences.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    /// Presence penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    /// Frequency penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Thinking configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking_config: Option<ThinkingConfig>,
+}
+
+/// Request for `generateContent` / `streamGenerateContent`.
+///
+/// The model is part of the URL path rather than the body.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateContentRequest {
+    /// Conversation turns.
+    pub contents: Vec<Content>,
+    /// System instruction, collected from system messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_instruction: Option<Content>,
+    /// Available tools.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<GeminiTool>>,
+    /// Tool selection strategy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_config: Option<ToolConfig>,
+    /// Sampling and output configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<GenerationConfig>,
+}
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Reason why generation stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GeminiFinishReason {
+    /// Natural stop point or stop sequence.
+    Stop,
+    /// Maximum tokens reached.
+    MaxTokens,
+    /// Flagged for safety reasons.
+    Safety,
+    /// Flagged for recitation.
+    Recitation,
+    /// Blocked by a terminology blocklist.
+    Blocklist,
+    /// Blocked for prohibited content.
+    ProhibitedContent,
+    /// Blocked for sensitive personally identifiable information.
+    Spii,
+    /// The model produced an invalid function call.
+    MalformedFunctionCall,
+    /// Any other or future reason.
+    #[serde(other)]
+    Other,
+}
+
+impl From<GeminiFinishReason> for FinishReason {
+    fn from(reason: GeminiFinishReason) -> Self {
+        match reason {
+            GeminiFinishReason::Stop => Self::Stop,
+            GeminiFinishReason::MaxTokens => Self::Length,
+            GeminiFinishReason::Safety
+            | GeminiFinishReason::Recitation
+            | GeminiFinishReason::Blocklist
+            | GeminiFinishReason::ProhibitedContent
+            | GeminiFinishReason::Spii => Self::ContentFilter,
+            GeminiFinishReason::MalformedFunctionCall | GeminiFinishReason::Other => {
+                Self::ModelError
+            }
+        }
+    }
+}
+
+/// A generated candidate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Candidate {
+    /// Generated content (absent when the candidate was blocked).
+    #[serde(default)]
+    pub content: Option<Content>,
+    /// Why generation stopped (only on the final chunk when streaming).
+    #[serde(default)]
+    pub finish_reason: Option<GeminiFinishReason>,
+}
+
+impl Candidate {
+    /// Iterate over the parts of this candidate's content.
+    pub fn parts(&self) -> impl Iterator<Item = &Part> {
+        self.content.iter().flat_map(|c| c.parts.iter())
+    }
+}
+
+/// Token usage statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageMetadata {
+    /// Tokens in the prompt (includes cached content).
+    #[serde(default)]
+    pub prompt_token_count: u32,
+    /// Tokens across all generated candidates.
+    #[serde(default)]
+    pub candidates_token_count: u32,
+    /// Tokens spent on thinking.
+    #[serde(default)]
+    pub thoughts_token_count: u32,
+    /// Tokens served from cached content.
+    #[serde(default)]
+    pub cached_content_token_count: u32,
+    /// Total tokens for the request.
+    #[serde(default)]
+    pub total_token_count: u32,
+}
+
+impl From<UsageMetadata> for Usage {
+    fn from(usage: UsageMetadata) -> Self {
+        // Gemini reports thinking tokens separately from candidates; fold them
+        // into the completion count to match OpenAI reasoning-model semantics.
+        let completion_tokens = usage.candidates_token_count + usage.thoughts_token_count;
+        Self {
+            prompt_tokens: usage.prompt_token_count,
+            completion_tokens,
+            total_tokens: usage.prompt_token_count + completion_tokens,
+            cost: None,
+            input_tokens_details: (usage.cached_content_token_count > 0).then_some(
+                InputTokensDetails {
+                    cached_tokens: usage.cached_content_token_count,
+                    cache_creation_tokens: 0,
+                },
+            ),
+            output_tokens_details: (usage.thoughts_token_count > 0).then_some(
+                OutputTokensDetails {
+                    reasoning_tokens: usage.thoughts_token_count,
+                },
+            ),
+        }
+    }
+}
+
+/// Response from `generateContent`; also the shape of each streamed SSE event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateContentResponse {
+    /// Generated candidates.
+    #[serde(default)]
+    pub candidates: Vec<Candidate>,
+    /// Token usage statistics.
+    #[serde(default)]
+    pub usage_metadata: Option<UsageMetadata>,
+    /// Model version that produced the response.
+    #[serde(default)]
+    pub model_version: Option<String>,
+    /// Response identifier.
+    #[serde(default)]
+    pub response_id: Option<String>,
+}
+
+// ============================================================================
+// Conversion Helpers
+// ============================================================================
+
+/// Wrap tool output as the JSON object `functionResponse.response` requires.
+///
+/// Output that already parses as a JSON object is passed through; anything
+/// else is wrapped as `{"content": <text>}`.
+fn function_response_payload(content: &str) -> serde_json::Value {
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(value @ serde_json::Value::Object(_)) => value,
+        _ => serde_json::json!({ "content": content }),
+    }
+}
+
+/// Convert our common Message to a Gemini content entry.
+impl From<&Message> for Content {
+    fn from(message: &Message) -> Self {
+        let mut parts = Vec::new();
+        if message.role == MessageRole::Tool {
+            parts.push(Part {
+                function_response: Some(GeminiFunctionResponse {
+                    id: None,
+                    name: message.name.clone().unwrap_or_default(),
+                    response: function_response_payload(&message.content),
+                }),
+                ..Part::default()
+            });
+        } else {
+            if !message.content.is_empty() {
+                parts.push(Part::text(message.content.clone()));
+            }
+            for tool_call in &message.tool_calls {
+                let args = serde_json::from_str(tool_call.function.arguments_json())
+                    .unwrap_or_else(|_| serde_json::Value::Object(serde_json::Map::new()));
+                parts.push(Part {
+                    function_call: Some(GeminiFunctionCall {
+                        id: None,
+                        name: tool_call.function.name.clone(),
+                        args,
+                    }),
+                    ..Part::default()
+                });
+            }
+        }
+
+        Self {
+            role: Some(GeminiRole::from(message.role)),
+            parts,
+        }
+    }
+}
+
+/// Conversion from `ChatRequest` to Gemini request format.
+impl From<(&ChatRequest, &Config)> for GenerateContentRequest {
+    fn from((request, _config): (&ChatRequest, &Config)) -> Self {
+        let mut system_parts = Vec::new();
+        let mut contents: Vec<Content> = Vec::new();
+
+        for message in request.messages.iter() {
+            if message.role.is_instruction() {
+                system_parts.push(Part::text(message.content.clone()));
+                continue;
+            }
+            let content = Content::from(message);
+            // Gemini expects parallel function responses in a single user turn,
+            // so fold adjacent entries with the same role together.
+            match contents.last_mut() {
+                Some(last) if last.role == content.role => last.parts.extend(content.parts),
+                _ => contents.push(content),
+            }
+        }
+
+        let system_instruction = (!system_parts.is_empty()).then_some(Content {
+            role: None,
+            parts: system_parts,
+        });
+
+        let tools = request.tools.as_ref().filter(|t| !t.is_empty()).map(|t| {
+            vec![GeminiTool {
+                function_declarations: t.iter().map(FunctionDeclaration::from).collect(),
+            }]
+        });
+
+        let tool_config = request.tool_choice.as_ref().map(ToolConfig::from);
+
+        let thinking_config = request.thinking.budget().map(|budget| ThinkingConfig {
+            thinking_budget: budget,
+            include_thoughts: true,
+        });
+
+        let generation_config = GenerationConfig {
+            temperature: request.temperature,
+            top_p: request.top_p,
+            max_output_tokens: request.max_tokens.or(request.max_completion_tokens),
+            stop_sequences: request.stop.clone(),
+            presence_penalty: request.presence_penalty,
+            frequency_penalty: request.frequency_penalty,
+            thinking_config,
+        };
+
+        Self {
+            contents,
+            system_instruction,
+            tools,
+            tool_config,
+            generation_config: Some(generation_config),
+        }
+    }
+}
+
+/// Convert a Gemini function call into our common `ToolCall`.
+///
+/// Gemini rarely assigns call ids, so one is synthesized when absent; tool
+/// results are matched back by function name on the next request.
+#[must_use]
+pub fn convert_function_call(call: &GeminiFunctionCall) -> ToolCall {
+    let id = call
+        .id
+        .clone()
+        .unwrap_or_else(|| format!("call_{}", Uuid::new_v4().simple()));
+    ToolCall {
+        id,
+        call_type: "function".to_string(),
+        function: FunctionCall {
+            name: call.name.clone(),
+            arguments: call.args.to_string(),
+        },
+        index: None,
+    }
+}
+
+/// Map a candidate's finish reason, reporting `ToolCalls` when the candidate
+/// stopped normally after requesting functions (Gemini reports `STOP`).
+#[must_use]
+pub fn candidate_finish_reason(candidate: &Candidate) -> Option<FinishReason> {
+    let reason = candidate.finish_reason?;
+    let has_calls = candidate.parts().any(|p| p.function_call.is_some());
+    if reason == GeminiFinishReason::Stop && has_calls {
+        Some(FinishReason::ToolCalls)
+    } else {
+        Some(reason.into())
+    }
+}
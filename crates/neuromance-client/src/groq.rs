@@ -0,0 +1,204 @@
+//! Groq client: the Chat Completions protocol with Groq's quirks smoothed over.
+//!
+//! Groq serves an OpenAI-compatible API but rejects some parameters outright.
+//! [`GroqClient`] wraps [`ChatCompletionsClient`], defaults to Groq's base URL,
+//! and strips those parameters from every request before it is sent. Groq's
+//! `x-ratelimit-reset-*` headers become the `retry_after` of a
+//! [`ClientError::RateLimitError`], and usage reported in the `x_groq` block
+//! of a stream is read like standard usage.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use neuromance_client::GroqClient;
+//! use neuromance_common::client::Config;
+//!
+//! let config = Config::new("groq", "llama-3.3-70b-versatile").with_api_key("gsk_...");
+//! let client = GroqClient::new(config)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::Stream;
+use tracing::debug;
+
+use neuromance_common::client::{Capabilities, ChatChunk, ChatRequest, ChatResponse, Config};
+
+use crate::chat_completions::ChatCompletionRequest;
+use crate::{ChatCompletionsClient, ClientError, Interceptor, LLMClient};
+
+/// Default base URL for the Groq API.
+pub const DEFAULT_BASE_URL: &str = "https://api.groq.com/openai/v1";
+
+/// Model id prefixes of Groq's reasoning models, which reject
+/// `presence_penalty` and `frequency_penalty`.
+const PENALTY_UNSUPPORTED_MODELS: &[&str] = &["openai/gpt-oss", "qwen/qwen3", "deepseek-r1"];
+
+/// Client for Groq's OpenAI-compatible chat API.
+#[derive(Debug)]
+pub struct GroqClient(ChatCompletionsClient);
+
+impl GroqClient {
+    /// Create a new Groq client, using [`DEFAULT_BASE_URL`] unless `config`
+    /// names another.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key is missing or HTTP client creation fails.
+    pub fn new(mut config: Config) -> Result<Self, ClientError> {
+        config
+            .base_url
+            .get_or_insert_with(|| DEFAULT_BASE_URL.to_string());
+        let client = ChatCompletionsClient::new(config)?.with_request_adapter(strip_unsupported);
+        Ok(Self(client))
+    }
+
+    /// Set a custom base URL for the API endpoint.
+    #[must_use]
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Self(self.0.with_base_url(base_url))
+    }
+
+    /// Set the model to use for chat completions.
+    #[must_use]
+    pub fn with_model(self, model: impl Into<String>) -> Self {
+        Self(self.0.with_model(model))
+    }
+
+    /// Add an [`Interceptor`] to run around every request.
+    #[must_use]
+    pub fn with_interceptor(self, interceptor: Arc<dyn Interceptor>) -> Self {
+        Self(self.0.with_interceptor(interceptor))
+    }
+}
+
+/// Remove the parameters Groq rejects: `logit_bias` always, `n` above 1, and
+/// the penalties for reasoning models.
+fn strip_unsupported(request: &mut ChatCompletionRequest) {
+    if request.logit_bias.take().is_some() {
+        debug!("dropping logit_bias, which Groq does not support");
+    }
+    if request.n.is_some_and(|n| n > 1) {
+        debug!("dropping n > 1, which Groq does not support");
+        request.n = None;
+    }
+    if PENALTY_UNSUPPORTED_MODELS
+        .iter()
+        .any(|prefix| request.model.starts_with(prefix))
+    {
+        request.presence_penalty = None;
+        request.frequency_penalty = None;
+    }
+}
+
+#[async_trait]
+impl LLMClient for GroqClient {
+    fn config(&self) -> &Config {
+        self.0.config()
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            vision: false,
+            json_schema: false,
+            prompt_caching: false,
+            ..self.0.capabilities()
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        self.0.request_payload(request)
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        self.0.chat(request).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        self.0.chat_stream(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+
+    use neuromance_common::chat::Message;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn create_test_config(model: &str) -> Config {
+        Config::new("groq", model).with_api_key("gsk_test")
+    }
+
+    fn create_test_request() -> ChatRequest {
+        ChatRequest::new(vec![Message::user(uuid::Uuid::new_v4(), "Hi")])
+            .with_n(2)
+            .with_presence_penalty(0.5)
+            .with_logit_bias(HashMap::from([("50256".to_string(), -100.0)]))
+    }
+
+    #[test]
+    fn test_unsupported_fields_removed_from_request() {
+        let request = create_test_request();
+
+        let client = GroqClient::new(create_test_config("llama-3.3-70b-versatile")).unwrap();
+        let body = client.request_payload(&request).unwrap();
+        assert!(body.get("n").is_none());
+        assert!(body.get("logit_bias").is_none());
+        assert_eq!(body["presence_penalty"], 0.5);
+        assert_eq!(client.config().base_url.as_deref(), Some(DEFAULT_BASE_URL));
+
+        let client = GroqClient::new(create_test_config("openai/gpt-oss-120b")).unwrap();
+        let body = client.request_payload(&request).unwrap();
+        assert!(body.get("presence_penalty").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_reset_headers_become_retry_after() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("x-ratelimit-remaining-tokens", "0")
+                    .insert_header("x-ratelimit-reset-tokens", "1.5s")
+                    .set_body_json(serde_json::json!({
+                        "error": {"message": "Rate limit reached", "type": "tokens"}
+                    })),
+            )
+            .mount(&server)
+            .await;
+        // Disable client-level retries so the 429 surfaces immediately.
+        let mut config = create_test_config("llama-3.3-70b-versatile").with_base_url(server.uri());
+        config.retry_config.max_retries = 0;
+        let client = GroqClient::new(config).unwrap();
+
+        let error = client.chat(&create_test_request()).await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            ClientError::RateLimitError { retry_after: Some(d) }
+                if d == std::time::Duration::from_millis(1500)
+        ));
+    }
+}
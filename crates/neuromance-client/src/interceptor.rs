@@ -0,0 +1,69 @@
+//! Hooks run around a client's `chat` calls.
+//!
+//! Interceptors let callers log, redact, or measure requests without forking
+//! a client: [`ChatCompletionsClient`](crate::ChatCompletionsClient) and
+//! [`AnthropicClient`](crate::AnthropicClient) call each registered
+//! interceptor, in registration order, before building the provider request
+//! and again once the response is converted.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use neuromance_common::client::{ChatRequest, ChatResponse};
+
+/// A hook around `chat` and `chat_stream` calls.
+///
+/// Both methods default to doing nothing, so an interceptor implements only
+/// the side it cares about.
+///
+/// # Example
+///
+/// ```
+/// use neuromance_client::Interceptor;
+/// use neuromance_common::client::ChatRequest;
+///
+/// /// Tags every request with a correlation id.
+/// struct CorrelationId(String);
+///
+/// impl Interceptor for CorrelationId {
+///     fn before_request(&self, request: &mut ChatRequest) {
+///         request
+///             .metadata
+///             .insert("correlation_id".to_string(), self.0.clone().into());
+///     }
+/// }
+/// ```
+pub trait Interceptor: Send + Sync {
+    /// Inspects or rewrites `request` before it is sent.
+    ///
+    /// Runs after the caller builds the request but before validation, so
+    /// changes are validated and sent like the caller's own.
+    fn before_request(&self, _request: &mut ChatRequest) {}
+
+    /// Observes a successful non-streaming response.
+    ///
+    /// Not called for streams or failed requests.
+    fn after_response(&self, _response: &ChatResponse) {}
+}
+
+/// Runs `before_request` hooks, cloning `request` only when there are any.
+pub fn intercept_request<'a>(
+    interceptors: &[Arc<dyn Interceptor>],
+    request: &'a ChatRequest,
+) -> Cow<'a, ChatRequest> {
+    if interceptors.is_empty() {
+        return Cow::Borrowed(request);
+    }
+    let mut request = request.clone();
+    for interceptor in interceptors {
+        interceptor.before_request(&mut request);
+    }
+    Cow::Owned(request)
+}
+
+/// Runs `after_response` hooks.
+pub fn intercept_response(interceptors: &[Arc<dyn Interceptor>], response: &ChatResponse) {
+    for interceptor in interceptors {
+        interceptor.after_response(response);
+    }
+}
@@ -0,0 +1,116 @@
+//! Best-effort repair of truncated JSON from streamed tool arguments.
+//!
+//! A model that stops mid-call leaves its tool arguments cut off, e.g.
+//! `{"path": "src/main.rs", "lines": [1, 2,`. [`repair_json`] closes any
+//! open string, array, and object and drops trailing commas, which recovers
+//! most such fragments. It does not invent missing values: a fragment ending
+//! after a key, like `{"path":`, stays invalid.
+
+use serde_json::Value;
+
+/// Parse `json`, repairing it with [`repair_json`] if it is invalid as given.
+///
+/// # Errors
+///
+/// Returns the error from parsing the original `json` if the repaired text
+/// is still invalid.
+pub fn parse_repaired(json: &str) -> Result<Value, serde_json::Error> {
+    serde_json::from_str(json)
+        .or_else(|error| serde_json::from_str(&repair_json(json)).map_err(|_| error))
+}
+
+/// Close the open strings, arrays, and objects at the end of `json` and
+/// remove commas directly before a closing bracket.
+///
+/// Valid JSON comes back unchanged.
+#[must_use]
+pub fn repair_json(json: &str) -> String {
+    let mut repaired = String::with_capacity(json.len() + 8);
+    let mut closers = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in json.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else {
+            match c {
+                '"' => in_string = true,
+                '{' => closers.push('}'),
+                '[' => closers.push(']'),
+                '}' | ']' => {
+                    closers.pop();
+                    trim_trailing_comma(&mut repaired);
+                }
+                _ => {}
+            }
+        }
+        repaired.push(c);
+    }
+
+    if in_string {
+        // A dangling escape would swallow the closing quote.
+        if escaped {
+            repaired.pop();
+        }
+        repaired.push('"');
+    }
+    while let Some(closer) = closers.pop() {
+        trim_trailing_comma(&mut repaired);
+        repaired.push(closer);
+    }
+    repaired
+}
+
+/// Drop a trailing comma, and any whitespace after it, from `json`.
+fn trim_trailing_comma(json: &mut String) {
+    let end = json.trim_end().len();
+    if json[..end].ends_with(',') {
+        json.truncate(end - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_repairs_truncated_fragments() {
+        let cases = [
+            (r#"{"location": "Paris""#, json!({"location": "Paris"})),
+            (r#"{"location": "San Fr"#, json!({"location": "San Fr"})),
+            (r#"{"lines": [1, 2,"#, json!({"lines": [1, 2]})),
+            (
+                r#"{"a": {"b": [true, {"c": null},"#,
+                json!({"a": {"b": [true, {"c": null}]}}),
+            ),
+            (r#"{"path": "C:\\dir\"#, json!({"path": "C:\\dir"})),
+            (r#"{"a": 1, "b": [2,],}"#, json!({"a": 1, "b": [2]})),
+        ];
+        for (fragment, expected) in cases {
+            assert_eq!(parse_repaired(fragment).unwrap(), expected, "{fragment}");
+        }
+    }
+
+    #[test]
+    fn test_valid_json_is_unchanged() {
+        let valid = r#"{"text": "a, } ] \" b", "list": []}"#;
+        assert_eq!(repair_json(valid), valid);
+    }
+
+    #[test]
+    fn test_missing_value_is_not_repaired() {
+        let error = parse_repaired(r#"{"path":"#).unwrap_err();
+        assert!(error.is_eof());
+    }
+}
@@ -14,31 +14,55 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use reqwest_middleware::ClientWithMiddleware;
-use reqwest_retry::{RetryTransientMiddleware, policies::ExponentialBackoff};
+use reqwest_retry::RetryTransientMiddleware;
 use reqwest_retry_after::RetryAfterMiddleware;
 
-use neuromance_common::client::{ChatChunk, Provider, ProxyConfig, resolve_model_prefix};
+use crate::retry_policy::StrategyPolicy;
+use neuromance_common::client::{
+    Capabilities, ChatChunk, Provider, ProxyConfig, context_window_for, resolve_model_prefix,
+};
 use neuromance_common::{ChatRequest, ChatResponse, Config};
 use secrecy::SecretString;
 
 pub mod anthropic;
+pub mod azure;
+pub mod bedrock;
+#[cfg(feature = "cassette")]
+pub mod cassette;
 pub mod chat_completions;
+pub mod cohere;
 pub mod embedding;
 mod error;
+pub mod gemini;
+pub mod groq;
+mod interceptor;
+pub mod json_repair;
 pub(crate) mod message;
+pub mod mistral;
+pub mod ollama;
+pub(crate) mod request_tracing;
 pub mod responses;
 pub(crate) mod retry_logging;
+pub(crate) mod retry_policy;
 pub(crate) mod streaming;
 pub(crate) mod transport;
 
 pub use anthropic::AnthropicClient;
+pub use azure::AzureOpenAIClient;
+pub use bedrock::BedrockClient;
 pub use chat_completions::{ChatCompletionsClient, OpenAIEmbedding};
+pub use cohere::CohereClient;
 pub use embedding::{
     EmbeddingClient, EmbeddingConfig, EmbeddingInput, EmbeddingRequest, EmbeddingResponse,
 };
 pub use error::ClientError;
+pub use gemini::GeminiClient;
+pub use groq::GroqClient;
+pub use interceptor::Interceptor;
+pub use mistral::MistralClient;
+pub use ollama::OllamaClient;
 pub use responses::ResponsesClient;
 
 /// Shared resources produced by client constructor logic.
@@ -115,18 +139,41 @@ pub(crate) fn build_client_resources(
         None => (original_url, None),
     };
 
-    let retry_policy = ExponentialBackoff::builder()
-        .retry_bounds(
-            config.retry_config.initial_delay,
-            config.retry_config.max_delay,
-        )
-        .build_with_max_retries(config.retry_config.max_retries);
+    let (client, reqwest_client) = build_http_clients(&config, proxy_config.as_ref())?;
+
+    Ok(ClientResources {
+        client,
+        streaming_client: reqwest_client,
+        api_key: Arc::new(api_key),
+        base_url,
+        config: Arc::new(config),
+        proxy_config,
+    })
+}
+
+/// Build the retry-wrapped and raw HTTP clients for `config`.
+///
+/// The raw client honours `config.timeout_seconds` and, when `proxy` is set,
+/// routes through it as an HTTP forward proxy. Split out of
+/// [`build_client_resources`] for clients that authenticate without an API
+/// key, such as [`BedrockClient`].
+///
+/// # Errors
+///
+/// Returns `ClientError::ConfigurationError` if the proxy URL cannot be
+/// parsed by reqwest, or `ClientError::NetworkError` if the client fails to
+/// build.
+pub(crate) fn build_http_clients(
+    config: &Config,
+    proxy: Option<&ProxyConfig>,
+) -> Result<(ClientWithMiddleware, reqwest::Client), ClientError> {
+    let retry_policy = StrategyPolicy::new(config.retry_config.backoff());
 
     let mut client_builder = reqwest::Client::builder();
     if let Some(timeout) = config.timeout_seconds {
         client_builder = client_builder.timeout(Duration::from_secs(timeout));
     }
-    if let Some(ref proxy) = proxy_config {
+    if let Some(proxy) = proxy {
         let proxy = reqwest::Proxy::http(&proxy.proxy_url).map_err(|e| {
             ClientError::ConfigurationError(format!("invalid proxy URL '{}': {e}", proxy.proxy_url))
         })?;
@@ -146,14 +193,7 @@ pub(crate) fn build_client_resources(
         .with(retry_logging::RetryLoggingMiddleware)
         .build();
 
-    Ok(ClientResources {
-        client,
-        streaming_client: reqwest_client,
-        api_key: Arc::new(api_key),
-        base_url,
-        config: Arc::new(config),
-        proxy_config,
-    })
+    Ok((client, reqwest_client))
 }
 
 /// A retry policy for SSE streams that never retries.
@@ -207,7 +247,7 @@ pub fn build_client(config: Config) -> Result<Box<dyn LLMClient>, ClientError> {
         ClientError::ConfigurationError(format!(
             "unknown provider '{}'. Use Config::from_model(\"openai:gpt-4o\") \
              or one of: openai, openai-responses, anthropic, ollama, groq, \
-             openrouter, together, mistral, deepseek, xai, chat_completions, responses",
+             openrouter, together, mistral, deepseek, xai, gemini, chat_completions, responses",
             config.provider
         ))
     })?;
@@ -216,6 +256,7 @@ pub fn build_client(config: Config) -> Result<Box<dyn LLMClient>, ClientError> {
         Provider::Anthropic => Ok(Box::new(AnthropicClient::new(config)?)),
         Provider::ChatCompletions => Ok(Box::new(ChatCompletionsClient::new(config)?)),
         Provider::Responses => Ok(Box::new(ResponsesClient::new(config)?)),
+        Provider::Gemini => Ok(Box::new(GeminiClient::new(config)?)),
     }
 }
 
@@ -240,12 +281,49 @@ pub trait LLMClient: Send + Sync {
         request: &ChatRequest,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>;
 
+    /// Send many independent chat requests, at most `concurrency` at a time.
+    ///
+    /// Results come back in the order of `requests`; a failed request yields
+    /// its own `Err` without affecting the others. A `concurrency` of 0 is
+    /// treated as 1.
+    async fn chat_batch(
+        &self,
+        requests: Vec<ChatRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<ChatResponse, ClientError>> {
+        let mut results: Vec<_> = futures::stream::iter(requests.into_iter().enumerate())
+            .map(|(index, request)| async move { (index, self.chat(&request).await) })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+        results.sort_unstable_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
     /// Check if the client supports tool/function calling.
     fn supports_tools(&self) -> bool;
 
     /// Check if the client supports streaming responses.
     fn supports_streaming(&self) -> bool;
 
+    /// What this client and its configured model support.
+    ///
+    /// The default reports only [`supports_tools`](Self::supports_tools),
+    /// [`supports_streaming`](Self::supports_streaming), and the model's
+    /// context window when it is known; clients override it with the rest.
+    fn capabilities(&self) -> Capabilities {
+        base_capabilities(self)
+    }
+
+    /// The provider-specific JSON body [`chat`](Self::chat) would send for
+    /// `request`, built without touching the network.
+    ///
+    /// Returns `None` if the client has no such payload to show or it cannot
+    /// be serialized.
+    fn request_payload(&self, _request: &ChatRequest) -> Option<serde_json::Value> {
+        None
+    }
+
     /// Validate a configuration object.
     ///
     /// Checks parameter ranges: `temperature` (0.0-2.0), `top_p` (0.0-1.0), `frequency_penalty` (-2.0-2.0).
@@ -277,16 +355,28 @@ pub trait LLMClient: Send + Sync {
 
     /// Validate a chat request before sending.
     ///
-    /// Checks messages exist and that tools/streaming are supported if requested.
+    /// Checks messages exist, that tools/streaming are supported if requested,
+    /// and that the model is allowed when [`Config::allowed_models`] is set.
     ///
     /// # Errors
     ///
     /// Returns an error if validation fails.
     fn validate_request(&self, request: &ChatRequest) -> Result<(), ClientError> {
         request
-            .validate_has_messages()
+            .validate()
             .map_err(|e| ClientError::InvalidRequest(e.to_string()))?;
 
+        let config = self.config();
+        if let Some(allowed) = &config.allowed_models {
+            let model = request.model.as_deref().unwrap_or(&config.model);
+            if !allowed.iter().any(|m| m == model) {
+                return Err(ClientError::ModelNotAllowed {
+                    model: model.to_string(),
+                    suggestion: closest_model(model, allowed).map(str::to_string),
+                });
+            }
+        }
+
         if !self.supports_tools() && request.has_tools() {
             return Err(ClientError::ToolsNotSupported);
         }
@@ -299,6 +389,46 @@ pub trait LLMClient: Send + Sync {
     }
 }
 
+/// The allowed model nearest to `model` by edit distance, if it is close
+/// enough to be a likely typo: at most a third of `model`'s length, and at
+/// least two edits.
+fn closest_model<'a>(model: &str, allowed: &'a [String]) -> Option<&'a str> {
+    let max_distance = (model.chars().count() / 3).max(2);
+    allowed
+        .iter()
+        .map(|candidate| (edit_distance(model, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Levenshtein distance between `a` and `b`, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// The capabilities every client can report without provider knowledge:
+/// tool and streaming support plus the model's known context window.
+pub(crate) fn base_capabilities<C: LLMClient + ?Sized>(client: &C) -> Capabilities {
+    Capabilities {
+        tools: client.supports_tools(),
+        streaming: client.supports_streaming(),
+        context_window: context_window_for(&client.config().model),
+        ..Capabilities::default()
+    }
+}
+
 /// Blanket impl so [`build_client`] output (`Box<dyn LLMClient>`) plugs into
 /// any API that is generic over `C: LLMClient`, such as `Core<C>`.
 #[async_trait]
@@ -319,6 +449,14 @@ impl<T: LLMClient + ?Sized> LLMClient for Box<T> {
         (**self).chat_stream(request).await
     }
 
+    async fn chat_batch(
+        &self,
+        requests: Vec<ChatRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<ChatResponse, ClientError>> {
+        (**self).chat_batch(requests, concurrency).await
+    }
+
     fn supports_tools(&self) -> bool {
         (**self).supports_tools()
     }
@@ -326,6 +464,14 @@ impl<T: LLMClient + ?Sized> LLMClient for Box<T> {
     fn supports_streaming(&self) -> bool {
         (**self).supports_streaming()
     }
+
+    fn capabilities(&self) -> Capabilities {
+        (**self).capabilities()
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        (**self).request_payload(request)
+    }
 }
 
 /// Blanket impl mirroring the [`Box`] one, but for `Arc`. Lets a single client
@@ -349,6 +495,14 @@ impl<T: LLMClient + ?Sized> LLMClient for std::sync::Arc<T> {
         (**self).chat_stream(request).await
     }
 
+    async fn chat_batch(
+        &self,
+        requests: Vec<ChatRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<ChatResponse, ClientError>> {
+        (**self).chat_batch(requests, concurrency).await
+    }
+
     fn supports_tools(&self) -> bool {
         (**self).supports_tools()
     }
@@ -356,6 +510,14 @@ impl<T: LLMClient + ?Sized> LLMClient for std::sync::Arc<T> {
     fn supports_streaming(&self) -> bool {
         (**self).supports_streaming()
     }
+
+    fn capabilities(&self) -> Capabilities {
+        (**self).capabilities()
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        (**self).request_payload(request)
+    }
 }
 
 #[cfg(test)]
@@ -419,6 +581,7 @@ mod tests {
                 created_at: chrono::Utc::now(),
                 response_id: Some("test-response".to_string()),
                 metadata: HashMap::new(),
+                additional_choices: Vec::new(),
             })
         }
 
@@ -483,6 +646,7 @@ mod tests {
             messages: vec![].into(),
             tools: None,
             tool_choice: None,
+            response_format: None,
             temperature: None,
             max_tokens: None,
             max_completion_tokens: None,
@@ -490,10 +654,15 @@ mod tests {
             frequency_penalty: None,
             presence_penalty: None,
             stop: None,
+            n: None,
+            seed: None,
+            logit_bias: None,
             stream: false,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: HashMap::new(),
         };
 
@@ -511,6 +680,7 @@ mod tests {
             messages: vec![create_test_message()].into(),
             tools: None,
             tool_choice: None,
+            response_format: None,
             temperature: None,
             max_tokens: None,
             max_completion_tokens: None,
@@ -518,10 +688,15 @@ mod tests {
             frequency_penalty: None,
             presence_penalty: None,
             stop: None,
+            n: None,
+            seed: None,
+            logit_bias: None,
             stream: false,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: HashMap::new(),
         };
 
@@ -529,6 +704,47 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_validate_request_allowed_models() {
+        let mut client = MockLLMClient::new();
+        client.config =
+            Config::new("openai", "gpt-4o").with_allowed_models(["gpt-4o", "gpt-4o-mini"]);
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        // The config's own model and an allowed override both pass.
+        assert!(client.validate_request(&request).is_ok());
+        let mini = request.clone().with_model("gpt-4o-mini");
+        assert!(client.validate_request(&mini).is_ok());
+
+        let typo = request.clone().with_model("gpt-4o-minii");
+        let error = client.validate_request(&typo).unwrap_err();
+        assert!(matches!(
+            &error,
+            ClientError::ModelNotAllowed { model, suggestion }
+                if model == "gpt-4o-minii" && suggestion.as_deref() == Some("gpt-4o-mini")
+        ));
+        assert!(error.to_string().contains("did you mean 'gpt-4o-mini'?"));
+
+        // Nothing close enough: no suggestion.
+        let unrelated = request.with_model("claude-sonnet-4");
+        let error = client.validate_request(&unrelated).unwrap_err();
+        assert!(matches!(
+            error,
+            ClientError::ModelNotAllowed {
+                suggestion: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("gpt-4o", "gpt-4o"), 0);
+        assert_eq!(edit_distance("gpt-4o-minii", "gpt-4o-mini"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
     #[test]
     fn test_validate_request_tools_not_supported() {
         let client = MockLLMClient::without_tools();
@@ -537,6 +753,7 @@ mod tests {
             messages: vec![create_test_message()].into(),
             tools: Some(vec![create_test_tool()]),
             tool_choice: None,
+            response_format: None,
             temperature: None,
             max_tokens: None,
             max_completion_tokens: None,
@@ -544,10 +761,15 @@ mod tests {
             frequency_penalty: None,
             presence_penalty: None,
             stop: None,
+            n: None,
+            seed: None,
+            logit_bias: None,
             stream: false,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: HashMap::new(),
         };
 
@@ -565,6 +787,7 @@ mod tests {
             messages: vec![create_test_message()].into(),
             tools: Some(vec![create_test_tool()]),
             tool_choice: Some(ToolChoice::Auto),
+            response_format: None,
             temperature: None,
             max_tokens: None,
             max_completion_tokens: None,
@@ -572,10 +795,15 @@ mod tests {
             frequency_penalty: None,
             presence_penalty: None,
             stop: None,
+            n: None,
+            seed: None,
+            logit_bias: None,
             stream: false,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: HashMap::new(),
         };
 
@@ -591,6 +819,7 @@ mod tests {
             messages: vec![create_test_message()].into(),
             tools: None,
             tool_choice: None,
+            response_format: None,
             temperature: None,
             max_tokens: None,
             max_completion_tokens: None,
@@ -598,10 +827,15 @@ mod tests {
             frequency_penalty: None,
             presence_penalty: None,
             stop: None,
+            n: None,
+            seed: None,
+            logit_bias: None,
             stream: true,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: HashMap::new(),
         };
 
@@ -619,6 +853,7 @@ mod tests {
             messages: vec![create_test_message()].into(),
             tools: None,
             tool_choice: None,
+            response_format: None,
             temperature: None,
             max_tokens: None,
             max_completion_tokens: None,
@@ -626,10 +861,15 @@ mod tests {
             frequency_penalty: None,
             presence_penalty: None,
             stop: None,
+            n: None,
+            seed: None,
+            logit_bias: None,
             stream: true,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: HashMap::new(),
         };
 
@@ -671,6 +911,7 @@ mod tests {
             messages: vec![create_test_message()].into(),
             tools: None,
             tool_choice: None,
+            response_format: None,
             temperature: None,
             max_tokens: None,
             max_completion_tokens: None,
@@ -678,10 +919,15 @@ mod tests {
             frequency_penalty: None,
             presence_penalty: None,
             stop: None,
+            n: None,
+            seed: None,
+            logit_bias: None,
             stream: false,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: HashMap::new(),
         };
 
@@ -712,6 +958,73 @@ mod tests {
         assert_eq!(client.config().provider, "anthropic");
     }
 
+    #[test]
+    fn test_capabilities_per_client() {
+        let config = |provider: &str, model: &str| Config::new(provider, model).with_api_key("k");
+
+        // The default reports only what every client knows.
+        let mock = MockLLMClient::without_tools().capabilities();
+        assert!(!mock.tools && mock.streaming && !mock.vision);
+        assert_eq!(mock.context_window, None);
+
+        let openai = ChatCompletionsClient::new(config("openai", "gpt-4o"))
+            .unwrap()
+            .capabilities();
+        assert!(openai.tools && openai.parallel_tool_calls && openai.vision);
+        assert!(openai.json_schema && openai.prompt_caching);
+        assert_eq!(openai.context_window, Some(128_000));
+
+        let anthropic = AnthropicClient::new(config("anthropic", "claude-sonnet-4-5"))
+            .unwrap()
+            .capabilities();
+        assert!(anthropic.vision && anthropic.reasoning && anthropic.prompt_caching);
+        assert_eq!(anthropic.context_window, Some(200_000));
+
+        let responses = ResponsesClient::new(config("responses", "o3"))
+            .unwrap()
+            .capabilities();
+        assert!(responses.vision && responses.reasoning && !responses.json_schema);
+
+        let groq = GroqClient::new(config("groq", "llama-3.3-70b-versatile"))
+            .unwrap()
+            .capabilities();
+        assert!(groq.tools && groq.parallel_tool_calls && !groq.vision && !groq.json_schema);
+        assert_eq!(groq.context_window, Some(128_000));
+
+        let gemini = GeminiClient::new(config("gemini", "gemini-2.5-pro"))
+            .unwrap()
+            .capabilities();
+        assert!(gemini.reasoning && !gemini.vision);
+        assert_eq!(gemini.context_window, Some(1_048_576));
+
+        for text_only in [
+            MistralClient::new(config("mistral", "mistral-large-latest"))
+                .unwrap()
+                .capabilities(),
+            CohereClient::new(config("cohere", "command-r-plus"))
+                .unwrap()
+                .capabilities(),
+            OllamaClient::new(config("ollama", "llama3.2"))
+                .unwrap()
+                .capabilities(),
+        ] {
+            assert!(text_only.tools && text_only.streaming);
+            assert!(!text_only.vision && !text_only.json_schema && !text_only.prompt_caching);
+        }
+
+        let aws = neuromance_common::AwsCredentials::new("AKID", "secret", "us-east-1");
+        let bedrock = |model: &str| {
+            BedrockClient::new(config("bedrock", model).with_aws_credentials(aws.clone()))
+                .unwrap()
+                .capabilities()
+        };
+        let claude = bedrock("us.anthropic.claude-sonnet-4-20250514-v1:0");
+        assert!(claude.tools && claude.reasoning);
+        assert_eq!(claude.context_window, Some(200_000));
+        let titan = bedrock("amazon.titan-text-express-v1");
+        assert!(!titan.tools && !titan.reasoning);
+    }
+
     #[test]
     fn build_client_rejects_unknown_provider() {
         let config = Config::new("totally-fake", "some-model").with_api_key("k");
@@ -733,4 +1046,60 @@ mod tests {
         let boxed: Box<dyn LLMClient> = build_client(config).unwrap();
         assert_llm(&boxed);
     }
+
+    #[tokio::test]
+    async fn chat_batch_preserves_order_and_isolates_failures() {
+        use wiremock::matchers::{body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for i in [0, 1, 3] {
+            Mock::given(method("POST"))
+                .and(path("/chat/completions"))
+                .and(body_string_contains(format!("prompt-{i}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "id": format!("chatcmpl-{i}"),
+                    "object": "chat.completion",
+                    "created": 1_677_652_288,
+                    "model": "gpt-4o",
+                    "choices": [{
+                        "index": 0,
+                        "message": {"role": "assistant", "content": format!("answer-{i}")},
+                        "finish_reason": "stop"
+                    }]
+                })))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_string_contains("prompt-2"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": {"message": "bad prompt", "type": "invalid_request_error"}
+            })))
+            .mount(&server)
+            .await;
+
+        let config = Config::new("openai", "gpt-4o")
+            .with_api_key("test-key")
+            .with_base_url(server.uri());
+        let client = ChatCompletionsClient::new(config).unwrap();
+        let requests = (0..4)
+            .map(|i| ChatRequest::new(vec![Message::user(Uuid::new_v4(), format!("prompt-{i}"))]))
+            .collect();
+
+        let results = client.chat_batch(requests, 2).await;
+
+        assert_eq!(results.len(), 4);
+        for (i, result) in results.iter().enumerate() {
+            if i == 2 {
+                assert!(result.is_err());
+            } else {
+                assert_eq!(
+                    result.as_ref().unwrap().message.content,
+                    format!("answer-{i}")
+                );
+            }
+        }
+    }
 }
@@ -17,12 +17,17 @@
 //! [`Message`]: neuromance_common::chat::Message
 
 use std::collections::HashMap;
+use std::time::Instant;
 
 use chrono::Utc;
 use smallvec::SmallVec;
 use uuid::Uuid;
 
-use neuromance_common::chat::{Message, MessageRole, ReasoningContent};
+use neuromance_common::chat::{
+    CACHED_METADATA_KEY, LATENCY_MS_METADATA_KEY, Message, MessageRole, PROVIDER_METADATA_KEY,
+    ReasoningContent,
+};
+use neuromance_common::client::Usage;
 use neuromance_common::tools::ToolCall;
 
 /// Incremental builder for a single [`Message`].
@@ -141,10 +146,39 @@ impl MessageBuilder {
             model: None,
             provider: None,
             usage: None,
+            parent_id: None,
+            content_parts: Vec::new(),
         }
     }
 }
 
+/// Records the well-known response metadata on `message`: the provider, the
+/// milliseconds since `started`, and whether `usage` reports prompt-cache
+/// hits.
+pub fn record_response_metadata(
+    message: &mut Message,
+    provider: &str,
+    started: Instant,
+    usage: Option<&Usage>,
+) {
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    message
+        .metadata
+        .insert(LATENCY_MS_METADATA_KEY.to_string(), latency_ms.into());
+    message
+        .metadata
+        .insert(PROVIDER_METADATA_KEY.to_string(), provider.into());
+    if let Some(usage) = usage {
+        let cached = usage
+            .input_tokens_details
+            .as_ref()
+            .is_some_and(|details| details.cached_tokens > 0);
+        message
+            .metadata
+            .insert(CACHED_METADATA_KEY.to_string(), cached.into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
@@ -0,0 +1,662 @@
+//! Mistral native client implementation.
+//!
+//! This module provides a client for Mistral's `/v1/chat/completions`
+//! endpoint, including Codestral models.
+//!
+//! # Features
+//!
+//! - **Request options**: the `safe_prompt` and `prefix` request metadata keys
+//!   map to Mistral's safety prompt and assistant-prefix continuation
+//! - **Tool/Function Calling**: tool call ids are rewritten to the 9-character
+//!   alphanumeric form Mistral requires
+//! - **Streaming**: server-sent events via [`run_sse_stream`], with tool call
+//!   indices assigned client-side
+//! - **Reasoning**: thinking chunks from Magistral models surface as reasoning
+//!   content
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use neuromance_client::{LLMClient, MistralClient};
+//! use neuromance_common::client::{Config, ChatRequest};
+//! use neuromance_common::chat::Conversation;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let config = Config::new("mistral", "mistral-large-latest")
+//!     .with_api_key("your-api-key");
+//! let client = MistralClient::new(config)?;
+//!
+//! let mut conversation = Conversation::new();
+//! conversation.add_message(conversation.user_message("Hello!"))?;
+//!
+//! let request = ChatRequest::new(conversation.get_messages().to_vec())
+//!     .with_metadata([("safe_prompt".to_string(), serde_json::json!(true))].into());
+//! let response = client.chat(&request).await?;
+//!
+//! println!("Response: {}", response.message.content);
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::stream::Stream;
+use reqwest_middleware::ClientWithMiddleware;
+use secrecy::{ExposeSecret, SecretString};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::error;
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{
+    Capabilities, ChatChunk, ChatRequest, ChatResponse, Config, ProxyConfig,
+};
+
+use crate::error::ClientError;
+use crate::message::MessageBuilder;
+use crate::request_tracing::{traced_chat, traced_stream};
+use crate::streaming::{ChatChunkStream, StreamingProvider, run_sse_stream};
+use crate::transport::{add_proxy_headers, send_json};
+use crate::{LLMClient, base_capabilities, build_client_resources};
+
+use super::{
+    DEFAULT_BASE_URL, MistralChatChunk, MistralChatRequest, MistralChatResponse, MistralContent,
+    MistralMessage, convert_tool_call, parse_finish_reason,
+};
+
+/// Client for Mistral's native chat completions endpoint.
+///
+/// # Security
+///
+/// The API key is stored using the `secrecy` crate to prevent accidental
+/// exposure through debug logs or memory dumps.
+///
+/// # Proxy Support
+///
+/// When a [`ProxyConfig`] is provided in the [`Config`], requests are routed
+/// through a tokenizer proxy. The proxy intercepts requests and injects real
+/// credentials, allowing agents to use sealed tokens instead of raw API keys.
+#[derive(Clone)]
+pub struct MistralClient {
+    client: ClientWithMiddleware,
+    streaming_client: reqwest::Client,
+    api_key: Arc<SecretString>,
+    base_url: String,
+    config: Arc<Config>,
+    proxy_config: Option<ProxyConfig>,
+}
+
+impl std::fmt::Debug for MistralClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MistralClient")
+            .field("api_key", &"[REDACTED]")
+            .field("base_url", &self.base_url)
+            .field("config", &self.config)
+            .field("proxy_config", &self.proxy_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MistralClient {
+    /// Create a new Mistral client from a configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neuromance_client::MistralClient;
+    /// use neuromance_common::client::Config;
+    ///
+    /// let config = Config::new("mistral", "codestral-latest").with_api_key("your-api-key");
+    /// let client = MistralClient::new(config)?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key is missing or HTTP client creation fails.
+    pub fn new(config: Config) -> Result<Self, ClientError> {
+        let r = build_client_resources(config, DEFAULT_BASE_URL)?;
+
+        Ok(Self {
+            client: r.client,
+            streaming_client: r.streaming_client,
+            api_key: r.api_key,
+            base_url: r.base_url,
+            config: r.config,
+            proxy_config: r.proxy_config,
+        })
+    }
+
+    /// Set a custom base URL for the API endpoint.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        Arc::make_mut(&mut self.config).base_url = Some(base_url.clone());
+        self.base_url = base_url;
+        self
+    }
+
+    /// Set the model to use for chat.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.config).model = model.into();
+        self
+    }
+
+    /// Build and validate the `/chat/completions` URL.
+    fn chat_url(&self) -> Result<String, ClientError> {
+        let url = format!("{}/chat/completions", self.base_url);
+        reqwest::Url::parse(&url)
+            .map_err(|e| ClientError::ConfigurationError(format!("Invalid URL '{url}': {e}")))?;
+        Ok(url)
+    }
+
+    /// Convert a Mistral response message to our internal Message format.
+    fn convert_message(message: &MistralMessage, conversation_id: uuid::Uuid) -> Message {
+        let mut builder = MessageBuilder::new(conversation_id, MessageRole::Assistant);
+        if let Some(content) = &message.content {
+            builder.set_content(content.text());
+            if let Some(thinking) = content.thinking() {
+                builder.append_reasoning(&thinking, "");
+            }
+        }
+        for call in &message.tool_calls {
+            builder.push_tool_call(convert_tool_call(call));
+        }
+        builder.build()
+    }
+}
+
+/// Per-stream state for [`MistralClient`]'s SSE stream.
+#[derive(Debug, Default)]
+pub struct MistralStreamState {
+    /// Number of tool calls seen so far; the next call's index.
+    tool_calls_seen: u32,
+}
+
+/// Convert one streamed Mistral chunk to our common `ChatChunk` format.
+///
+/// Mistral sends every tool call whole and does not reliably increment its
+/// `index`, so indices are assigned here in arrival order; otherwise
+/// `ToolCall::merge_deltas` would fold distinct calls into one.
+#[must_use]
+pub fn convert_chunk_to_chat_chunk(
+    chunk: &MistralChatChunk,
+    state: &mut MistralStreamState,
+) -> ChatChunk {
+    let choice = chunk.choices.first();
+    let content = choice.and_then(|c| c.delta.content.as_ref());
+
+    let delta_tool_calls = choice
+        .and_then(|c| c.delta.tool_calls.as_ref())
+        .map(|calls| {
+            calls
+                .iter()
+                .map(|call| {
+                    let mut tool_call = convert_tool_call(call);
+                    let index = if call.id.is_empty() {
+                        // Argument continuation of the previous call.
+                        state.tool_calls_seen.saturating_sub(1)
+                    } else {
+                        state.tool_calls_seen += 1;
+                        state.tool_calls_seen - 1
+                    };
+                    tool_call.index = Some(index);
+                    tool_call
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|calls| !calls.is_empty());
+
+    ChatChunk {
+        model: chunk.model.clone(),
+        delta_content: content.map(MistralContent::text).filter(|c| !c.is_empty()),
+        delta_reasoning_content: content.and_then(MistralContent::thinking),
+        delta_role: choice
+            .and_then(|c| c.delta.role)
+            .map(|_| MessageRole::Assistant),
+        delta_tool_calls,
+        finish_reason: choice
+            .and_then(|c| c.finish_reason.as_deref())
+            .and_then(parse_finish_reason),
+        usage: chunk.usage.clone(),
+        response_id: (!chunk.id.is_empty()).then(|| chunk.id.clone()),
+        created_at: Utc::now(),
+        metadata: HashMap::new(),
+    }
+}
+
+impl MistralClient {
+    /// [`LLMClient::chat`] without its request span.
+    async fn chat_inner(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        self.validate_request(request)?;
+
+        let mut mistral_request = MistralChatRequest::from((request, self.config.as_ref()));
+        mistral_request.stream = false;
+        let url = self.chat_url()?;
+
+        let mut request_builder = self
+            .client
+            .post(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.api_key.expose_secret()),
+            )
+            .header("Content-Type", "application/json");
+
+        request_builder =
+            add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
+
+        let request_builder = request_builder.body(
+            serde_json::to_string(&mistral_request).map_err(ClientError::SerializationError)?,
+        );
+
+        let response: MistralChatResponse = send_json(request_builder).await?;
+
+        let choice = response.choices.first().ok_or_else(|| {
+            ClientError::InvalidResponse("API returned no choices in response".to_string())
+        })?;
+
+        let conversation_id = request
+            .messages
+            .first()
+            .ok_or_else(|| {
+                error!("Request has no messages despite passing validation");
+                ClientError::InvalidRequest("Request must contain at least one message".to_string())
+            })?
+            .conversation_id;
+
+        let message = Self::convert_message(&choice.message, conversation_id);
+        let finish_reason = choice
+            .finish_reason
+            .as_deref()
+            .and_then(parse_finish_reason);
+
+        Ok(ChatResponse {
+            message,
+            usage: response.usage,
+            model: response.model,
+            finish_reason,
+            created_at: Utc::now(),
+            response_id: (!response.id.is_empty()).then_some(response.id),
+            metadata: HashMap::new(),
+            additional_choices: Vec::new(),
+        })
+    }
+
+    /// [`LLMClient::chat_stream`] without its request span.
+    fn chat_stream_inner(&self, request: &ChatRequest) -> Result<ChatChunkStream, ClientError> {
+        self.validate_request(request)?;
+
+        let mut mistral_request = MistralChatRequest::from((request, self.config.as_ref()));
+        mistral_request.stream = true;
+        let url = self.chat_url()?;
+
+        let mut request_builder = self
+            .streaming_client
+            .post(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.api_key.expose_secret()),
+            )
+            .header("Content-Type", "application/json");
+
+        request_builder =
+            add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
+
+        let request_builder = request_builder.json(&mistral_request);
+
+        run_sse_stream(self, request_builder)
+    }
+}
+
+#[async_trait]
+impl LLMClient for MistralClient {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            parallel_tool_calls: true,
+            reasoning: true,
+            ..base_capabilities(self)
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        let mut mistral_request = MistralChatRequest::from((request, self.config.as_ref()));
+        mistral_request.stream = false;
+        serde_json::to_value(&mistral_request).ok()
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        traced_chat(self.config(), request, self.chat_inner(request)).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        traced_stream(self.config(), request, async {
+            self.chat_stream_inner(request)
+        })
+        .await
+    }
+}
+
+impl StreamingProvider for MistralClient {
+    type Event = MistralChatChunk;
+    type State = MistralStreamState;
+
+    fn initial_state(&self) -> Self::State {
+        MistralStreamState::default()
+    }
+
+    fn is_stream_end(data: &str) -> bool {
+        data == "[DONE]"
+    }
+
+    fn process_event(
+        state: &mut Self::State,
+        event: Self::Event,
+    ) -> Option<Result<ChatChunk, ClientError>> {
+        Some(Ok(convert_chunk_to_chat_chunk(&event, state)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::streaming::collect_stream;
+    use futures::StreamExt;
+    use neuromance_common::client::FinishReason;
+    use neuromance_common::tools::{Function, Tool, ToolCall};
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_test_config(base_url: &str) -> Config {
+        Config::new("mistral", "mistral-large-latest")
+            .with_api_key("test-key")
+            .with_base_url(base_url)
+    }
+
+    fn create_test_message() -> Message {
+        Message::new(uuid::Uuid::new_v4(), MessageRole::User, "Hello")
+    }
+
+    fn weather_tool() -> Tool {
+        Tool {
+            r#type: "function".to_string(),
+            function: Function {
+                name: "get_weather".to_string(),
+                description: "Get the weather".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {"location": {"type": "string"}},
+                    "required": ["location"]
+                }),
+            },
+        }
+    }
+
+    fn sse(events: &[serde_json::Value]) -> String {
+        let mut body = events.iter().fold(String::new(), |mut body, event| {
+            body.push_str("data: ");
+            body.push_str(&event.to_string());
+            body.push_str("\n\n");
+            body
+        });
+        body.push_str("data: [DONE]\n\n");
+        body
+    }
+
+    #[tokio::test]
+    async fn test_successful_chat_maps_options_and_usage() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(header("authorization", "Bearer test-key"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "mistral-large-latest",
+                "stream": false,
+                "safe_prompt": true,
+                "max_tokens": 64
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "cmpl-1",
+                "object": "chat.completion",
+                "model": "mistral-large-latest",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Bonjour!"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 8, "completion_tokens": 3, "total_tokens": 11}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = MistralClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()])
+            .with_max_tokens(64)
+            .with_metadata([("safe_prompt".to_string(), serde_json::json!(true))].into());
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.model, "mistral-large-latest");
+        assert_eq!(response.message.content, "Bonjour!");
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(response.response_id.as_deref(), Some("cmpl-1"));
+        assert_eq!(response.usage.unwrap().total_tokens, 11);
+    }
+
+    #[tokio::test]
+    async fn test_chat_with_tool_calls() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "tools": [{"type": "function", "function": {"name": "get_weather"}}],
+                "tool_choice": "required"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "cmpl-2",
+                "model": "mistral-large-latest",
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": "",
+                        "tool_calls": [{
+                            "id": "D681PevKs",
+                            "function": {"name": "get_weather", "arguments": "{\"location\": \"Paris\"}"}
+                        }]
+                    },
+                    "finish_reason": "tool_calls"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = MistralClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()])
+            .with_tools(vec![weather_tool()])
+            .with_tool_choice(neuromance_common::ToolChoice::Required);
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.finish_reason, Some(FinishReason::ToolCalls));
+        assert_eq!(response.message.tool_calls.len(), 1);
+        let call = &response.message.tool_calls[0];
+        assert_eq!(call.id, "D681PevKs");
+        assert_eq!(call.function.name, "get_weather");
+        let args: serde_json::Value = serde_json::from_str(&call.function.arguments).unwrap();
+        assert_eq!(args["location"], "Paris");
+    }
+
+    #[test]
+    fn test_request_conversion_rewrites_ids_and_sets_prefix() {
+        let conversation_id = uuid::Uuid::new_v4();
+        let call = ToolCall::new("get_weather", r#"{"location":"Rome"}"#);
+        let mut assistant = Message::new(conversation_id, MessageRole::Assistant, "");
+        assistant.tool_calls.push(call.clone());
+        let messages = vec![
+            Message::developer(conversation_id, "Use metric units."),
+            Message::user(conversation_id, "Weather?"),
+            assistant,
+            Message::tool(conversation_id, "sunny", call.id, "get_weather".into()).unwrap(),
+            Message::assistant(conversation_id, "It is"),
+        ];
+        let request = ChatRequest::new(messages)
+            .with_metadata([("prefix".to_string(), serde_json::json!(true))].into());
+        let config = Config::new("mistral", "mistral-large-latest");
+
+        let body = serde_json::to_value(MistralChatRequest::from((&request, &config))).unwrap();
+        let messages = body["messages"].as_array().unwrap();
+
+        assert_eq!(messages[0]["role"], "system");
+        assert!(messages[2].get("content").is_none());
+        let id = messages[2]["tool_calls"][0]["id"].as_str().unwrap();
+        assert_eq!(id.len(), 9);
+        assert!(id.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(messages[3]["tool_call_id"], id);
+        assert_eq!(
+            messages[2]["tool_calls"][0]["function"]["arguments"],
+            r#"{"location":"Rome"}"#
+        );
+        assert_eq!(messages[4]["prefix"], true);
+        assert!(messages[1].get("prefix").is_none());
+        assert!(body.get("safe_prompt").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_text_and_usage() {
+        let mock_server = MockServer::start().await;
+
+        let body = sse(&[
+            serde_json::json!({
+                "id": "cmpl-3", "model": "codestral-latest",
+                "choices": [{"index": 0, "delta": {"role": "assistant", "content": "fn "}}]
+            }),
+            serde_json::json!({
+                "id": "cmpl-3", "model": "codestral-latest",
+                "choices": [{"index": 0, "delta": {"content": "main()"}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}
+            }),
+        ]);
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_partial_json(serde_json::json!({"stream": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&mock_server)
+            .await;
+
+        let client = MistralClient::new(create_test_config(&mock_server.uri()))
+            .unwrap()
+            .with_model("codestral-latest");
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let chunks: Vec<ChatChunk> = client
+            .chat_stream(&request)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let content: String = chunks
+            .iter()
+            .filter_map(|c| c.delta_content.as_deref())
+            .collect();
+        assert_eq!(content, "fn main()");
+        let last = chunks.last().unwrap();
+        assert_eq!(last.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(last.usage.as_ref().unwrap().total_tokens, 7);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_tool_calls_with_repeated_index_stay_distinct() {
+        let mock_server = MockServer::start().await;
+
+        let call = |id: &str, city: &str| {
+            serde_json::json!({
+                "id": id,
+                "function": {"name": "get_weather", "arguments": format!("{{\"location\":\"{city}\"}}")},
+                "index": 0
+            })
+        };
+        let body = sse(&[
+            serde_json::json!({
+                "id": "cmpl-4", "model": "mistral-large-latest",
+                "choices": [{"index": 0, "delta": {"role": "assistant", "content": ""}}]
+            }),
+            serde_json::json!({
+                "id": "cmpl-4", "model": "mistral-large-latest",
+                "choices": [{"index": 0, "delta": {"tool_calls": [call("aaaaaaaa1", "Oslo")]}}]
+            }),
+            serde_json::json!({
+                "id": "cmpl-4", "model": "mistral-large-latest",
+                "choices": [{
+                    "index": 0,
+                    "delta": {"tool_calls": [call("bbbbbbbb2", "Rome")]},
+                    "finish_reason": "tool_calls"
+                }]
+            }),
+        ]);
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&mock_server)
+            .await;
+
+        let client = MistralClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request =
+            ChatRequest::new(vec![create_test_message()]).with_tools(vec![weather_tool()]);
+
+        let stream = client.chat_stream(&request).await.unwrap();
+        let response = collect_stream(stream, uuid::Uuid::new_v4()).await.unwrap();
+
+        assert_eq!(response.finish_reason, Some(FinishReason::ToolCalls));
+        let calls = &response.message.tool_calls;
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "aaaaaaaa1");
+        assert_eq!(calls[0].function.arguments, r#"{"location":"Oslo"}"#);
+        assert_eq!(calls[1].id, "bbbbbbbb2");
+        assert_eq!(calls[1].function.arguments, r#"{"location":"Rome"}"#);
+    }
+
+    #[test]
+    fn test_thinking_chunks_become_reasoning() {
+        let message: MistralMessage = serde_json::from_value(serde_json::json!({
+            "role": "assistant",
+            "content": [
+                {"type": "thinking", "thinking": [{"type": "text", "text": "2+2 is 4."}]},
+                {"type": "text", "text": "4"}
+            ]
+        }))
+        .unwrap();
+
+        let converted = MistralClient::convert_message(&message, uuid::Uuid::new_v4());
+
+        assert_eq!(converted.content, "4");
+        assert_eq!(converted.reasoning.unwrap().content, "2+2 is 4.");
+    }
+}
@@ -0,0 +1,440 @@
+//! Mistral chat completions API types and client implementation.
+//!
+//! Mistral's `/v1/chat/completions` is close to `OpenAI`'s, but it adds the
+//! `safe_prompt` flag and assistant-message `prefix` continuation, only accepts
+//! 9-character alphanumeric tool call ids, and streams each tool call whole in
+//! a single delta (frequently with `index: 0` for every call).
+
+use serde::{Deserialize, Serialize};
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{ChatRequest, Config, FinishReason, Usage};
+use neuromance_common::tools::{FunctionCall, Tool, ToolCall};
+
+pub mod client;
+pub use client::MistralClient;
+
+/// Default base URL for the Mistral API.
+pub const DEFAULT_BASE_URL: &str = "https://api.mistral.ai/v1";
+
+/// Request metadata key read into `safe_prompt`; a boolean that prepends
+/// Mistral's safety system prompt.
+pub const SAFE_PROMPT_METADATA_KEY: &str = "safe_prompt";
+
+/// Request metadata key that, when `true`, sends the final assistant message
+/// with `prefix: true` so the model continues it instead of replying to it.
+pub const PREFIX_METADATA_KEY: &str = "prefix";
+
+/// Length of the tool call ids Mistral accepts.
+const TOOL_CALL_ID_LEN: usize = 9;
+
+/// Digits used when deriving a tool call id from a foreign one.
+const TOOL_CALL_ID_ALPHABET: &[u8; 62] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+// ============================================================================
+// Request Types
+// ============================================================================
+
+/// Role of a message in the Mistral API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MistralRole {
+    /// System instructions.
+    System,
+    /// User message.
+    User,
+    /// Assistant message.
+    Assistant,
+    /// Tool result.
+    Tool,
+}
+
+impl From<MessageRole> for MistralRole {
+    fn from(role: MessageRole) -> Self {
+        match role {
+            MessageRole::System | MessageRole::Developer => Self::System,
+            MessageRole::Assistant => Self::Assistant,
+            MessageRole::Tool => Self::Tool,
+            _ => Self::User,
+        }
+    }
+}
+
+/// Message content: plain text, or typed chunks as returned by reasoning
+/// models such as Magistral.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MistralContent {
+    /// Plain text content.
+    Text(String),
+    /// Typed content chunks.
+    Chunks(Vec<MistralContentChunk>),
+}
+
+impl MistralContent {
+    /// Concatenated text, excluding thinking chunks.
+    #[must_use]
+    pub fn text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Chunks(chunks) => chunks
+                .iter()
+                .filter_map(MistralContentChunk::text)
+                .collect(),
+        }
+    }
+
+    /// Concatenated thinking text, if any thinking chunks are present.
+    #[must_use]
+    pub fn thinking(&self) -> Option<String> {
+        let Self::Chunks(chunks) = self else {
+            return None;
+        };
+        let thinking: String = chunks
+            .iter()
+            .filter_map(|chunk| match chunk {
+                MistralContentChunk::Thinking { thinking } => Some(
+                    thinking
+                        .iter()
+                        .filter_map(MistralContentChunk::text)
+                        .collect::<String>(),
+                ),
+                _ => None,
+            })
+            .collect();
+        (!thinking.is_empty()).then_some(thinking)
+    }
+}
+
+/// A typed content chunk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MistralContentChunk {
+    /// Answer text.
+    Text {
+        /// The text.
+        text: String,
+    },
+    /// Reasoning output, itself made of text chunks.
+    Thinking {
+        /// The reasoning chunks.
+        thinking: Vec<Self>,
+    },
+    /// Chunk types this client does not interpret (images, references, ...).
+    #[serde(other)]
+    Other,
+}
+
+impl MistralContentChunk {
+    fn text(&self) -> Option<&str> {
+        match self {
+            Self::Text { text } => Some(text),
+            _ => None,
+        }
+    }
+}
+
+/// A chat message in the Mistral format; also the `message` of each choice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralMessage {
+    /// Author of the message.
+    pub role: MistralRole,
+    /// Message content; absent on assistant messages that only call tools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<MistralContent>,
+    /// Tool calls requested by the assistant.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<MistralToolCall>,
+    /// Id of the tool call this message answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    /// Name of the tool whose result this message carries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Continue this assistant message rather than answering it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub prefix: bool,
+}
+
+/// A tool call in the Mistral format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralToolCall {
+    /// Call id; 9 alphanumeric characters.
+    #[serde(default)]
+    pub id: String,
+    /// Call type, always `function` when present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    /// The function to call.
+    pub function: MistralFunctionCall,
+    /// Position of the call in a streamed response. Mistral does not
+    /// reliably increment this across calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+}
+
+/// Function name and arguments of a Mistral tool call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralFunctionCall {
+    /// Name of the function to call.
+    pub name: String,
+    /// Arguments, sent as a JSON string; responses may carry either a string
+    /// or an object.
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+/// Request body for `/chat/completions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralChatRequest {
+    /// Model name, e.g. `mistral-large-latest` or `codestral-latest`.
+    pub model: String,
+    /// Conversation messages.
+    pub messages: Vec<MistralMessage>,
+    /// Tools the model may call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    /// Tool selection, in the `OpenAI` shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<serde_json::Value>,
+    /// Sampling temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling threshold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Maximum number of tokens to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Stop sequences.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    /// Presence penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    /// Frequency penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Structured output format, in the `OpenAI` shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<serde_json::Value>,
+    /// Prepend Mistral's safety system prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_prompt: Option<bool>,
+    /// Whether to stream server-sent events.
+    pub stream: bool,
+}
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Response from `/chat/completions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralChatResponse {
+    /// Response id.
+    #[serde(default)]
+    pub id: String,
+    /// Model that produced the response.
+    #[serde(default)]
+    pub model: String,
+    /// Generated choices.
+    pub choices: Vec<MistralChoice>,
+    /// Token usage.
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+/// A single generated choice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralChoice {
+    /// Index of the choice.
+    #[serde(default)]
+    pub index: u32,
+    /// The generated message.
+    pub message: MistralMessage,
+    /// Why generation stopped.
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// One streamed chunk of a chat completion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralChatChunk {
+    /// Response id.
+    #[serde(default)]
+    pub id: String,
+    /// Model generating the stream.
+    #[serde(default)]
+    pub model: String,
+    /// Delta choices.
+    #[serde(default)]
+    pub choices: Vec<MistralStreamChoice>,
+    /// Token usage, present on the final chunk.
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+/// A single choice within a streamed chunk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralStreamChoice {
+    /// Index of the choice.
+    #[serde(default)]
+    pub index: u32,
+    /// Incremental message update.
+    pub delta: MistralDelta,
+    /// Why generation stopped, set on the final chunk.
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// Incremental message update in a streamed chunk.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MistralDelta {
+    /// Role, sent on the first chunk.
+    #[serde(default)]
+    pub role: Option<MistralRole>,
+    /// Content fragment.
+    #[serde(default)]
+    pub content: Option<MistralContent>,
+    /// Complete tool calls.
+    #[serde(default)]
+    pub tool_calls: Option<Vec<MistralToolCall>>,
+}
+
+/// Map a Mistral `finish_reason` to our [`FinishReason`].
+///
+/// Mistral adds `model_length` (context window exhausted) and `error` to the
+/// `OpenAI` set.
+#[must_use]
+pub fn parse_finish_reason(reason: &str) -> Option<FinishReason> {
+    match reason {
+        "stop" => Some(FinishReason::Stop),
+        "length" | "model_length" => Some(FinishReason::Length),
+        "tool_calls" => Some(FinishReason::ToolCalls),
+        "error" => Some(FinishReason::ModelError),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Conversion Helpers
+// ============================================================================
+
+/// Map a tool call id to one Mistral accepts.
+///
+/// Ids that are already 9 alphanumeric characters pass through; any other id
+/// (e.g. `call_...` from another provider) is hashed to a stable 9-character
+/// base62 id, so a tool call and its result still match after conversion.
+#[must_use]
+pub fn mistral_tool_call_id(id: &str) -> String {
+    if id.len() == TOOL_CALL_ID_LEN && id.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return id.to_string();
+    }
+    // FNV-1a: stable across runs and platforms, unlike `DefaultHasher`.
+    let mut hash = id.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    (0..TOOL_CALL_ID_LEN)
+        .map(|_| {
+            #[allow(clippy::cast_possible_truncation)] // always < 62
+            let digit = TOOL_CALL_ID_ALPHABET[(hash % 62) as usize];
+            hash /= 62;
+            char::from(digit)
+        })
+        .collect()
+}
+
+/// Convert our common Message to the Mistral format.
+impl From<&Message> for MistralMessage {
+    fn from(message: &Message) -> Self {
+        let tool_calls: Vec<_> = message
+            .tool_calls
+            .iter()
+            .map(|tool_call| MistralToolCall {
+                id: mistral_tool_call_id(&tool_call.id),
+                r#type: Some("function".to_string()),
+                function: MistralFunctionCall {
+                    name: tool_call.function.name.clone(),
+                    arguments: serde_json::Value::String(
+                        tool_call.function.arguments_json().to_string(),
+                    ),
+                },
+                index: None,
+            })
+            .collect();
+        let content = (!message.content.is_empty() || tool_calls.is_empty())
+            .then(|| MistralContent::Text(message.content.clone()));
+
+        Self {
+            role: MistralRole::from(message.role),
+            content,
+            tool_calls,
+            tool_call_id: message.tool_call_id.as_deref().map(mistral_tool_call_id),
+            name: (message.role == MessageRole::Tool)
+                .then(|| message.name.clone())
+                .flatten(),
+            prefix: false,
+        }
+    }
+}
+
+/// Conversion from `ChatRequest` to the Mistral request format.
+impl From<(&ChatRequest, &Config)> for MistralChatRequest {
+    fn from((request, config): (&ChatRequest, &Config)) -> Self {
+        let metadata_flag = |key: &str| {
+            request
+                .metadata
+                .get(key)
+                .and_then(serde_json::Value::as_bool)
+        };
+
+        let mut messages: Vec<MistralMessage> =
+            request.messages.iter().map(MistralMessage::from).collect();
+        if metadata_flag(PREFIX_METADATA_KEY) == Some(true)
+            && let Some(last) = messages.last_mut()
+            && last.role == MistralRole::Assistant
+        {
+            last.prefix = true;
+        }
+
+        Self {
+            model: request
+                .model
+                .clone()
+                .unwrap_or_else(|| config.model.clone()),
+            messages,
+            tools: request.tools.clone().filter(|t| !t.is_empty()),
+            tool_choice: request.tool_choice.clone().map(Into::into),
+            temperature: request.temperature,
+            top_p: request.top_p,
+            max_tokens: request.max_tokens.or(request.max_completion_tokens),
+            stop: request.stop.clone(),
+            presence_penalty: request.presence_penalty,
+            frequency_penalty: request.frequency_penalty,
+            response_format: request.response_format.clone().map(Into::into),
+            safe_prompt: metadata_flag(SAFE_PROMPT_METADATA_KEY),
+            stream: request.stream,
+        }
+    }
+}
+
+/// Convert a Mistral tool call into our common `ToolCall`.
+#[must_use]
+pub fn convert_tool_call(call: &MistralToolCall) -> ToolCall {
+    let arguments = match &call.function.arguments {
+        serde_json::Value::String(arguments) => arguments.clone(),
+        serde_json::Value::Null => String::new(),
+        arguments => arguments.to_string(),
+    };
+    ToolCall {
+        id: call.id.clone(),
+        call_type: "function".to_string(),
+        function: FunctionCall {
+            name: call.function.name.clone(),
+            arguments,
+        },
+        index: None,
+    }
+}
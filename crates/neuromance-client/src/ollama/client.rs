@@ -0,0 +1,665 @@
+//! Ollama native client implementation.
+//!
+//! This module provides a client for Ollama's native `/api/chat` endpoint.
+//!
+//! # Features
+//!
+//! - **Runtime options**: `max_tokens` maps to `options.num_predict`; the
+//!   `num_ctx` and `keep_alive` request metadata keys pass through
+//! - **Tool/Function Calling**: `OpenAI`-shaped `tools` in, object-argument
+//!   `tool_calls` out
+//! - **Streaming**: newline-delimited JSON via [`run_ndjson_stream`]
+//! - **Optional API Key**: local servers need none; when set, it is sent as a
+//!   bearer token for authenticated deployments
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use neuromance_client::{LLMClient, OllamaClient};
+//! use neuromance_common::client::{Config, ChatRequest};
+//! use neuromance_common::chat::Conversation;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = OllamaClient::new(Config::new("ollama", "llama3.1"))?;
+//!
+//! let mut conversation = Conversation::new();
+//! conversation.add_message(conversation.user_message("Hello!"))?;
+//!
+//! let request = ChatRequest::new(conversation.get_messages().to_vec())
+//!     .with_metadata([("keep_alive".to_string(), serde_json::json!("10m"))].into());
+//! let response = client.chat(&request).await?;
+//!
+//! println!("Response: {}", response.message.content);
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::stream::Stream;
+use reqwest_middleware::ClientWithMiddleware;
+use secrecy::{ExposeSecret, SecretString};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::error;
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{
+    Capabilities, ChatChunk, ChatRequest, ChatResponse, Config, ProxyConfig,
+};
+
+use crate::error::ClientError;
+use crate::message::MessageBuilder;
+use crate::request_tracing::{traced_chat, traced_stream};
+use crate::streaming::{ChatChunkStream, StreamingProvider, run_ndjson_stream};
+use crate::transport::{add_proxy_headers, send_json};
+use crate::{LLMClient, base_capabilities, build_client_resources};
+
+use super::{
+    DEFAULT_BASE_URL, OllamaChatRequest, OllamaChatResponse, OllamaMessage, convert_tool_call,
+};
+
+/// Placeholder key for local servers, which do not check credentials.
+const PLACEHOLDER_API_KEY: &str = "ollama";
+
+/// Client for Ollama's native `/api/chat` endpoint.
+///
+/// # Security
+///
+/// The API key is stored using the `secrecy` crate to prevent accidental
+/// exposure through debug logs or memory dumps.
+///
+/// # Proxy Support
+///
+/// When a [`ProxyConfig`] is provided in the [`Config`], requests are routed
+/// through a tokenizer proxy. The proxy intercepts requests and injects real
+/// credentials, allowing agents to use sealed tokens instead of raw API keys.
+#[derive(Clone)]
+pub struct OllamaClient {
+    client: ClientWithMiddleware,
+    streaming_client: reqwest::Client,
+    api_key: Arc<SecretString>,
+    base_url: String,
+    config: Arc<Config>,
+    proxy_config: Option<ProxyConfig>,
+}
+
+impl std::fmt::Debug for OllamaClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OllamaClient")
+            .field("api_key", &"[REDACTED]")
+            .field("base_url", &self.base_url)
+            .field("config", &self.config)
+            .field("proxy_config", &self.proxy_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OllamaClient {
+    /// Create a new Ollama client from a configuration.
+    ///
+    /// The API key is optional; without one a placeholder is used.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neuromance_client::OllamaClient;
+    /// use neuromance_common::client::Config;
+    ///
+    /// let client = OllamaClient::new(Config::new("ollama", "llama3.1"))?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if HTTP client creation fails.
+    pub fn new(mut config: Config) -> Result<Self, ClientError> {
+        if config.api_key.is_none() {
+            config = config.with_api_key(PLACEHOLDER_API_KEY);
+        }
+        let r = build_client_resources(config, DEFAULT_BASE_URL)?;
+
+        Ok(Self {
+            client: r.client,
+            streaming_client: r.streaming_client,
+            api_key: r.api_key,
+            base_url: r.base_url,
+            config: r.config,
+            proxy_config: r.proxy_config,
+        })
+    }
+
+    /// Set a custom base URL for the API endpoint.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        Arc::make_mut(&mut self.config).base_url = Some(base_url.clone());
+        self.base_url = base_url;
+        self
+    }
+
+    /// Set the model to use for chat.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.config).model = model.into();
+        self
+    }
+
+    /// Build and validate the `/api/chat` URL.
+    fn chat_url(&self) -> Result<String, ClientError> {
+        let url = format!("{}/api/chat", self.base_url);
+        reqwest::Url::parse(&url)
+            .map_err(|e| ClientError::ConfigurationError(format!("Invalid URL '{url}': {e}")))?;
+        Ok(url)
+    }
+
+    /// Convert an Ollama response message to our internal Message format.
+    fn convert_message(message: &OllamaMessage, conversation_id: uuid::Uuid) -> Message {
+        let mut builder = MessageBuilder::new(conversation_id, MessageRole::Assistant);
+        builder.set_content(message.content.clone());
+        if let Some(thinking) = message.thinking.as_deref().filter(|t| !t.is_empty()) {
+            builder.append_reasoning(thinking, "");
+        }
+        for call in &message.tool_calls {
+            builder.push_tool_call(convert_tool_call(call));
+        }
+        builder.build()
+    }
+}
+
+/// Convert one streamed Ollama line to our common `ChatChunk` format.
+///
+/// Ollama streams text as incremental fragments and tool calls complete in a
+/// single line; the final line (`done: true`) carries the token counts.
+#[must_use]
+pub fn convert_response_to_chat_chunk(
+    response: &OllamaChatResponse,
+    has_tool_calls: bool,
+) -> ChatChunk {
+    let message = response.message.as_ref();
+    let tool_calls: Vec<_> = message
+        .into_iter()
+        .flat_map(|m| &m.tool_calls)
+        .map(convert_tool_call)
+        .collect();
+
+    ChatChunk {
+        model: response.model.clone(),
+        delta_content: message.map(|m| m.content.clone()).filter(|c| !c.is_empty()),
+        delta_reasoning_content: message
+            .and_then(|m| m.thinking.clone())
+            .filter(|t| !t.is_empty()),
+        delta_role: message.map(|_| MessageRole::Assistant),
+        finish_reason: response.finish_reason(has_tool_calls || !tool_calls.is_empty()),
+        delta_tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+        usage: response.usage(),
+        response_id: None,
+        created_at: Utc::now(),
+        metadata: HashMap::new(),
+    }
+}
+
+impl OllamaClient {
+    /// [`LLMClient::chat`] without its request span.
+    async fn chat_inner(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        self.validate_request(request)?;
+
+        let mut ollama_request = OllamaChatRequest::from((request, self.config.as_ref()));
+        ollama_request.stream = false;
+        let url = self.chat_url()?;
+
+        let mut request_builder = self
+            .client
+            .post(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.api_key.expose_secret()),
+            )
+            .header("Content-Type", "application/json");
+
+        request_builder =
+            add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
+
+        let request_builder = request_builder
+            .body(serde_json::to_string(&ollama_request).map_err(ClientError::SerializationError)?);
+
+        let response: OllamaChatResponse = send_json(request_builder).await?;
+
+        if let Some(message) = response.error {
+            return Err(ClientError::RequestError(message));
+        }
+
+        let ollama_message = response.message.as_ref().ok_or_else(|| {
+            ClientError::InvalidResponse("API returned no message in response".to_string())
+        })?;
+
+        let conversation_id = request
+            .messages
+            .first()
+            .ok_or_else(|| {
+                error!("Request has no messages despite passing validation");
+                ClientError::InvalidRequest("Request must contain at least one message".to_string())
+            })?
+            .conversation_id;
+
+        let message = Self::convert_message(ollama_message, conversation_id);
+        let finish_reason = response.finish_reason(!message.tool_calls.is_empty());
+
+        Ok(ChatResponse {
+            message,
+            usage: response.usage(),
+            model: response.model,
+            finish_reason,
+            created_at: Utc::now(),
+            response_id: None,
+            metadata: HashMap::new(),
+            additional_choices: Vec::new(),
+        })
+    }
+
+    /// [`LLMClient::chat_stream`] without its request span.
+    async fn chat_stream_inner(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<ChatChunkStream, ClientError> {
+        self.validate_request(request)?;
+
+        let mut ollama_request = OllamaChatRequest::from((request, self.config.as_ref()));
+        ollama_request.stream = true;
+        let url = self.chat_url()?;
+
+        let mut request_builder = self
+            .streaming_client
+            .post(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.api_key.expose_secret()),
+            )
+            .header("Content-Type", "application/json");
+
+        request_builder =
+            add_proxy_headers(request_builder, self.proxy_config.as_ref(), &self.api_key);
+
+        let request_builder = request_builder.json(&ollama_request);
+
+        run_ndjson_stream(self, request_builder).await
+    }
+}
+
+#[async_trait]
+impl LLMClient for OllamaClient {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            reasoning: true,
+            ..base_capabilities(self)
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        let mut ollama_request = OllamaChatRequest::from((request, self.config.as_ref()));
+        ollama_request.stream = false;
+        serde_json::to_value(&ollama_request).ok()
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        traced_chat(self.config(), request, self.chat_inner(request)).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        traced_stream(self.config(), request, self.chat_stream_inner(request)).await
+    }
+}
+
+impl StreamingProvider for OllamaClient {
+    type Event = OllamaChatResponse;
+    /// Whether a tool call has been streamed, so the final `stop` can be
+    /// reported as `ToolCalls`.
+    type State = bool;
+
+    fn initial_state(&self) -> Self::State {
+        false
+    }
+
+    fn process_event(
+        state: &mut Self::State,
+        event: Self::Event,
+    ) -> Option<Result<ChatChunk, ClientError>> {
+        if let Some(message) = event.error {
+            return Some(Err(ClientError::RequestError(message)));
+        }
+        let chunk = convert_response_to_chat_chunk(&event, *state);
+        *state |= chunk.delta_tool_calls.is_some();
+        Some(Ok(chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use futures::StreamExt;
+    use neuromance_common::client::FinishReason;
+    use neuromance_common::tools::{Function, Tool, ToolCall};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_test_config(base_url: &str) -> Config {
+        Config::new("ollama", "llama3.1").with_base_url(base_url)
+    }
+
+    fn create_test_message() -> Message {
+        Message::new(uuid::Uuid::new_v4(), MessageRole::User, "Hello")
+    }
+
+    fn weather_tool() -> Tool {
+        Tool {
+            r#type: "function".to_string(),
+            function: Function {
+                name: "get_weather".to_string(),
+                description: "Get the weather".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {"location": {"type": "string"}},
+                    "required": ["location"]
+                }),
+            },
+        }
+    }
+
+    fn ndjson(lines: &[serde_json::Value]) -> String {
+        lines.iter().fold(String::new(), |mut body, line| {
+            body.push_str(&line.to_string());
+            body.push('\n');
+            body
+        })
+    }
+
+    #[tokio::test]
+    async fn test_successful_chat_maps_options_and_usage() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "llama3.1",
+                "stream": false,
+                "keep_alive": "10m",
+                "options": {"temperature": 0.2, "num_predict": 64, "num_ctx": 8192}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama3.1",
+                "created_at": "2025-01-01T00:00:00Z",
+                "message": {"role": "assistant", "content": "Hi there!"},
+                "done": true,
+                "done_reason": "stop",
+                "prompt_eval_count": 12,
+                "eval_count": 4
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let metadata = HashMap::from([
+            ("keep_alive".to_string(), serde_json::json!("10m")),
+            ("num_ctx".to_string(), serde_json::json!(8192)),
+        ]);
+        let request = ChatRequest::new(vec![create_test_message()])
+            .with_temperature(0.2)
+            .with_max_tokens(64)
+            .with_metadata(metadata);
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.model, "llama3.1");
+        assert_eq!(response.message.content, "Hi there!");
+        assert_eq!(response.message.role, MessageRole::Assistant);
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 12);
+        assert_eq!(usage.completion_tokens, 4);
+        assert_eq!(usage.total_tokens, 16);
+    }
+
+    #[tokio::test]
+    async fn test_chat_with_tool_calls() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .and(body_partial_json(serde_json::json!({
+                "tools": [{"type": "function", "function": {"name": "get_weather"}}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama3.1",
+                "message": {
+                    "role": "assistant",
+                    "content": "",
+                    "tool_calls": [{
+                        "function": {"name": "get_weather", "arguments": {"location": "Paris"}}
+                    }]
+                },
+                "done": true,
+                "done_reason": "stop"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request =
+            ChatRequest::new(vec![create_test_message()]).with_tools(vec![weather_tool()]);
+
+        let response = client.chat(&request).await.unwrap();
+
+        assert_eq!(response.finish_reason, Some(FinishReason::ToolCalls));
+        assert_eq!(response.message.tool_calls.len(), 1);
+        let call = &response.message.tool_calls[0];
+        assert_eq!(call.function.name, "get_weather");
+        assert!(call.id.starts_with("call_"));
+        let args: serde_json::Value = serde_json::from_str(&call.function.arguments).unwrap();
+        assert_eq!(args["location"], "Paris");
+    }
+
+    #[test]
+    fn test_request_conversion_maps_roles_and_tool_messages() {
+        let conversation_id = uuid::Uuid::new_v4();
+        let mut assistant = Message::new(conversation_id, MessageRole::Assistant, "");
+        assistant
+            .tool_calls
+            .push(ToolCall::new("get_weather", r#"{"location":"Rome"}"#));
+        let messages = vec![
+            Message::system(conversation_id, "Be brief."),
+            Message::developer(conversation_id, "Use metric units."),
+            Message::user(conversation_id, "Weather?"),
+            assistant,
+            Message::tool(conversation_id, "sunny", "a".into(), "get_weather".into()).unwrap(),
+        ];
+        let request = ChatRequest::new(messages);
+        let config = Config::new("ollama", "llama3.1");
+
+        let body = serde_json::to_value(OllamaChatRequest::from((&request, &config))).unwrap();
+
+        let roles: Vec<&str> = body["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["system", "system", "user", "assistant", "tool"]);
+        assert_eq!(
+            body["messages"][3]["tool_calls"][0]["function"]["arguments"]["location"],
+            "Rome"
+        );
+        assert_eq!(body["messages"][4]["tool_name"], "get_weather");
+        assert!(body.get("keep_alive").is_none());
+        assert!(body.get("tools").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_parses_ndjson() {
+        let mock_server = MockServer::start().await;
+
+        let body = ndjson(&[
+            serde_json::json!({
+                "model": "llama3.1",
+                "message": {"role": "assistant", "content": "Hel"},
+                "done": false
+            }),
+            serde_json::json!({
+                "model": "llama3.1",
+                "message": {"role": "assistant", "content": "lo"},
+                "done": false
+            }),
+            serde_json::json!({
+                "model": "llama3.1",
+                "message": {"role": "assistant", "content": ""},
+                "done": true,
+                "done_reason": "stop",
+                "prompt_eval_count": 5,
+                "eval_count": 2
+            }),
+        ]);
+
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .and(body_partial_json(serde_json::json!({"stream": true})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/x-ndjson")
+                    .set_body_string(body),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let chunks: Vec<ChatChunk> = client
+            .chat_stream(&request)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 3);
+        let content: String = chunks
+            .iter()
+            .filter_map(|c| c.delta_content.as_deref())
+            .collect();
+        assert_eq!(content, "Hello");
+        let last = chunks.last().unwrap();
+        assert_eq!(last.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(last.usage.as_ref().unwrap().total_tokens, 7);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_tool_call_sets_final_finish_reason() {
+        let mock_server = MockServer::start().await;
+
+        let body = ndjson(&[
+            serde_json::json!({
+                "model": "llama3.1",
+                "message": {
+                    "role": "assistant",
+                    "content": "",
+                    "tool_calls": [{
+                        "function": {"name": "get_weather", "arguments": {"location": "Oslo"}}
+                    }]
+                },
+                "done": false
+            }),
+            serde_json::json!({
+                "model": "llama3.1",
+                "message": {"role": "assistant", "content": ""},
+                "done": true,
+                "done_reason": "stop"
+            }),
+        ]);
+
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request =
+            ChatRequest::new(vec![create_test_message()]).with_tools(vec![weather_tool()]);
+
+        let chunks: Vec<ChatChunk> = client
+            .chat_stream(&request)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let calls = chunks[0].delta_tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].function.name, "get_weather");
+        assert_eq!(calls[0].function.arguments, r#"{"location":"Oslo"}"#);
+        assert_eq!(chunks[1].finish_reason, Some(FinishReason::ToolCalls));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_error_line_surfaces_as_error() {
+        let mock_server = MockServer::start().await;
+
+        let body = ndjson(&[serde_json::json!({"error": "model runner crashed"})]);
+
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(create_test_config(&mock_server.uri())).unwrap();
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let items: Vec<_> = client.chat_stream(&request).await.unwrap().collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(
+            matches!(&items[0], Err(ClientError::RequestError(m)) if m == "model runner crashed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_model_not_found_maps_to_request_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .set_body_json(serde_json::json!({"error": "model 'nope' not found"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(create_test_config(&mock_server.uri()))
+            .unwrap()
+            .with_model("nope");
+        let request = ChatRequest::new(vec![create_test_message()]);
+
+        let result = client.chat_stream(&request).await;
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+    }
+}
@@ -0,0 +1,288 @@
+//! Ollama native `/api/chat` API types and client implementation.
+//!
+//! Ollama also serves an OpenAI-compatible endpoint (used by the `ollama`
+//! alias with [`crate::ChatCompletionsClient`]). The native API additionally
+//! exposes `keep_alive` and runtime `options` such as `num_ctx`, and streams
+//! newline-delimited JSON rather than SSE.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::client::{ChatRequest, Config, FinishReason, Usage};
+use neuromance_common::tools::{FunctionCall, Tool, ToolCall};
+
+pub mod client;
+pub use client::OllamaClient;
+
+/// Default base URL for a local Ollama server.
+pub const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+/// Request metadata key read into the `keep_alive` field, e.g. `"10m"` or `-1`.
+pub const KEEP_ALIVE_METADATA_KEY: &str = "keep_alive";
+
+/// Request metadata key read into `options.num_ctx`.
+pub const NUM_CTX_METADATA_KEY: &str = "num_ctx";
+
+// ============================================================================
+// Request Types
+// ============================================================================
+
+/// Role of a message in the Ollama API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OllamaRole {
+    /// System instructions.
+    System,
+    /// User message.
+    User,
+    /// Assistant message.
+    Assistant,
+    /// Tool result.
+    Tool,
+}
+
+impl From<MessageRole> for OllamaRole {
+    fn from(role: MessageRole) -> Self {
+        match role {
+            MessageRole::System | MessageRole::Developer => Self::System,
+            MessageRole::Assistant => Self::Assistant,
+            MessageRole::Tool => Self::Tool,
+            _ => Self::User,
+        }
+    }
+}
+
+/// A chat message in the Ollama format; also the `message` of each response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaMessage {
+    /// Author of the message.
+    pub role: OllamaRole,
+    /// Text content.
+    #[serde(default)]
+    pub content: String,
+    /// Reasoning output from thinking models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
+    /// Tool calls requested by the assistant.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<OllamaToolCall>,
+    /// Name of the tool whose result this message carries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+}
+
+/// A tool call in the Ollama format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaToolCall {
+    /// The function to call.
+    pub function: OllamaFunctionCall,
+}
+
+/// Function name and arguments of an Ollama tool call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaFunctionCall {
+    /// Name of the function to call.
+    pub name: String,
+    /// Arguments as a JSON object (not a string, unlike `OpenAI`).
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+/// Runtime model options.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatOptions {
+    /// Sampling temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling threshold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Maximum number of tokens to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_predict: Option<u32>,
+    /// Context window size to load the model with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_ctx: Option<u32>,
+    /// Stop sequences.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    /// Presence penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    /// Frequency penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+}
+
+/// Request body for `/api/chat`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaChatRequest {
+    /// Model name, e.g. `llama3.1`.
+    pub model: String,
+    /// Conversation messages.
+    pub messages: Vec<OllamaMessage>,
+    /// Tools the model may call; Ollama accepts the `OpenAI` tool shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    /// Whether to stream newline-delimited JSON.
+    pub stream: bool,
+    /// Enable thinking output for models that support it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub think: Option<bool>,
+    /// Runtime model options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<ChatOptions>,
+    /// How long the model stays loaded after the request, as a duration
+    /// string (`"5m"`) or a number of seconds (`-1` keeps it loaded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_alive: Option<serde_json::Value>,
+}
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Response from `/api/chat`; also the shape of each streamed line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaChatResponse {
+    /// Model that produced the response.
+    #[serde(default)]
+    pub model: String,
+    /// Generated message (a delta when streaming).
+    #[serde(default)]
+    pub message: Option<OllamaMessage>,
+    /// True on the final response.
+    #[serde(default)]
+    pub done: bool,
+    /// Why generation stopped (`stop`, `length`, `load`, ...), set when `done`.
+    #[serde(default)]
+    pub done_reason: Option<String>,
+    /// Number of prompt tokens evaluated.
+    #[serde(default)]
+    pub prompt_eval_count: Option<u32>,
+    /// Number of tokens generated.
+    #[serde(default)]
+    pub eval_count: Option<u32>,
+    /// Error reported in place of a response, e.g. mid-stream.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl OllamaChatResponse {
+    /// Token usage, present on the final response.
+    #[must_use]
+    pub fn usage(&self) -> Option<Usage> {
+        if self.prompt_eval_count.is_none() && self.eval_count.is_none() {
+            return None;
+        }
+        let prompt_tokens = self.prompt_eval_count.unwrap_or(0);
+        let completion_tokens = self.eval_count.unwrap_or(0);
+        Some(Usage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+            cost: None,
+            input_tokens_details: None,
+            output_tokens_details: None,
+        })
+    }
+
+    /// Map `done_reason`, reporting `ToolCalls` when the model stopped after
+    /// requesting tools (Ollama reports `stop`).
+    #[must_use]
+    pub fn finish_reason(&self, has_tool_calls: bool) -> Option<FinishReason> {
+        if !self.done {
+            return None;
+        }
+        match self.done_reason.as_deref() {
+            Some("length") => Some(FinishReason::Length),
+            _ if has_tool_calls => Some(FinishReason::ToolCalls),
+            _ => Some(FinishReason::Stop),
+        }
+    }
+}
+
+// ============================================================================
+// Conversion Helpers
+// ============================================================================
+
+/// Convert our common Message to the Ollama format.
+impl From<&Message> for OllamaMessage {
+    fn from(message: &Message) -> Self {
+        let tool_calls = message
+            .tool_calls
+            .iter()
+            .map(|tool_call| OllamaToolCall {
+                function: OllamaFunctionCall {
+                    name: tool_call.function.name.clone(),
+                    arguments: serde_json::from_str(tool_call.function.arguments_json())
+                        .unwrap_or_else(|_| serde_json::Value::Object(serde_json::Map::new())),
+                },
+            })
+            .collect();
+
+        Self {
+            role: OllamaRole::from(message.role),
+            content: message.content.clone(),
+            thinking: None,
+            tool_calls,
+            tool_name: (message.role == MessageRole::Tool)
+                .then(|| message.name.clone())
+                .flatten(),
+        }
+    }
+}
+
+/// Conversion from `ChatRequest` to the Ollama request format.
+///
+/// `tool_choice` has no Ollama equivalent and is dropped.
+impl From<(&ChatRequest, &Config)> for OllamaChatRequest {
+    fn from((request, config): (&ChatRequest, &Config)) -> Self {
+        let num_ctx = request
+            .metadata
+            .get(NUM_CTX_METADATA_KEY)
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok());
+
+        let options = ChatOptions {
+            temperature: request.temperature,
+            top_p: request.top_p,
+            num_predict: request.max_tokens.or(request.max_completion_tokens),
+            num_ctx,
+            stop: request.stop.clone(),
+            presence_penalty: request.presence_penalty,
+            frequency_penalty: request.frequency_penalty,
+        };
+
+        Self {
+            model: request
+                .model
+                .clone()
+                .unwrap_or_else(|| config.model.clone()),
+            messages: request.messages.iter().map(OllamaMessage::from).collect(),
+            tools: request.tools.clone().filter(|t| !t.is_empty()),
+            stream: request.stream,
+            think: request.thinking.budget().map(|_| true),
+            options: Some(options),
+            keep_alive: request.metadata.get(KEEP_ALIVE_METADATA_KEY).cloned(),
+        }
+    }
+}
+
+/// Convert an Ollama tool call into our common `ToolCall`.
+///
+/// Ollama does not assign call ids, so one is synthesized; tool results are
+/// matched back by `tool_name` on the next request.
+#[must_use]
+pub fn convert_tool_call(call: &OllamaToolCall) -> ToolCall {
+    ToolCall {
+        id: format!("call_{}", Uuid::new_v4().simple()),
+        call_type: "function".to_string(),
+        function: FunctionCall {
+            name: call.function.name.clone(),
+            arguments: call.function.arguments.to_string(),
+        },
+        index: None,
+    }
+}
@@ -0,0 +1,359 @@
+//! `tracing` spans around each client request.
+//!
+//! Every provider's `chat` and `chat_stream` run inside an `llm_request` span
+//! carrying the provider, model, message count, and whether the request
+//! streams, so client work nests under the caller's spans (e.g. the daemon's
+//! per-turn span). When the request completes, an event inside the span
+//! records its latency and token counts. For a stream, "completes" means the
+//! stream ran to its end; the span is re-entered on every poll so parse
+//! warnings from the stream driver land in it too.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use futures::Stream;
+use tracing::{Instrument, Span, info, info_span, warn};
+
+use neuromance_common::client::{ChatChunk, ChatRequest, ChatResponse, Config, Usage};
+
+use crate::error::ClientError;
+use crate::streaming::ChatChunkStream;
+
+/// Name of the span each request runs in.
+pub const REQUEST_SPAN_NAME: &str = "llm_request";
+
+/// The span for one request to `config`'s provider.
+///
+/// Clients build it from the request as sent, after interceptors have run, so
+/// a rewritten model or message list shows up in the span.
+fn request_span(config: &Config, request: &ChatRequest, streaming: bool) -> Span {
+    info_span!(
+        REQUEST_SPAN_NAME,
+        provider = %config.provider,
+        model = %request.model.as_deref().unwrap_or(&config.model),
+        message_count = request.messages.len(),
+        streaming,
+    )
+}
+
+/// Record a finished request inside the current span.
+fn record_completion(started: Instant, usage: Option<&Usage>) {
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    info!(
+        latency_ms,
+        prompt_tokens = usage.map(|u| u.prompt_tokens),
+        completion_tokens = usage.map(|u| u.completion_tokens),
+        total_tokens = usage.map(|u| u.total_tokens),
+        "llm request completed"
+    );
+}
+
+/// Record a failed request inside the current span.
+fn record_failure(started: Instant, error: &ClientError) {
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    warn!(latency_ms, error = %error, "llm request failed");
+}
+
+/// Run a unary `chat` call inside a request span, recording its outcome.
+///
+/// # Errors
+///
+/// Returns the error `chat` produced.
+pub async fn traced_chat(
+    config: &Config,
+    request: &ChatRequest,
+    chat: impl Future<Output = Result<ChatResponse, ClientError>> + Send,
+) -> Result<ChatResponse, ClientError> {
+    async {
+        let started = Instant::now();
+        let result = chat.await;
+        match &result {
+            Ok(response) => record_completion(started, response.usage.as_ref()),
+            Err(error) => record_failure(started, error),
+        }
+        result
+    }
+    .instrument(request_span(config, request, false))
+    .await
+}
+
+/// Open a stream inside a request span, recording its outcome once the
+/// stream ends.
+///
+/// # Errors
+///
+/// Returns the error `open` produced.
+pub async fn traced_stream(
+    config: &Config,
+    request: &ChatRequest,
+    open: impl Future<Output = Result<ChatChunkStream, ClientError>> + Send,
+) -> Result<ChatChunkStream, ClientError> {
+    let span = request_span(config, request, true);
+    let started = Instant::now();
+    match open.instrument(span.clone()).await {
+        Ok(inner) => Ok(Box::pin(TracedStream {
+            inner,
+            span,
+            started,
+            usage: None,
+        })),
+        Err(error) => {
+            span.in_scope(|| record_failure(started, &error));
+            Err(error)
+        }
+    }
+}
+
+/// A chunk stream polled inside its request span.
+struct TracedStream {
+    inner: ChatChunkStream,
+    span: Span,
+    started: Instant,
+    /// Latest usage reported by a chunk.
+    usage: Option<Usage>,
+}
+
+impl Stream for TracedStream {
+    type Item = Result<ChatChunk, ClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let _entered = this.span.enter();
+        let next = this.inner.as_mut().poll_next(cx);
+        match &next {
+            Poll::Ready(Some(Ok(chunk))) => {
+                if let Some(usage) = &chunk.usage {
+                    this.usage = Some(usage.clone());
+                }
+            }
+            Poll::Ready(Some(Err(error))) => record_failure(this.started, error),
+            Poll::Ready(None) => record_completion(this.started, this.usage.as_ref()),
+            Poll::Pending => {}
+        }
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use futures::StreamExt;
+    use tracing::field::{Field, Visit};
+    use tracing::span::Attributes;
+    use tracing::{Event, Id, Subscriber};
+    use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::{Layer, Registry};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use neuromance_common::chat::Message;
+
+    use crate::{ChatCompletionsClient, Interceptor, LLMClient};
+
+    use super::*;
+
+    /// Field name to recorded value, as text.
+    type Fields = HashMap<String, String>;
+
+    struct FieldVisitor<'a>(&'a mut Fields);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    /// Captures the fields of `llm_request` spans and of events inside them.
+    #[derive(Clone, Default)]
+    struct Capture {
+        spans: Arc<Mutex<Vec<Fields>>>,
+        events: Arc<Mutex<Vec<Fields>>>,
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: LayerContext<'_, S>) {
+            if attrs.metadata().name() == REQUEST_SPAN_NAME {
+                let mut fields = Fields::new();
+                attrs.record(&mut FieldVisitor(&mut fields));
+                self.spans.lock().unwrap().push(fields);
+            }
+        }
+
+        fn on_event(&self, event: &Event<'_>, ctx: LayerContext<'_, S>) {
+            let in_request = ctx
+                .event_span(event)
+                .is_some_and(|span| span.name() == REQUEST_SPAN_NAME);
+            if in_request {
+                let mut fields = Fields::new();
+                event.record(&mut FieldVisitor(&mut fields));
+                self.events.lock().unwrap().push(fields);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_span_carries_fields_and_completion_event() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "chatcmpl-trace",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "gpt-4o",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "Hi" },
+                    "finish_reason": "stop"
+                }],
+                "usage": { "prompt_tokens": 12, "completion_tokens": 3, "total_tokens": 15 }
+            })))
+            .mount(&server)
+            .await;
+
+        let capture = Capture::default();
+        let _guard = tracing::subscriber::set_default(Registry::default().with(capture.clone()));
+
+        let config = Config::new("openai", "gpt-4o")
+            .with_api_key("test-key")
+            .with_base_url(server.uri());
+        let client = ChatCompletionsClient::new(config).unwrap();
+        let conversation_id = uuid::Uuid::new_v4();
+        let request = ChatRequest::new(vec![
+            Message::system(conversation_id, "Be brief."),
+            Message::user(conversation_id, "Hello"),
+        ]);
+        client.chat(&request).await.unwrap();
+
+        let spans = capture.spans.lock().unwrap().clone();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0]["provider"], "openai");
+        assert_eq!(spans[0]["model"], "gpt-4o");
+        assert_eq!(spans[0]["message_count"], "2");
+        assert_eq!(spans[0]["streaming"], "false");
+
+        let events = capture.events.lock().unwrap().clone();
+        let done = events
+            .iter()
+            .find(|fields| {
+                fields
+                    .get("message")
+                    .is_some_and(|m| m == "llm request completed")
+            })
+            .unwrap();
+        assert!(done.contains_key("latency_ms"));
+        assert_eq!(done["prompt_tokens"], "12");
+        assert_eq!(done["total_tokens"], "15");
+    }
+
+    #[tokio::test]
+    async fn test_stream_completion_event_fires_when_stream_ends() {
+        let server = MockServer::start().await;
+        let chunk = serde_json::json!({
+            "id": "chatcmpl-trace",
+            "object": "chat.completion.chunk",
+            "created": 1_677_652_288,
+            "model": "gpt-4o",
+            "choices": [],
+            "usage": { "prompt_tokens": 7, "completion_tokens": 2, "total_tokens": 9 }
+        });
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                format!("data: {chunk}\n\ndata: [DONE]\n\n"),
+                "text/event-stream",
+            ))
+            .mount(&server)
+            .await;
+
+        let capture = Capture::default();
+        let _guard = tracing::subscriber::set_default(Registry::default().with(capture.clone()));
+
+        let config = Config::new("openai", "gpt-4o")
+            .with_api_key("test-key")
+            .with_base_url(server.uri());
+        let client = ChatCompletionsClient::new(config).unwrap();
+        let request =
+            ChatRequest::new(vec![Message::user(uuid::Uuid::new_v4(), "Hello")]).with_model("o3");
+        let stream = client.chat_stream(&request).await.unwrap();
+        assert!(capture.events.lock().unwrap().is_empty());
+        let _: Vec<_> = stream.collect().await;
+
+        let spans = capture.spans.lock().unwrap().clone();
+        assert_eq!(spans[0]["model"], "o3");
+        assert_eq!(spans[0]["streaming"], "true");
+        let events = capture.events.lock().unwrap().clone();
+        let done = events
+            .iter()
+            .find(|fields| {
+                fields
+                    .get("message")
+                    .is_some_and(|m| m == "llm request completed")
+            })
+            .unwrap();
+        assert_eq!(done["completion_tokens"], "2");
+    }
+
+    /// Drops every message but the last and swaps in another model.
+    struct Shrink;
+
+    impl Interceptor for Shrink {
+        fn before_request(&self, request: &mut ChatRequest) {
+            request.model = Some("gpt-4o-mini".to_string());
+            let last = request.messages.last().cloned();
+            request.messages = last.into_iter().collect::<Vec<_>>().into();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_span_reflects_interceptor_rewrites() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "chatcmpl-trace",
+                "object": "chat.completion",
+                "created": 1_677_652_288,
+                "model": "gpt-4o-mini",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "Hi" },
+                    "finish_reason": "stop"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let capture = Capture::default();
+        let _guard = tracing::subscriber::set_default(Registry::default().with(capture.clone()));
+
+        let config = Config::new("openai", "gpt-4o")
+            .with_api_key("test-key")
+            .with_base_url(server.uri());
+        let client = ChatCompletionsClient::new(config)
+            .unwrap()
+            .with_interceptor(Arc::new(Shrink));
+        let conversation_id = uuid::Uuid::new_v4();
+        let request = ChatRequest::new(vec![
+            Message::system(conversation_id, "Be brief."),
+            Message::user(conversation_id, "Hello"),
+        ]);
+        client.chat(&request).await.unwrap();
+
+        let spans = capture.spans.lock().unwrap().clone();
+        assert_eq!(spans[0]["model"], "gpt-4o-mini");
+        assert_eq!(spans[0]["message_count"], "1");
+    }
+}
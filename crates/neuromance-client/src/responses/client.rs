@@ -14,17 +14,20 @@ use std::sync::Arc;
 use tracing::{error, warn};
 
 use neuromance_common::chat::MessageRole;
-use neuromance_common::client::{ChatChunk, ChatRequest, ChatResponse, Config, ProxyConfig, Usage};
+use neuromance_common::client::{
+    Capabilities, ChatChunk, ChatRequest, ChatResponse, Config, ProxyConfig, Usage,
+};
 use neuromance_common::tools::{FunctionCall, ToolCall};
 
 use crate::error::ClientError;
-use crate::streaming::{StreamingProvider, run_sse_stream};
+use crate::request_tracing::{traced_chat, traced_stream};
+use crate::streaming::{ChatChunkStream, StreamingProvider, run_sse_stream};
 use crate::transport::{add_proxy_headers, send_json};
-use crate::{LLMClient, build_client_resources};
+use crate::{LLMClient, base_capabilities, build_client_resources};
 
 use super::{
     OutputItem, ResponsesRequest, ResponsesResponse, StreamEvent, StreamingFunctionCall,
-    convert_response_to_message,
+    StreamingReasoningSummary, convert_response_to_message,
 };
 
 /// Default base URL for the Responses API.
@@ -138,21 +141,9 @@ impl ResponsesClient {
     }
 }
 
-#[async_trait]
-impl LLMClient for ResponsesClient {
-    fn config(&self) -> &Config {
-        &self.config
-    }
-
-    fn supports_tools(&self) -> bool {
-        true
-    }
-
-    fn supports_streaming(&self) -> bool {
-        true
-    }
-
-    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+impl ResponsesClient {
+    /// [`LLMClient::chat`] without its request span.
+    async fn chat_inner(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
         self.validate_request(request)?;
 
         let mut responses_request = ResponsesRequest::from((request, self.config.as_ref()));
@@ -189,14 +180,12 @@ impl LLMClient for ResponsesClient {
             created_at: DateTime::from_timestamp(response.created_at, 0).unwrap_or_else(Utc::now),
             response_id: Some(response.id),
             metadata: response.metadata,
+            additional_choices: Vec::new(),
         })
     }
 
-    async fn chat_stream(
-        &self,
-        request: &ChatRequest,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
-    {
+    /// [`LLMClient::chat_stream`] without its request span.
+    fn chat_stream_inner(&self, request: &ChatRequest) -> Result<ChatChunkStream, ClientError> {
         self.validate_request(request)?;
 
         let mut responses_request = ResponsesRequest::from((request, self.config.as_ref()));
@@ -225,17 +214,66 @@ impl LLMClient for ResponsesClient {
     }
 }
 
+#[async_trait]
+impl LLMClient for ResponsesClient {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            parallel_tool_calls: true,
+            vision: true,
+            reasoning: true,
+            prompt_caching: true,
+            ..base_capabilities(self)
+        }
+    }
+
+    fn request_payload(&self, request: &ChatRequest) -> Option<serde_json::Value> {
+        let mut responses_request = ResponsesRequest::from((request, self.config.as_ref()));
+        responses_request.stream = Some(false);
+        serde_json::to_value(&responses_request).ok()
+    }
+
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+        traced_chat(self.config(), request, self.chat_inner(request)).await
+    }
+
+    async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>, ClientError>
+    {
+        traced_stream(self.config(), request, async {
+            self.chat_stream_inner(request)
+        })
+        .await
+    }
+}
+
 /// Per-stream accumulator for the Responses streaming protocol.
 ///
 /// `model` and `response_id` are populated by the first `ResponseCreated`
 /// event; `streaming_function_calls` accumulates partial tool-use arguments
 /// across `OutputItemAdded` / `FunctionCallArgumentsDelta` /
-/// `FunctionCallArgumentsDone` / `OutputItemDone`.
+/// `FunctionCallArgumentsDone` / `OutputItemDone`; `reasoning_summary` joins
+/// reasoning summary parts across `ReasoningSummaryTextDelta` /
+/// `ReasoningSummaryTextDone`.
 #[derive(Default)]
 pub struct ResponsesStreamState {
     model: String,
     response_id: String,
     streaming_function_calls: HashMap<u32, StreamingFunctionCall>,
+    reasoning_summary: StreamingReasoningSummary,
 }
 
 impl StreamingProvider for ResponsesClient {
@@ -334,18 +372,25 @@ fn convert_event_to_chunk(
             metadata: HashMap::new(),
         })),
 
-        StreamEvent::ReasoningSummaryTextDelta { delta, .. } => Some(Ok(ChatChunk {
-            model: state.model.clone(),
-            delta_content: None,
-            delta_reasoning_content: Some(delta),
-            delta_role: None,
-            delta_tool_calls: None,
-            finish_reason: None,
-            usage: None,
-            response_id: Some(state.response_id.clone()),
-            created_at: Utc::now(),
-            metadata: HashMap::new(),
-        })),
+        StreamEvent::ReasoningSummaryTextDelta {
+            output_index,
+            summary_index,
+            delta,
+        } => {
+            let delta = state
+                .reasoning_summary
+                .push_delta(output_index, summary_index, &delta);
+            Some(Ok(reasoning_chunk(state, delta)))
+        }
+
+        StreamEvent::ReasoningSummaryTextDone {
+            output_index,
+            summary_index,
+            text,
+        } => state
+            .reasoning_summary
+            .finish_part(output_index, summary_index, &text)
+            .map(|delta| Ok(reasoning_chunk(state, delta))),
 
         StreamEvent::OutputItemAdded { output_index, item } => {
             if let OutputItem::FunctionCall { call_id, name, .. } = item {
@@ -440,11 +485,26 @@ fn convert_event_to_chunk(
         StreamEvent::ContentPartAdded { .. }
         | StreamEvent::ContentPartDone { .. }
         | StreamEvent::OutputTextDone { .. }
-        | StreamEvent::ReasoningSummaryTextDone { .. }
         | StreamEvent::Unknown => None,
     }
 }
 
+/// A chunk carrying only a reasoning delta.
+fn reasoning_chunk(state: &ResponsesStreamState, delta: String) -> ChatChunk {
+    ChatChunk {
+        model: state.model.clone(),
+        delta_content: None,
+        delta_reasoning_content: Some(delta),
+        delta_role: None,
+        delta_tool_calls: None,
+        finish_reason: None,
+        usage: None,
+        response_id: Some(state.response_id.clone()),
+        created_at: Utc::now(),
+        metadata: HashMap::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
@@ -480,6 +540,8 @@ mod tests {
             model: None,
             provider: None,
             usage: None,
+            parent_id: None,
+            content_parts: Vec::new(),
         }
     }
 
@@ -725,6 +787,50 @@ mod tests {
         assert_eq!(chunk.model, "o3");
     }
 
+    /// Summary parts stream in order, joined like the non-streaming
+    /// conversion; `Done` only emits text the deltas did not cover.
+    #[tokio::test]
+    async fn test_stream_reasoning_summary_parts_accumulate() {
+        let mut state = make_state();
+        let delta = |summary_index: u32, delta: &str| StreamEvent::ReasoningSummaryTextDelta {
+            output_index: 0,
+            summary_index,
+            delta: delta.to_string(),
+        };
+        let done = |summary_index: u32, text: &str| StreamEvent::ReasoningSummaryTextDone {
+            output_index: 0,
+            summary_index,
+            text: text.to_string(),
+        };
+
+        let events = vec![
+            delta(0, "Check the "),
+            delta(0, "units."),
+            done(0, "Check the units."),
+            delta(1, "Then "),
+            // The tail of part 1 arrives only in its Done event.
+            done(1, "Then convert."),
+            // Part 2 has no deltas at all.
+            done(2, "Answer."),
+        ];
+        let mut streamed = String::new();
+        for event in events {
+            if let Some(chunk) = convert_event_to_chunk(event, &mut state) {
+                streamed.push_str(&chunk.unwrap().delta_reasoning_content.unwrap());
+            }
+        }
+
+        let expected = "Check the units.\nThen convert.\nAnswer.";
+        assert_eq!(streamed, expected);
+        let reasoning = state.reasoning_summary.finalize().unwrap();
+        assert_eq!(reasoning.content, expected);
+
+        // A Done event repeating fully streamed text emits nothing.
+        let mut state = make_state();
+        convert_event_to_chunk(delta(0, "all here"), &mut state);
+        assert!(convert_event_to_chunk(done(0, "all here"), &mut state).is_none());
+    }
+
     #[tokio::test]
     async fn test_stream_response_completed_with_usage() {
         let mut state = make_state();
@@ -978,14 +1084,6 @@ mod tests {
         };
         assert!(convert_event_to_chunk(event, &mut state).is_none());
 
-        // ReasoningSummaryTextDone
-        let event = StreamEvent::ReasoningSummaryTextDone {
-            output_index: 0,
-            summary_index: 0,
-            text: "done".to_string(),
-        };
-        assert!(convert_event_to_chunk(event, &mut state).is_none());
-
         // Unknown
         assert!(convert_event_to_chunk(StreamEvent::Unknown, &mut state).is_none());
     }
@@ -1154,6 +1252,8 @@ mod tests {
             model: None,
             provider: None,
             usage: None,
+            parent_id: None,
+            content_parts: Vec::new(),
         }
     }
 
@@ -1183,6 +1283,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_conversion_tool_choice_any_maps_to_required() {
+        use neuromance_common::client::ToolChoice;
+
+        let request = ChatRequest::new(vec![make_message(MessageRole::User, "Hello")])
+            .with_tool_choice(ToolChoice::Any {
+                disable_parallel: false,
+            });
+        let responses_req = super::super::ResponsesRequest::from((&request, &default_config()));
+
+        assert_eq!(
+            serde_json::to_value(responses_req).unwrap()["tool_choice"],
+            "required"
+        );
+    }
+
     #[test]
     fn test_conversion_tool_message_without_call_id_is_skipped() {
         let user_msg = make_message(MessageRole::User, "Hello");
@@ -1223,6 +1339,23 @@ mod tests {
         assert_eq!(responses_req.input.len(), 1);
     }
 
+    #[test]
+    fn test_conversion_developer_messages_join_instructions() {
+        let sys = make_message(MessageRole::System, "You are helpful.");
+        let dev = make_message(MessageRole::Developer, "Answer in French.");
+        let user_msg = make_message(MessageRole::User, "Hi");
+
+        let request = ChatRequest::new(vec![sys, dev, user_msg]);
+        let config = default_config();
+        let responses_req = super::super::ResponsesRequest::from((&request, &config));
+
+        assert_eq!(
+            responses_req.instructions.as_deref(),
+            Some("You are helpful.\n\nAnswer in French.")
+        );
+        assert_eq!(responses_req.input.len(), 1);
+    }
+
     #[test]
     fn test_conversion_previous_response_id_from_metadata() {
         let user_msg = make_message(MessageRole::User, "Continue");
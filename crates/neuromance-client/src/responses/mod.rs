@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use tracing::warn;
 use typed_builder::TypedBuilder;
 
-use neuromance_common::chat::{Message, MessageRole};
+use neuromance_common::chat::{Message, MessageRole, ReasoningContent};
 use neuromance_common::client::{ChatRequest, Config, Usage};
 use neuromance_common::features::ReasoningLevel;
 use neuromance_common::tools::{FunctionCall, Tool, ToolCall};
@@ -39,7 +39,7 @@ impl From<MessageRole> for ResponsesRole {
     fn from(role: MessageRole) -> Self {
         match role {
             MessageRole::Assistant => Self::Assistant,
-            MessageRole::System => Self::System,
+            MessageRole::System | MessageRole::Developer => Self::System,
             MessageRole::User => Self::User,
             // Tool messages should be converted to FunctionCallOutput input items,
             // not mapped to a role. This fallback exists for safety but indicates
@@ -232,7 +232,10 @@ impl From<&neuromance_common::client::ToolChoice> for ResponsesToolChoice {
     fn from(choice: &neuromance_common::client::ToolChoice) -> Self {
         match choice {
             neuromance_common::client::ToolChoice::None => Self::Mode("none".to_string()),
-            neuromance_common::client::ToolChoice::Required => Self::Mode("required".to_string()),
+            neuromance_common::client::ToolChoice::Required
+            | neuromance_common::client::ToolChoice::Any { .. } => {
+                Self::Mode("required".to_string())
+            }
             neuromance_common::client::ToolChoice::Function { name } => Self::Function {
                 choice_type: "function".to_string(),
                 name: name.clone(),
@@ -361,8 +364,8 @@ impl From<(&ChatRequest, &Config)> for ResponsesRequest {
         // Convert messages to input items
         for message in request.messages.iter() {
             match message.role {
-                MessageRole::System => {
-                    // System messages become instructions
+                MessageRole::System | MessageRole::Developer => {
+                    // System and developer messages become instructions
                     if let Some(ref mut inst) = instructions {
                         inst.push_str("\n\n");
                         inst.push_str(&message.content);
@@ -942,6 +945,79 @@ impl StreamingFunctionCall {
     }
 }
 
+/// Accumulator for reasoning summary text from streaming events.
+///
+/// A reasoning item may carry several summary parts, each streamed as
+/// `ReasoningSummaryTextDelta` events keyed by `(output_index,
+/// summary_index)`. Parts are joined with a newline, matching
+/// [`convert_response_to_message`], so the streamed text equals the
+/// non-streaming reasoning content.
+#[derive(Debug, Clone, Default)]
+pub struct StreamingReasoningSummary {
+    /// The part currently being streamed, as `(output_index, summary_index)`.
+    current: Option<(u32, u32)>,
+    /// Text streamed so far for the current part.
+    part: String,
+    /// Reasoning text across all parts, separators included.
+    pub accumulated: String,
+}
+
+impl StreamingReasoningSummary {
+    /// Separator placed between summary parts.
+    const SEPARATOR: &str = "\n";
+
+    /// Appends a delta for the given part and returns the text to emit,
+    /// prefixed with a separator when the delta starts a new part.
+    pub fn push_delta(&mut self, output_index: u32, summary_index: u32, delta: &str) -> String {
+        let mut emitted = self.start_part(output_index, summary_index);
+        emitted.push_str(delta);
+        self.part.push_str(delta);
+        self.accumulated.push_str(delta);
+        emitted
+    }
+
+    /// Closes a part with its final `text`, returning whatever was not
+    /// already streamed: the missing tail if deltas were dropped, or the whole
+    /// part if none arrived. Returns `None` when nothing is left to emit.
+    pub fn finish_part(
+        &mut self,
+        output_index: u32,
+        summary_index: u32,
+        text: &str,
+    ) -> Option<String> {
+        let mut emitted = self.start_part(output_index, summary_index);
+        let remainder = text.strip_prefix(self.part.as_str()).unwrap_or_default();
+        emitted.push_str(remainder);
+        self.part.push_str(remainder);
+        self.accumulated.push_str(remainder);
+        (!emitted.is_empty()).then_some(emitted)
+    }
+
+    /// Finalizes the accumulated summary into `ReasoningContent`, or `None`
+    /// if no summary text was streamed.
+    #[must_use]
+    pub fn finalize(self) -> Option<ReasoningContent> {
+        (!self.accumulated.is_empty()).then(|| ReasoningContent::new(self.accumulated))
+    }
+
+    /// Switches to the given part, returning the separator to emit if it
+    /// follows an earlier part.
+    fn start_part(&mut self, output_index: u32, summary_index: u32) -> String {
+        let key = (output_index, summary_index);
+        if self.current == Some(key) {
+            return String::new();
+        }
+        self.current = Some(key);
+        self.part.clear();
+        if self.accumulated.is_empty() {
+            String::new()
+        } else {
+            self.accumulated.push_str(Self::SEPARATOR);
+            Self::SEPARATOR.to_string()
+        }
+    }
+}
+
 // ============================================================================
 // Conversion Helpers
 // ============================================================================
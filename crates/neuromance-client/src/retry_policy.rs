@@ -0,0 +1,36 @@
+//! Adapter that lets `reqwest-retry` consult a [`BackoffStrategy`].
+//!
+//! The retry middleware asks its policy after every transient failure; this
+//! forwards the question to the strategy from [`RetryConfig::backoff`], so a
+//! custom strategy governs HTTP-level retries as well as the ones `Core`
+//! makes itself. The middleware does not surface `Retry-After`, which
+//! `RetryAfterMiddleware` handles on its own.
+//!
+//! [`RetryConfig::backoff`]: neuromance_common::client::RetryConfig::backoff
+
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use reqwest_retry::{RetryDecision, RetryPolicy};
+
+use neuromance_common::client::BackoffStrategy;
+
+/// A [`RetryPolicy`] backed by a [`BackoffStrategy`].
+#[derive(Debug, Clone)]
+pub struct StrategyPolicy(Arc<dyn BackoffStrategy>);
+
+impl StrategyPolicy {
+    pub fn new(strategy: Arc<dyn BackoffStrategy>) -> Self {
+        Self(strategy)
+    }
+}
+
+impl RetryPolicy for StrategyPolicy {
+    fn should_retry(&self, _request_start_time: SystemTime, n_past_retries: u32) -> RetryDecision {
+        self.0
+            .next_delay(n_past_retries, None)
+            .map_or(RetryDecision::DoNotRetry, |delay| RetryDecision::Retry {
+                execute_after: SystemTime::now() + delay,
+            })
+    }
+}
@@ -0,0 +1,203 @@
+//! Fold a streamed response back into a single [`ChatResponse`].
+//!
+//! Some OpenAI-compatible servers (vLLM, assorted gateways) only report token
+//! usage reliably on the streaming path. [`collect_stream`] lets a client take
+//! that path while still handing its caller the same [`ChatResponse`] shape
+//! the non-streaming `chat` call produces.
+
+use std::collections::HashMap;
+
+use futures::StreamExt;
+use uuid::Uuid;
+
+use neuromance_common::chat::MessageRole;
+use neuromance_common::client::{ChatResponse, Usage};
+use neuromance_common::tools::ToolCall;
+
+use super::ChatChunkStream;
+use crate::error::ClientError;
+use crate::message::MessageBuilder;
+
+/// Drain `stream` and assemble the chunks into one [`ChatResponse`].
+///
+/// - Content deltas are concatenated; reasoning deltas likewise.
+/// - Tool-call deltas are stitched with [`ToolCall::merge_deltas`], then the
+///   streaming `index` is cleared so the calls match the non-streaming shape.
+/// - The finish reason is taken from the last chunk that carried one.
+/// - Usage is merged across chunks, keeping the largest reported counts
+///   (providers either repeat cumulative totals or report once at the end).
+/// - `model`, `response_id`, and `created_at` come from the final chunk.
+///
+/// # Errors
+///
+/// Propagates the first error yielded by the stream, and returns
+/// [`ClientError::InvalidResponse`] if the stream ends without any chunks.
+pub async fn collect_stream(
+    mut stream: ChatChunkStream,
+    conversation_id: Uuid,
+) -> Result<ChatResponse, ClientError> {
+    let mut role = None;
+    let mut content = String::new();
+    let mut reasoning: Option<String> = None;
+    let mut tool_calls: Vec<ToolCall> = Vec::new();
+    let mut finish_reason = None;
+    let mut usage: Option<Usage> = None;
+    let mut last_chunk = None;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+
+        if role.is_none() {
+            role = chunk.delta_role;
+        }
+        if let Some(ref delta) = chunk.delta_content {
+            content.push_str(delta);
+        }
+        if let Some(ref delta) = chunk.delta_reasoning_content {
+            reasoning.get_or_insert_with(String::new).push_str(delta);
+        }
+        if let Some(ref deltas) = chunk.delta_tool_calls {
+            tool_calls = ToolCall::merge_deltas(tool_calls, deltas);
+        }
+        if chunk.finish_reason.is_some() {
+            finish_reason = chunk.finish_reason;
+        }
+        if let Some(ref chunk_usage) = chunk.usage {
+            usage = Some(merge_usage(usage, chunk_usage));
+        }
+
+        last_chunk = Some(chunk);
+    }
+
+    let last_chunk = last_chunk.ok_or_else(|| {
+        ClientError::InvalidResponse("Stream ended without any chunks".to_string())
+    })?;
+
+    let mut builder = MessageBuilder::new(conversation_id, role.unwrap_or(MessageRole::Assistant));
+    builder.set_content(content);
+    for mut tool_call in tool_calls {
+        tool_call.index = None;
+        builder.push_tool_call(tool_call);
+    }
+    if let Some(reasoning) = reasoning {
+        builder.append_reasoning(&reasoning, "");
+    }
+
+    Ok(ChatResponse {
+        message: builder.build(),
+        model: last_chunk.model,
+        usage,
+        finish_reason,
+        created_at: last_chunk.created_at,
+        response_id: last_chunk.response_id,
+        metadata: HashMap::new(),
+        additional_choices: Vec::new(),
+    })
+}
+
+/// Merge a chunk's usage into the running total.
+fn merge_usage(acc: Option<Usage>, chunk: &Usage) -> Usage {
+    let Some(mut acc) = acc else {
+        return chunk.clone();
+    };
+    acc.prompt_tokens = acc.prompt_tokens.max(chunk.prompt_tokens);
+    acc.completion_tokens = acc.completion_tokens.max(chunk.completion_tokens);
+    acc.total_tokens = acc.prompt_tokens + acc.completion_tokens;
+    if acc.input_tokens_details.is_none() {
+        acc.input_tokens_details
+            .clone_from(&chunk.input_tokens_details);
+    }
+    if acc.output_tokens_details.is_none() {
+        acc.output_tokens_details
+            .clone_from(&chunk.output_tokens_details);
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use chrono::Utc;
+    use futures::stream;
+    use neuromance_common::client::{ChatChunk, FinishReason};
+
+    use super::*;
+
+    fn chunk() -> ChatChunk {
+        ChatChunk {
+            model: "m".to_string(),
+            delta_content: None,
+            delta_reasoning_content: None,
+            delta_role: None,
+            delta_tool_calls: None,
+            finish_reason: None,
+            usage: None,
+            response_id: Some("r".to_string()),
+            created_at: Utc::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn usage(prompt: u32, completion: u32) -> Usage {
+        Usage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+            cost: None,
+            input_tokens_details: None,
+            output_tokens_details: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn concatenates_content_and_keeps_last_finish_reason_and_max_usage() {
+        let chunks = vec![
+            Ok(ChatChunk {
+                delta_role: Some(MessageRole::Assistant),
+                delta_content: Some("Hel".to_string()),
+                usage: Some(usage(7, 1)),
+                ..chunk()
+            }),
+            Ok(ChatChunk {
+                delta_content: Some("lo".to_string()),
+                delta_reasoning_content: Some("think".to_string()),
+                finish_reason: Some(FinishReason::Stop),
+                ..chunk()
+            }),
+            Ok(ChatChunk {
+                usage: Some(usage(7, 5)),
+                ..chunk()
+            }),
+        ];
+
+        let response = collect_stream(Box::pin(stream::iter(chunks)), Uuid::new_v4())
+            .await
+            .unwrap();
+
+        assert_eq!(response.message.content, "Hello");
+        assert_eq!(response.message.role, MessageRole::Assistant);
+        assert_eq!(response.message.reasoning.unwrap().content, "think");
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 7);
+        assert_eq!(usage.completion_tokens, 5);
+        assert_eq!(usage.total_tokens, 12);
+    }
+
+    #[tokio::test]
+    async fn empty_stream_is_an_invalid_response() {
+        let result = collect_stream(Box::pin(stream::iter(Vec::new())), Uuid::new_v4()).await;
+        assert!(matches!(result, Err(ClientError::InvalidResponse(_))));
+    }
+
+    #[tokio::test]
+    async fn stream_errors_propagate() {
+        let chunks = vec![
+            Ok(chunk()),
+            Err(ClientError::InvalidResponse("boom".to_string())),
+        ];
+        let result = collect_stream(Box::pin(stream::iter(chunks)), Uuid::new_v4()).await;
+        assert!(matches!(result, Err(ClientError::InvalidResponse(m)) if m == "boom"));
+    }
+}
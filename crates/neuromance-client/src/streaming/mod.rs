@@ -1,11 +1,20 @@
-//! Shared streaming infrastructure for SSE-based provider clients.
+//! Shared streaming infrastructure for provider clients.
 //!
 //! Each provider's wire protocol differs (event types, sentinels, accumulator
 //! state) but the SSE plumbing — connection setup, retry-policy disablement,
 //! `Event::Open` skipping, JSON parsing, stream-end detection, HTTP-status
 //! error extraction — is identical. This module factors that plumbing out
-//! behind the [`StreamingProvider`] trait.
+//! behind the [`StreamingProvider`] trait, and [`collect_stream`] folds the
+//! resulting chunks back into a single response for callers that want one.
+//! Providers that stream newline-delimited JSON instead of SSE plug the same
+//! trait into [`run_ndjson_stream`].
 
+mod collect;
+mod ndjson;
 mod sse;
+mod stop;
 
-pub use sse::{StreamingProvider, run_sse_stream};
+pub use collect::collect_stream;
+pub use ndjson::run_ndjson_stream;
+pub use sse::{ChatChunkStream, StreamingProvider, run_sse_stream};
+pub use stop::{enforce_stop_sequences, truncate_at_stop};
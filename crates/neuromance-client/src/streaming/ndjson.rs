@@ -0,0 +1,205 @@
+//! Newline-delimited JSON stream driver.
+//!
+//! Some providers (notably Ollama's native API) stream one JSON object per
+//! line instead of SSE. [`run_ndjson_stream`] drives such a response through
+//! the same [`StreamingProvider`] contract as [`super::run_sse_stream`]: each
+//! non-empty line is parsed as a [`StreamingProvider::Event`].
+
+use futures::StreamExt;
+use tracing::{debug, error, warn};
+
+use super::{ChatChunkStream, StreamingProvider};
+use crate::error::ClientError;
+use crate::transport::map_http_error;
+
+/// Send `request` and stream its newline-delimited JSON body through a
+/// [`StreamingProvider`], yielding a stream of chunks.
+///
+/// Behaviour:
+/// - Lines may be split across network reads; they are buffered until the
+///   newline arrives. A final line without a trailing newline is still parsed.
+/// - Blank lines are skipped.
+/// - Provider stream-end sentinels (via [`StreamingProvider::is_stream_end`])
+///   terminate the stream cleanly.
+/// - JSON parse failures yield [`ClientError::SerializationError`] but do
+///   not terminate the stream — subsequent valid lines still flow.
+/// - Body read errors yield [`ClientError::NetworkError`] and terminate.
+///
+/// # Errors
+///
+/// Unlike the SSE driver, the request is sent before the stream is returned,
+/// so transport failures surface as [`ClientError::NetworkError`] and HTTP
+/// error statuses as the variant chosen by [`map_http_error`].
+pub async fn run_ndjson_stream<P: StreamingProvider + Sync>(
+    provider: &P,
+    request: reqwest::RequestBuilder,
+) -> Result<ChatChunkStream, ClientError> {
+    let response = request.send().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let error_text = response.text().await.unwrap_or_default();
+        let error = map_http_error(status, &headers, &error_text);
+        error!("API error: {error}");
+        return Err(error);
+    }
+
+    let stream = futures::stream::unfold(
+        LineState {
+            bytes: Box::pin(response.bytes_stream().fuse()),
+            buffer: Vec::new(),
+            provider_state: provider.initial_state(),
+            terminated: false,
+        },
+        |mut s| async move {
+            loop {
+                let line = if let Some(pos) = s.buffer.iter().position(|&b| b == b'\n') {
+                    s.buffer.drain(..=pos).collect::<Vec<u8>>()
+                } else if s.terminated {
+                    return None;
+                } else {
+                    match s.bytes.next().await {
+                        Some(Ok(bytes)) => {
+                            s.buffer.extend_from_slice(&bytes);
+                            continue;
+                        }
+                        Some(Err(e)) => {
+                            let error = ClientError::NetworkError(e);
+                            error!("Stream error: {error}");
+                            s.terminated = true;
+                            s.buffer.clear();
+                            return Some((Err(error), s));
+                        }
+                        None => {
+                            s.terminated = true;
+                            std::mem::take(&mut s.buffer)
+                        }
+                    }
+                };
+
+                let line = String::from_utf8_lossy(&line);
+                let data = line.trim();
+                if data.is_empty() {
+                    continue;
+                }
+                if P::is_stream_end(data) {
+                    debug!("Stream completed via provider sentinel");
+                    return None;
+                }
+                match serde_json::from_str::<P::Event>(data) {
+                    Ok(event) => {
+                        if let Some(item) = P::process_event(&mut s.provider_state, event) {
+                            return Some((item, s));
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse streaming line: {e}");
+                        debug!("Problematic line: {data}");
+                        return Some((Err(ClientError::SerializationError(e)), s));
+                    }
+                }
+            }
+        },
+    );
+
+    Ok(Box::pin(stream))
+}
+
+/// Internal carrier for state threaded through the unfold closure.
+struct LineState<B, S> {
+    bytes: B,
+    buffer: Vec<u8>,
+    provider_state: S,
+    terminated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use serde::Deserialize;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use neuromance_common::client::ChatChunk;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct TestEvent {
+        text: Option<String>,
+    }
+
+    struct TestProvider;
+
+    impl StreamingProvider for TestProvider {
+        type Event = TestEvent;
+        type State = ();
+
+        fn initial_state(&self) -> Self::State {}
+
+        fn process_event(
+            _state: &mut Self::State,
+            event: Self::Event,
+        ) -> Option<Result<ChatChunk, ClientError>> {
+            let text = event.text?;
+            Some(Ok(ChatChunk {
+                model: "m".to_string(),
+                delta_content: Some(text),
+                delta_reasoning_content: None,
+                delta_role: None,
+                delta_tool_calls: None,
+                finish_reason: None,
+                usage: None,
+                response_id: None,
+                created_at: Utc::now(),
+                metadata: HashMap::new(),
+            }))
+        }
+    }
+
+    async fn run(body: &str, status: u16) -> Result<Vec<Result<String, String>>, ClientError> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/stream"))
+            .respond_with(ResponseTemplate::new(status).set_body_string(body.to_string()))
+            .mount(&server)
+            .await;
+
+        let request = reqwest::Client::new().post(format!("{}/stream", server.uri()));
+        let stream = run_ndjson_stream(&TestProvider, request).await?;
+        Ok(stream
+            .map(|item| {
+                item.map(|c| c.delta_content.unwrap_or_default())
+                    .map_err(|e| e.to_string())
+            })
+            .collect()
+            .await)
+    }
+
+    #[tokio::test]
+    async fn yields_one_chunk_per_line_and_flushes_unterminated_tail() {
+        let body = "{\"text\":\"a\"}\n\n{}\n{\"text\":\"b\"}\n{\"text\":\"c\"}";
+        let items = run(body, 200).await.unwrap();
+        let texts: Vec<String> = items.into_iter().map(Result::unwrap).collect();
+        assert_eq!(texts, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn parse_failure_emits_error_but_stream_continues() {
+        let items = run("not json\n{\"text\":\"ok\"}\n", 200).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items[0].as_ref().unwrap_err().contains("Serialization"));
+        assert_eq!(items[1].as_deref(), Ok("ok"));
+    }
+
+    #[tokio::test]
+    async fn http_error_status_is_returned_up_front() {
+        let result = run(r#"{"error":"model not found"}"#, 404).await;
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+    }
+}
@@ -11,7 +11,7 @@ use std::pin::Pin;
 use futures::{Stream, StreamExt};
 use reqwest_eventsource::{Event, EventSource};
 use serde::de::DeserializeOwned;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, trace, warn};
 
 use neuromance_common::client::ChatChunk;
 
@@ -29,6 +29,7 @@ pub type ChatChunkStream = Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientErr
 /// - per-stream accumulator state ([`Self::State`]; use `()` if none)
 /// - the seed for that state ([`Self::initial_state`]; can read provider config)
 /// - any stream-end sentinel ([`Self::is_stream_end`]; e.g. `OpenAI`'s `[DONE]`)
+/// - any keepalive frames to drop unparsed ([`Self::is_keepalive`])
 /// - the event-to-chunk translation ([`Self::process_event`])
 ///
 /// The driver ([`run_sse_stream`]) handles connection setup, retry-policy
@@ -58,6 +59,16 @@ pub trait StreamingProvider {
         false
     }
 
+    /// True if `data` is a keepalive or otherwise empty frame that carries
+    /// no event and should be skipped without being parsed.
+    ///
+    /// Default returns `false`. Providers whose servers (or the gateways in
+    /// front of them) emit blank `data:` lines or ping payloads override it
+    /// so those frames don't surface as parse errors.
+    fn is_keepalive(_data: &str) -> bool {
+        false
+    }
+
     /// Translate one provider event into a stream item.
     ///
     /// - `None` skips emission — for events that update accumulator state
@@ -83,6 +94,8 @@ pub trait StreamingProvider {
 ///
 /// Behaviour:
 /// - `EventSource::Open` events are logged and skipped.
+/// - Provider keepalive frames (via [`StreamingProvider::is_keepalive`]) are
+///   skipped before parsing.
 /// - Provider stream-end sentinels (via [`StreamingProvider::is_stream_end`])
 ///   terminate the stream cleanly.
 /// - JSON parse failures yield [`ClientError::SerializationError`] but do
@@ -123,6 +136,10 @@ pub fn run_sse_stream<P: StreamingProvider>(
                         debug!("Stream connection opened");
                     }
                     Some(Ok(Event::Message(message))) => {
+                        if P::is_keepalive(&message.data) {
+                            trace!("Skipping keepalive frame");
+                            continue;
+                        }
                         if P::is_stream_end(&message.data) {
                             debug!("Stream completed via provider sentinel");
                             return None;
@@ -173,15 +190,16 @@ struct StreamState<S> {
 
 /// Extract a typed [`ClientError`] from an HTTP error response.
 ///
-/// Reads the body text and delegates the status/body-to-error mapping to
+/// Reads the body text and delegates the status/headers/body-to-error mapping to
 /// [`crate::transport::map_http_error`], the single canonical mapping shared
 /// with the non-streaming request path.
 async fn extract_error_from_response(
     status: reqwest::StatusCode,
     response: reqwest::Response,
 ) -> ClientError {
+    let headers = response.headers().clone();
     let error_text = response.text().await.unwrap_or_default();
-    crate::transport::map_http_error(status, &error_text)
+    crate::transport::map_http_error(status, &headers, &error_text)
 }
 
 #[cfg(test)]
@@ -425,7 +443,11 @@ mod tests {
         let server = MockServer::start().await;
         Mock::given(method("POST"))
             .and(path("/stream"))
-            .respond_with(ResponseTemplate::new(429).set_body_string(""))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("retry-after", "2")
+                    .set_body_string(""),
+            )
             .mount(&server)
             .await;
 
@@ -435,7 +457,7 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(matches!(
             &results[0],
-            Err(ClientError::RateLimitError { .. })
+            Err(ClientError::RateLimitError { retry_after: Some(d) }) if d.as_secs() == 2
         ));
     }
 
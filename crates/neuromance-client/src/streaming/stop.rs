@@ -0,0 +1,201 @@
+//! Client-side stop-sequence enforcement.
+//!
+//! Some OpenAI-compatible servers accept `stop` but keep generating past it.
+//! Clients opted in through `Config::enforce_stop_client_side` cut the content
+//! at the first stop sequence themselves: [`truncate_at_stop`] for a complete
+//! message, [`enforce_stop_sequences`] for a stream.
+
+use chrono::Utc;
+use futures::StreamExt;
+
+use neuromance_common::client::{ChatChunk, FinishReason};
+
+use super::ChatChunkStream;
+
+/// Byte offset of the earliest stop sequence in `text`, if any occurs.
+fn find_stop(text: &str, stops: &[String]) -> Option<usize> {
+    stops
+        .iter()
+        .filter(|stop| !stop.is_empty())
+        .filter_map(|stop| text.find(stop.as_str()))
+        .min()
+}
+
+/// Length of the longest suffix of `text` that could still grow into a stop
+/// sequence, i.e. that is a proper prefix of one.
+fn partial_stop_len(text: &str, stops: &[String]) -> usize {
+    stops
+        .iter()
+        .filter_map(|stop| {
+            (1..stop.len())
+                .rev()
+                .filter(|&n| stop.is_char_boundary(n))
+                .find(|&n| text.ends_with(&stop[..n]))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Cuts `content` at the first stop sequence, returning whether it was cut.
+pub fn truncate_at_stop(content: &mut String, stops: &[String]) -> bool {
+    find_stop(content, stops).is_some_and(|at| {
+        content.truncate(at);
+        true
+    })
+}
+
+struct StopState {
+    inner: ChatChunkStream,
+    stops: Vec<String>,
+    /// Content received but not yet emitted because it may begin a stop
+    /// sequence that the next delta completes.
+    pending: String,
+    stopped: bool,
+    model: String,
+    response_id: Option<String>,
+}
+
+/// Wraps `stream` so content ends before the first stop sequence.
+///
+/// Text that might be the start of a stop sequence is held back until the
+/// next delta settles it. Once a stop sequence is seen the chunk carrying it
+/// finishes with [`FinishReason::Stop`]; later chunks are dropped, except for
+/// any usage they report, which is passed on with their deltas removed.
+pub fn enforce_stop_sequences(stream: ChatChunkStream, stops: Vec<String>) -> ChatChunkStream {
+    let state = StopState {
+        inner: stream,
+        stops,
+        pending: String::new(),
+        stopped: false,
+        model: String::new(),
+        response_id: None,
+    };
+    Box::pin(futures::stream::unfold(state, |mut state| async move {
+        loop {
+            let Some(item) = state.inner.next().await else {
+                // Flush held-back text that never became a stop sequence.
+                if state.stopped || state.pending.is_empty() {
+                    return None;
+                }
+                let chunk = ChatChunk {
+                    model: state.model.clone(),
+                    delta_content: Some(std::mem::take(&mut state.pending)),
+                    delta_reasoning_content: None,
+                    delta_role: None,
+                    delta_tool_calls: None,
+                    finish_reason: None,
+                    usage: None,
+                    response_id: state.response_id.clone(),
+                    created_at: Utc::now(),
+                    metadata: std::collections::HashMap::new(),
+                };
+                return Some((Ok(chunk), state));
+            };
+            let mut chunk = match item {
+                Ok(chunk) => chunk,
+                Err(e) => return Some((Err(e), state)),
+            };
+            state.model.clone_from(&chunk.model);
+            state.response_id.clone_from(&chunk.response_id);
+
+            if state.stopped {
+                if chunk.usage.is_none() {
+                    continue;
+                }
+                chunk.delta_content = None;
+                chunk.delta_reasoning_content = None;
+                chunk.delta_tool_calls = None;
+                chunk.finish_reason = None;
+                return Some((Ok(chunk), state));
+            }
+
+            if let Some(delta) = chunk.delta_content.take() {
+                state.pending.push_str(&delta);
+            }
+            if let Some(at) = find_stop(&state.pending, &state.stops) {
+                state.pending.truncate(at);
+                state.stopped = true;
+                chunk.finish_reason = Some(FinishReason::Stop);
+                chunk.delta_tool_calls = None;
+            }
+            let keep = if state.stopped || chunk.finish_reason.is_some() {
+                0
+            } else {
+                partial_stop_len(&state.pending, &state.stops)
+            };
+            let ready = state.pending.split_off(state.pending.len() - keep);
+            let ready = std::mem::replace(&mut state.pending, ready);
+            if !ready.is_empty() {
+                chunk.delta_content = Some(ready);
+            }
+            return Some((Ok(chunk), state));
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn stops() -> Vec<String> {
+        vec!["END".to_string()]
+    }
+
+    fn content_chunk(content: &str) -> ChatChunk {
+        ChatChunk {
+            model: "m".to_string(),
+            delta_content: Some(content.to_string()),
+            delta_reasoning_content: None,
+            delta_role: None,
+            delta_tool_calls: None,
+            finish_reason: None,
+            usage: None,
+            response_id: None,
+            created_at: Utc::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    async fn run(deltas: &[&str]) -> (String, Option<FinishReason>) {
+        let chunks: Vec<_> = deltas.iter().map(|d| Ok(content_chunk(d))).collect();
+        let mut stream = enforce_stop_sequences(Box::pin(futures::stream::iter(chunks)), stops());
+        let mut content = String::new();
+        let mut finish = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            content.push_str(chunk.delta_content.as_deref().unwrap_or_default());
+            finish = finish.or(chunk.finish_reason);
+        }
+        (content, finish)
+    }
+
+    #[test]
+    fn test_truncate_at_stop_uses_earliest_sequence() {
+        let stops = vec!["\n\n".to_string(), "END".to_string()];
+        let mut content = "answer END more\n\n".to_string();
+        assert!(truncate_at_stop(&mut content, &stops));
+        assert_eq!(content, "answer ");
+
+        let mut untouched = "no stop here".to_string();
+        assert!(!truncate_at_stop(&mut untouched, &stops));
+        assert_eq!(untouched, "no stop here");
+    }
+
+    #[tokio::test]
+    async fn test_stream_stops_at_sequence_split_across_chunks() {
+        let (content, finish) = run(&["Hello E", "ND and ", "more"]).await;
+        assert_eq!(content, "Hello ");
+        assert_eq!(finish, Some(FinishReason::Stop));
+    }
+
+    #[tokio::test]
+    async fn test_stream_flushes_held_back_text_without_stop() {
+        let (content, finish) = run(&["Hello E", "N"]).await;
+        assert_eq!(content, "Hello EN");
+        assert_eq!(finish, None);
+    }
+}
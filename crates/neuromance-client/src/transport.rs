@@ -1,4 +1,9 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
 use neuromance_common::client::ProxyConfig;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
 use secrecy::{ExposeSecret, SecretString};
 use serde::de::DeserializeOwned;
 use tracing::{error, trace, warn};
@@ -46,15 +51,45 @@ pub fn add_proxy_headers<B: WithHeader>(
     }
 }
 
-/// Map an HTTP error response (status + body) to a typed [`ClientError`].
+/// Adds the configured extra headers to a request.
+///
+/// A header whose name matches one in `reserved` or the proxy token header
+/// (case-insensitively) is skipped with a warning, so a gateway header can
+/// never replace the client's own authentication or content type.
+pub fn add_extra_headers<B: WithHeader>(
+    builder: B,
+    extra_headers: &HashMap<String, String>,
+    reserved: &[&str],
+    proxy_config: Option<&ProxyConfig>,
+) -> B {
+    let proxy_header = proxy_config.map(|proxy| proxy.token_header.as_str());
+    extra_headers
+        .iter()
+        .fold(builder, |builder, (name, value)| {
+            if reserved
+                .iter()
+                .copied()
+                .chain(proxy_header)
+                .any(|r| r.eq_ignore_ascii_case(name))
+            {
+                warn!(header = %name, "ignoring extra header that would override a client header");
+                builder
+            } else {
+                builder.header(name, value)
+            }
+        })
+}
+
+/// Map an HTTP error response (status + headers + body) to a typed [`ClientError`].
 ///
 /// Tries to parse `body` as a structured [`ErrorResponse`], falling back to the
 /// raw body text, then to `HTTP {status}` when the body is empty. The status
-/// code selects the variant. This is the single canonical mapping shared by the
+/// code selects the variant, and a `429` carries the `Retry-After` header as
+/// its `retry_after`. This is the single canonical mapping shared by the
 /// streaming ([`crate::streaming`]) and non-streaming ([`send_json`]) paths, so
 /// both agree that any `5xx` is a retryable [`ClientError::ServiceUnavailable`].
 #[must_use]
-pub fn map_http_error(status: reqwest::StatusCode, body: &str) -> ClientError {
+pub fn map_http_error(status: reqwest::StatusCode, headers: &HeaderMap, body: &str) -> ClientError {
     let message = match serde_json::from_str::<ErrorResponse>(body) {
         Ok(parsed) => parsed.error.message,
         Err(_) if body.is_empty() => format!("HTTP {status}"),
@@ -63,12 +98,84 @@ pub fn map_http_error(status: reqwest::StatusCode, body: &str) -> ClientError {
 
     match status.as_u16() {
         401 => ClientError::AuthenticationError(message),
-        429 => ClientError::RateLimitError { retry_after: None },
+        429 => ClientError::RateLimitError {
+            retry_after: parse_retry_after(headers, Utc::now())
+                .or_else(|| parse_rate_limit_reset(headers)),
+        },
         500..=599 => ClientError::ServiceUnavailable(message),
         _ => ClientError::RequestError(message),
     }
 }
 
+/// Parse a `Retry-After` header as either delay-seconds or an HTTP-date.
+///
+/// Dates are measured from `now`; a date already in the past yields a zero
+/// delay. Returns `None` when the header is absent or malformed.
+fn parse_retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Delay until the exhausted rate-limit budget resets, read from the
+/// `x-ratelimit-reset-requests` and `x-ratelimit-reset-tokens` headers that
+/// OpenAI-compatible servers such as Groq send with a 429.
+///
+/// Prefers the budget whose `x-ratelimit-remaining-*` header is zero; if that
+/// does not single one out, the later reset wins.
+fn parse_rate_limit_reset(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: String| headers.get(name)?.to_str().ok();
+    let reset =
+        |kind: &str| header(format!("x-ratelimit-reset-{kind}")).and_then(parse_reset_duration);
+    let exhausted = |kind: &str| {
+        header(format!("x-ratelimit-remaining-{kind}")).is_some_and(|v| v.trim() == "0")
+    };
+
+    let (requests, tokens) = (reset("requests"), reset("tokens"));
+    match (exhausted("requests"), exhausted("tokens")) {
+        (true, false) => requests.or(tokens),
+        (false, true) => tokens.or(requests),
+        _ => requests.max(tokens),
+    }
+}
+
+/// Parse a Go-style duration such as `"2m59.56s"`, `"7.66s"`, or `"120ms"`.
+/// A bare number is read as seconds.
+fn parse_reset_duration(value: &str) -> Option<Duration> {
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    let mut seconds = 0.0_f64;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(number_len);
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let scale = match unit {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" | "" => 1.0,
+            "ms" => 0.001,
+            _ => return None,
+        };
+        seconds = number.parse::<f64>().ok()?.mul_add(scale, seconds);
+        rest = tail;
+    }
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
 /// Send a fully-built request and deserialize its JSON success body into `T`.
 ///
 /// Owns the shared non-streaming transport tail: send, HTTP-status error mapping
@@ -90,11 +197,12 @@ pub async fn send_json<T: DeserializeOwned>(
 
     if !response.status().is_success() {
         let status = response.status();
+        let headers = response.headers().clone();
         let error_text = response.text().await.map_err(|e| {
             warn!("Failed to read error response body: {e}");
             ClientError::NetworkError(e)
         })?;
-        let error = map_http_error(status, &error_text);
+        let error = map_http_error(status, &headers, &error_text);
         error!(
             "API request failed with status {}: {error}",
             status.as_u16()
@@ -114,11 +222,39 @@ mod tests {
 
     use super::*;
     use reqwest::StatusCode;
+    use reqwest::header::HeaderValue;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn retry_after_headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_str(value).expect("valid header"),
+        );
+        headers
+    }
+
+    /// Send a request through a middleware client with no retry layers, so
+    /// the 429 reaches `send_json` on the first attempt.
+    async fn send_to_429(retry_after: &str) -> ClientError {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", retry_after))
+            .mount(&server)
+            .await;
+
+        let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+        send_json::<serde_json::Value>(client.post(server.uri()))
+            .await
+            .expect_err("429 should fail")
+    }
 
     #[test]
     fn structured_body_uses_error_message() {
         let err = map_http_error(
             StatusCode::BAD_REQUEST,
+            &HeaderMap::new(),
             r#"{"error":{"message":"bad tool schema"}}"#,
         );
         assert!(matches!(err, ClientError::RequestError(m) if m == "bad tool schema"));
@@ -126,25 +262,29 @@ mod tests {
 
     #[test]
     fn non_json_body_is_used_verbatim() {
-        let err = map_http_error(StatusCode::BAD_REQUEST, "upstream exploded");
+        let err = map_http_error(
+            StatusCode::BAD_REQUEST,
+            &HeaderMap::new(),
+            "upstream exploded",
+        );
         assert!(matches!(err, ClientError::RequestError(m) if m == "upstream exploded"));
     }
 
     #[test]
     fn empty_body_falls_back_to_status_line() {
-        let err = map_http_error(StatusCode::BAD_REQUEST, "");
+        let err = map_http_error(StatusCode::BAD_REQUEST, &HeaderMap::new(), "");
         assert!(matches!(err, ClientError::RequestError(m) if m == "HTTP 400 Bad Request"));
     }
 
     #[test]
     fn maps_401_to_authentication_error() {
-        let err = map_http_error(StatusCode::UNAUTHORIZED, "");
+        let err = map_http_error(StatusCode::UNAUTHORIZED, &HeaderMap::new(), "");
         assert!(matches!(err, ClientError::AuthenticationError(_)));
     }
 
     #[test]
     fn maps_429_to_rate_limit_error() {
-        let err = map_http_error(StatusCode::TOO_MANY_REQUESTS, "");
+        let err = map_http_error(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), "");
         assert!(matches!(
             err,
             ClientError::RateLimitError { retry_after: None }
@@ -155,7 +295,7 @@ mod tests {
     fn maps_all_5xx_to_service_unavailable() {
         for code in [500u16, 503, 529, 599] {
             let status = StatusCode::from_u16(code).expect("valid status");
-            let err = map_http_error(status, "overloaded");
+            let err = map_http_error(status, &HeaderMap::new(), "overloaded");
             assert!(
                 matches!(err, ClientError::ServiceUnavailable(_)),
                 "status {code} should map to ServiceUnavailable"
@@ -168,11 +308,96 @@ mod tests {
     fn maps_other_4xx_to_request_error() {
         for code in [400u16, 403, 418] {
             let status = StatusCode::from_u16(code).expect("valid status");
-            let err = map_http_error(status, "nope");
+            let err = map_http_error(status, &HeaderMap::new(), "nope");
             assert!(
                 matches!(err, ClientError::RequestError(_)),
                 "status {code} should map to RequestError"
             );
         }
     }
+
+    #[test]
+    fn retry_after_parses_delay_seconds() {
+        let headers = retry_after_headers("120");
+        assert_eq!(
+            parse_retry_after(&headers, Utc::now()),
+            Some(Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn rate_limit_reset_headers_fill_missing_retry_after() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-ratelimit-remaining-requests", "14"),
+            ("x-ratelimit-reset-requests", "2m59.56s"),
+            ("x-ratelimit-remaining-tokens", "0"),
+            ("x-ratelimit-reset-tokens", "7.66s"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+        let err = map_http_error(StatusCode::TOO_MANY_REQUESTS, &headers, "");
+        assert!(matches!(
+            err,
+            ClientError::RateLimitError { retry_after: Some(d) } if d == Duration::from_millis(7660)
+        ));
+
+        // Without a remaining count to go on, the later reset wins.
+        headers.remove("x-ratelimit-remaining-tokens");
+        headers.remove("x-ratelimit-remaining-requests");
+        assert_eq!(
+            parse_rate_limit_reset(&headers),
+            Some(Duration::from_millis(179_560))
+        );
+        assert_eq!(parse_reset_duration("1h2m"), Some(Duration::from_mins(62)));
+        assert_eq!(
+            parse_reset_duration("250ms"),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(parse_reset_duration("soon"), None);
+    }
+
+    #[test]
+    fn retry_after_parses_http_date() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .expect("valid date")
+            .with_timezone(&Utc);
+        let headers = retry_after_headers("Wed, 21 Oct 2015 07:28:30 GMT");
+        assert_eq!(
+            parse_retry_after(&headers, now),
+            Some(Duration::from_secs(30))
+        );
+
+        // A date already in the past means "retry now".
+        let headers = retry_after_headers("Wed, 21 Oct 2015 07:27:00 GMT");
+        assert_eq!(parse_retry_after(&headers, now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn retry_after_ignores_missing_or_malformed_header() {
+        assert_eq!(parse_retry_after(&HeaderMap::new(), Utc::now()), None);
+        let headers = retry_after_headers("soon");
+        assert_eq!(parse_retry_after(&headers, Utc::now()), None);
+    }
+
+    #[tokio::test]
+    async fn send_json_429_carries_retry_after_seconds() {
+        let err = send_to_429("7").await;
+        assert!(err.is_rate_limit_error());
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(7)));
+    }
+
+    #[tokio::test]
+    async fn send_json_429_carries_retry_after_http_date() {
+        let date = (Utc::now() + chrono::Duration::seconds(60))
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+        let err = send_to_429(&date).await;
+        let retry_after = err.retry_after().expect("retry_after should be set");
+        // Allow for the second-level precision of the header and test latency.
+        assert!(
+            retry_after > Duration::from_secs(55) && retry_after <= Duration::from_secs(60),
+            "unexpected retry_after {retry_after:?}"
+        );
+    }
 }
@@ -45,6 +45,19 @@
 //! - **Long-term**: Persistent knowledge (key-value string storage)
 //! - **Working memory**: Arbitrary JSON data for active processing
 //!
+//! Working memory has typed accessors, so agent steps can stash structured state
+//! between turns:
+//!
+//! ```
+//! use neuromance_common::agents::AgentMemory;
+//!
+//! let mut memory = AgentMemory::default();
+//! memory.remember("visited", &vec!["docs.rs", "crates.io"]).unwrap();
+//!
+//! let visited: Option<Vec<String>> = memory.recall("visited");
+//! assert_eq!(visited.unwrap().len(), 2);
+//! ```
+//!
 //! ## Context Updates
 //!
 //! The [`ContextUpdate`] enum allows dynamic modification of agent context:
@@ -63,7 +76,9 @@
 
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::time::Duration;
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::chat::Message;
@@ -147,6 +162,15 @@ pub struct AgentStats {
     pub successful_tool_calls: usize,
     /// Number of failed tool executions.
     pub failed_tool_calls: usize,
+    /// Total tool executions, successful or not.
+    #[serde(default)]
+    pub tool_call_count: usize,
+    /// Number of completed agent runs (one per `execute` call).
+    #[serde(default)]
+    pub turn_count: u32,
+    /// Cumulative wall-clock time spent in completed runs.
+    #[serde(default)]
+    pub total_duration: Duration,
 }
 
 /// Context describing the agent's current task and environment.
@@ -241,3 +265,80 @@ pub struct AgentMemory {
     /// Arbitrary JSON data for active processing.
     pub working_memory: HashMap<String, serde_json::Value>,
 }
+
+impl AgentMemory {
+    /// Stores `value` in working memory under `key`, replacing any previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` cannot be serialized to JSON.
+    pub fn remember<T: Serialize + ?Sized>(
+        &mut self,
+        key: impl Into<String>,
+        value: &T,
+    ) -> Result<(), serde_json::Error> {
+        self.working_memory
+            .insert(key.into(), serde_json::to_value(value)?);
+        Ok(())
+    }
+
+    /// Reads the working-memory value under `key` as a `T`.
+    ///
+    /// Returns `None` if the key is absent or the stored value does not
+    /// deserialize as `T`.
+    #[must_use]
+    pub fn recall<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.working_memory
+            .get(key)
+            .and_then(|value| T::deserialize(value).ok())
+    }
+
+    /// Removes `key` from working memory, returning its raw value if present.
+    pub fn forget(&mut self, key: &str) -> Option<serde_json::Value> {
+        self.working_memory.remove(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct SearchProgress {
+        query: String,
+        pages_read: u32,
+        sources: Vec<String>,
+        best_score: Option<f64>,
+    }
+
+    #[test]
+    fn test_working_memory_recalls_typed_values() {
+        let mut memory = AgentMemory::default();
+        let progress = SearchProgress {
+            query: "tokio runtime".to_string(),
+            pages_read: 3,
+            sources: vec!["docs.rs".to_string(), "tokio.rs".to_string()],
+            best_score: Some(0.875),
+        };
+
+        memory.remember("progress", &progress).unwrap();
+        assert_eq!(memory.recall::<SearchProgress>("progress"), Some(progress));
+        assert_eq!(memory.recall::<SearchProgress>("missing"), None);
+
+        assert!(memory.forget("progress").is_some());
+        assert_eq!(memory.recall::<SearchProgress>("progress"), None);
+        assert!(memory.forget("progress").is_none());
+    }
+
+    #[test]
+    fn test_working_memory_type_mismatch_recalls_none() {
+        let mut memory = AgentMemory::default();
+        memory.remember("pages_read", &3_u32).unwrap();
+
+        assert_eq!(memory.recall::<u32>("pages_read"), Some(3));
+        assert_eq!(memory.recall::<String>("pages_read"), None);
+        assert_eq!(memory.recall::<SearchProgress>("pages_read"), None);
+    }
+}
@@ -10,6 +10,11 @@
 //! - [`Conversation`]: A thread of messages with lifecycle management and metadata
 //! - [`MessageRole`]: Enum for message roles (system, user, assistant, tool)
 //! - [`ConversationStatus`]: Enum for conversation lifecycle states
+//! - [`MessageQuery`]: Filter for [`Conversation::search`]
+//! - [`Turn`]: One user exchange, from [`Conversation::turns`]
+//! - [`NormalizeReport`]: What [`Conversation::normalize`] changed
+//! - [`TimeSortReport`]: What [`Conversation::sort_messages_by_time`] changed
+//! - [`ConversationDiff`]: What changed between two versions, from [`Conversation::diff`]
 //!
 //! # Example
 //!
@@ -42,10 +47,14 @@
 //! conv.add_message(tool_msg).unwrap();
 //! ```
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
 
+use base64::prelude::*;
 use chrono::{DateTime, Utc};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use typed_builder::TypedBuilder;
@@ -111,6 +120,53 @@ pub enum MessageRole {
     /// Messages containing tool execution results with `tool_call_id` and `name` fields.
     #[serde(rename = "tool")]
     Tool,
+
+    /// Developer instructions, `OpenAI`'s successor to the system role for
+    /// reasoning models.
+    ///
+    /// Providers without a developer role fold these into their system
+    /// instructions.
+    #[serde(rename = "developer")]
+    Developer,
+}
+
+impl MessageRole {
+    /// Returns `true` for roles that carry instructions rather than turns:
+    /// [`System`](Self::System) and [`Developer`](Self::Developer).
+    #[must_use]
+    pub const fn is_instruction(self) -> bool {
+        matches!(self, Self::System | Self::Developer)
+    }
+}
+
+/// Image media types accepted by [`Message::with_image_base64`].
+pub const SUPPORTED_IMAGE_MEDIA_TYPES: [&str; 4] =
+    ["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Metadata key for the wall-clock milliseconds the provider took to answer
+/// (`u64`). Set by the Chat Completions and Anthropic clients.
+pub const LATENCY_MS_METADATA_KEY: &str = "latency_ms";
+
+/// Metadata key for the provider that produced the message (`String`), as
+/// recorded by the client. Set by the Chat Completions and Anthropic clients.
+pub const PROVIDER_METADATA_KEY: &str = "provider";
+
+/// Metadata key for whether the provider served part of the prompt from its
+/// cache (`bool`). Set by the Chat Completions and Anthropic clients when the
+/// response reports usage.
+pub const CACHED_METADATA_KEY: &str = "cached";
+
+/// Non-text content sent alongside a message's text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    /// An inline image.
+    Image {
+        /// Media type, one of [`SUPPORTED_IMAGE_MEDIA_TYPES`].
+        media_type: String,
+        /// Base64-encoded image bytes.
+        data: String,
+    },
 }
 
 /// A single message in a conversation.
@@ -132,6 +188,11 @@ pub struct Message {
     /// The text content of the message.
     pub content: String,
 
+    /// Images and other non-text parts of the message, sent before `content`.
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_parts: Vec<ContentPart>,
+
     /// Application-specific metadata.
     #[builder(default)]
     pub metadata: HashMap<String, serde_json::Value>,
@@ -172,6 +233,14 @@ pub struct Message {
     #[builder(default)]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub usage: Option<Usage>,
+
+    /// Message this one replies to, for conversations that branch.
+    ///
+    /// When `None`, the message follows the one before it in the
+    /// conversation, so linear conversations never need to set it.
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
 }
 
 impl Message {
@@ -182,6 +251,7 @@ impl Message {
             conversation_id,
             role,
             content: content.into(),
+            content_parts: Vec::new(),
             metadata: HashMap::new(),
             timestamp: Utc::now(),
             tool_calls: SmallVec::new(),
@@ -191,6 +261,7 @@ impl Message {
             model: None,
             provider: None,
             usage: None,
+            parent_id: None,
         }
     }
 
@@ -199,6 +270,11 @@ impl Message {
         Self::new(conversation_id, MessageRole::System, content)
     }
 
+    /// Creates a new developer message.
+    pub fn developer(conversation_id: Uuid, content: impl Into<String>) -> Self {
+        Self::new(conversation_id, MessageRole::Developer, content)
+    }
+
     /// Creates a new user message.
     pub fn user(conversation_id: Uuid, content: impl Into<String>) -> Self {
         Self::new(conversation_id, MessageRole::User, content)
@@ -239,6 +315,36 @@ impl Message {
         Ok(msg)
     }
 
+    /// Sets the message this one replies to, starting a branch when the
+    /// parent already has a reply.
+    #[must_use]
+    pub const fn with_parent(mut self, parent_id: Uuid) -> Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
+
+    /// Attaches a base64-encoded image to this message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `media_type` is not one of
+    /// [`SUPPORTED_IMAGE_MEDIA_TYPES`] or `data` is not valid base64.
+    pub fn with_image_base64(mut self, media_type: &str, data: &str) -> anyhow::Result<Self> {
+        if !SUPPORTED_IMAGE_MEDIA_TYPES.contains(&media_type) {
+            anyhow::bail!(
+                "Unsupported image media type {media_type:?}, expected one of {SUPPORTED_IMAGE_MEDIA_TYPES:?}"
+            );
+        }
+        BASE64_STANDARD
+            .decode(data)
+            .map_err(|e| anyhow::anyhow!("Invalid base64 image data: {e}"))?;
+        self.content_parts.push(ContentPart::Image {
+            media_type: media_type.to_string(),
+            data: data.to_string(),
+        });
+        Ok(self)
+    }
+
     /// Adds a metadata key-value pair to this message.
     #[must_use]
     pub fn with_metadata(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
@@ -263,6 +369,44 @@ impl Message {
         Ok(self)
     }
 
+    /// Stores `value` under `key` in this message's metadata, replacing any
+    /// previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn set_meta<T: Serialize>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> anyhow::Result<()> {
+        self.metadata
+            .insert(key.into(), serde_json::to_value(value)?);
+        Ok(())
+    }
+
+    /// Reads the metadata stored under `key` as a `T`.
+    ///
+    /// Returns `None` when the key is missing or holds a value that does not
+    /// deserialize as `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use neuromance_common::chat::{LATENCY_MS_METADATA_KEY, Message};
+    /// use uuid::Uuid;
+    ///
+    /// let mut message = Message::assistant(Uuid::new_v4(), "Hi");
+    /// message.set_meta(LATENCY_MS_METADATA_KEY, 420_u64).unwrap();
+    /// assert_eq!(message.get_meta::<u64>(LATENCY_MS_METADATA_KEY), Some(420));
+    /// ```
+    #[must_use]
+    pub fn get_meta<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.metadata
+            .get(key)
+            .and_then(|value| T::deserialize(value).ok())
+    }
+
     /// Sets the tool calls for this message.
     ///
     /// # Errors
@@ -297,11 +441,82 @@ impl Message {
         self.tool_calls.push(tool_call);
         Ok(())
     }
+
+    /// Returns a copy with every match of `patterns` in `content` and in
+    /// tool-call arguments replaced by [`REDACTED`].
+    ///
+    /// Use before logging a message; [`default_redaction_patterns`] covers
+    /// common secret formats. Arguments are redacted as raw JSON text, so a
+    /// pattern spanning a quote can leave them unparseable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use neuromance_common::chat::{Message, default_redaction_patterns};
+    /// use uuid::Uuid;
+    ///
+    /// let message = Message::user(Uuid::new_v4(), "key is sk-abcdefghijklmnopqrstuv");
+    /// let redacted = message.redact(default_redaction_patterns());
+    /// assert_eq!(redacted.content, "key is [REDACTED]");
+    /// ```
+    #[must_use]
+    pub fn redact(&self, patterns: &[Regex]) -> Self {
+        let mut redacted = self.clone();
+        redacted.content = redact_text(&redacted.content, patterns);
+        for tool_call in &mut redacted.tool_calls {
+            tool_call.function.arguments = redact_text(&tool_call.function.arguments, patterns);
+        }
+        redacted
+    }
+}
+
+/// Replacement text for spans removed by [`Message::redact`].
+pub const REDACTED: &str = "[REDACTED]";
+
+static DEFAULT_REDACTION_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        // OpenAI/Anthropic-style secret keys (`sk-...`, `sk-ant-...`).
+        r"\bsk-[A-Za-z0-9_-]{16,}",
+        // HTTP bearer credentials.
+        r"(?i)\bbearer\s+[A-Za-z0-9._~+/-]+=*",
+    ]
+    .into_iter()
+    .filter_map(|pattern| Regex::new(pattern).ok())
+    .collect()
+});
+
+/// Patterns for common secret formats: `sk-` API keys and bearer tokens.
+#[must_use]
+pub fn default_redaction_patterns() -> &'static [Regex] {
+    &DEFAULT_REDACTION_PATTERNS
+}
+
+/// Returns `text` with every match of `patterns` replaced by [`REDACTED`].
+#[must_use]
+pub fn redact_text(text: &str, patterns: &[Regex]) -> String {
+    patterns.iter().fold(text.to_string(), |text, pattern| {
+        pattern.replace_all(&text, REDACTED).into_owned()
+    })
 }
 
 /// The lifecycle status of a conversation.
 ///
-/// Statuses serialize to lowercase strings: "active", "paused", "archived", "deleted".
+/// Statuses serialize to lowercase strings: "active", "paused", "completed",
+/// "archived", "deleted".
+///
+/// [`Conversation::set_status`] enforces these transitions (setting the
+/// current status again is always allowed):
+///
+/// | From       | Active | Paused | Completed | Archived | Deleted |
+/// |------------|--------|--------|-----------|----------|---------|
+/// | Active     | -      | yes    | yes       | yes      | yes     |
+/// | Paused     | yes    | -      | yes       | yes      | yes     |
+/// | Completed  | reopen | no     | -         | yes      | yes     |
+/// | Archived   | reopen | no     | no        | -        | yes     |
+/// | Deleted    | no     | no     | no        | no       | -       |
+///
+/// "reopen" transitions go through [`Conversation::reopen`]. Only `Active`
+/// and `Paused` conversations accept new messages.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[non_exhaustive]
 pub enum ConversationStatus {
@@ -313,6 +528,10 @@ pub enum ConversationStatus {
     #[serde(rename = "paused")]
     Paused,
 
+    /// The conversation reached its end; reopen it to continue.
+    #[serde(rename = "completed")]
+    Completed,
+
     /// The conversation has been archived.
     #[serde(rename = "archived")]
     Archived,
@@ -322,6 +541,26 @@ pub enum ConversationStatus {
     Deleted,
 }
 
+impl ConversationStatus {
+    /// Whether [`Conversation::set_status`] may move from this status to
+    /// `next`. See the type-level table.
+    #[must_use]
+    pub fn can_transition_to(&self, next: &Self) -> bool {
+        use ConversationStatus::{Active, Archived, Completed, Deleted, Paused};
+        self == next
+            || matches!(
+                (self, next),
+                (Active | Paused, _) | (Completed | Archived, Deleted) | (Completed, Archived)
+            )
+    }
+
+    /// Whether a conversation in this status accepts new messages.
+    #[must_use]
+    pub const fn accepts_messages(&self) -> bool {
+        matches!(self, Self::Active | Self::Paused)
+    }
+}
+
 /// The lifecycle status of a serve-mode task.
 ///
 /// Statuses serialize to lowercase strings: "pending", "running", "succeeded",
@@ -428,9 +667,52 @@ impl Conversation {
     }
 
     /// Changes the status of this conversation and updates the timestamp.
-    pub fn set_status(&mut self, status: ConversationStatus) {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transition is not allowed (see
+    /// [`ConversationStatus`]); the status is left unchanged.
+    pub fn set_status(&mut self, status: ConversationStatus) -> anyhow::Result<()> {
+        if !self.status.can_transition_to(&status) {
+            let hint = if status == ConversationStatus::Active
+                && matches!(
+                    self.status,
+                    ConversationStatus::Completed | ConversationStatus::Archived
+                ) {
+                "; use reopen() instead"
+            } else {
+                ""
+            };
+            anyhow::bail!(
+                "Conversation {} cannot move from {:?} to {status:?}{hint}",
+                self.id,
+                self.status
+            );
+        }
         self.status = status;
-        self.updated_at = Utc::now();
+        self.touch();
+        Ok(())
+    }
+
+    /// Moves a completed or archived conversation back to `Active`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the conversation is not completed or archived.
+    pub fn reopen(&mut self) -> anyhow::Result<()> {
+        if !matches!(
+            self.status,
+            ConversationStatus::Completed | ConversationStatus::Archived
+        ) {
+            anyhow::bail!(
+                "Conversation {} cannot be reopened from {:?}",
+                self.id,
+                self.status
+            );
+        }
+        self.status = ConversationStatus::Active;
+        self.touch();
+        Ok(())
     }
 
     /// Updates the `updated_at` timestamp to the current time.
@@ -442,8 +724,160 @@ impl Conversation {
     ///
     /// # Errors
     ///
-    /// Returns an error if the message's conversation ID doesn't match.
+    /// Returns an error if the conversation does not accept messages in its
+    /// current status, if the message's conversation ID doesn't match, or if
+    /// its `parent_id` names a message not in this conversation.
     pub fn add_message(&mut self, message: Message) -> anyhow::Result<()> {
+        self.ensure_accepts_messages()?;
+        self.check_new_message(&message)?;
+        Arc::make_mut(&mut self.messages).push(message);
+        self.touch();
+        Ok(())
+    }
+
+    /// Inserts a message at `index`, shifting later messages back.
+    ///
+    /// System messages must stay ahead of every other message, and nothing
+    /// may be inserted between an assistant's tool calls and their results. A
+    /// tool result must answer a call made earlier in the conversation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for the same reasons as [`Self::add_message`], if
+    /// `index` is past the end, or if the insertion would break one of the
+    /// orderings above.
+    pub fn insert_message_at(&mut self, index: usize, message: Message) -> anyhow::Result<()> {
+        self.ensure_accepts_messages()?;
+        self.check_new_message(&message)?;
+        if index > self.messages.len() {
+            anyhow::bail!(
+                "Index {index} is out of bounds for conversation {} with {} messages",
+                self.id,
+                self.messages.len()
+            );
+        }
+
+        let leading_system = self
+            .messages
+            .iter()
+            .take_while(|m| m.role == MessageRole::System)
+            .count();
+        if message.role == MessageRole::System && index > leading_system {
+            anyhow::bail!("System messages must precede all other messages");
+        }
+        if message.role != MessageRole::System && index < leading_system {
+            anyhow::bail!(
+                "Cannot insert a {:?} message before a system message",
+                message.role
+            );
+        }
+
+        if message.role == MessageRole::Tool {
+            let answers_earlier_call = message.tool_call_id.as_ref().is_some_and(|id| {
+                self.messages[..index]
+                    .iter()
+                    .any(|m| m.tool_calls.iter().any(|c| &c.id == id))
+            });
+            if !answers_earlier_call {
+                anyhow::bail!(
+                    "Tool result {:?} does not answer a tool call before index {index}",
+                    message.tool_call_id
+                );
+            }
+        } else if self
+            .messages
+            .get(index)
+            .is_some_and(|m| m.role == MessageRole::Tool)
+        {
+            anyhow::bail!(
+                "Cannot insert a {:?} message between tool calls and their results",
+                message.role
+            );
+        }
+
+        Arc::make_mut(&mut self.messages).insert(index, message);
+        self.touch();
+        Ok(())
+    }
+
+    /// Removes the message with `id`, returning it, or `None` if no such
+    /// message exists.
+    ///
+    /// An assistant message whose tool calls still have results cannot be
+    /// removed; remove the results first so none is left orphaned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the conversation does not accept edits in its
+    /// current status, if a tool result still answers one of the message's
+    /// tool calls, or if another message names it as its `parent_id`.
+    pub fn remove_message(&mut self, id: Uuid) -> anyhow::Result<Option<Message>> {
+        self.ensure_accepts_messages()?;
+        let Some(index) = self.messages.iter().position(|m| m.id == id) else {
+            return Ok(None);
+        };
+
+        let target = &self.messages[index];
+        if let Some(result) = self.messages.iter().find(|m| {
+            m.role == MessageRole::Tool
+                && m.tool_call_id
+                    .as_ref()
+                    .is_some_and(|call_id| target.tool_calls.iter().any(|c| &c.id == call_id))
+        }) {
+            anyhow::bail!(
+                "Removing message {id} would orphan tool result {} for call {:?}",
+                result.id,
+                result.tool_call_id
+            );
+        }
+        if let Some(child) = self.messages.iter().find(|m| m.parent_id == Some(id)) {
+            anyhow::bail!("Message {id} is the parent of message {}", child.id);
+        }
+
+        let removed = Arc::make_mut(&mut self.messages).remove(index);
+        self.touch();
+        Ok(Some(removed))
+    }
+
+    /// Replaces the text content of the message with `id`, leaving its role,
+    /// tool calls, and links untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the conversation does not accept edits in its
+    /// current status or if no message with `id` exists.
+    pub fn replace_message(
+        &mut self,
+        id: Uuid,
+        new_content: impl Into<String>,
+    ) -> anyhow::Result<()> {
+        self.ensure_accepts_messages()?;
+        let conversation_id = self.id;
+        let message = Arc::make_mut(&mut self.messages)
+            .iter_mut()
+            .find(|m| m.id == id)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Message {id} not found in conversation {conversation_id}")
+            })?;
+        message.content = new_content.into();
+        self.touch();
+        Ok(())
+    }
+
+    fn ensure_accepts_messages(&self) -> anyhow::Result<()> {
+        if !self.status.accepts_messages() {
+            anyhow::bail!(
+                "Conversation {} is {:?} and does not accept new messages",
+                self.id,
+                self.status
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that `message` belongs to this conversation and that its
+    /// parent, if any, is already present.
+    fn check_new_message(&self, message: &Message) -> anyhow::Result<()> {
         if message.conversation_id != self.id {
             anyhow::bail!(
                 "Message conversation_id {} does not match conversation id {}",
@@ -451,8 +885,14 @@ impl Conversation {
                 self.id
             );
         }
-        Arc::make_mut(&mut self.messages).push(message);
-        self.touch();
+        if let Some(parent_id) = message.parent_id
+            && !self.messages.iter().any(|m| m.id == parent_id)
+        {
+            anyhow::bail!(
+                "Parent message {parent_id} not found in conversation {}",
+                self.id
+            );
+        }
         Ok(())
     }
 
@@ -462,6 +902,101 @@ impl Conversation {
         &self.messages
     }
 
+    /// Returns the messages in timestamp order, leaving the stored order
+    /// untouched.
+    ///
+    /// Uses the same ordering as [`Self::sort_messages_by_time`].
+    #[must_use]
+    pub fn get_messages_by_time(&self) -> Vec<&Message> {
+        self.time_order()
+            .into_iter()
+            .map(|i| &self.messages[i])
+            .collect()
+    }
+
+    /// Reorders the messages by timestamp, for conversations that were
+    /// merged or reloaded out of order.
+    ///
+    /// The sort is stable: messages with equal timestamps keep their stored
+    /// order. Tool results move with the assistant message that made the
+    /// call, directly after it, so no call is separated from its result.
+    /// Results whose call is not in the conversation are sorted on their
+    /// own. System messages are sorted like any other; follow with
+    /// [`Self::normalize`] to bring the system prompt back to the front.
+    pub fn sort_messages_by_time(&mut self) -> TimeSortReport {
+        let order = self.time_order();
+        let report = TimeSortReport {
+            reordered: order.iter().enumerate().any(|(i, &j)| i != j),
+            duplicate_timestamps: self.duplicate_timestamps(),
+        };
+        if report.reordered {
+            let mut slots: Vec<Option<Message>> = std::mem::take(Arc::make_mut(&mut self.messages))
+                .into_iter()
+                .map(Some)
+                .collect();
+            self.messages = Arc::new(order.into_iter().filter_map(|i| slots[i].take()).collect());
+            self.touch();
+        }
+        report
+    }
+
+    /// Groups of message IDs that share a timestamp, in stored order.
+    ///
+    /// Such messages cannot be ordered by time alone, so their relative
+    /// order after [`Self::sort_messages_by_time`] is their stored order.
+    #[must_use]
+    pub fn duplicate_timestamps(&self) -> Vec<Vec<Uuid>> {
+        let mut groups: HashMap<DateTime<Utc>, Vec<Uuid>> = HashMap::new();
+        // Timestamps in order of first appearance, so groups come out in
+        // stored order.
+        let mut first_seen = Vec::new();
+        for message in self.messages.iter() {
+            groups
+                .entry(message.timestamp)
+                .or_insert_with(|| {
+                    first_seen.push(message.timestamp);
+                    Vec::new()
+                })
+                .push(message.id);
+        }
+        first_seen
+            .into_iter()
+            .filter_map(|timestamp| groups.remove(&timestamp))
+            .filter(|ids| ids.len() > 1)
+            .collect()
+    }
+
+    /// Stored indices in timestamp order, with each tool result placed
+    /// right after the assistant message that made its call.
+    fn time_order(&self) -> Vec<usize> {
+        let caller: HashMap<&str, usize> = self
+            .messages
+            .iter()
+            .enumerate()
+            .flat_map(|(i, m)| m.tool_calls.iter().map(move |c| (c.id.as_str(), i)))
+            .collect();
+        let call_index = |message: &Message| {
+            (message.role == MessageRole::Tool)
+                .then(|| caller.get(message.tool_call_id.as_deref()?).copied())
+                .flatten()
+        };
+
+        let mut results: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut leads = Vec::new();
+        for (i, message) in self.messages.iter().enumerate() {
+            match call_index(message) {
+                Some(call) => results.entry(call).or_default().push(i),
+                None => leads.push(i),
+            }
+        }
+        leads.sort_by_key(|&i| self.messages[i].timestamp);
+
+        leads
+            .into_iter()
+            .flat_map(|i| std::iter::once(i).chain(results.remove(&i).unwrap_or_default()))
+            .collect()
+    }
+
     /// Creates a new user message for this conversation.
     pub fn user_message(&self, content: impl Into<String>) -> Message {
         Message::user(self.id, content)
@@ -472,11 +1007,28 @@ impl Conversation {
         Message::assistant(self.id, content)
     }
 
+    /// Creates a new assistant message for this conversation carrying the
+    /// [`Usage`] reported for the turn that produced it.
+    pub fn assistant_message_with_usage(
+        &self,
+        content: impl Into<String>,
+        usage: Usage,
+    ) -> Message {
+        let mut message = Message::assistant(self.id, content);
+        message.usage = Some(usage);
+        message
+    }
+
     /// Creates a new system message for this conversation.
     pub fn system_message(&self, content: impl Into<String>) -> Message {
         Message::system(self.id, content)
     }
 
+    /// Creates a new developer message for this conversation.
+    pub fn developer_message(&self, content: impl Into<String>) -> Message {
+        Message::developer(self.id, content)
+    }
+
     /// Creates a new tool result message for this conversation.
     ///
     /// # Errors
@@ -490,67 +1042,927 @@ impl Conversation {
     ) -> anyhow::Result<Message> {
         Message::tool(self.id, content, tool_call_id, function_name)
     }
-}
 
-impl Default for Conversation {
-    fn default() -> Self {
-        Self::new()
+    /// Sums the token usage recorded on assistant messages.
+    ///
+    /// Adds `prompt_tokens`, `completion_tokens`, and `total_tokens` (and
+    /// `cost`, when any message has one); token detail breakdowns are not
+    /// aggregated. Messages without usage contribute nothing.
+    #[must_use]
+    pub fn total_usage(&self) -> Usage {
+        self.messages
+            .iter()
+            .filter(|m| m.role == MessageRole::Assistant)
+            .filter_map(|m| m.usage.as_ref())
+            .fold(
+                Usage {
+                    prompt_tokens: 0,
+                    completion_tokens: 0,
+                    total_tokens: 0,
+                    cost: None,
+                    input_tokens_details: None,
+                    output_tokens_details: None,
+                },
+                |mut total, usage| {
+                    total.prompt_tokens = total.prompt_tokens.saturating_add(usage.prompt_tokens);
+                    total.completion_tokens = total
+                        .completion_tokens
+                        .saturating_add(usage.completion_tokens);
+                    total.total_tokens = total.total_tokens.saturating_add(usage.total_tokens);
+                    if let Some(cost) = usage.cost {
+                        total.cost = Some(total.cost.unwrap_or(0.0) + cost);
+                    }
+                    total
+                },
+            )
     }
-}
 
-#[cfg(test)]
-mod tests {
-    #![allow(clippy::unwrap_used)]
-    #![allow(clippy::expect_used)]
+    /// Returns the parent of the message at `index`: its `parent_id`, or the
+    /// preceding message when unset.
+    fn parent_of(&self, index: usize) -> Option<Uuid> {
+        self.messages[index]
+            .parent_id
+            .or_else(|| index.checked_sub(1).map(|i| self.messages[i].id))
+    }
 
-    use super::*;
+    /// Returns the indices of the messages from the root to `message_id`,
+    /// or `None` if the message is not in this conversation.
+    fn path_to(&self, message_id: Uuid) -> Option<Vec<usize>> {
+        let index_of: HashMap<Uuid, usize> = self
+            .messages
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.id, i))
+            .collect();
+
+        let mut path = Vec::new();
+        let mut current = index_of.get(&message_id).copied();
+        // A malformed parent cycle can visit at most every message once.
+        while let Some(i) = current
+            && path.len() < self.messages.len()
+        {
+            path.push(i);
+            current = self.parent_of(i).and_then(|p| index_of.get(&p).copied());
+        }
+        if path.is_empty() {
+            return None;
+        }
+        path.reverse();
+        Some(path)
+    }
 
-    #[test]
-    fn test_message_creation() {
-        let conv_id = Uuid::new_v4();
-        let msg = Message::user(conv_id, "Hello, world!");
+    /// Creates a new conversation holding the path from the root to
+    /// `message_id`, leaving this conversation untouched.
+    ///
+    /// The fork gets a new conversation ID and copies the title, description,
+    /// and metadata. Forked messages get new IDs and are re-linked so each
+    /// one's `parent_id` names its predecessor in the fork.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `message_id` is not in this conversation.
+    pub fn fork_at(&self, message_id: Uuid) -> anyhow::Result<Self> {
+        let path = self.path_to(message_id).ok_or_else(|| {
+            anyhow::anyhow!("Message {message_id} not found in conversation {}", self.id)
+        })?;
+
+        let mut fork = Self::new();
+        fork.title.clone_from(&self.title);
+        fork.description.clone_from(&self.description);
+        fork.metadata.clone_from(&self.metadata);
+
+        let mut parent_id = None;
+        let messages = path
+            .into_iter()
+            .map(|i| {
+                let mut message = self.messages[i].clone();
+                message.id = Uuid::new_v4();
+                message.conversation_id = fork.id;
+                message.parent_id = parent_id;
+                parent_id = Some(message.id);
+                message
+            })
+            .collect();
+        fork.messages = Arc::new(messages);
+        Ok(fork)
+    }
 
-        assert_eq!(msg.conversation_id, conv_id);
-        assert_eq!(msg.role, MessageRole::User);
-        assert_eq!(msg.content, "Hello, world!");
-        assert!(msg.tool_calls.is_empty());
+    /// Enumerates every branch as message IDs ordered from leaf to root.
+    ///
+    /// A leaf is a message that no other message replies to. A conversation
+    /// without any `parent_id` set has exactly one branch.
+    #[must_use]
+    pub fn branches(&self) -> Vec<Vec<Uuid>> {
+        let parents: HashSet<Uuid> = (0..self.messages.len())
+            .filter_map(|i| self.parent_of(i))
+            .collect();
+
+        self.messages
+            .iter()
+            .filter(|m| !parents.contains(&m.id))
+            .filter_map(|leaf| self.path_to(leaf.id))
+            .map(|path| {
+                path.into_iter()
+                    .rev()
+                    .map(|i| self.messages[i].id)
+                    .collect()
+            })
+            .collect()
     }
 
-    #[test]
-    fn test_conversation_creation() {
-        let conv = Conversation::new()
-            .with_title("Test Conversation")
-            .with_description("A test conversation");
+    /// Removes the oldest non-system messages until the estimated total fits
+    /// within `max_tokens`, returning the IDs of the removed messages.
+    ///
+    /// System and developer messages are always kept. An assistant message with tool calls
+    /// is removed together with the tool results answering it, so no call is
+    /// left without its result or vice versa. If the system messages alone
+    /// exceed the budget, every other message is removed.
+    pub fn trim_to_token_budget(
+        &mut self,
+        max_tokens: usize,
+        estimator: impl Fn(&Message) -> usize,
+    ) -> Vec<Uuid> {
+        let costs: Vec<usize> = self.messages.iter().map(&estimator).collect();
+        let mut total: usize = costs.iter().sum();
+        if total <= max_tokens {
+            return Vec::new();
+        }
 
-        assert_eq!(conv.title, Some("Test Conversation".to_string()));
-        assert_eq!(conv.description, Some("A test conversation".to_string()));
-        assert_eq!(conv.status, ConversationStatus::Active);
+        let mut remove = vec![false; self.messages.len()];
+        for (i, message) in self.messages.iter().enumerate() {
+            if total <= max_tokens {
+                break;
+            }
+            if remove[i] || message.role.is_instruction() {
+                continue;
+            }
+            remove[i] = true;
+            total -= costs[i];
+            for (j, later) in self.messages.iter().enumerate().skip(i + 1) {
+                let answers_call = later.role == MessageRole::Tool
+                    && later
+                        .tool_call_id
+                        .as_ref()
+                        .is_some_and(|id| message.tool_calls.iter().any(|c| &c.id == id));
+                if answers_call && !remove[j] {
+                    remove[j] = true;
+                    total -= costs[j];
+                }
+            }
+        }
+
+        let messages = Arc::make_mut(&mut self.messages);
+        let mut removed = Vec::new();
+        let mut flags = remove.into_iter();
+        messages.retain(|m| {
+            let drop = flags.next().unwrap_or(false);
+            if drop {
+                removed.push(m.id);
+            }
+            !drop
+        });
+        if !removed.is_empty() {
+            self.touch();
+        }
+        removed
     }
 
-    #[test]
-    fn test_tool_call_creation() {
-        let tool_call = ToolCall::new("test_function", r#"{"param": "value"}"#);
+    /// Returns a copy holding only the messages `predicate` accepts, e.g. to
+    /// hide internal tool traffic from a UI. This conversation is untouched.
+    ///
+    /// Tool calls stay paired with their results: dropping an assistant
+    /// message drops the results answering it, and dropping a tool result
+    /// removes the call it answers from the kept assistant message. An
+    /// assistant message left with neither calls nor content is dropped too.
+    /// A kept message whose `parent_id` named a dropped message is re-linked
+    /// to its nearest kept ancestor. The copy keeps this conversation's ID,
+    /// status, and metadata.
+    #[must_use]
+    pub fn filtered(&self, predicate: impl Fn(&Message) -> bool) -> Self {
+        let mut keep: Vec<bool> = self.messages.iter().map(&predicate).collect();
+
+        let issuers: HashMap<&str, usize> = self
+            .messages
+            .iter()
+            .enumerate()
+            .flat_map(|(i, m)| m.tool_calls.iter().map(move |c| (c.id.as_str(), i)))
+            .collect();
+        let mut dropped_calls: HashSet<&str> = HashSet::new();
+        for (i, message) in self.messages.iter().enumerate() {
+            if message.role != MessageRole::Tool {
+                continue;
+            }
+            let Some(call_id) = message.tool_call_id.as_deref() else {
+                continue;
+            };
+            match issuers.get(call_id) {
+                Some(&issuer) if !keep[issuer] => keep[i] = false,
+                _ if !keep[i] => {
+                    dropped_calls.insert(call_id);
+                }
+                _ => {}
+            }
+        }
 
-        assert_eq!(tool_call.function.name, "test_function");
-        assert_eq!(tool_call.function.arguments, r#"{"param": "value"}"#);
-        assert_eq!(tool_call.call_type, "function");
-        assert!(!tool_call.id.is_empty());
-    }
+        let mut messages = Vec::with_capacity(self.messages.len());
+        for (message, kept) in self.messages.iter().zip(keep) {
+            if !kept {
+                continue;
+            }
+            let mut message = message.clone();
+            if message
+                .tool_calls
+                .iter()
+                .any(|c| dropped_calls.contains(c.id.as_str()))
+            {
+                message
+                    .tool_calls
+                    .retain(|c| !dropped_calls.contains(c.id.as_str()));
+                if message.tool_calls.is_empty() && message.content.trim().is_empty() {
+                    continue;
+                }
+            }
+            messages.push(message);
+        }
 
-    #[test]
-    fn test_message_with_tool_calls() {
-        let conv_id = Uuid::new_v4();
-        let tool_call = ToolCall::new("get_weather", r#"{"location": "New York"}"#);
-        let msg = Message::assistant(conv_id, "I'll check the weather for you.")
-            .with_tool_calls(vec![tool_call])
-            .expect("Failed to add tool calls");
+        let parents: HashMap<Uuid, Option<Uuid>> =
+            self.messages.iter().map(|m| (m.id, m.parent_id)).collect();
+        let kept_ids: HashSet<Uuid> = messages.iter().map(|m| m.id).collect();
+        for message in &mut messages {
+            let mut parent = message.parent_id;
+            // A malformed parent cycle can visit at most every message once;
+            // a walk that never reaches a kept message leaves no parent.
+            for _ in 0..self.messages.len() {
+                match parent {
+                    Some(id) if !kept_ids.contains(&id) => {
+                        parent = parents.get(&id).copied().flatten();
+                    }
+                    _ => break,
+                }
+            }
+            message.parent_id = parent.filter(|id| kept_ids.contains(id));
+        }
 
-        assert_eq!(msg.tool_calls.len(), 1);
-        assert_eq!(msg.tool_calls[0].function.name, "get_weather");
-        assert_eq!(
-            msg.tool_calls[0].function.arguments,
-            r#"{"location": "New York"}"#
-        );
+        let mut filtered = self.clone();
+        filtered.messages = Arc::new(messages);
+        filtered
+    }
+
+    /// Compares this conversation with a later version of it, matching
+    /// messages by ID.
+    ///
+    /// A message in both versions is modified when its role, content, or
+    /// tool calls differ, with a line diff of its content; otherwise it is
+    /// unchanged. Shared messages whose order relative to one another
+    /// changed are also reported as reordered, whether or not they were
+    /// modified.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> ConversationDiff {
+        let before: HashMap<Uuid, &Message> = self.messages.iter().map(|m| (m.id, m)).collect();
+        let after: HashMap<Uuid, &Message> = other.messages.iter().map(|m| (m.id, m)).collect();
+
+        let mut diff = ConversationDiff {
+            added: other
+                .messages
+                .iter()
+                .filter(|m| !before.contains_key(&m.id))
+                .map(|m| m.id)
+                .collect(),
+            removed: self
+                .messages
+                .iter()
+                .filter(|m| !after.contains_key(&m.id))
+                .map(|m| m.id)
+                .collect(),
+            ..ConversationDiff::default()
+        };
+
+        for old in self.messages.as_slice() {
+            let Some(new) = after.get(&old.id) else {
+                continue;
+            };
+            if old.role == new.role
+                && old.content == new.content
+                && old.tool_calls == new.tool_calls
+            {
+                diff.unchanged.push(old.id);
+            } else {
+                diff.modified.push(MessageChange {
+                    id: old.id,
+                    role: (old.role != new.role).then_some((old.role, new.role)),
+                    content: diff_lines(&old.content, &new.content),
+                    tool_calls_changed: old.tool_calls != new.tool_calls,
+                });
+            }
+        }
+
+        let old_order: Vec<Uuid> = self
+            .messages
+            .iter()
+            .map(|m| m.id)
+            .filter(|id| after.contains_key(id))
+            .collect();
+        let new_order: Vec<Uuid> = other
+            .messages
+            .iter()
+            .map(|m| m.id)
+            .filter(|id| before.contains_key(id))
+            .collect();
+        let in_place: HashSet<usize> = longest_common_subsequence(&old_order, &new_order)
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect();
+        diff.reordered = old_order
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !in_place.contains(i))
+            .map(|(_, id)| *id)
+            .collect();
+
+        diff
+    }
+
+    /// Folds every system message into one at the front of the conversation,
+    /// so each provider sees the same single system prompt.
+    ///
+    /// The first system message is kept and moved to the front; later ones
+    /// are removed and their content appended to it, separated by blank
+    /// lines. Messages whose `parent_id` named a removed message now name
+    /// the kept one. Developer messages are left in place.
+    pub fn normalize(&mut self) -> NormalizeReport {
+        let mut report = NormalizeReport::default();
+        let mut systems = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.role == MessageRole::System)
+            .map(|(i, _)| i);
+        let Some(first) = systems.next() else {
+            return report;
+        };
+        if first == 0 && systems.next().is_none() {
+            return report;
+        }
+
+        let messages = Arc::make_mut(&mut self.messages);
+        let mut system = messages.remove(first);
+        let mut sections: Vec<String> = vec![std::mem::take(&mut system.content)];
+        messages.retain_mut(|m| {
+            if m.role != MessageRole::System {
+                return true;
+            }
+            report.merged.push(m.id);
+            sections.push(std::mem::take(&mut m.content));
+            false
+        });
+        sections.retain(|section| !section.is_empty());
+        system.content = sections.join("\n\n");
+        for message in messages.iter_mut() {
+            if message
+                .parent_id
+                .is_some_and(|parent| report.merged.contains(&parent))
+            {
+                message.parent_id = Some(system.id);
+            }
+        }
+        report.moved_to_front = first != 0;
+        messages.insert(0, system);
+        self.touch();
+        report
+    }
+
+    /// Returns the messages matching `query`, in chronological order.
+    ///
+    /// Messages with equal timestamps keep their order in the conversation.
+    #[must_use]
+    pub fn search(&self, query: &MessageQuery) -> Vec<&Message> {
+        let mut matches: Vec<&Message> =
+            self.messages.iter().filter(|m| query.matches(m)).collect();
+        matches.sort_by_key(|m| m.timestamp);
+        matches
+    }
+
+    /// Iterates the conversation as exchanges rather than flat messages.
+    ///
+    /// Each user message opens a [`Turn`] that runs until the next user
+    /// message. Messages before the first user message (usually the system
+    /// prompt) form a preamble turn with `user: None`. Turns follow the
+    /// stored message order, ignoring any branching via `parent_id`.
+    pub fn turns(&self) -> impl Iterator<Item = Turn<'_>> {
+        let mut messages = self.messages.iter().peekable();
+        std::iter::from_fn(move || {
+            let first = messages.next()?;
+            let mut turn = Turn::default();
+            let mut pending = if first.role == MessageRole::User {
+                turn.user = Some(first);
+                messages.next_if(|m| m.role != MessageRole::User)
+            } else {
+                Some(first)
+            };
+            while let Some(message) = pending {
+                turn.push(message);
+                pending = messages.next_if(|m| m.role != MessageRole::User);
+            }
+            Some(turn)
+        })
+    }
+
+    /// Renders the conversation as a Markdown transcript, including reasoning.
+    ///
+    /// See [`Self::to_markdown_with`].
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(true)
+    }
+
+    /// Renders the conversation as a Markdown transcript.
+    ///
+    /// Each message gets a role heading. Message text is escaped so it renders
+    /// literally. Tool calls appear as fenced JSON blocks, each followed by the
+    /// tool message that answers it; tool results without a matching call are
+    /// rendered on their own. With `include_reasoning`, assistant reasoning is
+    /// shown in a collapsed `<details>` block.
+    #[must_use]
+    pub fn to_markdown_with(&self, include_reasoning: bool) -> String {
+        let mut out = String::new();
+        if let Some(title) = &self.title {
+            let _ = writeln!(out, "# {}\n", escape_markdown(title));
+        }
+
+        let results: HashMap<&str, &Message> = self
+            .messages
+            .iter()
+            .filter(|m| m.role == MessageRole::Tool)
+            .filter_map(|m| Some((m.tool_call_id.as_deref()?, m)))
+            .collect();
+        let called: HashSet<&str> = self
+            .messages
+            .iter()
+            .flat_map(|m| &m.tool_calls)
+            .map(|c| c.id.as_str())
+            .collect();
+
+        for message in self.messages.iter() {
+            let heading = match message.role {
+                MessageRole::System => "System",
+                MessageRole::Developer => "Developer",
+                MessageRole::User => "User",
+                MessageRole::Assistant => "Assistant",
+                MessageRole::Tool => {
+                    // Results of known calls are rendered under the call.
+                    if message
+                        .tool_call_id
+                        .as_deref()
+                        .is_some_and(|id| called.contains(id))
+                    {
+                        continue;
+                    }
+                    let name = message.name.as_deref().unwrap_or("unknown");
+                    let _ = writeln!(out, "## Tool result `{name}`\n");
+                    push_fenced(&mut out, "", &message.content);
+                    continue;
+                }
+            };
+            let _ = writeln!(out, "## {heading}\n");
+
+            if include_reasoning
+                && let Some(reasoning) =
+                    message.reasoning.as_ref().filter(|r| !r.content.is_empty())
+            {
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary>Reasoning</summary>\n\n{}\n\n</details>\n",
+                    escape_markdown(&reasoning.content)
+                );
+            }
+            if !message.content.is_empty() {
+                let _ = writeln!(out, "{}\n", escape_markdown(&message.content));
+            }
+
+            for call in &message.tool_calls {
+                let _ = writeln!(out, "**Tool call** `{}`\n", call.function.name);
+                let arguments = serde_json::from_str::<serde_json::Value>(&call.function.arguments)
+                    .and_then(|v| serde_json::to_string_pretty(&v))
+                    .unwrap_or_else(|_| call.function.arguments.clone());
+                push_fenced(&mut out, "json", &arguments);
+                if let Some(result) = results.get(call.id.as_str()) {
+                    out.push_str("**Result**\n\n");
+                    push_fenced(&mut out, "", &result.content);
+                }
+            }
+        }
+
+        let trimmed = out.trim_end().len();
+        out.truncate(trimmed);
+        out.push('\n');
+        out
+    }
+
+    /// Writes this conversation, including all messages, to `path` as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails or the file cannot be written.
+    pub fn save_to_path(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, json)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Reads a conversation previously written by [`Self::save_to_path`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or if any message's
+    /// conversation ID doesn't match the conversation's ID (each mismatch is listed).
+    pub fn load_from_path(path: &Path) -> anyhow::Result<Self> {
+        let json = std::fs::read(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", path.display()))?;
+        let conversation: Self = serde_json::from_slice(&json)?;
+
+        let mismatches: Vec<String> = conversation
+            .messages
+            .iter()
+            .filter(|m| m.conversation_id != conversation.id)
+            .map(|m| format!("message {} has conversation_id {}", m.id, m.conversation_id))
+            .collect();
+        if !mismatches.is_empty() {
+            anyhow::bail!(
+                "Conversation {} contains messages from other conversations: {}",
+                conversation.id,
+                mismatches.join("; ")
+            );
+        }
+
+        Ok(conversation)
+    }
+}
+
+impl Default for Conversation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One exchange yielded by [`Conversation::turns`]: a user message and
+/// everything up to the next one.
+#[derive(Debug, Clone, Default)]
+pub struct Turn<'a> {
+    /// The user message opening the turn; `None` for the preamble.
+    pub user: Option<&'a Message>,
+    /// System and developer messages within the turn, in order.
+    pub instructions: Vec<&'a Message>,
+    /// Assistant tool-call messages and tool results between the user
+    /// message and the final reply, in order.
+    pub steps: Vec<&'a Message>,
+    /// The last assistant message, or `None` if the user got no reply.
+    pub assistant: Option<&'a Message>,
+}
+
+impl<'a> Turn<'a> {
+    fn push(&mut self, message: &'a Message) {
+        match message.role {
+            MessageRole::System | MessageRole::Developer => self.instructions.push(message),
+            MessageRole::Assistant => {
+                self.steps.extend(self.assistant.replace(message));
+            }
+            _ => {
+                self.steps.extend(self.assistant.take());
+                self.steps.push(message);
+            }
+        }
+    }
+}
+
+/// What [`Conversation::normalize`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NormalizeReport {
+    /// System messages folded into the first one and removed, in order.
+    pub merged: Vec<Uuid>,
+    /// Whether the system message was moved to the front.
+    pub moved_to_front: bool,
+}
+
+impl NormalizeReport {
+    /// Whether the conversation was already normalized.
+    #[must_use]
+    pub const fn is_unchanged(&self) -> bool {
+        self.merged.is_empty() && !self.moved_to_front
+    }
+}
+
+/// What changed between two versions of a conversation, from
+/// [`Conversation::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConversationDiff {
+    /// Messages only in the newer version, in its order.
+    pub added: Vec<Uuid>,
+    /// Messages only in the older version, in its order.
+    pub removed: Vec<Uuid>,
+    /// Messages in both versions whose role, content, or tool calls changed,
+    /// in the older version's order.
+    pub modified: Vec<MessageChange>,
+    /// Messages identical in both versions, in the older version's order.
+    pub unchanged: Vec<Uuid>,
+    /// Messages in both versions that moved relative to the others, in the
+    /// older version's order.
+    pub reordered: Vec<Uuid>,
+}
+
+impl ConversationDiff {
+    /// Whether the two versions hold the same messages in the same order.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.reordered.is_empty()
+    }
+}
+
+/// One message that differs between two conversation versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageChange {
+    /// The message's ID.
+    pub id: Uuid,
+    /// The old and new role, if the role changed.
+    pub role: Option<(MessageRole, MessageRole)>,
+    /// Line diff from the old content to the new; all [`DiffLine::Same`]
+    /// when only the role or tool calls changed.
+    pub content: Vec<DiffLine>,
+    /// Whether the tool calls differ.
+    pub tool_calls_changed: bool,
+}
+
+/// One line of a [`MessageChange`] content diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Present in both versions.
+    Same(String),
+    /// Only in the old version.
+    Removed(String),
+    /// Only in the new version.
+    Added(String),
+}
+
+/// Line diff from `old` to `new`, built on their longest common subsequence.
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    for (common_i, common_j) in longest_common_subsequence(&old, &new) {
+        lines.extend(
+            old[i..common_i]
+                .iter()
+                .map(|l| DiffLine::Removed((*l).to_string())),
+        );
+        lines.extend(
+            new[j..common_j]
+                .iter()
+                .map(|l| DiffLine::Added((*l).to_string())),
+        );
+        lines.push(DiffLine::Same(old[common_i].to_string()));
+        (i, j) = (common_i + 1, common_j + 1);
+    }
+    lines.extend(old[i..].iter().map(|l| DiffLine::Removed((*l).to_string())));
+    lines.extend(new[j..].iter().map(|l| DiffLine::Added((*l).to_string())));
+    lines
+}
+
+/// Index pairs of a longest common subsequence of `a` and `b`, in order.
+fn longest_common_subsequence<T: PartialEq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    // lengths[i][j]: LCS length of a[i..] and b[j..].
+    let mut lengths = vec![vec![0_usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let mut pairs = Vec::with_capacity(lengths[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// What [`Conversation::sort_messages_by_time`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimeSortReport {
+    /// Whether any message moved.
+    pub reordered: bool,
+    /// Groups of message IDs sharing a timestamp, as returned by
+    /// [`Conversation::duplicate_timestamps`].
+    pub duplicate_timestamps: Vec<Vec<Uuid>>,
+}
+
+/// Filter for [`Conversation::search`].
+///
+/// Every criterion that is set must match; an empty query matches every message.
+///
+/// # Example
+///
+/// ```
+/// use neuromance_common::chat::{Conversation, MessageQuery, MessageRole};
+/// use regex::Regex;
+///
+/// let mut conv = Conversation::new();
+/// conv.add_message(conv.user_message("error: disk full")).unwrap();
+/// conv.add_message(conv.assistant_message("Let me look.")).unwrap();
+///
+/// let query = MessageQuery::new()
+///     .with_role(MessageRole::User)
+///     .with_pattern(Regex::new(r"(?i)^error:").unwrap());
+/// assert_eq!(conv.search(&query).len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageQuery {
+    /// Only messages with this role.
+    pub role: Option<MessageRole>,
+    /// Only messages whose content contains this substring (case-sensitive).
+    pub text: Option<String>,
+    /// Only messages whose content matches this regex.
+    pub pattern: Option<Regex>,
+    /// Only messages sent at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only messages sent before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Only messages that do (`true`) or do not (`false`) carry tool calls.
+    pub has_tool_calls: Option<bool>,
+}
+
+impl MessageQuery {
+    /// Creates a query that matches every message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts matches to `role`.
+    #[must_use]
+    pub const fn with_role(mut self, role: MessageRole) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    /// Restricts matches to content containing `text`.
+    #[must_use]
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Restricts matches to content matching `pattern`.
+    #[must_use]
+    pub fn with_pattern(mut self, pattern: Regex) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// Restricts matches to messages sent in `[since, until)`.
+    #[must_use]
+    pub const fn with_time_range(
+        mut self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.since = since;
+        self.until = until;
+        self
+    }
+
+    /// Restricts matches to messages with or without tool calls.
+    #[must_use]
+    pub const fn with_tool_calls(mut self, has_tool_calls: bool) -> Self {
+        self.has_tool_calls = Some(has_tool_calls);
+        self
+    }
+
+    /// Whether `message` satisfies every criterion of this query.
+    #[must_use]
+    pub fn matches(&self, message: &Message) -> bool {
+        self.role.is_none_or(|role| message.role == role)
+            && self
+                .text
+                .as_deref()
+                .is_none_or(|text| message.content.contains(text))
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&message.content))
+            && self.since.is_none_or(|since| message.timestamp >= since)
+            && self.until.is_none_or(|until| message.timestamp < until)
+            && self
+                .has_tool_calls
+                .is_none_or(|wanted| message.tool_calls.is_empty() != wanted)
+    }
+}
+
+/// Backslash-escapes characters that Markdown would otherwise interpret,
+/// including list, heading, and quote markers at the start of a line.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let indent = line.len() - line.trim_start().len();
+        let (lead, rest) = line.split_at(indent);
+        out.push_str(lead);
+
+        // Block markers only matter at the start of a line.
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let ordered_marker = digits > 0 && matches!(rest.as_bytes().get(digits), Some(b'.' | b')'));
+        for (j, c) in rest.char_indices() {
+            let block_marker =
+                (j == 0 && matches!(c, '-' | '+' | '=')) || (ordered_marker && j == digits);
+            if block_marker || "\\`*_[]<>#|~".contains(c) {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Appends `content` as a fenced code block, using a fence longer than any
+/// backtick run inside it.
+fn push_fenced(out: &mut String, lang: &str, content: &str) {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let _ = writeln!(
+        out,
+        "{fence}{lang}\n{}\n{fence}\n",
+        content.trim_end_matches('\n')
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_message_creation() {
+        let conv_id = Uuid::new_v4();
+        let msg = Message::user(conv_id, "Hello, world!");
+
+        assert_eq!(msg.conversation_id, conv_id);
+        assert_eq!(msg.role, MessageRole::User);
+        assert_eq!(msg.content, "Hello, world!");
+        assert!(msg.tool_calls.is_empty());
+    }
+
+    #[test]
+    fn test_conversation_creation() {
+        let conv = Conversation::new()
+            .with_title("Test Conversation")
+            .with_description("A test conversation");
+
+        assert_eq!(conv.title, Some("Test Conversation".to_string()));
+        assert_eq!(conv.description, Some("A test conversation".to_string()));
+        assert_eq!(conv.status, ConversationStatus::Active);
+    }
+
+    #[test]
+    fn test_tool_call_creation() {
+        let tool_call = ToolCall::new("test_function", r#"{"param": "value"}"#);
+
+        assert_eq!(tool_call.function.name, "test_function");
+        assert_eq!(tool_call.function.arguments, r#"{"param": "value"}"#);
+        assert_eq!(tool_call.call_type, "function");
+        assert!(!tool_call.id.is_empty());
+    }
+
+    #[test]
+    fn test_message_with_tool_calls() {
+        let conv_id = Uuid::new_v4();
+        let tool_call = ToolCall::new("get_weather", r#"{"location": "New York"}"#);
+        let msg = Message::assistant(conv_id, "I'll check the weather for you.")
+            .with_tool_calls(vec![tool_call])
+            .expect("Failed to add tool calls");
+
+        assert_eq!(msg.tool_calls.len(), 1);
+        assert_eq!(msg.tool_calls[0].function.name, "get_weather");
+        assert_eq!(
+            msg.tool_calls[0].function.arguments,
+            r#"{"location": "New York"}"#
+        );
     }
 
     #[test]
@@ -593,6 +2005,36 @@ mod tests {
         assert_eq!(msg.name, Some("test_func".to_string()));
     }
 
+    #[test]
+    fn test_with_image_base64() {
+        let msg = Message::user(Uuid::new_v4(), "What is this?")
+            .with_image_base64("image/png", "iVBORw0KGgo=")
+            .unwrap();
+        assert_eq!(
+            msg.content_parts,
+            vec![ContentPart::Image {
+                media_type: "image/png".to_string(),
+                data: "iVBORw0KGgo=".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_with_image_base64_rejects_unsupported_media_type() {
+        let err = Message::user(Uuid::new_v4(), "")
+            .with_image_base64("image/bmp", "iVBORw0KGgo=")
+            .unwrap_err();
+        assert!(err.to_string().contains("image/bmp"));
+    }
+
+    #[test]
+    fn test_with_image_base64_rejects_malformed_data() {
+        let err = Message::user(Uuid::new_v4(), "")
+            .with_image_base64("image/jpeg", "not base64!")
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid base64"));
+    }
+
     #[test]
     fn test_conversation_add_message() {
         let mut conv = Conversation::new();
@@ -613,6 +2055,873 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_trim_to_token_budget_keeps_system_and_tool_pairs() {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.system_message("sys")).unwrap();
+        conv.add_message(conv.user_message("first")).unwrap();
+        let calls = vec![
+            ToolCall::new("read", r#"{"path": "a"}"#),
+            ToolCall::new("read", r#"{"path": "b"}"#),
+        ];
+        let assistant = conv
+            .assistant_message("")
+            .with_tool_calls(calls.clone())
+            .unwrap();
+        let assistant_id = assistant.id;
+        conv.add_message(assistant).unwrap();
+        for call in &calls {
+            let result = conv
+                .tool_message("contents", call.id.clone(), "read".to_string())
+                .unwrap();
+            conv.add_message(result).unwrap();
+        }
+        conv.add_message(conv.assistant_message("summary")).unwrap();
+        conv.add_message(conv.user_message("second")).unwrap();
+
+        // Every message costs 10: 70 in total. A budget of 35 forces out the
+        // first user message and then the whole tool-call group.
+        let removed = conv.trim_to_token_budget(35, |_| 10);
+
+        assert_eq!(removed.len(), 4);
+        assert!(removed.contains(&assistant_id));
+        let roles: Vec<MessageRole> = conv.messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                MessageRole::System,
+                MessageRole::Assistant,
+                MessageRole::User
+            ]
+        );
+        assert!(conv.messages.iter().all(|m| m.role != MessageRole::Tool));
+    }
+
+    #[test]
+    fn test_filtered_drops_think_calls_with_their_results() {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.user_message("find the bug")).unwrap();
+
+        // A think-only turn disappears entirely.
+        let think = ToolCall::new("think", r#"{"thought": "plan"}"#);
+        let thinking = conv
+            .assistant_message("")
+            .with_tool_calls(vec![think.clone()])
+            .unwrap();
+        conv.add_message(thinking).unwrap();
+        let think_result = conv
+            .tool_message("noted", think.id, "think".to_string())
+            .unwrap();
+        conv.add_message(think_result).unwrap();
+
+        // A mixed turn keeps its read call and result only.
+        let read = ToolCall::new("read", r#"{"path": "a"}"#);
+        let think_again = ToolCall::new("think", r#"{"thought": "check"}"#);
+        let mixed = conv
+            .assistant_message("Reading a.")
+            .with_tool_calls(vec![read.clone(), think_again.clone()])
+            .unwrap();
+        let mixed_id = mixed.id;
+        conv.add_message(mixed).unwrap();
+        for (call, name) in [(&read, "read"), (&think_again, "think")] {
+            let result = conv
+                .tool_message("ok", call.id.clone(), name.to_string())
+                .unwrap();
+            conv.add_message(result).unwrap();
+        }
+        conv.add_message(conv.assistant_message("Fixed.")).unwrap();
+
+        let filtered = conv.filtered(|m| m.name.as_deref() != Some("think"));
+
+        assert_eq!(conv.messages.len(), 7, "original is untouched");
+        assert_eq!(filtered.id, conv.id);
+        let roles: Vec<MessageRole> = filtered.messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                MessageRole::User,
+                MessageRole::Assistant,
+                MessageRole::Tool,
+                MessageRole::Assistant
+            ]
+        );
+        let kept = &filtered.messages[1];
+        assert_eq!(kept.id, mixed_id);
+        assert_eq!(kept.tool_calls.len(), 1);
+        assert_eq!(kept.tool_calls[0].id, read.id);
+        assert_eq!(
+            filtered.messages[2].tool_call_id.as_deref(),
+            Some(read.id.as_str())
+        );
+
+        // Every kept call has its result and every result its call.
+        let call_ids: HashSet<&str> = filtered
+            .messages
+            .iter()
+            .flat_map(|m| m.tool_calls.iter().map(|c| c.id.as_str()))
+            .collect();
+        let result_ids: HashSet<&str> = filtered
+            .messages
+            .iter()
+            .filter_map(|m| m.tool_call_id.as_deref())
+            .collect();
+        assert_eq!(call_ids, result_ids);
+
+        // Dropping an assistant message drops the results answering it.
+        let without_mixed = conv.filtered(|m| m.id != mixed_id);
+        assert!(without_mixed.messages.iter().all(|m| {
+            m.tool_call_id.as_deref() != Some(read.id.as_str())
+                && m.tool_call_id.as_deref() != Some(think_again.id.as_str())
+        }));
+        assert_eq!(without_mixed.messages.len(), 4);
+    }
+
+    #[test]
+    fn test_filtered_survives_a_parent_cycle_among_dropped_messages() {
+        let mut conv = Conversation::new();
+        for content in ["a", "b", "c"] {
+            conv.add_message(conv.user_message(content)).unwrap();
+        }
+        let ids: Vec<Uuid> = conv.messages.iter().map(|m| m.id).collect();
+        let messages = Arc::make_mut(&mut conv.messages);
+        messages[0].parent_id = Some(ids[1]);
+        messages[1].parent_id = Some(ids[0]);
+        messages[2].parent_id = Some(ids[1]);
+
+        let filtered = conv.filtered(|m| m.content == "c");
+
+        assert_eq!(filtered.messages.len(), 1);
+        assert_eq!(filtered.messages[0].parent_id, None);
+    }
+
+    fn three_message_conversation() -> Conversation {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.system_message("sys")).unwrap();
+        conv.add_message(conv.user_message("line one\nline two"))
+            .unwrap();
+        conv.add_message(conv.assistant_message("answer")).unwrap();
+        conv
+    }
+
+    #[test]
+    fn test_diff_reports_edit_with_line_diff() {
+        let conv = three_message_conversation();
+        let mut edited = conv.clone();
+        Arc::make_mut(&mut edited.messages)[1].content = "line one\nline 2\nline three".into();
+
+        let diff = conv.diff(&edited);
+
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.reordered.is_empty());
+        assert_eq!(
+            diff.unchanged,
+            vec![conv.messages[0].id, conv.messages[2].id]
+        );
+        assert_eq!(diff.modified.len(), 1);
+        let change = &diff.modified[0];
+        assert_eq!(change.id, conv.messages[1].id);
+        assert_eq!(change.role, None);
+        assert!(!change.tool_calls_changed);
+        assert_eq!(
+            change.content,
+            vec![
+                DiffLine::Same("line one".into()),
+                DiffLine::Removed("line two".into()),
+                DiffLine::Added("line 2".into()),
+                DiffLine::Added("line three".into()),
+            ]
+        );
+
+        assert!(conv.diff(&conv.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_insertion_and_deletion() {
+        let conv = three_message_conversation();
+
+        let mut inserted = conv.clone();
+        let note = inserted.user_message("follow-up");
+        let note_id = note.id;
+        Arc::make_mut(&mut inserted.messages).insert(2, note);
+        let diff = conv.diff(&inserted);
+        assert_eq!(diff.added, vec![note_id]);
+        assert!(diff.removed.is_empty() && diff.modified.is_empty());
+        assert!(diff.reordered.is_empty(), "an insertion moves nothing");
+        assert_eq!(diff.unchanged.len(), 3);
+
+        let mut deleted = conv.clone();
+        let removed_id = Arc::make_mut(&mut deleted.messages).remove(1).id;
+        let diff = conv.diff(&deleted);
+        assert_eq!(diff.removed, vec![removed_id]);
+        assert!(diff.added.is_empty() && diff.modified.is_empty() && diff.reordered.is_empty());
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_detects_reordering() {
+        let conv = three_message_conversation();
+        let mut swapped = conv.clone();
+        Arc::make_mut(&mut swapped.messages).swap(1, 2);
+
+        let diff = conv.diff(&swapped);
+
+        assert!(diff.modified.is_empty() && diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.unchanged.len(), 3);
+        assert_eq!(diff.reordered.len(), 1);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_remove_message_refuses_to_orphan_tool_result() {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.system_message("sys")).unwrap();
+        conv.add_message(conv.user_message("read a")).unwrap();
+        let call = ToolCall::new("read", r#"{"path": "a"}"#);
+        let assistant = conv
+            .assistant_message("")
+            .with_tool_calls(vec![call.clone()])
+            .unwrap();
+        let assistant_id = assistant.id;
+        conv.add_message(assistant).unwrap();
+        let result = conv
+            .tool_message("contents", call.id, "read".to_string())
+            .unwrap();
+        let result_id = result.id;
+        conv.add_message(result).unwrap();
+
+        let err = conv.remove_message(assistant_id).unwrap_err();
+        assert!(err.to_string().contains("orphan tool result"));
+        assert_eq!(conv.messages.len(), 4);
+
+        // Removing the result first frees the assistant message.
+        let removed = conv.remove_message(result_id).unwrap().unwrap();
+        assert_eq!(removed.role, MessageRole::Tool);
+        let removed = conv.remove_message(assistant_id).unwrap().unwrap();
+        assert_eq!(removed.id, assistant_id);
+        assert_eq!(conv.messages.len(), 2);
+        assert!(conv.remove_message(assistant_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_insert_and_replace_message_keep_ordering() {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.system_message("sys")).unwrap();
+        conv.add_message(conv.user_message("read a")).unwrap();
+        let call = ToolCall::new("read", r#"{"path": "a"}"#);
+        let assistant = conv
+            .assistant_message("")
+            .with_tool_calls(vec![call.clone()])
+            .unwrap();
+        conv.add_message(assistant).unwrap();
+        let result = conv
+            .tool_message("contents", call.id.clone(), "read".to_string())
+            .unwrap();
+        conv.add_message(result).unwrap();
+
+        // Non-system messages cannot go ahead of the system prompt, and a
+        // system message cannot follow a user message.
+        assert!(conv.insert_message_at(0, conv.user_message("x")).is_err());
+        assert!(conv.insert_message_at(2, conv.system_message("x")).is_err());
+        // Nothing may split a tool call from its result.
+        assert!(conv.insert_message_at(3, conv.user_message("x")).is_err());
+        // A tool result needs a call before it.
+        let early = conv
+            .tool_message("early", call.id, "read".to_string())
+            .unwrap();
+        assert!(conv.insert_message_at(1, early).is_err());
+        assert!(conv.insert_message_at(9, conv.user_message("x")).is_err());
+        assert_eq!(conv.messages.len(), 4);
+
+        conv.insert_message_at(1, conv.system_message("more rules"))
+            .unwrap();
+        let note = conv.user_message("context first");
+        let note_id = note.id;
+        conv.insert_message_at(2, note).unwrap();
+        let roles: Vec<MessageRole> = conv.messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                MessageRole::System,
+                MessageRole::System,
+                MessageRole::User,
+                MessageRole::User,
+                MessageRole::Assistant,
+                MessageRole::Tool
+            ]
+        );
+
+        conv.replace_message(note_id, "edited").unwrap();
+        assert_eq!(conv.messages[2].content, "edited");
+        assert!(conv.replace_message(Uuid::new_v4(), "nope").is_err());
+    }
+
+    #[test]
+    fn test_sort_messages_by_time_keeps_tool_results_with_calls() {
+        let mut conv = Conversation::new();
+        let start = Utc::now();
+        let at = |mut message: Message, seconds: i64| {
+            message.timestamp = start + chrono::Duration::seconds(seconds);
+            message
+        };
+        let call = ToolCall::new("read", r#"{"path": "a"}"#);
+        let system = at(conv.system_message("sys"), 0);
+        let question = at(conv.user_message("read a"), 1);
+        let assistant = at(
+            conv.assistant_message("")
+                .with_tool_calls(vec![call.clone()])
+                .unwrap(),
+            2,
+        );
+        // Recorded with a skewed clock, so it looks older than its call.
+        let result = at(
+            conv.tool_message("contents", call.id, "read".to_string())
+                .unwrap(),
+            -5,
+        );
+        let answer = at(conv.assistant_message("a says hi"), 4);
+        let follow_up = at(conv.user_message("thanks"), 4);
+        let expected = [
+            system.id,
+            question.id,
+            assistant.id,
+            result.id,
+            answer.id,
+            follow_up.id,
+        ];
+        conv.messages = Arc::new(vec![answer, result, follow_up, question, system, assistant]);
+
+        let by_time: Vec<Uuid> = conv.get_messages_by_time().iter().map(|m| m.id).collect();
+        // Equal timestamps keep their stored order: answer before follow_up.
+        assert_eq!(by_time, expected);
+        assert_eq!(conv.messages[0].id, expected[4]);
+
+        let report = conv.sort_messages_by_time();
+        assert!(report.reordered);
+        assert_eq!(
+            report.duplicate_timestamps,
+            vec![vec![expected[4], expected[5]]]
+        );
+        let sorted: Vec<Uuid> = conv.messages.iter().map(|m| m.id).collect();
+        assert_eq!(sorted, expected);
+
+        let report = conv.sort_messages_by_time();
+        assert!(!report.reordered);
+    }
+
+    #[test]
+    fn test_trim_to_token_budget_noop_when_within_budget() {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.system_message("sys")).unwrap();
+        conv.add_message(conv.user_message("hi")).unwrap();
+
+        assert!(conv.trim_to_token_budget(100, |_| 10).is_empty());
+        assert_eq!(conv.messages.len(), 2);
+
+        let removed = conv.trim_to_token_budget(0, |_| 10);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(conv.messages[0].role, MessageRole::System);
+    }
+
+    #[test]
+    fn test_redact_replaces_secrets_and_keeps_surrounding_text() {
+        let conv_id = Uuid::new_v4();
+        let message = Message::assistant(
+            conv_id,
+            "Using key sk-test1234567890abcdefXYZ for the call.",
+        )
+        .with_tool_calls(vec![ToolCall::new(
+            "fetch",
+            r#"{"url":"https://api.test","auth":"Bearer abc.DEF-123"}"#,
+        )])
+        .unwrap();
+
+        let redacted = message.redact(default_redaction_patterns());
+
+        assert_eq!(redacted.content, "Using key [REDACTED] for the call.");
+        assert_eq!(
+            redacted.tool_calls[0].function.arguments,
+            r#"{"url":"https://api.test","auth":"[REDACTED]"}"#
+        );
+        assert_eq!(redacted.id, message.id);
+        assert!(message.content.contains("sk-test"));
+    }
+
+    #[test]
+    fn test_turns_group_tool_usage_between_user_and_reply() {
+        let mut conv = Conversation::new();
+        let call = ToolCall::new("get_weather", r#"{"city":"Oslo"}"#);
+        let messages = vec![
+            conv.system_message("Be brief."),
+            conv.user_message("Weather in Oslo?"),
+            conv.assistant_message("")
+                .with_tool_calls(vec![call.clone()])
+                .unwrap(),
+            conv.tool_message("4C, rain", call.id, "get_weather".to_string())
+                .unwrap(),
+            conv.assistant_message("4C and raining."),
+            conv.user_message("Thanks"),
+        ];
+        for message in messages {
+            conv.add_message(message).unwrap();
+        }
+
+        let turns: Vec<Turn<'_>> = conv.turns().collect();
+
+        assert_eq!(turns.len(), 3);
+        let preamble = &turns[0];
+        assert!(preamble.user.is_none() && preamble.assistant.is_none());
+        assert_eq!(preamble.instructions[0].content, "Be brief.");
+
+        let exchange = &turns[1];
+        assert_eq!(exchange.user.unwrap().content, "Weather in Oslo?");
+        let roles: Vec<MessageRole> = exchange.steps.iter().map(|m| m.role).collect();
+        assert_eq!(roles, [MessageRole::Assistant, MessageRole::Tool]);
+        assert_eq!(exchange.steps[1].content, "4C, rain");
+        assert_eq!(exchange.assistant.unwrap().content, "4C and raining.");
+
+        let trailing = &turns[2];
+        assert_eq!(trailing.user.unwrap().content, "Thanks");
+        assert!(trailing.steps.is_empty());
+        assert!(trailing.assistant.is_none());
+    }
+
+    #[test]
+    fn test_turns_without_preamble() {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.user_message("hi")).unwrap();
+        conv.add_message(conv.assistant_message("hello")).unwrap();
+
+        let turns: Vec<Turn<'_>> = conv.turns().collect();
+
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].user.unwrap().content, "hi");
+        assert_eq!(turns[0].assistant.unwrap().content, "hello");
+        assert!(Conversation::new().turns().next().is_none());
+    }
+
+    #[test]
+    fn test_normalize_merges_system_messages() {
+        let mut conv = Conversation::new();
+        let first = conv.system_message("You are terse.");
+        let first_id = first.id;
+        conv.add_message(first).unwrap();
+        let second = conv.system_message("Answer in French.");
+        let second_id = second.id;
+        conv.add_message(second).unwrap();
+        conv.add_message(conv.user_message("hi")).unwrap();
+
+        let report = conv.normalize();
+
+        assert_eq!(report.merged, vec![second_id]);
+        assert!(!report.moved_to_front);
+        assert_eq!(conv.messages.len(), 2);
+        assert_eq!(conv.messages[0].id, first_id);
+        assert_eq!(
+            conv.messages[0].content,
+            "You are terse.\n\nAnswer in French."
+        );
+        assert!(conv.normalize().is_unchanged());
+    }
+
+    #[test]
+    fn test_normalize_moves_misplaced_system_message_to_front() {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.user_message("hi")).unwrap();
+        conv.add_message(conv.system_message("Be kind.")).unwrap();
+        conv.add_message(conv.assistant_message("hello")).unwrap();
+
+        let report = conv.normalize();
+
+        assert!(report.moved_to_front);
+        assert!(report.merged.is_empty());
+        let roles: Vec<MessageRole> = conv.messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            [
+                MessageRole::System,
+                MessageRole::User,
+                MessageRole::Assistant
+            ]
+        );
+        assert_eq!(conv.messages[0].content, "Be kind.");
+    }
+
+    #[test]
+    fn test_search_by_regex() {
+        let mut conv = Conversation::new();
+        conv.add_message(conv.user_message("build failed: error E0308"))
+            .unwrap();
+        conv.add_message(conv.assistant_message("That is a type mismatch."))
+            .unwrap();
+        conv.add_message(conv.user_message("now it says error E0599"))
+            .unwrap();
+
+        let query = MessageQuery::new().with_pattern(Regex::new(r"E0\d{3}").unwrap());
+        let found: Vec<&str> = conv
+            .search(&query)
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(
+            found,
+            ["build failed: error E0308", "now it says error E0599"]
+        );
+
+        let query = MessageQuery::new().with_text("mismatch");
+        assert_eq!(conv.search(&query).len(), 1);
+        assert_eq!(conv.search(&MessageQuery::new()).len(), 3);
+    }
+
+    #[test]
+    fn test_search_by_role_and_time_range() {
+        let start = Utc::now();
+        let at = |minutes| start + chrono::Duration::minutes(minutes);
+
+        let mut conv = Conversation::new();
+        // Added out of timestamp order to check results are chronological.
+        for (minutes, role) in [
+            (3, MessageRole::User),
+            (0, MessageRole::User),
+            (1, MessageRole::Assistant),
+            (2, MessageRole::User),
+            (5, MessageRole::User),
+        ] {
+            let mut message = Message::new(conv.id, role, format!("t{minutes}"));
+            message.timestamp = at(minutes);
+            conv.add_message(message).unwrap();
+        }
+        let tool_call = ToolCall::new("read", "{}");
+        let mut assistant = conv
+            .assistant_message("calling")
+            .with_tool_calls(vec![tool_call])
+            .unwrap();
+        assistant.timestamp = at(4);
+        conv.add_message(assistant).unwrap();
+
+        let query = MessageQuery::new()
+            .with_role(MessageRole::User)
+            .with_time_range(Some(at(1)), Some(at(5)));
+        let found: Vec<&str> = conv
+            .search(&query)
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(found, ["t2", "t3"]);
+
+        let query = MessageQuery::new().with_tool_calls(true);
+        let found: Vec<&str> = conv
+            .search(&query)
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(found, ["calling"]);
+    }
+
+    /// Builds `sys -> q -> a1`, then edits the question: `sys -> q2 -> a2`.
+    fn branched_conversation() -> (Conversation, [Uuid; 5]) {
+        let mut conv = Conversation::new().with_title("branchy");
+        let sys = conv.system_message("sys");
+        let q = conv.user_message("q");
+        let a1 = conv.assistant_message("a1");
+        let q2 = conv.user_message("q2").with_parent(sys.id);
+        let a2 = conv.assistant_message("a2");
+        let ids = [sys.id, q.id, a1.id, q2.id, a2.id];
+        for message in [sys, q, a1, q2, a2] {
+            conv.add_message(message).unwrap();
+        }
+        (conv, ids)
+    }
+
+    #[test]
+    fn test_branches_enumerates_leaf_to_root_paths() {
+        let (conv, [sys, q, a1, q2, a2]) = branched_conversation();
+        assert_eq!(conv.branches(), vec![vec![a1, q, sys], vec![a2, q2, sys]]);
+
+        let mut linear = Conversation::new();
+        let first = linear.user_message("hi");
+        let first_id = first.id;
+        linear.add_message(first).unwrap();
+        assert_eq!(linear.branches(), vec![vec![first_id]]);
+        assert!(Conversation::new().branches().is_empty());
+    }
+
+    #[test]
+    fn test_fork_at_preserves_ancestors_with_new_ids() {
+        let (conv, [_, _, a1, _, a2]) = branched_conversation();
+
+        let fork = conv.fork_at(a1).unwrap();
+        assert_ne!(fork.id, conv.id);
+        assert_eq!(fork.title.as_deref(), Some("branchy"));
+        let contents: Vec<&str> = fork.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["sys", "q", "a1"]);
+        assert!(fork.messages.iter().all(|m| m.conversation_id == fork.id));
+        assert!(fork.messages.iter().all(|m| m.id != a1));
+        assert_eq!(fork.messages[0].parent_id, None);
+        assert_eq!(fork.messages[2].parent_id, Some(fork.messages[1].id));
+        assert_eq!(fork.branches().len(), 1);
+
+        let other = conv.fork_at(a2).unwrap();
+        let contents: Vec<&str> = other.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["sys", "q2", "a2"]);
+
+        // The source conversation is untouched.
+        assert_eq!(conv.messages.len(), 5);
+        assert!(conv.fork_at(Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_add_message_rejects_unknown_parent() {
+        let mut conv = Conversation::new();
+        let orphan = conv.user_message("hi").with_parent(Uuid::new_v4());
+        assert!(conv.add_message(orphan).is_err());
+        assert!(conv.messages.is_empty());
+    }
+
+    #[test]
+    fn test_developer_role_serde_roundtrip() {
+        let conv = Conversation::new();
+        let msg = conv.developer_message("Answer tersely.");
+        assert_eq!(msg.role, MessageRole::Developer);
+        assert!(msg.role.is_instruction());
+        assert!(MessageRole::System.is_instruction());
+        assert!(!MessageRole::User.is_instruction());
+
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["role"], "developer");
+        let back: Message = serde_json::from_value(json).unwrap();
+        assert_eq!(back.role, MessageRole::Developer);
+        assert_eq!(back.content, "Answer tersely.");
+    }
+
+    #[test]
+    fn test_conversation_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conversation.json");
+
+        let mut conv = Conversation::new().with_title("Notebook");
+        conv.metadata
+            .insert("cell".to_string(), serde_json::json!(3));
+        conv.add_message(conv.system_message("Be helpful")).unwrap();
+        let tool_call = ToolCall::new("get_weather", r#"{"location": "Oslo"}"#);
+        let mut assistant = conv
+            .assistant_message("Checking.")
+            .with_tool_calls(vec![tool_call.clone()])
+            .unwrap();
+        assistant.reasoning = Some(ReasoningContent::with_signature("Need weather", "sig"));
+        conv.add_message(assistant).unwrap();
+        let tool_msg = conv
+            .tool_message("cold", tool_call.id, "get_weather".to_string())
+            .unwrap();
+        conv.add_message(tool_msg).unwrap();
+        conv.set_status(ConversationStatus::Archived).unwrap();
+
+        conv.save_to_path(&path).unwrap();
+        let loaded = Conversation::load_from_path(&path).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&conv).unwrap()
+        );
+        assert_eq!(
+            loaded.messages[1].tool_calls[0].function.name,
+            "get_weather"
+        );
+        assert_eq!(
+            loaded.messages[1]
+                .reasoning
+                .as_ref()
+                .unwrap()
+                .signature
+                .as_deref(),
+            Some("sig")
+        );
+    }
+
+    #[test]
+    fn test_status_transition_matrix() {
+        use ConversationStatus::{Active, Archived, Completed, Deleted, Paused};
+
+        let all = [Active, Paused, Completed, Archived, Deleted];
+        let allowed: &[(ConversationStatus, &[ConversationStatus])] = &[
+            (Active, &[Active, Paused, Completed, Archived, Deleted]),
+            (Paused, &[Active, Paused, Completed, Archived, Deleted]),
+            (Completed, &[Completed, Archived, Deleted]),
+            (Archived, &[Archived, Deleted]),
+            (Deleted, &[Deleted]),
+        ];
+        for (from, targets) in allowed {
+            for to in &all {
+                let mut conv = Conversation::new();
+                conv.status = from.clone();
+                let result = conv.set_status(to.clone());
+                if targets.contains(to) {
+                    assert!(result.is_ok(), "{from:?} -> {to:?} should be allowed");
+                    assert_eq!(&conv.status, to);
+                } else {
+                    let err = result.unwrap_err().to_string();
+                    assert!(
+                        err.contains(&format!("from {from:?} to {to:?}")),
+                        "unexpected error for {from:?} -> {to:?}: {err}"
+                    );
+                    assert_eq!(&conv.status, from);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reopen_and_message_acceptance() {
+        let mut conv = Conversation::new();
+        conv.set_status(ConversationStatus::Paused).unwrap();
+        conv.add_message(conv.user_message("still open")).unwrap();
+        assert!(conv.reopen().is_err());
+
+        conv.set_status(ConversationStatus::Completed).unwrap();
+        let err = conv.add_message(conv.user_message("late")).unwrap_err();
+        assert!(err.to_string().contains("does not accept new messages"));
+        let err = conv.set_status(ConversationStatus::Active).unwrap_err();
+        assert!(err.to_string().contains("use reopen()"));
+
+        conv.set_status(ConversationStatus::Archived).unwrap();
+        assert!(conv.add_message(conv.user_message("late")).is_err());
+        conv.reopen().unwrap();
+        assert_eq!(conv.status, ConversationStatus::Active);
+        conv.add_message(conv.user_message("back")).unwrap();
+        assert_eq!(conv.messages.len(), 2);
+
+        conv.set_status(ConversationStatus::Deleted).unwrap();
+        assert!(conv.reopen().is_err());
+        assert!(conv.add_message(conv.user_message("gone")).is_err());
+    }
+
+    #[test]
+    fn test_to_markdown_snapshot() {
+        let mut conv = Conversation::new().with_title("Math *help*");
+        conv.add_message(conv.system_message("Be precise."))
+            .unwrap();
+        conv.add_message(conv.user_message("What is 2*3?\n- show work\n1. briefly"))
+            .unwrap();
+        let call = ToolCall::new("calc", r#"{"expr":"2*3"}"#);
+        let mut assistant = conv
+            .assistant_message("Let me use `calc`.")
+            .with_tool_calls(vec![call.clone()])
+            .unwrap();
+        assistant.reasoning = Some(ReasoningContent::new("Multiply_them"));
+        conv.add_message(assistant).unwrap();
+        conv.add_message(conv.tool_message("6", call.id, "calc".to_string()).unwrap())
+            .unwrap();
+        conv.add_message(
+            conv.tool_message("```stray```", "orphan".to_string(), "ls".to_string())
+                .unwrap(),
+        )
+        .unwrap();
+        conv.add_message(conv.assistant_message("It is 6."))
+            .unwrap();
+
+        let expected = r#"# Math \*help\*
+
+## System
+
+Be precise.
+
+## User
+
+What is 2\*3?
+\- show work
+1\. briefly
+
+## Assistant
+
+<details>
+<summary>Reasoning</summary>
+
+Multiply\_them
+
+</details>
+
+Let me use \`calc\`.
+
+**Tool call** `calc`
+
+```json
+{
+  "expr": "2*3"
+}
+```
+
+**Result**
+
+```
+6
+```
+
+## Tool result `ls`
+
+````
+```stray```
+````
+
+## Assistant
+
+It is 6.
+"#;
+        assert_eq!(conv.to_markdown(), expected);
+
+        let without = conv.to_markdown_with(false);
+        assert!(!without.contains("<details>"));
+        assert!(without.contains("**Result**\n\n```\n6\n```"));
+    }
+
+    #[test]
+    fn test_total_usage_sums_assistant_turns() {
+        let usage = |prompt: u32, completion: u32, cost: Option<f64>| Usage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+            cost,
+            input_tokens_details: None,
+            output_tokens_details: None,
+        };
+
+        let mut conv = Conversation::new();
+        assert_eq!(conv.total_usage().total_tokens, 0);
+        assert!(conv.total_usage().cost.is_none());
+
+        conv.add_message(conv.user_message("Hi")).unwrap();
+        conv.add_message(conv.assistant_message_with_usage("Hello", usage(10, 5, Some(0.25))))
+            .unwrap();
+        conv.add_message(conv.user_message("More")).unwrap();
+        conv.add_message(conv.assistant_message("No usage reported"))
+            .unwrap();
+        conv.add_message(conv.assistant_message_with_usage("Sure", usage(30, 7, None)))
+            .unwrap();
+        // Usage on non-assistant messages is ignored.
+        let mut user = conv.user_message("Stray");
+        user.usage = Some(usage(1000, 1000, None));
+        conv.add_message(user).unwrap();
+
+        let total = conv.total_usage();
+        assert_eq!(total.prompt_tokens, 40);
+        assert_eq!(total.completion_tokens, 12);
+        assert_eq!(total.total_tokens, 52);
+        assert_eq!(total.cost, Some(0.25));
+    }
+
+    #[test]
+    fn test_conversation_load_rejects_foreign_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conversation.json");
+
+        let mut conv = Conversation::new();
+        conv.add_message(conv.user_message("hi")).unwrap();
+        let stray = Message::user(Uuid::new_v4(), "stray");
+        let stray_id = stray.id;
+        Arc::make_mut(&mut conv.messages).push(stray);
+        conv.save_to_path(&path).unwrap();
+
+        let err = Conversation::load_from_path(&path).unwrap_err();
+        assert!(err.to_string().contains(&stray_id.to_string()));
+    }
+
     #[test]
     fn test_message_with_metadata_typed() {
         let conv_id = Uuid::new_v4();
@@ -623,6 +2932,28 @@ mod tests {
         assert_eq!(msg.metadata.get("count"), Some(&serde_json::json!(42)));
     }
 
+    #[test]
+    fn test_message_set_and_get_meta_typed() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Route {
+            region: String,
+            attempt: u32,
+        }
+
+        let mut msg = Message::assistant(Uuid::new_v4(), "Hi");
+        let route = Route {
+            region: "eu-west-1".to_string(),
+            attempt: 2,
+        };
+        msg.set_meta("route", &route).unwrap();
+        msg.set_meta(CACHED_METADATA_KEY, true).unwrap();
+
+        assert_eq!(msg.get_meta::<Route>("route"), Some(route));
+        assert_eq!(msg.get_meta::<bool>(CACHED_METADATA_KEY), Some(true));
+        assert_eq!(msg.get_meta::<u64>(CACHED_METADATA_KEY), None);
+        assert_eq!(msg.get_meta::<u64>(LATENCY_MS_METADATA_KEY), None);
+    }
+
     #[test]
     fn test_tool_call_with_json_args() {
         let tool_call = ToolCall::new("complex_function", r#"{"key": "value"}"#);
@@ -663,13 +2994,14 @@ mod proptests {
         #[test]
         fn message_serialization_roundtrip(
             content in ".*",
-            role_idx in 0usize..4,
+            role_idx in 0usize..5,
         ) {
             let conv_id = Uuid::new_v4();
             let role = match role_idx {
                 0 => MessageRole::User,
                 1 => MessageRole::Assistant,
                 2 => MessageRole::System,
+                3 => MessageRole::Developer,
                 _ => MessageRole::Tool,
             };
 
@@ -744,7 +3076,7 @@ mod proptests {
             };
 
             let mut conv = Conversation::new();
-            conv.set_status(status.clone());
+            prop_assert!(conv.set_status(status.clone()).is_ok());
 
             assert_eq!(conv.status, status);
         }
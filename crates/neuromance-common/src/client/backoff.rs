@@ -0,0 +1,265 @@
+//! Pluggable delay policies for retrying failed requests.
+//!
+//! Clients ask a [`BackoffStrategy`] how long to wait before each retry and
+//! whether to retry at all. [`RetryConfig`] builds an [`ExponentialBackoff`]
+//! from its fields unless a custom strategy is set with
+//! [`RetryConfig::with_strategy`].
+
+use std::collections::hash_map::RandomState;
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use super::RetryConfig;
+
+/// Decides the delay before each retry, and when to stop retrying.
+pub trait BackoffStrategy: Debug + Send + Sync {
+    /// The delay before the next retry, or `None` to give up.
+    ///
+    /// `attempt` counts the retries already made, so it is 0 before the
+    /// first retry. `retry_after` is the server's `Retry-After` hint, if it
+    /// sent one; implementations should prefer it to their own schedule.
+    fn next_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration>;
+}
+
+/// Delays that grow by `multiplier` each attempt, capped at `max_delay`.
+///
+/// With `jitter`, each delay is drawn uniformly from the upper half of the
+/// computed delay so concurrent clients don't retry in lockstep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExponentialBackoff {
+    /// Delay before the first retry.
+    pub initial_delay: Duration,
+    /// Upper bound for any delay, including a server's `Retry-After`.
+    pub max_delay: Duration,
+    /// Growth factor per attempt.
+    pub multiplier: f64,
+    /// Retries allowed before giving up.
+    pub max_retries: u32,
+    /// Whether to randomize each delay.
+    pub jitter: bool,
+}
+
+impl ExponentialBackoff {
+    /// Doubling delays from `initial_delay` up to `max_delay`, without jitter.
+    #[must_use]
+    pub const fn new(initial_delay: Duration, max_delay: Duration, max_retries: u32) -> Self {
+        Self {
+            initial_delay,
+            max_delay,
+            multiplier: 2.0,
+            max_retries,
+            jitter: false,
+        }
+    }
+
+    /// Sets the growth factor per attempt.
+    #[must_use]
+    pub const fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets whether each delay is randomized.
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+}
+
+impl From<&RetryConfig> for ExponentialBackoff {
+    fn from(config: &RetryConfig) -> Self {
+        Self {
+            initial_delay: config.initial_delay,
+            max_delay: config.max_delay,
+            multiplier: config.backoff_multiplier,
+            max_retries: config.max_retries,
+            jitter: config.jitter,
+        }
+    }
+}
+
+impl BackoffStrategy for ExponentialBackoff {
+    fn next_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        if let Some(retry_after) = retry_after {
+            return Some(retry_after.min(self.max_delay));
+        }
+        let delay = scaled(self.initial_delay, self.multiplier, attempt, self.max_delay);
+        Some(if self.jitter {
+            random_between(delay / 2, delay)
+        } else {
+            delay
+        })
+    }
+}
+
+/// AWS-style decorrelated jitter, in a stateless form so one strategy can be
+/// shared by concurrent requests.
+///
+/// Each delay is drawn uniformly between `base` and an upper bound that
+/// starts at three times `base` and triples every attempt, capped at
+/// `max_delay`. Delays spread out quickly while staying unpredictable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecorrelatedJitter {
+    /// Smallest delay, and the seed of the growing upper bound.
+    pub base: Duration,
+    /// Upper bound for any delay, including a server's `Retry-After`.
+    pub max_delay: Duration,
+    /// Retries allowed before giving up.
+    pub max_retries: u32,
+}
+
+impl DecorrelatedJitter {
+    #[must_use]
+    pub const fn new(base: Duration, max_delay: Duration, max_retries: u32) -> Self {
+        Self {
+            base,
+            max_delay,
+            max_retries,
+        }
+    }
+}
+
+impl BackoffStrategy for DecorrelatedJitter {
+    fn next_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        if let Some(retry_after) = retry_after {
+            return Some(retry_after.min(self.max_delay));
+        }
+        let upper = scaled(self.base, 3.0, attempt.saturating_add(1), self.max_delay);
+        Some(random_between(self.base.min(upper), upper))
+    }
+}
+
+/// `initial * multiplier^attempt`, capped at `max`.
+fn scaled(initial: Duration, multiplier: f64, attempt: u32, max: Duration) -> Duration {
+    let factor = multiplier.powi(i32::try_from(attempt).unwrap_or(i32::MAX));
+    Duration::try_from_secs_f64(initial.as_secs_f64() * factor).map_or(max, |delay| delay.min(max))
+}
+
+/// A duration drawn uniformly from `low..=high`.
+fn random_between(low: Duration, high: Duration) -> Duration {
+    let Some(range) = high.checked_sub(low) else {
+        return low;
+    };
+    let span = u64::try_from(range.as_nanos()).unwrap_or(u64::MAX);
+    // Each `RandomState` is freshly keyed, which is random enough for jitter
+    // without pulling in an RNG.
+    let random = RandomState::new().build_hasher().finish();
+    low + Duration::from_nanos(random % span.saturating_add(1))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_exponential_delay_sequence() {
+        let backoff =
+            ExponentialBackoff::new(Duration::from_millis(100), Duration::from_millis(500), 5);
+        let delays: Vec<Option<Duration>> = (0..6).map(|n| backoff.next_delay(n, None)).collect();
+        assert_eq!(
+            delays,
+            vec![
+                Some(Duration::from_millis(100)),
+                Some(Duration::from_millis(200)),
+                Some(Duration::from_millis(400)),
+                Some(Duration::from_millis(500)),
+                Some(Duration::from_millis(500)),
+                None,
+            ]
+        );
+
+        let tripling = backoff.with_multiplier(3.0);
+        assert_eq!(
+            tripling.next_delay(1, None),
+            Some(Duration::from_millis(300))
+        );
+    }
+
+    #[test]
+    fn test_exponential_jitter_stays_in_upper_half() {
+        let backoff = ExponentialBackoff::new(Duration::from_secs(1), Duration::from_secs(30), 10)
+            .with_jitter(true);
+        for attempt in 0..5 {
+            let full = Duration::from_secs(1 << attempt);
+            let delay = backoff.next_delay(attempt, None).unwrap();
+            assert!(delay >= full / 2 && delay <= full, "{delay:?} vs {full:?}");
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_bounds() {
+        let base = Duration::from_millis(100);
+        let backoff = DecorrelatedJitter::new(base, Duration::from_secs(2), 6);
+        let uppers = [300, 900, 2000, 2000, 2000, 2000].map(Duration::from_millis);
+        for (attempt, upper) in (0..).zip(uppers) {
+            for _ in 0..20 {
+                let delay = backoff.next_delay(attempt, None).unwrap();
+                assert!(
+                    delay >= base && delay <= upper,
+                    "attempt {attempt}: {delay:?}"
+                );
+            }
+        }
+        assert_eq!(backoff.next_delay(6, None), None);
+    }
+
+    #[test]
+    fn test_retry_after_takes_precedence() {
+        let hint = Some(Duration::from_secs(7));
+        let exponential =
+            ExponentialBackoff::new(Duration::from_millis(100), Duration::from_secs(30), 3)
+                .with_jitter(true);
+        let decorrelated =
+            DecorrelatedJitter::new(Duration::from_millis(100), Duration::from_secs(30), 3);
+        assert_eq!(
+            exponential.next_delay(0, hint),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            decorrelated.next_delay(2, hint),
+            Some(Duration::from_secs(7))
+        );
+
+        // The hint is still capped, and does not extend the retry budget.
+        let long_hint = Some(Duration::from_secs(120));
+        assert_eq!(
+            exponential.next_delay(0, long_hint),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(decorrelated.next_delay(3, hint), None);
+    }
+
+    #[test]
+    fn test_retry_config_backoff() {
+        let config = RetryConfig {
+            jitter: false,
+            ..RetryConfig::default()
+        };
+        assert_eq!(
+            config.backoff().next_delay(1, None),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(config.backoff().next_delay(3, None), None);
+
+        let custom = config.with_strategy(DecorrelatedJitter::new(
+            Duration::from_millis(10),
+            Duration::from_millis(10),
+            1,
+        ));
+        assert_eq!(
+            custom.backoff().next_delay(0, None),
+            Some(Duration::from_millis(10))
+        );
+        assert_eq!(custom.backoff().next_delay(1, None), None);
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+/// What a client and its configured model can do, so callers can adapt a
+/// request before sending it (e.g. drop images for a text-only model).
+///
+/// Flags describe what the client implements for its provider, not every
+/// feature the provider offers: a provider that accepts images is still
+/// reported without `vision` if the client does not send them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Capabilities {
+    /// Tool/function calling.
+    pub tools: bool,
+    /// Streaming responses.
+    pub streaming: bool,
+    /// Several tool calls in a single assistant turn.
+    pub parallel_tool_calls: bool,
+    /// Image inputs.
+    pub vision: bool,
+    /// Output constrained to a JSON schema via
+    /// [`ResponseFormat::JsonSchema`](super::ResponseFormat::JsonSchema).
+    pub json_schema: bool,
+    /// Reasoning or thinking content surfaced alongside the reply.
+    pub reasoning: bool,
+    /// Prompt prefixes cached by the provider, automatically or via markers
+    /// the client sends.
+    pub prompt_caching: bool,
+    /// Maximum context window in tokens, when known for the model.
+    pub context_window: Option<usize>,
+}
+
+/// Context windows of common models, matched by longest prefix.
+const CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("gpt-4o", 128_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-5", 400_000),
+    ("gpt-oss", 131_072),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4-mini", 200_000),
+    ("claude", 200_000),
+    ("gemini-1.5-pro", 2_097_152),
+    ("gemini-1.5-flash", 1_048_576),
+    ("gemini-2", 1_048_576),
+    ("mistral-large", 128_000),
+    ("mistral-small", 128_000),
+    ("command-r", 128_000),
+    ("command-a", 256_000),
+    ("llama-3.3", 128_000),
+];
+
+/// The context window of `model` in tokens, if it is a known model.
+///
+/// Provider and vendor prefixes are ignored, so `openai/gpt-4o` and the
+/// Bedrock id `us.anthropic.claude-sonnet-4-20250514-v1:0` both resolve.
+#[must_use]
+pub fn context_window_for(model: &str) -> Option<usize> {
+    let suffixes = std::iter::once(model).chain(
+        model
+            .match_indices(['/', '.'])
+            .map(|(at, _)| &model[at + 1..]),
+    );
+    suffixes
+        .flat_map(|suffix| {
+            CONTEXT_WINDOWS
+                .iter()
+                .filter(move |(prefix, _)| suffix.starts_with(prefix))
+        })
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, window)| *window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_window_for_known_and_prefixed_models() {
+        assert_eq!(context_window_for("gpt-4o-mini"), Some(128_000));
+        assert_eq!(context_window_for("gpt-4.1-nano"), Some(1_047_576));
+        assert_eq!(context_window_for("openai/gpt-oss-120b"), Some(131_072));
+        assert_eq!(
+            context_window_for("us.anthropic.claude-sonnet-4-20250514-v1:0"),
+            Some(200_000)
+        );
+        assert_eq!(context_window_for("gemini-1.5-pro-002"), Some(2_097_152));
+        assert_eq!(context_window_for("my-finetune"), None);
+    }
+}
@@ -1,17 +1,21 @@
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
+use super::backoff::{BackoffStrategy, ExponentialBackoff};
 use super::enums::resolve_model_prefix;
 
 /// Configuration for exponential backoff retry behavior.
 ///
 /// This struct controls how failed requests are retried with increasing delays
 /// between attempts. Supports optional jitter to avoid thundering herd problems.
+/// For other policies, plug in a [`BackoffStrategy`] with
+/// [`with_strategy`](Self::with_strategy).
 ///
 /// # Examples
 ///
@@ -26,6 +30,7 @@ use super::enums::resolve_model_prefix;
 ///     max_delay: Duration::from_secs(60),
 ///     backoff_multiplier: 2.0,
 ///     jitter: true,
+///     strategy: None,
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -40,6 +45,27 @@ pub struct RetryConfig {
     pub backoff_multiplier: f64,
     /// Whether to add random jitter to retry delays to prevent thundering herd.
     pub jitter: bool,
+    /// Custom delay policy. When set it decides every retry delay and when to
+    /// stop, and the fields above are ignored.
+    pub strategy: Option<Arc<dyn BackoffStrategy>>,
+}
+
+impl RetryConfig {
+    /// Retries with `strategy` instead of exponential backoff.
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: impl BackoffStrategy + 'static) -> Self {
+        self.strategy = Some(Arc::new(strategy));
+        self
+    }
+
+    /// The policy clients retry with: the custom strategy if one is set,
+    /// otherwise an [`ExponentialBackoff`] built from this config.
+    #[must_use]
+    pub fn backoff(&self) -> Arc<dyn BackoffStrategy> {
+        self.strategy
+            .clone()
+            .unwrap_or_else(|| Arc::new(ExponentialBackoff::from(self)))
+    }
 }
 
 impl Default for RetryConfig {
@@ -50,6 +76,7 @@ impl Default for RetryConfig {
             max_delay: Duration::from_secs(30),
             backoff_multiplier: 2.0,
             jitter: true,
+            strategy: None,
         }
     }
 }
@@ -230,6 +257,92 @@ fn default_token_header() -> String {
     "X-Tokenizer-Token".to_string()
 }
 
+/// AWS credentials for providers that sign requests with `SigV4`, such as
+/// Bedrock.
+///
+/// Like [`Config::api_key`], these are never serialized.
+///
+/// # Examples
+///
+/// ```
+/// use neuromance_common::{AwsCredentials, Config};
+///
+/// let config = Config::new("bedrock", "anthropic.claude-3-5-sonnet-20240620-v1:0")
+///     .with_aws_credentials(AwsCredentials::new(
+///         "AKIDEXAMPLE",
+///         "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+///         "us-east-1",
+///     ));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AwsCredentials {
+    /// Access key id (`AKIA...` or `ASIA...`).
+    pub access_key_id: String,
+    /// Secret access key.
+    pub secret_access_key: SecretString,
+    /// Session token, for temporary credentials.
+    pub session_token: Option<SecretString>,
+    /// Region the requests are sent to (e.g., `"us-east-1"`).
+    pub region: String,
+}
+
+impl AwsCredentials {
+    /// Creates long-term credentials for `region`.
+    pub fn new(
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+        region: impl Into<String>,
+    ) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: SecretString::from(secret_access_key.into()),
+            session_token: None,
+            region: region.into(),
+        }
+    }
+
+    /// Adds the session token that accompanies temporary credentials.
+    #[must_use]
+    pub fn with_session_token(mut self, session_token: impl Into<String>) -> Self {
+        self.session_token = Some(SecretString::from(session_token.into()));
+        self
+    }
+}
+
+/// An Azure `OpenAI` deployment to address requests to.
+///
+/// Azure routes by deployment name rather than model, and pins the API
+/// surface with an `api-version` query parameter. The resource endpoint
+/// (e.g., `https://my-resource.openai.azure.com`) goes in
+/// [`Config::base_url`].
+///
+/// # Examples
+///
+/// ```
+/// use neuromance_common::{AzureDeployment, Config};
+///
+/// let config = Config::new("azure", "gpt-4o")
+///     .with_base_url("https://my-resource.openai.azure.com")
+///     .with_azure_deployment(AzureDeployment::new("gpt-4o-prod", "2024-10-21"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AzureDeployment {
+    /// Deployment name chosen when the model was deployed.
+    pub deployment: String,
+    /// Value of the `api-version` query parameter (e.g., `"2024-10-21"`).
+    pub api_version: String,
+}
+
+impl AzureDeployment {
+    /// Creates a deployment reference.
+    pub fn new(deployment: impl Into<String>, api_version: impl Into<String>) -> Self {
+        Self {
+            deployment: deployment.into(),
+            api_version: api_version.into(),
+        }
+    }
+}
+
 /// Configuration for an LLM client.
 ///
 /// This struct holds both connection details (API keys, URLs) and default
@@ -288,6 +401,18 @@ pub struct Config {
     pub stop_sequences: Option<Vec<String>>,
     /// Additional metadata to attach to all requests.
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Extra HTTP headers sent with every request, for gateways that
+    /// require their own (e.g. `X-Org-Id`).
+    ///
+    /// Headers the client sets itself (authentication, content type, and the
+    /// proxy token header) take precedence; a same-named entry here is
+    /// dropped.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra_headers: HashMap<String, String>,
+    /// Models requests may name. When set, a request for any other model is
+    /// rejected before it is sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_models: Option<Vec<String>>,
     /// Tokenizer proxy configuration (optional).
     ///
     /// When configured, requests are routed through the proxy which
@@ -299,6 +424,26 @@ pub struct Config {
     /// is stored in `api_key`.
     #[serde(default)]
     pub proxy: Option<ProxyConfig>,
+    /// Truncate generated content at the first stop sequence on the client.
+    ///
+    /// For OpenAI-compatible servers that accept `stop` but keep generating
+    /// past it. Off by default since conforming servers already stop.
+    #[serde(default)]
+    pub enforce_stop_client_side: bool,
+    /// Repair streamed tool arguments that arrive as truncated JSON instead
+    /// of dropping the call.
+    ///
+    /// Closes unbalanced strings, brackets, and braces and trims trailing
+    /// commas before parsing. Off by default; honored by the Anthropic and
+    /// Bedrock Claude streams.
+    #[serde(default)]
+    pub repair_tool_arguments: bool,
+    /// AWS credentials for `SigV4`-signed providers (optional).
+    #[serde(skip)]
+    pub aws: Option<AwsCredentials>,
+    /// Azure `OpenAI` deployment and API version (optional).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub azure: Option<AzureDeployment>,
 }
 
 impl Default for Config {
@@ -318,7 +463,13 @@ impl Default for Config {
             presence_penalty: None,
             stop_sequences: None,
             metadata: HashMap::new(),
+            extra_headers: HashMap::new(),
+            allowed_models: None,
             proxy: None,
+            enforce_stop_client_side: false,
+            repair_tool_arguments: false,
+            aws: None,
+            azure: None,
         }
     }
 }
@@ -537,7 +688,7 @@ impl Config {
     ///
     /// * `retry_config` - The retry configuration
     #[must_use]
-    pub const fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
         self.retry_config = retry_config;
         self
     }
@@ -585,6 +736,78 @@ impl Config {
         self
     }
 
+    /// Sets the AWS credentials used by `SigV4`-signed providers.
+    ///
+    /// # Arguments
+    ///
+    /// * `credentials` - Access key, secret, and region
+    #[must_use]
+    pub fn with_aws_credentials(mut self, credentials: AwsCredentials) -> Self {
+        self.aws = Some(credentials);
+        self
+    }
+
+    /// Sets the Azure `OpenAI` deployment requests are addressed to.
+    ///
+    /// # Arguments
+    ///
+    /// * `deployment` - Deployment name and API version
+    #[must_use]
+    pub fn with_azure_deployment(mut self, deployment: AzureDeployment) -> Self {
+        self.azure = Some(deployment);
+        self
+    }
+
+    /// Sets whether stop sequences are enforced client-side.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to truncate content at the first stop sequence
+    #[must_use]
+    pub const fn with_enforce_stop_client_side(mut self, enabled: bool) -> Self {
+        self.enforce_stop_client_side = enabled;
+        self
+    }
+
+    /// Adds an HTTP header sent with every request.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Header name, e.g. `X-Org-Id`
+    /// * `value` - Header value
+    #[must_use]
+    pub fn with_extra_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Restricts requests to the given models, catching typos in a
+    /// per-request model override before a network round-trip.
+    ///
+    /// # Arguments
+    ///
+    /// * `models` - Every model name requests may use
+    #[must_use]
+    pub fn with_allowed_models<I, S>(mut self, models: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_models = Some(models.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets whether truncated streamed tool arguments are repaired.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to attempt repair before giving up on a call
+    #[must_use]
+    pub const fn with_repair_tool_arguments(mut self, enabled: bool) -> Self {
+        self.repair_tool_arguments = enabled;
+        self
+    }
+
     /// Validates the configuration parameters.
     ///
     /// Checks that all numeric parameters are within their valid ranges
@@ -16,6 +16,8 @@ pub enum Provider {
     ChatCompletions,
     /// `OpenAI` Responses API
     Responses,
+    /// Google Gemini `generateContent` API
+    Gemini,
 }
 
 impl fmt::Display for Provider {
@@ -24,6 +26,7 @@ impl fmt::Display for Provider {
             Self::Anthropic => write!(f, "anthropic"),
             Self::ChatCompletions => write!(f, "chat_completions"),
             Self::Responses => write!(f, "responses"),
+            Self::Gemini => write!(f, "gemini"),
         }
     }
 }
@@ -36,6 +39,7 @@ impl FromStr for Provider {
             "anthropic" => Ok(Self::Anthropic),
             "chat_completions" => Ok(Self::ChatCompletions),
             "responses" => Ok(Self::Responses),
+            "gemini" => Ok(Self::Gemini),
             other => Err(format!("unknown provider: {other}")),
         }
     }
@@ -63,6 +67,7 @@ impl FromStr for Provider {
 /// | `mistral`           | `ChatCompletions` | `https://api.mistral.ai/v1`             |
 /// | `deepseek`          | `ChatCompletions` | `https://api.deepseek.com/v1`           |
 /// | `xai`               | `ChatCompletions` | `https://api.x.ai/v1`                   |
+/// | `gemini`            | `Gemini`          | *(none — client uses its default)*      |
 /// | `chat_completions`  | `ChatCompletions` | *(none — client uses its default)*      |
 /// | `responses`         | `Responses`       | *(none — client uses its default)*      |
 #[must_use]
@@ -90,6 +95,7 @@ pub fn resolve_model_prefix(prefix: &str) -> Option<(Provider, Option<&'static s
             Some("https://api.deepseek.com/v1"),
         )),
         "xai" => Some((Provider::ChatCompletions, Some("https://api.x.ai/v1"))),
+        "gemini" => Some((Provider::Gemini, None)),
         "chat_completions" => Some((Provider::ChatCompletions, None)),
         "responses" => Some((Provider::Responses, None)),
         _ => None,
@@ -118,6 +124,16 @@ pub enum ToolChoice {
     /// The model must call a tool rather than responding directly.
     #[serde(rename = "required")]
     Required,
+    /// Require the model to call at least one tool, optionally at most one.
+    ///
+    /// Maps to Anthropic's `any`; providers without a parallel-call switch
+    /// treat it as [`ToolChoice::Required`] and ignore `disable_parallel`.
+    #[serde(rename = "any")]
+    Any {
+        /// Limit the response to a single tool call.
+        #[serde(default)]
+        disable_parallel: bool,
+    },
     /// Force the model to call a specific function by name.
     ///
     /// # Example
@@ -141,6 +157,7 @@ impl fmt::Display for ToolChoice {
             Self::Auto => write!(f, "auto"),
             Self::None => write!(f, "none"),
             Self::Required => write!(f, "required"),
+            Self::Any { .. } => write!(f, "any"),
             Self::Function { name } => write!(f, "{name}"),
         }
     }
@@ -151,7 +168,7 @@ impl From<ToolChoice> for serde_json::Value {
         match tool_choice {
             ToolChoice::Auto => Self::String("auto".to_string()),
             ToolChoice::None => Self::String("none".to_string()),
-            ToolChoice::Required => Self::String("required".to_string()),
+            ToolChoice::Required | ToolChoice::Any { .. } => Self::String("required".to_string()),
             ToolChoice::Function { name } => {
                 serde_json::json!({
                     "type": "function",
@@ -164,6 +181,62 @@ impl From<ToolChoice> for serde_json::Value {
     }
 }
 
+/// Output format the model must produce.
+///
+/// # Example
+///
+/// ```
+/// use neuromance_common::ResponseFormat;
+///
+/// let format = ResponseFormat::JsonSchema {
+///     name: "weather".to_string(),
+///     schema: serde_json::json!({
+///         "type": "object",
+///         "properties": { "temp_c": { "type": "number" } },
+///         "required": ["temp_c"],
+///     }),
+///     strict: true,
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Free-form text, the default.
+    Text,
+    /// Any valid JSON object.
+    JsonObject,
+    /// JSON matching a schema.
+    JsonSchema {
+        /// Name of the output, e.g. `weather_report`.
+        name: String,
+        /// JSON Schema the output must match.
+        schema: serde_json::Value,
+        /// Whether the provider must follow the schema exactly.
+        strict: bool,
+    },
+}
+
+impl From<ResponseFormat> for serde_json::Value {
+    fn from(format: ResponseFormat) -> Self {
+        match format {
+            ResponseFormat::Text => serde_json::json!({ "type": "text" }),
+            ResponseFormat::JsonObject => serde_json::json!({ "type": "json_object" }),
+            ResponseFormat::JsonSchema {
+                name,
+                schema,
+                strict,
+            } => serde_json::json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": name,
+                    "schema": schema,
+                    "strict": strict
+                }
+            }),
+        }
+    }
+}
+
 /// Reasoning effort level for thinking models (with support).
 ///
 /// Controls how much compute the model spends on reasoning before responding.
@@ -219,6 +292,13 @@ pub enum FinishReason {
     /// This typically indicates an internal error in the model.
     #[serde(rename = "model_error")]
     ModelError,
+    /// The run was cut short on the caller's side, not by the model.
+    ///
+    /// Providers never return this. Core sets it when the user cancels a
+    /// streamed reply, which keeps whatever streamed so far, and on the note
+    /// it appends when a run stops at its token budget.
+    #[serde(rename = "cancelled")]
+    Cancelled,
 }
 
 impl fmt::Display for FinishReason {
@@ -229,6 +309,7 @@ impl fmt::Display for FinishReason {
             Self::ToolCalls => write!(f, "tool_calls"),
             Self::ContentFilter => write!(f, "content_filter"),
             Self::ModelError => write!(f, "model_error"),
+            Self::Cancelled => write!(f, "cancelled"),
         }
     }
 }
@@ -243,7 +324,32 @@ impl FromStr for FinishReason {
             "tool_calls" => Ok(Self::ToolCalls),
             "content_filter" => Ok(Self::ContentFilter),
             "model_error" => Ok(Self::ModelError),
+            "cancelled" => Ok(Self::Cancelled),
             _ => anyhow::bail!("Unknown finish reason: {s}"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_finish_reason_cancelled_round_trips() {
+        let json = serde_json::to_string(&FinishReason::Cancelled).unwrap();
+        assert_eq!(json, r#""cancelled""#);
+        assert_eq!(
+            serde_json::from_str::<FinishReason>(&json).unwrap(),
+            FinishReason::Cancelled
+        );
+
+        let label = FinishReason::Cancelled.to_string();
+        assert_eq!(label, "cancelled");
+        assert_eq!(
+            label.parse::<FinishReason>().unwrap(),
+            FinishReason::Cancelled
+        );
+    }
+}
@@ -1,11 +1,19 @@
+mod backoff;
+mod capabilities;
 mod config;
 mod enums;
+mod pricing;
 mod request;
 mod response;
 mod usage;
 
-pub use config::{Config, ProxyConfig, RetryConfig};
-pub use enums::{FinishReason, Provider, ReasoningEffort, ToolChoice, resolve_model_prefix};
-pub use request::ChatRequest;
-pub use response::{ChatChunk, ChatResponse};
+pub use backoff::{BackoffStrategy, DecorrelatedJitter, ExponentialBackoff};
+pub use capabilities::{Capabilities, context_window_for};
+pub use config::{AwsCredentials, AzureDeployment, Config, ProxyConfig, RetryConfig};
+pub use enums::{
+    FinishReason, Provider, ReasoningEffort, ResponseFormat, ToolChoice, resolve_model_prefix,
+};
+pub use pricing::{ModelPricing, PricingTable};
+pub use request::{ChatRequest, FieldError, ValidationError};
+pub use response::{ChatChunk, ChatResponse, ResponseChoice};
 pub use usage::{CacheMetrics, InputTokensDetails, OutputTokensDetails, Usage};
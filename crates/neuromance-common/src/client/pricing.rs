@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::Usage;
+
+/// Per-million-token prices in USD for one model.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// Price per million uncached input tokens.
+    pub input_per_million: f64,
+    /// Price per million output tokens (including reasoning tokens).
+    pub output_per_million: f64,
+    /// Price per million input tokens served from cache.
+    pub cached_input_per_million: f64,
+}
+
+impl ModelPricing {
+    /// Creates pricing from input, output, and cached-input rates per million tokens.
+    #[must_use]
+    pub const fn new(input: f64, output: f64, cached_input: f64) -> Self {
+        Self {
+            input_per_million: input,
+            output_per_million: output,
+            cached_input_per_million: cached_input,
+        }
+    }
+
+    /// Computes the USD cost of `usage` at these rates.
+    ///
+    /// `prompt_tokens` includes cached tokens, so only the uncached remainder
+    /// is billed at the full input rate.
+    #[must_use]
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        let cached = usage
+            .input_tokens_details
+            .as_ref()
+            .map_or(0, |d| d.cached_tokens)
+            .min(usage.prompt_tokens);
+        let uncached = usage.prompt_tokens - cached;
+
+        let micro_dollars = f64::from(uncached).mul_add(
+            self.input_per_million,
+            f64::from(cached).mul_add(
+                self.cached_input_per_million,
+                f64::from(usage.completion_tokens) * self.output_per_million,
+            ),
+        );
+        micro_dollars / 1_000_000.0
+    }
+}
+
+/// Maps model names to their [`ModelPricing`].
+///
+/// Lookups match the longest registered prefix, so a dated snapshot such as
+/// `gpt-4o-2024-08-06` resolves to the `gpt-4o` entry while `gpt-4o-mini`
+/// keeps its own.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PricingTable {
+    models: HashMap<String, ModelPricing>,
+}
+
+impl PricingTable {
+    /// Creates an empty pricing table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a table with list prices for common `OpenAI` and Anthropic models.
+    ///
+    /// Prices change; override entries with [`Self::insert`] as needed.
+    #[must_use]
+    pub fn with_defaults() -> Self {
+        [
+            ("gpt-4o", ModelPricing::new(2.50, 10.00, 1.25)),
+            ("gpt-4o-mini", ModelPricing::new(0.15, 0.60, 0.075)),
+            ("gpt-4.1", ModelPricing::new(2.00, 8.00, 0.50)),
+            ("gpt-4.1-mini", ModelPricing::new(0.40, 1.60, 0.10)),
+            ("gpt-4.1-nano", ModelPricing::new(0.10, 0.40, 0.025)),
+            ("gpt-5", ModelPricing::new(1.25, 10.00, 0.125)),
+            ("gpt-5-mini", ModelPricing::new(0.25, 2.00, 0.025)),
+            ("gpt-5-nano", ModelPricing::new(0.05, 0.40, 0.005)),
+            ("o3", ModelPricing::new(2.00, 8.00, 0.50)),
+            ("o4-mini", ModelPricing::new(1.10, 4.40, 0.275)),
+            ("claude-opus-4", ModelPricing::new(15.00, 75.00, 1.50)),
+            ("claude-opus-4-5", ModelPricing::new(5.00, 25.00, 0.50)),
+            ("claude-sonnet-4", ModelPricing::new(3.00, 15.00, 0.30)),
+            ("claude-3-5-haiku", ModelPricing::new(0.80, 4.00, 0.08)),
+            ("claude-haiku-4-5", ModelPricing::new(1.00, 5.00, 0.10)),
+        ]
+        .into_iter()
+        .fold(Self::new(), |table, (model, pricing)| {
+            table.with_model(model, pricing)
+        })
+    }
+
+    /// Adds or replaces the pricing for a model name prefix.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>, pricing: ModelPricing) -> Self {
+        self.insert(model, pricing);
+        self
+    }
+
+    /// Adds or replaces the pricing for a model name prefix in place.
+    pub fn insert(&mut self, model: impl Into<String>, pricing: ModelPricing) {
+        self.models.insert(model.into(), pricing);
+    }
+
+    /// Looks up the pricing whose name is the longest prefix of `model`.
+    #[must_use]
+    pub fn get(&self, model: &str) -> Option<&ModelPricing> {
+        self.models
+            .iter()
+            .filter(|(name, _)| model.starts_with(name.as_str()))
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, pricing)| pricing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::client::InputTokensDetails;
+
+    fn usage(prompt: u32, cached: u32, completion: u32) -> Usage {
+        Usage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+            cost: None,
+            input_tokens_details: Some(InputTokensDetails {
+                cached_tokens: cached,
+                cache_creation_tokens: 0,
+            }),
+            output_tokens_details: None,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-12,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn cached_tokens_are_billed_at_the_cached_rate() {
+        let pricing = ModelPricing::new(2.0, 8.0, 0.5);
+
+        // 600k uncached * $2 + 400k cached * $0.50 + 100k output * $8
+        let cost = pricing.cost(&usage(1_000_000, 400_000, 100_000));
+        assert_close(cost, 1.2 + 0.2 + 0.8);
+
+        // Fully cached prompt costs a quarter of the uncached prompt.
+        let all_cached = pricing.cost(&usage(1_000_000, 1_000_000, 0));
+        let none_cached = pricing.cost(&usage(1_000_000, 0, 0));
+        assert_close(all_cached * 4.0, none_cached);
+    }
+
+    #[test]
+    fn cached_tokens_beyond_prompt_are_clamped() {
+        let pricing = ModelPricing::new(2.0, 8.0, 0.5);
+        assert_close(pricing.cost(&usage(100, 500, 0)), 100.0 * 0.5 / 1e6);
+    }
+
+    #[test]
+    fn lookup_uses_longest_prefix() {
+        let table = PricingTable::with_defaults();
+        let input_rate = |model: &str| table.get(model).unwrap().input_per_million;
+        assert_close(input_rate("gpt-4o-2024-08-06"), 2.50);
+        assert_close(input_rate("gpt-4o-mini"), 0.15);
+        assert_close(input_rate("claude-opus-4-5-20251101"), 5.00);
+        assert_close(input_rate("claude-opus-4-1"), 15.00);
+        assert!(table.get("unknown-model").is_none());
+    }
+
+    #[test]
+    fn with_cost_sets_usage_cost() {
+        let table = PricingTable::new().with_model("m", ModelPricing::new(1.0, 2.0, 0.1));
+        let mut u = usage(1_000_000, 500_000, 1_000_000);
+
+        let cost = u.with_cost("m-large", &table).unwrap();
+        assert_close(cost, 0.5 + 0.05 + 2.0);
+        assert_eq!(u.cost, Some(cost));
+
+        let mut unknown = usage(10, 0, 10);
+        assert!(unknown.with_cost("other", &table).is_none());
+        assert!(unknown.cost.is_none());
+    }
+}
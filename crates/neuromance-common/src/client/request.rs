@@ -3,11 +3,12 @@ use std::fmt;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use super::config::Config;
-use super::enums::ToolChoice;
+use super::enums::{ResponseFormat, ToolChoice};
 use crate::chat::Message;
-use crate::features::{ReasoningLevel, ThinkingMode};
+use crate::features::{CachePolicy, ReasoningLevel, ThinkingMode};
 use crate::tools::Tool;
 
 /// A request for a chat completion from an LLM.
@@ -61,10 +62,40 @@ pub struct ChatRequest {
     pub presence_penalty: Option<f32>,
     /// Sequences that will stop generation when encountered.
     pub stop: Option<Vec<String>>,
+    /// Number of completions to generate.
+    ///
+    /// Only Chat Completions honours this; extra completions come back in
+    /// [`ChatResponse::additional_choices`](super::ChatResponse::additional_choices).
+    /// Streams carry the first completion only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    /// Seed for best-effort deterministic sampling.
+    ///
+    /// Chat Completions sends it as `seed` and reports the backend's
+    /// `system_fingerprint` in [`ChatResponse::metadata`](super::ChatResponse::metadata),
+    /// which changes when the same seed may no longer reproduce a result.
+    /// The Responses API and Anthropic have no equivalent and ignore it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Bias added to the logits of the given token ids, from -100 to 100.
+    ///
+    /// Keys are token ids in the model's tokenizer, as strings. Chat
+    /// Completions sends it as `logit_bias`; other providers ignore it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<HashMap<String, f32>>,
     /// Tools available for the model to call.
     pub tools: Option<Vec<Tool>>,
     /// Strategy for tool selection.
     pub tool_choice: Option<ToolChoice>,
+    /// Structured output format the response must follow.
+    ///
+    /// - **Chat Completions**: Sent as `response_format`
+    /// - **Anthropic**: [`ResponseFormat::JsonSchema`] is enforced by forcing a
+    ///   single tool call whose input schema is the requested schema; `chat`
+    ///   returns that input as the message content, while streams carry it as
+    ///   the tool call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
     /// Whether to stream the response incrementally.
     pub stream: bool,
     /// End-user identifier for tracking and abuse prevention.
@@ -85,41 +116,98 @@ pub struct ChatRequest {
     /// - **Anthropic**: Can influence thinking budget heuristics
     #[serde(default)]
     pub reasoning_level: ReasoningLevel,
+    /// Prompt caching policy.
+    ///
+    /// `None` leaves the choice to the provider client; Anthropic then caches
+    /// with [`CachePolicy::Ephemeral`]. See [`CachePolicy`].
+    pub cache_control: Option<CachePolicy>,
+    /// Also cache the conversation history up to the message before the
+    /// newest one, so agent loops reuse the growing prefix.
+    ///
+    /// Off by default; Anthropic honors it unless `cache_control` is
+    /// [`CachePolicy::None`].
+    #[serde(default)]
+    pub cache_boundary: bool,
     /// Additional metadata to attach to this request.
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
+/// One request field that failed [`ChatRequest::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Name of the offending field, e.g. `"temperature"`.
+    pub field: &'static str,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+/// Every invalid field found by [`ChatRequest::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid chat request: {}", .errors.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join("; "))]
+pub struct ValidationError {
+    /// The offending fields, in declaration order.
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationError {
+    /// Names of the offending fields.
+    pub fn fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.errors.iter().map(|e| e.field)
+    }
+}
+
+/// Collect a [`FieldError`] for each sampling parameter outside its range.
+fn sampling_errors(
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    frequency_penalty: Option<f32>,
+    presence_penalty: Option<f32>,
+) -> Vec<FieldError> {
+    let checks = [
+        ("temperature", "Temperature", temperature, 0.0..=2.0),
+        ("top_p", "top_p", top_p, 0.0..=1.0),
+        (
+            "frequency_penalty",
+            "frequency_penalty",
+            frequency_penalty,
+            -2.0..=2.0,
+        ),
+        (
+            "presence_penalty",
+            "presence_penalty",
+            presence_penalty,
+            -2.0..=2.0,
+        ),
+    ];
+    checks
+        .into_iter()
+        .filter_map(|(field, label, value, range)| {
+            let value = value.filter(|v| !range.contains(v))?;
+            Some(FieldError {
+                field,
+                message: format!(
+                    "{label} must be between {:.1} and {:.1}, got {value}",
+                    range.start(),
+                    range.end()
+                ),
+            })
+        })
+        .collect()
+}
+
 pub(super) fn validate_sampling_params(
     temperature: Option<f32>,
     top_p: Option<f32>,
     frequency_penalty: Option<f32>,
     presence_penalty: Option<f32>,
 ) -> anyhow::Result<()> {
-    if let Some(temp) = temperature
-        && !(0.0..=2.0).contains(&temp)
+    match sampling_errors(temperature, top_p, frequency_penalty, presence_penalty)
+        .into_iter()
+        .next()
     {
-        anyhow::bail!("Temperature must be between 0.0 and 2.0, got {temp}");
+        Some(error) => Err(anyhow::anyhow!(error.message)),
+        None => Ok(()),
     }
-
-    if let Some(top_p) = top_p
-        && !(0.0..=1.0).contains(&top_p)
-    {
-        anyhow::bail!("top_p must be between 0.0 and 1.0, got {top_p}");
-    }
-
-    if let Some(freq) = frequency_penalty
-        && !(-2.0..=2.0).contains(&freq)
-    {
-        anyhow::bail!("frequency_penalty must be between -2.0 and 2.0, got {freq}");
-    }
-
-    if let Some(pres) = presence_penalty
-        && !(-2.0..=2.0).contains(&pres)
-    {
-        anyhow::bail!("presence_penalty must be between -2.0 and 2.0, got {pres}");
-    }
-
-    Ok(())
 }
 
 impl fmt::Display for ChatRequest {
@@ -161,12 +249,18 @@ impl ChatRequest {
             frequency_penalty: None,
             presence_penalty: None,
             stop: None,
+            n: None,
+            seed: None,
+            logit_bias: None,
             tools: None,
             tool_choice: None,
+            response_format: None,
             stream: false,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: HashMap::new(),
         }
     }
@@ -184,12 +278,18 @@ impl ChatRequest {
             frequency_penalty: config.frequency_penalty,
             presence_penalty: config.presence_penalty,
             stop: config.stop_sequences.clone(),
+            n: None,
+            seed: None,
+            logit_bias: None,
             tools: None,
             tool_choice: None,
+            response_format: None,
             stream: false,
             user: None,
             thinking: ThinkingMode::Default,
             reasoning_level: ReasoningLevel::Default,
+            cache_control: None,
+            cache_boundary: false,
             metadata: config.metadata.clone(),
         }
     }
@@ -274,6 +374,61 @@ impl ChatRequest {
         self
     }
 
+    /// Sets the prompt caching policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Which cache markers providers should add
+    #[must_use]
+    pub const fn with_cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_control = Some(policy);
+        self
+    }
+
+    /// Sets whether providers should also cache the conversation history.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to mark the message before the newest one
+    #[must_use]
+    pub const fn with_cache_boundary(mut self, enabled: bool) -> Self {
+        self.cache_boundary = enabled;
+        self
+    }
+
+    /// Sets the sampling seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed providers use to make sampling repeatable
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the logit bias.
+    ///
+    /// # Arguments
+    ///
+    /// * `logit_bias` - Map from token id to a bias between -100 and 100
+    #[must_use]
+    pub fn with_logit_bias(mut self, logit_bias: HashMap<String, f32>) -> Self {
+        self.logit_bias = Some(logit_bias);
+        self
+    }
+
+    /// Sets the end-user identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - Stable id for the end user, for provider abuse monitoring
+    #[must_use]
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
     /// Sets the nucleus sampling threshold.
     ///
     /// # Arguments
@@ -327,6 +482,17 @@ impl ChatRequest {
         self
     }
 
+    /// Sets the number of completions to generate.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - How many completions the provider should return
+    #[must_use]
+    pub const fn with_n(mut self, n: u32) -> Self {
+        self.n = Some(n);
+        self
+    }
+
     /// Sets the tools available for the model to call.
     ///
     /// # Arguments
@@ -349,6 +515,17 @@ impl ChatRequest {
         self
     }
 
+    /// Sets the structured output format for the response.
+    ///
+    /// # Arguments
+    ///
+    /// * `response_format` - Format the model's reply must follow
+    #[must_use]
+    pub fn with_response_format(mut self, response_format: ResponseFormat) -> Self {
+        self.response_format = Some(response_format);
+        self
+    }
+
     /// Enables or disables streaming for this request.
     ///
     /// # Arguments
@@ -448,19 +625,61 @@ impl ChatRequest {
         Ok(())
     }
 
-    /// Validates all configuration parameters
+    /// Validates all request parameters, reporting every invalid field.
+    ///
+    /// Checks that messages are present, the sampling parameters are in range
+    /// (`temperature` 0–2, `top_p` 0–1, penalties −2–2), token limits and `n`
+    /// are non-zero when set, and a [`ToolChoice::Function`] names a provided
+    /// tool.
     ///
     /// # Errors
     ///
-    /// Returns an error if validation fails (empty messages, invalid `temperature`/`top_p` ranges).
-    pub fn validate(&self) -> anyhow::Result<()> {
-        self.validate_has_messages()?;
-        validate_sampling_params(
+    /// Returns a [`ValidationError`] listing each offending field.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = Vec::new();
+        if self.messages.is_empty() {
+            errors.push(FieldError {
+                field: "messages",
+                message: "Chat request must have at least one message".to_string(),
+            });
+        }
+        errors.extend(sampling_errors(
             self.temperature,
             self.top_p,
             self.frequency_penalty,
             self.presence_penalty,
-        )
+        ));
+        for (field, limit) in [
+            ("max_tokens", self.max_tokens),
+            ("max_completion_tokens", self.max_completion_tokens),
+            ("n", self.n),
+        ] {
+            if limit == Some(0) {
+                errors.push(FieldError {
+                    field,
+                    message: format!("{field} must be greater than 0"),
+                });
+            }
+        }
+        if let Some(ToolChoice::Function { name }) = &self.tool_choice {
+            let provided = self
+                .tools
+                .iter()
+                .flatten()
+                .any(|tool| tool.function.name == *name);
+            if !provided {
+                errors.push(FieldError {
+                    field: "tool_choice",
+                    message: format!("tool_choice names function '{name}', which is not in tools"),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
     }
 
     /// Returns whether this request has tools configured.
@@ -584,6 +803,108 @@ mod tests {
         assert!(request.validate_has_messages().is_err());
     }
 
+    fn valid_request() -> ChatRequest {
+        use crate::chat::{Message, MessageRole};
+        use uuid::Uuid;
+
+        ChatRequest::new(vec![Message::new(
+            Uuid::new_v4(),
+            MessageRole::User,
+            "test",
+        )])
+    }
+
+    fn invalid_fields(request: &ChatRequest) -> Vec<&'static str> {
+        request.validate().unwrap_err().fields().collect()
+    }
+
+    #[test]
+    fn chat_request_validate_flags_each_field() {
+        assert!(valid_request().validate().is_ok());
+
+        assert_eq!(invalid_fields(&ChatRequest::new(vec![])), ["messages"]);
+        assert_eq!(
+            invalid_fields(&valid_request().with_temperature(2.5)),
+            ["temperature"]
+        );
+        assert_eq!(
+            invalid_fields(&valid_request().with_temperature(f32::NAN)),
+            ["temperature"]
+        );
+        assert_eq!(invalid_fields(&valid_request().with_top_p(1.5)), ["top_p"]);
+        assert_eq!(
+            invalid_fields(&valid_request().with_presence_penalty(-2.5)),
+            ["presence_penalty"]
+        );
+        assert_eq!(
+            invalid_fields(&valid_request().with_frequency_penalty(3.0)),
+            ["frequency_penalty"]
+        );
+        assert_eq!(
+            invalid_fields(&valid_request().with_max_tokens(0)),
+            ["max_tokens"]
+        );
+        assert_eq!(
+            invalid_fields(&valid_request().with_max_completion_tokens(0)),
+            ["max_completion_tokens"]
+        );
+    }
+
+    #[test]
+    fn chat_request_validate_checks_tool_choice_function() {
+        use crate::tools::{Function, Tool};
+
+        let tool = Tool::builder()
+            .function(Function {
+                name: "get_weather".to_string(),
+                description: "Get the weather".to_string(),
+                parameters: serde_json::json!({}),
+            })
+            .build();
+        let choice = |name: &str| ToolChoice::Function {
+            name: name.to_string(),
+        };
+
+        let matching = valid_request()
+            .with_tools(vec![tool.clone()])
+            .with_tool_choice(choice("get_weather"));
+        assert!(matching.validate().is_ok());
+
+        let unknown = valid_request()
+            .with_tools(vec![tool])
+            .with_tool_choice(choice("get_time"));
+        assert_eq!(invalid_fields(&unknown), ["tool_choice"]);
+
+        let no_tools = valid_request().with_tool_choice(choice("get_weather"));
+        assert_eq!(invalid_fields(&no_tools), ["tool_choice"]);
+        assert!(
+            valid_request()
+                .with_tool_choice(ToolChoice::Required)
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn chat_request_validate_reports_all_fields() {
+        let request = ChatRequest::new(vec![])
+            .with_temperature(-1.0)
+            .with_top_p(2.0)
+            .with_max_tokens(0);
+
+        let err = request.validate().unwrap_err();
+        assert_eq!(
+            err.fields().collect::<Vec<_>>(),
+            ["messages", "temperature", "top_p", "max_tokens"]
+        );
+        assert_eq!(
+            err.to_string(),
+            "invalid chat request: Chat request must have at least one message; \
+             Temperature must be between 0.0 and 2.0, got -1; \
+             top_p must be between 0.0 and 1.0, got 2; max_tokens must be greater than 0"
+        );
+    }
+
     #[test]
     fn chat_request_has_tools() {
         use crate::chat::{Message, MessageRole};
@@ -29,6 +29,7 @@ use crate::chat::Message;
 /// #     created_at: Utc::now(),
 /// #     response_id: Some("resp_123".to_string()),
 /// #     metadata: std::collections::HashMap::new(),
+/// #     additional_choices: Vec::new(),
 /// # };
 /// // Check why generation stopped
 /// if response.finish_reason == Some(FinishReason::Length) {
@@ -51,6 +52,20 @@ pub struct ChatResponse {
     pub response_id: Option<String>,
     /// Additional metadata about this response.
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Completions beyond the first when [`ChatRequest::n`](super::ChatRequest::n)
+    /// asked for more than one, in the provider's order. `message` and
+    /// `finish_reason` describe the first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_choices: Vec<ResponseChoice>,
+}
+
+/// One of several completions generated for a single request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseChoice {
+    /// The generated message.
+    pub message: Message,
+    /// Reason why generation of this completion stopped.
+    pub finish_reason: Option<FinishReason>,
 }
 
 /// A chunk from a streaming chat completion.
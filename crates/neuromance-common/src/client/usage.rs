@@ -1,5 +1,10 @@
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
 use serde::{Deserialize, Serialize};
 
+use super::PricingTable;
+
 /// Token usage statistics for a completion request.
 ///
 /// Tracks the number of tokens consumed by the prompt and completion,
@@ -9,6 +14,19 @@ use serde::{Deserialize, Serialize};
 ///
 /// Different providers may count tokens differently. The `total_tokens`
 /// should always equal `prompt_tokens + completion_tokens`.
+///
+/// Usage adds up with `+`, `+=`, and [`Sum`]: token counts and their
+/// breakdowns are summed, and `cost` stays known only while every term
+/// reports one.
+///
+/// ```
+/// use neuromance_common::Usage;
+///
+/// let turns = [Usage::default(), Usage::default()];
+/// let mut total: Usage = turns.iter().sum();
+/// total += Usage::default();
+/// assert_eq!(total.total_tokens, 0);
+/// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Usage {
     /// Number of tokens in the input prompt.
@@ -67,7 +85,7 @@ const fn is_zero(val: &u32) -> bool {
 ///
 /// Provides additional information about token usage in the model's response,
 /// including reasoning tokens for models that support chain-of-thought.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct OutputTokensDetails {
     /// Number of tokens used for internal reasoning (e.g., chain-of-thought).
     ///
@@ -90,6 +108,87 @@ impl Usage {
             .map_or(0, |d| d.cached_tokens);
         Some(f64::from(cached) / f64::from(self.prompt_tokens))
     }
+
+    /// Computes and stores [`Self::cost`] from `model`'s entry in `pricing`.
+    ///
+    /// Returns the computed cost, or `None` (leaving `cost` untouched) if
+    /// the table has no pricing for `model`.
+    pub fn with_cost(&mut self, model: &str, pricing: &PricingTable) -> Option<f64> {
+        let cost = pricing.get(model)?.cost(self);
+        self.cost = Some(cost);
+        Some(cost)
+    }
+}
+
+/// The zero value. Its `cost` is `Some(0.0)`, since no tokens cost nothing,
+/// so sums starting from it stay priced.
+impl Default for Usage {
+    fn default() -> Self {
+        Self {
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+            cost: Some(0.0),
+            input_tokens_details: None,
+            output_tokens_details: None,
+        }
+    }
+}
+
+impl AddAssign<&Self> for Usage {
+    fn add_assign(&mut self, rhs: &Self) {
+        self.prompt_tokens = self.prompt_tokens.saturating_add(rhs.prompt_tokens);
+        self.completion_tokens = self.completion_tokens.saturating_add(rhs.completion_tokens);
+        self.total_tokens = self.total_tokens.saturating_add(rhs.total_tokens);
+        self.cost = self.cost.zip(rhs.cost).map(|(a, b)| a + b);
+        if let Some(rhs) = &rhs.input_tokens_details {
+            let details = self
+                .input_tokens_details
+                .get_or_insert_with(Default::default);
+            details.cached_tokens = details.cached_tokens.saturating_add(rhs.cached_tokens);
+            details.cache_creation_tokens = details
+                .cache_creation_tokens
+                .saturating_add(rhs.cache_creation_tokens);
+        }
+        if let Some(rhs) = &rhs.output_tokens_details {
+            let details = self
+                .output_tokens_details
+                .get_or_insert_with(Default::default);
+            details.reasoning_tokens = details
+                .reasoning_tokens
+                .saturating_add(rhs.reasoning_tokens);
+        }
+    }
+}
+
+impl AddAssign for Usage {
+    fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs;
+    }
+}
+
+impl Add for Usage {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self += &rhs;
+        self
+    }
+}
+
+impl Sum for Usage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Self> for Usage {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut total, usage| {
+            total += usage;
+            total
+        })
+    }
 }
 
 /// Aggregate cache statistics across multiple LLM requests.
@@ -209,6 +308,72 @@ mod tests {
         assert!(usage.cache_hit_ratio().is_none());
     }
 
+    #[test]
+    fn usage_add_sums_tokens_details_and_cost() {
+        let first = Usage {
+            prompt_tokens: 100,
+            completion_tokens: 20,
+            total_tokens: 120,
+            cost: Some(0.5),
+            input_tokens_details: Some(InputTokensDetails {
+                cached_tokens: 80,
+                cache_creation_tokens: 10,
+            }),
+            output_tokens_details: Some(OutputTokensDetails {
+                reasoning_tokens: 5,
+            }),
+        };
+        let second = Usage {
+            prompt_tokens: 50,
+            completion_tokens: 30,
+            total_tokens: 80,
+            cost: Some(0.25),
+            input_tokens_details: Some(InputTokensDetails {
+                cached_tokens: 40,
+                cache_creation_tokens: 0,
+            }),
+            output_tokens_details: None,
+        };
+
+        let mut total = Usage::default();
+        total += first.clone();
+        total += &second;
+
+        assert_eq!(total.prompt_tokens, 150);
+        assert_eq!(total.completion_tokens, 50);
+        assert_eq!(total.total_tokens, 200);
+        assert_eq!(total.cost, Some(0.75));
+        let input = total.input_tokens_details.as_ref().unwrap();
+        assert_eq!(input.cached_tokens, 120);
+        assert_eq!(input.cache_creation_tokens, 10);
+        assert_eq!(
+            total
+                .output_tokens_details
+                .as_ref()
+                .unwrap()
+                .reasoning_tokens,
+            5
+        );
+        assert_eq!(
+            [first.clone(), second.clone()].iter().sum::<Usage>(),
+            first.clone() + second
+        );
+
+        let unpriced = Usage {
+            cost: None,
+            ..Usage::default()
+        };
+        assert_eq!((first + unpriced).cost, None);
+    }
+
+    #[test]
+    fn usage_default_adds_nothing() {
+        let zero = Usage::default();
+        assert_eq!(zero.total_tokens, 0);
+        assert!(zero.input_tokens_details.is_none());
+        assert_eq!(zero.clone() + Usage::default(), zero);
+    }
+
     #[test]
     fn cache_metrics_default_is_zeroed() {
         let m = CacheMetrics::default();
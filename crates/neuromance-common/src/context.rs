@@ -2,8 +2,8 @@
 //!
 //! [`ContextLedger`] pairs a [`Conversation`] with an append-only log of every
 //! edit applied to it. It is the single funnel through which a conversation's
-//! history is mutated: each edit goes through [`append`](ContextLedger::append)
-//! or [`replace`](ContextLedger::replace), which record an [`EditRecord`] into
+//! history is mutated: each edit goes through [`append`](ContextLedger::append),
+//! [`update`](ContextLedger::update) or [`replace`](ContextLedger::replace), which record an [`EditRecord`] into
 //! [`ContextMetadata`] capturing the [`Operation`], the [`EditSource`] that
 //! produced it (provenance), the roles touched, and the affected message ids.
 //!
@@ -26,6 +26,9 @@ use crate::chat::{Conversation, Message, MessageRole};
 pub enum Operation {
     /// One or more messages appended to the history.
     Append,
+    /// A single message was modified in place (e.g. approver-rewritten tool
+    /// arguments).
+    Update,
     /// The history was rewritten wholesale (e.g. compaction, filtering).
     Replace,
 }
@@ -205,6 +208,34 @@ impl ContextLedger {
             .record_edit(Operation::Append, source, roles, ids, None);
     }
 
+    /// Applies `edit` to the message with id `message_id`, recording one
+    /// [`Operation::Update`] attributed to `source`.
+    ///
+    /// Returns whether the message was found; a missing id records nothing.
+    pub fn update(
+        &mut self,
+        source: EditSource,
+        message_id: Uuid,
+        details: Option<Value>,
+        edit: impl FnOnce(&mut Message),
+    ) -> bool {
+        let messages = Arc::make_mut(&mut self.conversation.messages);
+        let Some(message) = messages.iter_mut().find(|m| m.id == message_id) else {
+            return false;
+        };
+        edit(message);
+        let role = message.role;
+        self.conversation.touch();
+        self.metadata.record_edit(
+            Operation::Update,
+            source,
+            vec![role],
+            vec![message_id],
+            details,
+        );
+        true
+    }
+
     /// Replaces the entire history with `msgs`, recording one
     /// [`Operation::Replace`] attributed to `source`.
     ///
@@ -320,6 +351,26 @@ mod tests {
         assert!(ledger.metadata().is_empty());
     }
 
+    #[test]
+    fn test_update_edits_one_message_and_records_it() {
+        let mut ledger = ledger();
+        let id = conv_id(&ledger);
+        let msg = Message::user(id, "draft");
+        let msg_id = msg.id;
+        ledger.append(EditSource::core(), [msg]);
+
+        assert!(ledger.update(EditSource::core(), msg_id, None, |m| {
+            m.content = "final".to_string();
+        }));
+        assert!(!ledger.update(EditSource::core(), Uuid::new_v4(), None, |_| {}));
+
+        assert_eq!(ledger.messages()[0].content, "final");
+        let records = ledger.metadata().records_for(Operation::Update);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].roles, vec![MessageRole::User]);
+        assert_eq!(records[0].message_ids, vec![msg_id]);
+    }
+
     #[test]
     fn test_replace_swaps_history_and_records_details() {
         let mut ledger = ledger();
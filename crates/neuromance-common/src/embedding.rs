@@ -0,0 +1,23 @@
+//! Text embedding as seen by code that only consumes vectors.
+//!
+//! [`Embedder`] is the part of an embedding client that ranking needs: the
+//! model name, so vectors from different models are never compared, and a
+//! batch call. Crates such as `neuromance-tools` depend on this trait rather
+//! than on `neuromance-client`, which implements it for `dyn EmbeddingClient`
+//! and its concrete embedding clients.
+
+use async_trait::async_trait;
+
+/// Turns text into embedding vectors.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// The error returned when embedding fails.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// The model producing the vectors. Vectors are only comparable when they
+    /// come from the same model.
+    fn embedding_model(&self) -> &str;
+
+    /// Embeds each of `texts`, returning the vectors in input order.
+    async fn embed_texts(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, Self::Error>;
+}
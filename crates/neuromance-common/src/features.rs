@@ -97,6 +97,33 @@ impl ThinkingMode {
     }
 }
 
+/// Prompt caching policy for providers that cache by explicit markers.
+///
+/// - **Anthropic**: Sets `cache_control` on the last system block and the
+///   last tool definition, caching the prompt prefix up to each, and on the
+///   history boundary when `ChatRequest::cache_boundary` is set
+/// - **Other providers**: Ignored; caching is automatic or unsupported
+///
+/// # Examples
+///
+/// ```
+/// use neuromance_common::features::CachePolicy;
+///
+/// let policy = CachePolicy::OneHour;
+/// assert_ne!(policy, CachePolicy::None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CachePolicy {
+    /// Add no cache markers.
+    None,
+    /// Cache with the provider's default lifetime (5 minutes on Anthropic).
+    Ephemeral,
+    /// Cache for one hour.
+    OneHour,
+}
+
 /// Reasoning effort level for models that support it.
 ///
 /// This is an abstraction over provider-specific reasoning controls:
@@ -84,26 +84,43 @@ pub mod agents;
 
 /// Out-of-band delegation lineage propagation for spawned subagents.
 pub mod delegation;
+/// The embedding seam used to rank text by similarity.
+pub mod embedding;
 
 /// The subagent contract (trait + error) for delegated work.
 pub mod subagent;
 /// Task and outcome types for subagent delegation.
 pub mod task;
+/// Conversation presets for common agent roles.
+pub mod template;
+/// Client-side token estimation.
+///
+/// Provides the [`tokens::TokenCounter`] trait for sizing requests before
+/// they are sent.
+pub mod tokens;
 
 pub use agents::{AgentContext, AgentMemory, AgentMessage, AgentResponse, AgentState, AgentStats};
 pub use chat::{
-    Conversation, ConversationStatus, Message, MessageRole, ReasoningContent, TaskStatus,
+    ContentPart, Conversation, ConversationDiff, ConversationStatus, DiffLine, Message,
+    MessageChange, MessageQuery, MessageRole, NormalizeReport, ReasoningContent, TaskStatus,
+    TimeSortReport, Turn,
 };
 pub use client::{
-    CacheMetrics, ChatRequest, ChatResponse, Config, FinishReason, InputTokensDetails,
-    OutputTokensDetails, Provider, ProxyConfig, ReasoningEffort, RetryConfig, ToolChoice, Usage,
+    AwsCredentials, AzureDeployment, BackoffStrategy, CacheMetrics, Capabilities, ChatRequest,
+    ChatResponse, Config, FinishReason, InputTokensDetails, ModelPricing, OutputTokensDetails,
+    PricingTable, Provider, ProxyConfig, ReasoningEffort, ResponseFormat, RetryConfig, ToolChoice,
+    Usage, ValidationError,
 };
 pub use context::{ContextLedger, ContextMetadata, EditRecord, EditSource, Operation};
 pub use delegation::DelegationContext;
-pub use features::{ReasoningLevel, ThinkingMode};
+pub use embedding::Embedder;
+pub use features::{CachePolicy, ReasoningLevel, ThinkingMode};
 pub use hook::{CompactionStats, FnReviewHook, Hook, HookContext, HookOutcome, TurnEnd};
 pub use subagent::{Subagent, SubagentError};
 pub use task::{Outcome, Task};
+pub use template::{ConversationTemplate, TemplateRegistry};
+pub use tokens::{HeuristicTokenCounter, TokenCounter};
 pub use tools::{
-    Function, FunctionCall, ObjectSchema, Parameters, Property, Tool, ToolApproval, ToolCall,
+    Function, FunctionCall, ObjectSchema, Parameters, ParametersBuilder, Property, Tool,
+    ToolApproval, ToolCall,
 };
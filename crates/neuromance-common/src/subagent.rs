@@ -30,6 +30,15 @@ pub enum SubagentError {
     /// A combinator was constructed with no member subagents.
     #[error("combinator requires at least one member subagent")]
     EmptyMembers,
+
+    /// Every attempt was rejected by a verifier.
+    #[error("not verified after {attempts} attempt(s); unmet: {unmet_criteria:?}")]
+    Unverified {
+        /// How many attempts were made.
+        attempts: usize,
+        /// Requirements the last verdict found unmet.
+        unmet_criteria: Vec<String>,
+    },
 }
 
 impl SubagentError {
@@ -49,6 +58,7 @@ pub trait Subagent: Send + Sync {
     ///
     /// # Errors
     /// Returns [`SubagentError`] if the underlying execution fails or, for
-    /// combinators, if no member produced an outcome.
+    /// combinators, if no member produced an outcome or no outcome was
+    /// verified.
     async fn run(&self, task: Task, cancel: CancellationToken) -> Result<Outcome, SubagentError>;
 }
@@ -0,0 +1,237 @@
+//! Reusable conversation presets for common agent roles.
+//!
+//! A [`ConversationTemplate`] pairs a system prompt with sampling hints, so a
+//! role like "coding-assistant" can be started without retyping its prompt.
+//! [`ConversationTemplate::builtin`] returns the presets shipped with this
+//! crate; a [`TemplateRegistry`] holds them alongside custom templates.
+//!
+//! # Example
+//!
+//! ```
+//! use neuromance_common::{Config, Conversation, ConversationTemplate};
+//!
+//! let template = ConversationTemplate::builtin("coding-assistant").unwrap();
+//! let conversation = Conversation::from_template(&template);
+//! let config = template.apply_config_hints(Config::new("openai", "gpt-4o"));
+//!
+//! assert_eq!(conversation.messages.len(), 1);
+//! assert_eq!(config.temperature, Some(0.2));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::chat::{Conversation, Message};
+use crate::client::Config;
+
+/// [`Conversation::metadata`] key holding the name of the template a
+/// conversation was created from.
+pub const TEMPLATE_METADATA_KEY: &str = "template";
+
+const CODING_ASSISTANT_PROMPT: &str = "You are an expert software engineer working in the \
+user's codebase. Read the relevant code before changing it, follow the conventions already \
+in use, and keep changes small and focused. Explain what you changed and why, and say so \
+plainly when you are unsure.";
+
+const RESEARCH_PROMPT: &str = "You are a careful research assistant. Break questions into \
+parts, gather evidence before drawing conclusions, and distinguish established facts from \
+inference. Cite your sources, note where they disagree, and end with a concise summary.";
+
+/// A named system prompt with default sampling hints.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationTemplate {
+    /// Name the template is looked up by, e.g. `"coding-assistant"`.
+    pub name: String,
+    /// One-line summary of the role.
+    #[serde(default)]
+    pub description: String,
+    /// System prompt that seeds every conversation made from the template.
+    pub system_prompt: String,
+    /// Suggested sampling temperature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Suggested maximum tokens per response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
+impl ConversationTemplate {
+    /// Names of the templates returned by [`builtin`](Self::builtin).
+    pub const BUILTIN_NAMES: &'static [&'static str] = &["coding-assistant", "research"];
+
+    /// Creates a template with no description or config hints.
+    pub fn new(name: impl Into<String>, system_prompt: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: String::new(),
+            system_prompt: system_prompt.into(),
+            temperature: None,
+            max_tokens: None,
+        }
+    }
+
+    /// Sets the description.
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the suggested temperature.
+    #[must_use]
+    pub const fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Sets the suggested maximum tokens per response.
+    #[must_use]
+    pub const fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// The preset named `name`, or `None` if there is no such builtin.
+    ///
+    /// See [`BUILTIN_NAMES`](Self::BUILTIN_NAMES).
+    #[must_use]
+    pub fn builtin(name: &str) -> Option<Self> {
+        let template = match name {
+            "coding-assistant" => Self::new(name, CODING_ASSISTANT_PROMPT)
+                .with_description("Reads, writes, and explains code")
+                .with_temperature(0.2),
+            "research" => Self::new(name, RESEARCH_PROMPT)
+                .with_description("Investigates questions and cites sources")
+                .with_temperature(0.5),
+            _ => return None,
+        };
+        Some(template)
+    }
+
+    /// Fill the sampling settings `config` leaves unset from this template's
+    /// hints. Values already set on `config` win.
+    #[must_use]
+    pub fn apply_config_hints(&self, mut config: Config) -> Config {
+        config.temperature = config.temperature.or(self.temperature);
+        config.max_tokens = config.max_tokens.or(self.max_tokens);
+        config
+    }
+}
+
+impl Conversation {
+    /// Creates a conversation seeded with `template`'s system prompt.
+    ///
+    /// The template's name is recorded under [`TEMPLATE_METADATA_KEY`].
+    #[must_use]
+    pub fn from_template(template: &ConversationTemplate) -> Self {
+        let mut conversation = Self::new();
+        conversation.messages = Arc::new(vec![Message::system(
+            conversation.id,
+            template.system_prompt.clone(),
+        )]);
+        conversation.metadata.insert(
+            TEMPLATE_METADATA_KEY.to_string(),
+            serde_json::Value::String(template.name.clone()),
+        );
+        conversation
+    }
+}
+
+/// Templates by name: the builtins plus any registered by the application.
+#[derive(Debug, Clone)]
+pub struct TemplateRegistry {
+    templates: HashMap<String, ConversationTemplate>,
+}
+
+impl Default for TemplateRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateRegistry {
+    /// Creates a registry holding every builtin template.
+    #[must_use]
+    pub fn new() -> Self {
+        let templates = ConversationTemplate::BUILTIN_NAMES
+            .iter()
+            .filter_map(|name| ConversationTemplate::builtin(name))
+            .map(|template| (template.name.clone(), template))
+            .collect();
+        Self { templates }
+    }
+
+    /// Creates a registry with no templates, not even the builtins.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            templates: HashMap::new(),
+        }
+    }
+
+    /// Adds `template`, returning the template it replaced under the same name.
+    pub fn register(&mut self, template: ConversationTemplate) -> Option<ConversationTemplate> {
+        self.templates.insert(template.name.clone(), template)
+    }
+
+    /// The template named `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&ConversationTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Names of every registered template, sorted.
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.templates.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::chat::MessageRole;
+
+    #[test]
+    fn test_conversation_from_builtin_template() {
+        let template = ConversationTemplate::builtin("coding-assistant").unwrap();
+        let conversation = Conversation::from_template(&template);
+
+        assert_eq!(conversation.messages.len(), 1);
+        let system = &conversation.messages[0];
+        assert_eq!(system.role, MessageRole::System);
+        assert_eq!(system.content, CODING_ASSISTANT_PROMPT);
+        assert_eq!(system.conversation_id, conversation.id);
+        assert_eq!(
+            conversation.metadata[TEMPLATE_METADATA_KEY],
+            "coding-assistant"
+        );
+        assert!(ConversationTemplate::builtin("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_registry_holds_builtins_and_custom_templates() {
+        let mut registry = TemplateRegistry::new();
+        assert_eq!(registry.names(), ConversationTemplate::BUILTIN_NAMES);
+
+        let reviewer = ConversationTemplate::new("reviewer", "Review the diff.")
+            .with_temperature(0.0)
+            .with_max_tokens(1024);
+        assert!(registry.register(reviewer).is_none());
+
+        let template = registry.get("reviewer").unwrap();
+        let conversation = Conversation::from_template(template);
+        assert_eq!(conversation.messages[0].content, "Review the diff.");
+
+        let config =
+            template.apply_config_hints(Config::new("openai", "gpt-4o").with_temperature(0.9));
+        assert_eq!(config.temperature, Some(0.9));
+        assert_eq!(config.max_tokens, Some(1024));
+    }
+}
@@ -0,0 +1,168 @@
+//! Client-side token estimation.
+//!
+//! Providers only report usage after a request completes. [`TokenCounter`]
+//! estimates the size of a message or a whole [`ChatRequest`] up front so
+//! callers can check it against a model's context window before sending.
+//!
+//! [`HeuristicTokenCounter`] needs no tokenizer and is good enough for
+//! budgeting. For exact counts, `neuromance-context` implements this trait for
+//! its tokenizer-backed `TokenCounter`.
+//!
+//! # Example
+//!
+//! ```
+//! use neuromance_common::tokens::{HeuristicTokenCounter, TokenCounter};
+//! use neuromance_common::{ChatRequest, Message};
+//! use uuid::Uuid;
+//!
+//! let request = ChatRequest::new(vec![Message::user(Uuid::new_v4(), "Hello!")]);
+//! let counter = HeuristicTokenCounter;
+//!
+//! assert!(counter.fits_within(&request, 128_000));
+//! ```
+
+use crate::chat::Message;
+use crate::client::ChatRequest;
+
+/// Approximate per-message overhead for role markers and formatting.
+pub const MESSAGE_OVERHEAD: usize = 4;
+
+/// Approximate overhead for the structure around each tool call.
+pub const TOOL_CALL_OVERHEAD: usize = 5;
+
+/// Approximate overhead for each tool definition beyond its JSON schema.
+pub const TOOL_DEFINITION_OVERHEAD: usize = 4;
+
+/// Tokens the provider adds to prime the assistant's reply.
+pub const REPLY_PRIMING: usize = 3;
+
+/// Estimates token counts for messages and requests.
+///
+/// Implementors supply [`count_text`](Self::count_text); the message and
+/// request counts are built from it, adding fixed overheads for role markers,
+/// tool calls, and tool definitions.
+pub trait TokenCounter: Send + Sync {
+    /// Counts the tokens in a piece of text.
+    fn count_text(&self, text: &str) -> usize;
+
+    /// Counts a single message: content, tool calls, and tool result metadata
+    /// plus [`MESSAGE_OVERHEAD`].
+    fn count_message(&self, msg: &Message) -> usize {
+        let mut total = self.count_text(&msg.content) + MESSAGE_OVERHEAD;
+
+        for tool_call in &msg.tool_calls {
+            total += self.count_text(&tool_call.function.name);
+            total += self.count_text(tool_call.function.arguments_json());
+            total += TOOL_CALL_OVERHEAD;
+        }
+
+        if let Some(tool_call_id) = &msg.tool_call_id {
+            total += self.count_text(tool_call_id);
+        }
+        if let Some(name) = &msg.name {
+            total += self.count_text(name);
+        }
+
+        total
+    }
+
+    /// Counts everything the request sends to the model: every message, the
+    /// JSON schema of every tool, and [`REPLY_PRIMING`].
+    fn count_request(&self, req: &ChatRequest) -> usize {
+        let messages: usize = req.messages.iter().map(|m| self.count_message(m)).sum();
+        let tools: usize = req
+            .tools
+            .iter()
+            .flatten()
+            .map(|tool| {
+                let schema = serde_json::to_string(&tool.function).unwrap_or_default();
+                self.count_text(&schema) + TOOL_DEFINITION_OVERHEAD
+            })
+            .sum();
+        messages + tools + REPLY_PRIMING
+    }
+
+    /// Returns `true` if the request's estimated size fits in `context_window`.
+    fn fits_within(&self, req: &ChatRequest, context_window: usize) -> bool {
+        self.count_request(req) <= context_window
+    }
+}
+
+/// Tokenizer-free estimate of roughly four characters per token.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicTokenCounter;
+
+impl TokenCounter for HeuristicTokenCounter {
+    fn count_text(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::tools::{Function, Tool, ToolCall};
+
+    #[test]
+    fn test_heuristic_count_text() {
+        let counter = HeuristicTokenCounter;
+        assert_eq!(counter.count_text(""), 0);
+        assert_eq!(counter.count_text("abc"), 1);
+        assert_eq!(counter.count_text("abcd"), 1);
+        assert_eq!(counter.count_text("Hello, world!"), 4);
+    }
+
+    #[test]
+    fn test_count_message_fixtures() {
+        let counter = HeuristicTokenCounter;
+        let conv_id = Uuid::new_v4();
+
+        // 12 chars -> 3 tokens, plus role overhead.
+        let user = Message::user(conv_id, "What is 2+2?");
+        assert_eq!(counter.count_message(&user), 3 + MESSAGE_OVERHEAD);
+
+        // Content "" -> 0; name "calc" -> 1; args {"x":1} (7 chars) -> 2.
+        let assistant = Message::assistant(conv_id, "")
+            .with_tool_calls(vec![ToolCall::new("calc", r#"{"x":1}"#)])
+            .unwrap();
+        assert_eq!(
+            counter.count_message(&assistant),
+            MESSAGE_OVERHEAD + 1 + 2 + TOOL_CALL_OVERHEAD
+        );
+
+        // Content "4" -> 1; id "call_1" -> 2; name "calc" -> 1.
+        let tool = Message::tool(conv_id, "4", "call_1".to_string(), "calc".to_string()).unwrap();
+        assert_eq!(counter.count_message(&tool), 1 + MESSAGE_OVERHEAD + 2 + 1);
+    }
+
+    #[test]
+    fn test_count_request_includes_tool_schemas() {
+        let counter = HeuristicTokenCounter;
+        let conv_id = Uuid::new_v4();
+        let messages = vec![Message::user(conv_id, "What is 2+2?")];
+        let bare = ChatRequest::new(messages.clone());
+        assert_eq!(
+            counter.count_request(&bare),
+            3 + MESSAGE_OVERHEAD + REPLY_PRIMING
+        );
+
+        let function = Function {
+            name: "calc".to_string(),
+            description: "Evaluate arithmetic".to_string(),
+            parameters: serde_json::json!({"type": "object"}),
+        };
+        let schema_tokens = counter.count_text(&serde_json::to_string(&function).unwrap());
+        let with_tools =
+            ChatRequest::new(messages).with_tools(vec![Tool::builder().function(function).build()]);
+        assert_eq!(
+            counter.count_request(&with_tools),
+            counter.count_request(&bare) + schema_tokens + TOOL_DEFINITION_OVERHEAD
+        );
+        assert!(counter.fits_within(&with_tools, 1_000));
+        assert!(!counter.fits_within(&with_tools, 10));
+    }
+}
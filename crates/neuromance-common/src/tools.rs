@@ -12,6 +12,9 @@ use uuid::Uuid;
 pub enum ToolApproval {
     /// The tool call is approved and should be executed.
     Approved,
+    /// The tool call is approved but should run with these JSON arguments
+    /// in place of the model's. The conversation keeps the original call.
+    ApprovedWithArguments(String),
     /// The tool call is denied with a reason.
     Denied(String),
     /// Quit the current operation.
@@ -91,6 +94,19 @@ impl Property {
         }
     }
 
+    /// Creates an integer property.
+    #[must_use]
+    pub fn integer(description: impl Into<String>) -> Self {
+        Self {
+            prop_type: "integer".to_string(),
+            description: description.into(),
+            enum_values: None,
+            items: None,
+            properties: None,
+            required: None,
+        }
+    }
+
     /// Creates a boolean property.
     #[must_use]
     pub fn boolean(description: impl Into<String>) -> Self {
@@ -170,6 +186,108 @@ impl Parameters {
             required,
         }
     }
+
+    /// Starts a [`ParametersBuilder`] with no properties.
+    ///
+    /// ```
+    /// use neuromance_common::tools::Parameters;
+    ///
+    /// let schema: serde_json::Value = Parameters::builder()
+    ///     .string("path", "File to read")
+    ///     .integer("limit", "Maximum lines to return")
+    ///     .required(["path"])
+    ///     .build()
+    ///     .into();
+    /// assert_eq!(schema["properties"]["limit"]["type"], "integer");
+    /// ```
+    #[must_use]
+    pub fn builder() -> ParametersBuilder {
+        ParametersBuilder::default()
+    }
+}
+
+/// Fluent builder for [`Parameters`], the JSON Schema of a tool's arguments.
+///
+/// Adding a property under a name that is already taken replaces it.
+#[derive(Debug, Clone, Default)]
+pub struct ParametersBuilder {
+    properties: HashMap<String, Property>,
+    required: Vec<String>,
+}
+
+impl ParametersBuilder {
+    /// Adds a property built by one of the [`Property`] constructors.
+    #[must_use]
+    pub fn property(mut self, name: impl Into<String>, property: Property) -> Self {
+        self.properties.insert(name.into(), property);
+        self
+    }
+
+    /// Adds a string property.
+    #[must_use]
+    pub fn string(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.property(name, Property::string(description))
+    }
+
+    /// Adds a string property restricted to `values`.
+    #[must_use]
+    pub fn string_enum(
+        self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        values: Vec<&str>,
+    ) -> Self {
+        self.property(name, Property::string_enum(description, values))
+    }
+
+    /// Adds an integer property.
+    #[must_use]
+    pub fn integer(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.property(name, Property::integer(description))
+    }
+
+    /// Adds a number property.
+    #[must_use]
+    pub fn number(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.property(name, Property::number(description))
+    }
+
+    /// Adds a boolean property.
+    #[must_use]
+    pub fn boolean(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.property(name, Property::boolean(description))
+    }
+
+    /// Adds an array property whose items follow `items`.
+    #[must_use]
+    pub fn array(
+        self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        items: ObjectSchema,
+    ) -> Self {
+        self.property(name, Property::array(description, items))
+    }
+
+    /// Marks `names` as required, after any already marked. Names already
+    /// required are not repeated.
+    #[must_use]
+    pub fn required(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        for name in names {
+            let name = name.into();
+            if !self.required.contains(&name) {
+                self.required.push(name);
+            }
+        }
+        self
+    }
+
+    /// Builds the [`Parameters`]; convert it with `.into()` for
+    /// [`Function::parameters`].
+    #[must_use]
+    pub fn build(self) -> Parameters {
+        Parameters::new(self.properties, self.required)
+    }
 }
 
 impl Parameters {
@@ -221,6 +339,110 @@ pub struct Tool {
     pub function: Function,
 }
 
+impl Tool {
+    /// The tool in `OpenAI`'s Chat Completions shape:
+    /// `{"type": "function", "function": {"name", "description", "parameters"}}`.
+    #[must_use]
+    pub fn to_openai_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": self.r#type,
+            "function": {
+                "name": self.function.name,
+                "description": self.function.description,
+                "parameters": self.function.parameters,
+            },
+        })
+    }
+
+    /// The tool in Anthropic's Messages shape:
+    /// `{"name", "description", "input_schema"}`.
+    #[must_use]
+    pub fn to_anthropic_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.function.name,
+            "description": self.function.description,
+            "input_schema": self.function.parameters,
+        })
+    }
+
+    /// Parses a tool in `OpenAI`'s Chat Completions shape, as produced by
+    /// [`to_openai_json`](Self::to_openai_json).
+    ///
+    /// A missing `type` defaults to `"function"`, a missing `description` to
+    /// empty, and missing `parameters` to an empty object schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` has no `function.name` or a field has the
+    /// wrong type.
+    pub fn from_openai_json(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Wire {
+            #[serde(rename = "type", default = "function_type")]
+            r#type: String,
+            function: WireFunction,
+        }
+        #[derive(Deserialize)]
+        struct WireFunction {
+            name: String,
+            #[serde(default)]
+            description: String,
+            #[serde(default = "empty_schema")]
+            parameters: serde_json::Value,
+        }
+
+        let wire = Wire::deserialize(value)?;
+        Ok(Self {
+            r#type: wire.r#type,
+            function: Function {
+                name: wire.function.name,
+                description: wire.function.description,
+                parameters: wire.function.parameters,
+            },
+        })
+    }
+
+    /// Parses a tool in Anthropic's Messages shape, as produced by
+    /// [`to_anthropic_json`](Self::to_anthropic_json).
+    ///
+    /// A missing `description` defaults to empty and a missing
+    /// `input_schema` to an empty object schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` has no `name` or a field has the wrong type.
+    pub fn from_anthropic_json(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Wire {
+            name: String,
+            #[serde(default)]
+            description: String,
+            #[serde(default = "empty_schema")]
+            input_schema: serde_json::Value,
+        }
+
+        let wire = Wire::deserialize(value)?;
+        Ok(Self {
+            r#type: function_type(),
+            function: Function {
+                name: wire.name,
+                description: wire.description,
+                parameters: wire.input_schema,
+            },
+        })
+    }
+}
+
+/// The `type` of every function tool.
+fn function_type() -> String {
+    "function".to_string()
+}
+
+/// A schema for a tool that takes no parameters.
+fn empty_schema() -> serde_json::Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
 /// Represents an invocation of a function with arguments.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct FunctionCall {
@@ -278,14 +500,25 @@ impl ToolCall {
         }
     }
 
-    /// Merges streaming tool-call deltas, concatenating argument fragments.
+    /// Merges streaming tool-call deltas into the calls accumulated so far.
+    ///
+    /// Every streaming client's deltas go through this one function, so the
+    /// contract is:
     ///
-    /// OpenAI-compatible streaming sends `id` and `function.name` only on the
-    /// first chunk for a given tool call; subsequent chunks carry just `index`
-    /// and an `arguments` fragment. When `index` is set, deltas are stitched
-    /// by index (preserving the id and name captured on the first chunk).
-    /// When `index` is `None` (e.g. non-streaming clients that already
-    /// accumulate internally) deltas are matched by `id` instead.
+    /// - **Keyed by `index` when set.** OpenAI-compatible streams send `id`
+    ///   and the start of `function.name` on the first chunk for a slot, then
+    ///   only `index` and an `arguments` fragment. A delta joins the call with
+    ///   the same `index`, even if it arrives interleaved with other slots.
+    /// - **Keyed by `id` otherwise.** Providers that accumulate internally
+    ///   (Anthropic, Responses) emit each call whole with `index: None`; a
+    ///   repeated id extends that call instead of duplicating it.
+    /// - **Arguments concatenate** in arrival order.
+    /// - **Names append** when they arrive in fragments; a delta that repeats
+    ///   the name accumulated so far is ignored. A missing `id` is filled in
+    ///   by the first delta that carries one.
+    /// - **Output is ordered by `index`**, so slots that start out of order
+    ///   still come back in the order the model produced them. Calls without
+    ///   an index sort first and keep their arrival order.
     #[must_use]
     pub fn merge_deltas(mut accumulated: Vec<Self>, deltas: &[Self]) -> Vec<Self> {
         for delta in deltas {
@@ -295,24 +528,23 @@ impl ToolCall {
                 accumulated.iter_mut().find(|tc| tc.id == delta.id)
             };
 
-            if let Some(existing) = existing {
-                existing
-                    .function
-                    .arguments
-                    .push_str(&delta.function.arguments);
-                // Late-arriving id/name (rare, but possible if a provider
-                // emits an arguments-only chunk before the metadata chunk).
-                if existing.id.is_empty() && !delta.id.is_empty() {
-                    existing.id.clone_from(&delta.id);
-                }
-                if existing.function.name.is_empty() && !delta.function.name.is_empty() {
-                    existing.function.name.clone_from(&delta.function.name);
-                }
-            } else {
+            let Some(existing) = existing else {
                 accumulated.push(delta.clone());
+                continue;
+            };
+            existing
+                .function
+                .arguments
+                .push_str(&delta.function.arguments);
+            if existing.id.is_empty() {
+                existing.id.clone_from(&delta.id);
+            }
+            if existing.function.name != delta.function.name {
+                existing.function.name.push_str(&delta.function.name);
             }
         }
 
+        accumulated.sort_by_key(|tc| tc.index);
         accumulated
     }
 }
@@ -324,6 +556,54 @@ mod tests {
 
     use super::*;
 
+    fn weather_tool() -> Tool {
+        Tool::builder()
+            .function(Function {
+                name: "get_weather".to_string(),
+                description: "Current weather for a city".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } },
+                    "required": ["city"],
+                }),
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_tool_openai_json_round_trip() {
+        let tool = weather_tool();
+        let json = tool.to_openai_json();
+        assert_eq!(json["type"], "function");
+        assert_eq!(json["function"]["name"], "get_weather");
+        assert_eq!(json["function"]["parameters"]["required"][0], "city");
+        assert_eq!(Tool::from_openai_json(&json).unwrap(), tool);
+
+        let minimal = serde_json::json!({ "function": { "name": "ping" } });
+        let parsed = Tool::from_openai_json(&minimal).unwrap();
+        assert_eq!(parsed.r#type, "function");
+        assert_eq!(parsed.function.description, "");
+        assert_eq!(parsed.function.parameters["type"], "object");
+
+        assert!(Tool::from_openai_json(&serde_json::json!({ "name": "flat" })).is_err());
+    }
+
+    #[test]
+    fn test_tool_anthropic_json_round_trip() {
+        let tool = weather_tool();
+        let json = tool.to_anthropic_json();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "name": "get_weather",
+                "description": "Current weather for a city",
+                "input_schema": tool.function.parameters,
+            })
+        );
+        assert_eq!(Tool::from_anthropic_json(&json).unwrap(), tool);
+        assert!(Tool::from_anthropic_json(&serde_json::json!({ "input_schema": {} })).is_err());
+    }
+
     #[test]
     fn test_tool_approval_variants() {
         let approved = ToolApproval::Approved;
@@ -462,6 +742,41 @@ mod tests {
         assert_eq!(value["properties"]["x"]["type"], "number");
     }
 
+    #[test]
+    fn test_parameters_builder_matches_hand_written_schema() {
+        let built: serde_json::Value = Parameters::builder()
+            .string("name", "Who to greet")
+            .integer("count", "How many times")
+            .boolean("shout", "Whether to use capitals")
+            .string_enum("tone", "Greeting tone", vec!["warm", "formal"])
+            .required(["name"])
+            .required(["count", "name"])
+            .build()
+            .into();
+
+        let expected = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "description": "Who to greet"},
+                "count": {"type": "integer", "description": "How many times"},
+                "shout": {"type": "boolean", "description": "Whether to use capitals"},
+                "tone": {
+                    "type": "string",
+                    "description": "Greeting tone",
+                    "enum": ["warm", "formal"]
+                }
+            },
+            "required": ["name", "count"]
+        });
+        assert_eq!(built, expected);
+
+        let optional_only: serde_json::Value = Parameters::builder()
+            .number("ratio", "Scale factor")
+            .build()
+            .into();
+        assert_eq!(optional_only["required"], serde_json::json!([]));
+    }
+
     #[test]
     fn test_function_creation() {
         let func = Function {
@@ -717,6 +1032,75 @@ mod tests {
         serde_json::from_str::<serde_json::Value>(&call2.function.arguments)
             .expect("Second call should be valid JSON");
     }
+
+    fn delta(index: u32, id: &str, name: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            id: id.to_string(),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: name.to_string(),
+                arguments: arguments.to_string(),
+            },
+            index: Some(index),
+        }
+    }
+
+    fn merge_one_by_one(deltas: &[ToolCall]) -> Vec<ToolCall> {
+        deltas.iter().fold(Vec::new(), |acc, delta| {
+            ToolCall::merge_deltas(acc, std::slice::from_ref(delta))
+        })
+    }
+
+    #[test]
+    fn test_merge_interleaved_indexed_deltas_with_fragmented_names() {
+        let deltas = [
+            delta(0, "call_a", "get_", ""),
+            delta(1, "call_b", "read", r#"{"pa"#),
+            delta(0, "", "weather", r#"{"city""#),
+            delta(1, "", "", r#"th":"/tmp"}"#),
+            delta(0, "", "", r#":"Oslo"}"#),
+        ];
+
+        let merged = merge_one_by_one(&deltas);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].id, "call_a");
+        assert_eq!(merged[0].function.name, "get_weather");
+        assert_eq!(merged[0].function.arguments, r#"{"city":"Oslo"}"#);
+        assert_eq!(merged[1].id, "call_b");
+        assert_eq!(merged[1].function.name, "read");
+        assert_eq!(merged[1].function.arguments, r#"{"path":"/tmp"}"#);
+    }
+
+    #[test]
+    fn test_merge_orders_calls_by_index() {
+        let deltas = [
+            delta(1, "call_b", "second", "{}"),
+            delta(0, "call_a", "first", "{"),
+            delta(0, "call_a", "first", "}"),
+        ];
+
+        // Same result whether deltas arrive one per chunk or all at once.
+        for merged in [
+            merge_one_by_one(&deltas),
+            ToolCall::merge_deltas(Vec::new(), &deltas),
+        ] {
+            let names: Vec<&str> = merged.iter().map(|tc| tc.function.name.as_str()).collect();
+            assert_eq!(names, ["first", "second"]);
+            assert_eq!(merged[0].function.arguments, "{}");
+        }
+    }
+
+    #[test]
+    fn test_merge_fills_late_id() {
+        let merged =
+            merge_one_by_one(&[delta(0, "", "", r#"{"x""#), delta(0, "call_x", "f", ":1}")]);
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].id, "call_x");
+        assert_eq!(merged[0].function.name, "f");
+        assert_eq!(merged[0].function.arguments, r#"{"x":1}"#);
+    }
 }
 
 #[cfg(test)]
@@ -589,6 +589,7 @@ impl<C: LLMClient> Compactor<C> {
                     MessageRole::User => "User",
                     MessageRole::Assistant => "Assistant",
                     MessageRole::Tool => "Tool",
+                    MessageRole::Developer => "Developer",
                     _ => "Unknown",
                 };
 
@@ -844,6 +845,7 @@ mod tests {
                 created_at: chrono::Utc::now(),
                 response_id: None,
                 metadata: std::collections::HashMap::new(),
+                additional_choices: Vec::new(),
             })
         }
 
@@ -399,6 +399,7 @@ mod tests {
                 created_at: chrono::Utc::now(),
                 response_id: None,
                 metadata: std::collections::HashMap::new(),
+                additional_choices: Vec::new(),
             })
         }
 
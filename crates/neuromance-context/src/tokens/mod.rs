@@ -7,13 +7,16 @@
 //! ([`template`]) and token-level navigation ([`navigation`]).
 
 use hf_hub::{Repo, RepoType, api::tokio::ApiBuilder};
+use neuromance_common::tokens::{
+    HeuristicTokenCounter, MESSAGE_OVERHEAD, TOOL_CALL_OVERHEAD, TokenCounter as CountTokens,
+};
 use neuromance_common::{Conversation, Message};
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tokenizers::Tokenizer;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::error::TokenCounterError;
 
@@ -329,7 +332,7 @@ impl TokenCounter {
 
         // Add tokens for role (approximate overhead: role name + formatting)
         // Most chat formats add ~4 tokens per message for role markers
-        total += 4;
+        total += MESSAGE_OVERHEAD;
 
         // Count tool call tokens if present
         for tool_call in &message.tool_calls {
@@ -340,7 +343,7 @@ impl TokenCounter {
             total += self.count_tokens(tool_call.function.arguments_json())?;
 
             // Overhead for tool call structure (~5 tokens for formatting)
-            total += 5;
+            total += TOOL_CALL_OVERHEAD;
         }
 
         // Count tool metadata if present
@@ -366,6 +369,19 @@ impl TokenCounter {
     }
 }
 
+/// Exact counts from the loaded tokenizer.
+///
+/// Text the tokenizer rejects falls back to [`HeuristicTokenCounter`], since
+/// an estimate is more useful to a budget check than no answer.
+impl CountTokens for TokenCounter {
+    fn count_text(&self, text: &str) -> usize {
+        self.count_tokens(text).unwrap_or_else(|e| {
+            warn!(error = %e, "tokenizer failed; falling back to heuristic count");
+            HeuristicTokenCounter.count_text(text)
+        })
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -418,6 +434,30 @@ mod tests {
         assert_eq!(count, content_tokens + 4);
     }
 
+    #[test]
+    fn test_token_counter_trait_fixtures() {
+        let counter = TokenCounter::from_tokenizer(create_test_tokenizer());
+        let conv_id = uuid::Uuid::new_v4();
+
+        assert_eq!(CountTokens::count_text(&counter, "hello, world!"), 4);
+
+        let user = Message::user(conv_id, "how are you?");
+        assert_eq!(counter.count_message(&user), 4 + MESSAGE_OVERHEAD);
+        assert_eq!(
+            counter.count_message(&user),
+            counter.count_message_tokens(&user).unwrap()
+        );
+
+        let assistant = Message::assistant(conv_id, "the weather is sunny today");
+        assert_eq!(counter.count_message(&assistant), 5 + MESSAGE_OVERHEAD);
+
+        let request = neuromance_common::ChatRequest::new(vec![user, assistant]);
+        assert_eq!(
+            counter.count_request(&request),
+            4 + 5 + 2 * MESSAGE_OVERHEAD + neuromance_common::tokens::REPLY_PRIMING
+        );
+    }
+
     #[test]
     fn test_model_config_constructors() {
         let config = ModelConfig::gpt_oss_20b();
@@ -205,7 +205,8 @@ impl TokenCounter {
             .iter()
             .map(|msg| {
                 let role_str = match msg.role {
-                    MessageRole::System => "system",
+                    // Chat templates have no developer role, so treat it as system
+                    MessageRole::System | MessageRole::Developer => "system",
                     MessageRole::User => "user",
                     MessageRole::Assistant => "assistant",
                     MessageRole::Tool => "tool",
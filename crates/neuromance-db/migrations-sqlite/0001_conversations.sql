@@ -0,0 +1,34 @@
+CREATE TABLE conversations (
+    id                     BLOB PRIMARY KEY,
+    title                  TEXT,
+    description            TEXT,
+    status                 TEXT NOT NULL DEFAULT 'active',
+    metadata               TEXT NOT NULL DEFAULT '{}',
+    created_at             TEXT NOT NULL,
+    updated_at             TEXT NOT NULL,
+    parent_conversation_id BLOB,
+    parent_message_id      BLOB,
+    parent_tool_call_id    TEXT
+);
+
+CREATE TABLE messages (
+    id              BLOB PRIMARY KEY,
+    conversation_id BLOB    NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    seq             INTEGER NOT NULL,
+    role            TEXT    NOT NULL,
+    content         TEXT    NOT NULL,
+    tool_calls      TEXT    NOT NULL DEFAULT '[]',
+    tool_call_id    TEXT,
+    name            TEXT,
+    reasoning       TEXT,
+    metadata        TEXT    NOT NULL DEFAULT '{}',
+    timestamp       TEXT    NOT NULL,
+    model           TEXT,
+    provider        TEXT,
+    usage           TEXT,
+    parent_id       BLOB,
+    content_parts   TEXT    NOT NULL DEFAULT '[]',
+    UNIQUE (conversation_id, seq)
+);
+
+CREATE INDEX conversations_updated_at_idx ON conversations (updated_at DESC);
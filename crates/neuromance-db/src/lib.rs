@@ -18,6 +18,10 @@
 //! Timestamps are stored as `TIMESTAMPTZ` and therefore truncated to
 //! microsecond precision on round-trip.
 //!
+//! With the `sqlite` feature, `SqliteConversationStore` offers a local
+//! single-file alternative for deployments without postgres. It stores whole
+//! conversation snapshots rather than an append-only log.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -59,9 +63,13 @@ mod error;
 mod hook;
 mod rows;
 mod sink;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod store;
 
 pub use error::DbError;
 pub use hook::PersistenceHook;
 pub use sink::ConversationSink;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteConversationStore;
 pub use store::{ConversationSummary, PgConversationStore, StoredTask, TaskStatusUpdate};
@@ -7,7 +7,7 @@ use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
 use neuromance_common::chat::{
-    ConversationStatus, Message, MessageRole, ReasoningContent, TaskStatus,
+    ContentPart, ConversationStatus, Message, MessageRole, ReasoningContent, TaskStatus,
 };
 use neuromance_common::client::Usage;
 use neuromance_common::tools::ToolCall;
@@ -59,6 +59,7 @@ pub fn role_from_str(value: &str, message_id: Uuid) -> Result<MessageRole, DbErr
         "user" => Ok(MessageRole::User),
         "assistant" => Ok(MessageRole::Assistant),
         "tool" => Ok(MessageRole::Tool),
+        "developer" => Ok(MessageRole::Developer),
         _ => Err(DbError::UnknownRole {
             value: value.to_string(),
             message_id,
@@ -79,6 +80,7 @@ pub fn status_from_str(value: &str, conversation_id: Uuid) -> Result<Conversatio
     match value {
         "active" => Ok(ConversationStatus::Active),
         "paused" => Ok(ConversationStatus::Paused),
+        "completed" => Ok(ConversationStatus::Completed),
         "archived" => Ok(ConversationStatus::Archived),
         "deleted" => Ok(ConversationStatus::Deleted),
         _ => Err(DbError::UnknownStatus {
@@ -117,6 +119,7 @@ pub struct MessageColumns {
     pub model: Option<String>,
     pub provider: Option<String>,
     pub usage: Option<Value>,
+    pub content_parts: Value,
 }
 
 /// Derives the encoded column values for inserting a [`Message`].
@@ -146,6 +149,22 @@ pub fn message_to_columns(message: &Message) -> Result<MessageColumns, DbError>
             .map(serde_json::to_value)
             .transpose()
             .map_err(|e| encode("usage", e))?,
+        content_parts: serde_json::to_value(&message.content_parts)
+            .map_err(|e| encode("content_parts", e))?,
+    })
+}
+
+/// Parses a stored `content_parts` column back into a message's
+/// [`ContentPart`]s.
+pub fn content_parts_from_value(
+    value: Value,
+    message_id: Uuid,
+) -> Result<Vec<ContentPart>, DbError> {
+    serde_json::from_value(value).map_err(|source| DbError::Decode {
+        table: "messages",
+        column: "content_parts",
+        id: message_id,
+        source,
     })
 }
 
@@ -200,6 +219,10 @@ impl MessageRow {
                 .map(serde_json::from_value::<Usage>)
                 .transpose()
                 .map_err(|e| decode("usage", e))?,
+            // The postgres schema has no branch link or image part columns;
+            // the SQLite store fills these in from its own.
+            parent_id: None,
+            content_parts: Vec::new(),
         })
     }
 }
@@ -356,6 +379,7 @@ mod tests {
             MessageRole::User,
             MessageRole::Assistant,
             MessageRole::Tool,
+            MessageRole::Developer,
         ] {
             let s = role_to_string(role, id).unwrap();
             assert_eq!(role_from_str(&s, id).unwrap(), role);
@@ -378,6 +402,7 @@ mod tests {
         for status in [
             ConversationStatus::Active,
             ConversationStatus::Paused,
+            ConversationStatus::Completed,
             ConversationStatus::Archived,
             ConversationStatus::Deleted,
         ] {
@@ -480,6 +505,7 @@ mod tests {
             ("user", MessageRole::User),
             ("assistant", MessageRole::Assistant),
             ("tool", MessageRole::Tool),
+            ("developer", MessageRole::Developer),
         ] {
             assert_eq!(role_from_str(stored, id).unwrap(), role);
             assert_eq!(role_to_string(role, id).unwrap(), stored);
@@ -494,6 +520,7 @@ mod tests {
         for (stored, status) in [
             ("active", ConversationStatus::Active),
             ("paused", ConversationStatus::Paused),
+            ("completed", ConversationStatus::Completed),
             ("archived", ConversationStatus::Archived),
             ("deleted", ConversationStatus::Deleted),
         ] {
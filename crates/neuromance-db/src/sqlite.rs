@@ -0,0 +1,393 @@
+//! SQLite-backed conversation store for single-process deployments.
+//!
+//! Unlike [`PgConversationStore`](crate::PgConversationStore), which appends to
+//! a shared message log, this store keeps one snapshot per conversation:
+//! [`SqliteConversationStore::save_conversation`] replaces the stored history,
+//! rewriting only the messages from the first one that changed. Queries are
+//! checked at runtime rather than through the `.sqlx/` offline metadata, which
+//! only covers postgres.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use neuromance_common::chat::{Conversation, Message};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePoolOptions, SqliteRow,
+};
+use sqlx::{Row, SqlitePool};
+use uuid::Uuid;
+
+use crate::error::{DbError, SqlxResultExt};
+use crate::rows::{
+    MessageColumns, MessageRow, content_parts_from_value, message_to_columns, status_from_str,
+    status_to_string,
+};
+use crate::store::ConversationSummary;
+
+/// Conversation store backed by a local SQLite file.
+///
+/// Conversations are keyed by [`Conversation::id`]; their messages live in a
+/// child table ordered by position, with tool calls, reasoning, usage, and
+/// image parts stored as JSON text. Branch links (`parent_id`) are kept, so a
+/// forked history reloads with the same branches.
+#[derive(Debug, Clone)]
+pub struct SqliteConversationStore {
+    pool: SqlitePool,
+}
+
+impl SqliteConversationStore {
+    /// Wraps an existing connection pool. Call [`Self::migrate`] before use.
+    #[must_use]
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Opens (creating if missing) the database file at `path` and applies
+    /// the embedded migrations.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError::Sqlx`] if the file cannot be opened, or
+    /// [`DbError::Migrate`] if a migration fails to apply.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, DbError> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options)
+            .await
+            .op("open database")?;
+        let store = Self::new(pool);
+        store.migrate().await?;
+        Ok(store)
+    }
+
+    /// Runs the embedded schema migrations.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError::Migrate`] if a migration fails to apply.
+    pub async fn migrate(&self) -> Result<(), DbError> {
+        sqlx::migrate!("./migrations-sqlite")
+            .run(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Saves `conversation` and replaces its stored messages with
+    /// `conversation.messages`.
+    ///
+    /// Stored messages that still match in order (same id, content, and tool
+    /// calls) are left in place; only the rest are deleted and re-inserted,
+    /// so saving after each turn costs the new messages rather than the whole
+    /// history.
+    ///
+    /// `created_at` is preserved when the conversation already exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError`] if the transaction fails or a field cannot be
+    /// serialized.
+    pub async fn save_conversation(&self, conversation: &Conversation) -> Result<(), DbError> {
+        let status = status_to_string(&conversation.status, conversation.id)?;
+        let metadata =
+            serde_json::to_value(&conversation.metadata).map_err(|source| DbError::Encode {
+                table: "conversations",
+                column: "metadata",
+                id: conversation.id,
+                source,
+            })?;
+
+        let mut tx = self.pool.begin().await.op("begin transaction")?;
+        sqlx::query(
+            r"
+            INSERT INTO conversations
+                (id, title, description, status, metadata, created_at, updated_at,
+                 parent_conversation_id, parent_message_id, parent_tool_call_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (id) DO UPDATE SET
+                title = excluded.title,
+                description = excluded.description,
+                status = excluded.status,
+                metadata = excluded.metadata,
+                updated_at = excluded.updated_at,
+                parent_conversation_id = excluded.parent_conversation_id,
+                parent_message_id = excluded.parent_message_id,
+                parent_tool_call_id = excluded.parent_tool_call_id
+            ",
+        )
+        .bind(conversation.id)
+        .bind(&conversation.title)
+        .bind(&conversation.description)
+        .bind(status)
+        .bind(metadata)
+        .bind(conversation.created_at)
+        .bind(conversation.updated_at)
+        .bind(conversation.parent_conversation_id)
+        .bind(conversation.parent_message_id)
+        .bind(&conversation.parent_tool_call_id)
+        .execute(&mut *tx)
+        .await
+        .op("upsert conversation")?;
+
+        let encoded = conversation
+            .messages
+            .iter()
+            .map(message_to_columns)
+            .collect::<Result<Vec<_>, _>>()?;
+        // Keep the stored prefix that still matches and rewrite from the first
+        // difference, so a turn that appends messages writes only those.
+        let (kept, stored) = unchanged_prefix(&mut tx, conversation, &encoded).await?;
+        if kept < stored {
+            sqlx::query("DELETE FROM messages WHERE conversation_id = ? AND seq >= ?")
+                .bind(conversation.id)
+                .bind(i64::try_from(kept).unwrap_or(i64::MAX))
+                .execute(&mut *tx)
+                .await
+                .op("delete conversation messages")?;
+        }
+
+        for ((seq, message), columns) in conversation
+            .messages
+            .iter()
+            .enumerate()
+            .zip(encoded)
+            .skip(kept)
+        {
+            sqlx::query(
+                r"
+                INSERT INTO messages
+                    (id, conversation_id, seq, role, content, tool_calls, tool_call_id,
+                     name, reasoning, metadata, timestamp, model, provider, usage,
+                     parent_id, content_parts)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ",
+            )
+            .bind(message.id)
+            .bind(conversation.id)
+            .bind(i64::try_from(seq).unwrap_or(i64::MAX))
+            .bind(columns.role)
+            .bind(&message.content)
+            .bind(columns.tool_calls)
+            .bind(&message.tool_call_id)
+            .bind(&message.name)
+            .bind(columns.reasoning)
+            .bind(columns.metadata)
+            .bind(message.timestamp)
+            .bind(columns.model)
+            .bind(columns.provider)
+            .bind(columns.usage)
+            .bind(message.parent_id)
+            .bind(columns.content_parts)
+            .execute(&mut *tx)
+            .await
+            .op("insert message")?;
+        }
+
+        tx.commit().await.op("commit transaction")?;
+        Ok(())
+    }
+
+    /// Loads a conversation and its messages in their saved order.
+    ///
+    /// Returns `None` if no conversation is stored under `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError`] if the query fails or a stored row cannot be
+    /// mapped back to its Rust type.
+    pub async fn load_conversation(&self, id: Uuid) -> Result<Option<Conversation>, DbError> {
+        let Some(row) = sqlx::query(
+            r"
+            SELECT id, title, description, status, metadata, created_at, updated_at,
+                   parent_conversation_id, parent_message_id, parent_tool_call_id
+            FROM conversations WHERE id = ?
+            ",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .op("select conversation")?
+        else {
+            return Ok(None);
+        };
+
+        let messages = sqlx::query(
+            r"
+            SELECT id, conversation_id, role, content, tool_calls, tool_call_id,
+                   name, reasoning, metadata, timestamp, model, provider, usage,
+                   parent_id, content_parts
+            FROM messages WHERE conversation_id = ? ORDER BY seq
+            ",
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .op("select conversation messages")?
+        .iter()
+        .map(decode_message)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let status: String = row.try_get("status").op("decode conversation")?;
+        let metadata = serde_json::from_value(row.try_get("metadata").op("decode conversation")?)
+            .map_err(|source| DbError::Decode {
+            table: "conversations",
+            column: "metadata",
+            id,
+            source,
+        })?;
+
+        Ok(Some(Conversation {
+            id,
+            title: row.try_get("title").op("decode conversation")?,
+            description: row.try_get("description").op("decode conversation")?,
+            created_at: row.try_get("created_at").op("decode conversation")?,
+            updated_at: row.try_get("updated_at").op("decode conversation")?,
+            metadata,
+            status: status_from_str(&status, id)?,
+            parent_conversation_id: row
+                .try_get("parent_conversation_id")
+                .op("decode conversation")?,
+            parent_message_id: row.try_get("parent_message_id").op("decode conversation")?,
+            parent_tool_call_id: row
+                .try_get("parent_tool_call_id")
+                .op("decode conversation")?,
+            messages: Arc::new(messages),
+        }))
+    }
+
+    /// Lists every stored conversation, most recently updated first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError`] if the query fails or a stored status is unknown.
+    pub async fn list_conversations(&self) -> Result<Vec<ConversationSummary>, DbError> {
+        let rows = sqlx::query(
+            r"
+            SELECT c.id, c.title, c.status, c.created_at, c.updated_at,
+                   c.parent_conversation_id, c.parent_message_id, c.parent_tool_call_id,
+                   COUNT(m.id) AS message_count,
+                   COUNT(CASE WHEN m.role = 'user' THEN 1 END) AS turn_count
+            FROM conversations c
+            LEFT JOIN messages m ON m.conversation_id = c.id
+            GROUP BY c.id
+            ORDER BY c.updated_at DESC
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .op("list conversations")?;
+
+        rows.iter()
+            .map(|row| {
+                let id: Uuid = row.try_get("id").op("decode conversation")?;
+                let status: String = row.try_get("status").op("decode conversation")?;
+                let message_count: i64 = row.try_get("message_count").op("decode conversation")?;
+                let turn_count: i64 = row.try_get("turn_count").op("decode conversation")?;
+                Ok(ConversationSummary {
+                    id,
+                    title: row.try_get("title").op("decode conversation")?,
+                    status: status_from_str(&status, id)?,
+                    created_at: row.try_get("created_at").op("decode conversation")?,
+                    updated_at: row.try_get("updated_at").op("decode conversation")?,
+                    message_count: u64::try_from(message_count).unwrap_or(0),
+                    turn_count: u64::try_from(turn_count).unwrap_or(0),
+                    parent_conversation_id: row
+                        .try_get("parent_conversation_id")
+                        .op("decode conversation")?,
+                    parent_message_id: row
+                        .try_get("parent_message_id")
+                        .op("decode conversation")?,
+                    parent_tool_call_id: row
+                        .try_get("parent_tool_call_id")
+                        .op("decode conversation")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Deletes a conversation and its messages. Returns whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError`] if the transaction fails.
+    pub async fn delete_conversation(&self, id: Uuid) -> Result<bool, DbError> {
+        let mut tx = self.pool.begin().await.op("begin transaction")?;
+        sqlx::query("DELETE FROM messages WHERE conversation_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .op("delete conversation messages")?;
+        let deleted = sqlx::query("DELETE FROM conversations WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .op("delete conversation")?
+            .rows_affected();
+        tx.commit().await.op("commit transaction")?;
+        Ok(deleted > 0)
+    }
+}
+
+/// Decodes a `messages` row, including the branch link and image parts that
+/// the shared [`MessageRow`] does not carry.
+/// Counts the leading stored messages of `conversation` that still match its
+/// new history (same id, content, and tool calls), returning that count along
+/// with the number of messages stored.
+async fn unchanged_prefix(
+    conn: &mut SqliteConnection,
+    conversation: &Conversation,
+    encoded: &[MessageColumns],
+) -> Result<(usize, usize), DbError> {
+    let stored = sqlx::query(
+        "SELECT id, content, tool_calls FROM messages WHERE conversation_id = ? ORDER BY seq",
+    )
+    .bind(conversation.id)
+    .fetch_all(conn)
+    .await
+    .op("select stored messages")?;
+    let mut kept = 0;
+    for ((row, message), columns) in stored.iter().zip(conversation.messages.iter()).zip(encoded) {
+        let unchanged = row.try_get::<Uuid, _>("id").op("decode message")? == message.id
+            && row.try_get::<String, _>("content").op("decode message")? == message.content
+            && row
+                .try_get::<serde_json::Value, _>("tool_calls")
+                .op("decode message")?
+                == columns.tool_calls;
+        if !unchanged {
+            break;
+        }
+        kept += 1;
+    }
+    Ok((kept, stored.len()))
+}
+
+fn decode_message(row: &SqliteRow) -> Result<Message, DbError> {
+    let mut message = message_row(row).op("decode message")?.into_message()?;
+    message.parent_id = row.try_get("parent_id").op("decode message")?;
+    message.content_parts = content_parts_from_value(
+        row.try_get("content_parts").op("decode message")?,
+        message.id,
+    )?;
+    Ok(message)
+}
+
+/// Reads the columns of a `messages` row into a [`MessageRow`].
+fn message_row(row: &SqliteRow) -> Result<MessageRow, sqlx::Error> {
+    Ok(MessageRow {
+        id: row.try_get("id")?,
+        conversation_id: row.try_get("conversation_id")?,
+        role: row.try_get("role")?,
+        content: row.try_get("content")?,
+        tool_calls: row.try_get("tool_calls")?,
+        tool_call_id: row.try_get("tool_call_id")?,
+        name: row.try_get("name")?,
+        reasoning: row.try_get("reasoning")?,
+        metadata: row.try_get("metadata")?,
+        timestamp: row.try_get("timestamp")?,
+        model: row.try_get("model")?,
+        provider: row.try_get("provider")?,
+        usage: row.try_get("usage")?,
+    })
+}
@@ -0,0 +1,234 @@
+//! Integration tests for the SQLite conversation store.
+//!
+//! Unlike the postgres suite these need no external service: each test works
+//! on a database file in a fresh temporary directory.
+
+#![cfg(feature = "sqlite")]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::expect_used)]
+
+use neuromance_common::chat::{Conversation, Message, ReasoningContent};
+use neuromance_common::client::Usage;
+use neuromance_common::tools::ToolCall;
+use neuromance_db::SqliteConversationStore;
+use uuid::Uuid;
+
+fn sample_conversation() -> Conversation {
+    let mut conversation = Conversation::new().with_title("weather");
+    conversation
+        .metadata
+        .insert("channel".to_string(), serde_json::json!("cli"));
+    let id = conversation.id;
+
+    let mut assistant = Message::assistant(id, "checking the weather")
+        .with_tool_calls(vec![ToolCall::new("get_weather", r#"{"city":"Berlin"}"#)])
+        .unwrap();
+    assistant.reasoning = Some(ReasoningContent::with_signature(
+        "The user wants Berlin's weather.",
+        "sig-abc123",
+    ));
+    assistant.model = Some("claude-sonnet-4-5-20250929".to_string());
+    assistant.provider = Some("anthropic".to_string());
+    assistant.usage = Some(Usage {
+        prompt_tokens: 120,
+        completion_tokens: 34,
+        total_tokens: 154,
+        cost: None,
+        input_tokens_details: None,
+        output_tokens_details: None,
+    });
+    let tool_call_id = assistant.tool_calls[0].id.clone();
+
+    for message in [
+        Message::system(id, "you are helpful"),
+        Message::user(id, "what's the weather in Berlin?"),
+        assistant,
+        Message::tool(id, "22C and sunny", tool_call_id, "get_weather".to_string()).unwrap(),
+    ] {
+        conversation.add_message(message).unwrap();
+    }
+    conversation
+}
+
+fn assert_same_conversation(restored: &Conversation, original: &Conversation) {
+    assert_eq!(restored.id, original.id);
+    assert_eq!(restored.title, original.title);
+    assert_eq!(restored.status, original.status);
+    assert_eq!(restored.metadata, original.metadata);
+    assert_eq!(restored.created_at, original.created_at);
+    assert_eq!(restored.messages.len(), original.messages.len());
+    for (restored, original) in restored.messages.iter().zip(original.messages.iter()) {
+        assert_eq!(restored.id, original.id);
+        assert_eq!(restored.role, original.role);
+        assert_eq!(restored.content, original.content);
+        assert_eq!(restored.timestamp, original.timestamp);
+        assert_eq!(restored.tool_calls, original.tool_calls);
+        assert_eq!(restored.tool_call_id, original.tool_call_id);
+        assert_eq!(restored.name, original.name);
+        assert_eq!(restored.reasoning, original.reasoning);
+        assert_eq!(restored.model, original.model);
+        assert_eq!(restored.usage, original.usage);
+    }
+}
+
+#[tokio::test]
+async fn conversation_survives_reopening_the_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("conversations.db");
+    let conversation = sample_conversation();
+
+    let store = SqliteConversationStore::open(&path).await.unwrap();
+    store.save_conversation(&conversation).await.unwrap();
+    drop(store);
+
+    // Simulate a restart: a fresh store over the same file.
+    let store = SqliteConversationStore::open(&path).await.unwrap();
+    let restored = store
+        .load_conversation(conversation.id)
+        .await
+        .unwrap()
+        .expect("conversation persisted");
+    assert_same_conversation(&restored, &conversation);
+
+    let listed = store.list_conversations().await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, conversation.id);
+    assert_eq!(listed[0].message_count, 4);
+    assert_eq!(listed[0].turn_count, 1);
+}
+
+#[tokio::test]
+async fn save_replaces_history_and_delete_removes_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SqliteConversationStore::open(dir.path().join("conversations.db"))
+        .await
+        .unwrap();
+    let mut conversation = sample_conversation();
+    let created_at = conversation.created_at;
+    store.save_conversation(&conversation).await.unwrap();
+
+    // A compacted history replaces the stored one rather than appending.
+    let summary = conversation.assistant_message("summary of the weather chat");
+    conversation.messages = std::sync::Arc::new(vec![summary]);
+    conversation.created_at = chrono::Utc::now();
+    store.save_conversation(&conversation).await.unwrap();
+
+    let restored = store
+        .load_conversation(conversation.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored.messages.len(), 1);
+    assert_eq!(restored.messages[0].content, "summary of the weather chat");
+    assert_eq!(restored.created_at, created_at);
+
+    assert!(store.delete_conversation(conversation.id).await.unwrap());
+    assert!(!store.delete_conversation(conversation.id).await.unwrap());
+    assert!(
+        store
+            .load_conversation(conversation.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        store
+            .load_conversation(Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(store.list_conversations().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn branches_and_image_parts_survive_a_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SqliteConversationStore::open(dir.path().join("conversations.db"))
+        .await
+        .unwrap();
+
+    let mut conversation = Conversation::new().with_title("photos");
+    let id = conversation.id;
+    let question = Message::user(id, "what is in this picture?")
+        .with_image_base64("image/png", "iVBORw0KGgo=")
+        .unwrap();
+    let question_id = question.id;
+    conversation.add_message(question).unwrap();
+    conversation
+        .add_message(Message::assistant(id, "a cat"))
+        .unwrap();
+    // A regenerated reply branches off the question rather than the first reply.
+    conversation
+        .add_message(Message::assistant(id, "a small cat").with_parent(question_id))
+        .unwrap();
+    let fork = conversation.fork_at(conversation.messages[2].id).unwrap();
+
+    for original in [&conversation, &fork] {
+        store.save_conversation(original).await.unwrap();
+        let restored = store
+            .load_conversation(original.id)
+            .await
+            .unwrap()
+            .expect("conversation persisted");
+        assert_same_conversation(&restored, original);
+        for (restored, original) in restored.messages.iter().zip(original.messages.iter()) {
+            assert_eq!(restored.parent_id, original.parent_id);
+            assert_eq!(restored.content_parts, original.content_parts);
+        }
+        assert_eq!(restored.branches(), original.branches());
+    }
+    assert_eq!(conversation.branches().len(), 2);
+    assert_eq!(fork.messages[0].content_parts.len(), 1);
+}
+
+#[tokio::test]
+async fn save_rewrites_only_messages_that_changed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("conversations.db");
+    let store = SqliteConversationStore::open(&path).await.unwrap();
+    let mut conversation = sample_conversation();
+    store.save_conversation(&conversation).await.unwrap();
+
+    // Mark the stored rows behind the store's back; a row that gets rewritten
+    // loses the mark.
+    let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}", path.display()))
+        .await
+        .unwrap();
+    sqlx::query("UPDATE messages SET metadata = '{\"marked\":true}'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let id = conversation.id;
+    let marked = || async {
+        sqlx::query_scalar::<_, String>(
+            "SELECT metadata FROM messages WHERE conversation_id = ? ORDER BY seq",
+        )
+        .bind(id)
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+        .iter()
+        .map(|metadata| metadata.contains("marked"))
+        .collect::<Vec<_>>()
+    };
+
+    // An appended turn leaves the stored rows untouched.
+    let reply = conversation.assistant_message("it is sunny in Berlin");
+    conversation.add_message(reply).unwrap();
+    store.save_conversation(&conversation).await.unwrap();
+    assert_eq!(marked().await, [true, true, true, true, false]);
+
+    // A message edited in place is rewritten along with everything after it.
+    std::sync::Arc::make_mut(&mut conversation.messages)[1].content =
+        "what's the weather in Paris?".to_string();
+    store.save_conversation(&conversation).await.unwrap();
+    assert_eq!(marked().await, [true, false, false, false, false]);
+
+    let restored = store
+        .load_conversation(conversation.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_same_conversation(&restored, &conversation);
+}
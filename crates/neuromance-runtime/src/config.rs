@@ -396,6 +396,14 @@ pub struct RuntimeSettings {
     pub health_addr: String,
     #[serde(default = "default_shutdown_grace")]
     pub shutdown_grace_seconds: u64,
+    /// Serve Prometheus metrics at `GET /metrics`. When `false` no recorder
+    /// is installed and every metric call is a no-op.
+    #[serde(default = "default_true")]
+    pub metrics_enabled: bool,
+    /// Serve `/metrics` on its own listener at this address instead of
+    /// alongside the health probes on `health_addr`.
+    #[serde(default)]
+    pub metrics_addr: Option<String>,
     /// Maximum number of tasks buffered for the worker. `POST /tasks/new`
     /// returns `429` once the queue is at capacity. Sized for a single
     /// serial worker — buffering far beyond a handful is dishonest about
@@ -411,6 +419,11 @@ pub struct RuntimeSettings {
     /// configured.
     #[serde(default = "default_max_delegation_depth")]
     pub max_delegation_depth: u32,
+    /// SQLite file that persists serve-mode conversations when no `[database]`
+    /// is configured, so a restart recovers them. Without either, conversations
+    /// live only in memory. Cannot be combined with `[database]`.
+    #[serde(default)]
+    pub conversation_db: Option<PathBuf>,
 }
 
 impl Default for RuntimeSettings {
@@ -419,8 +432,11 @@ impl Default for RuntimeSettings {
             listen_addr: default_listen_addr(),
             health_addr: default_health_addr(),
             shutdown_grace_seconds: default_shutdown_grace(),
+            metrics_enabled: true,
+            metrics_addr: None,
             max_queue_depth: default_max_queue_depth(),
             max_delegation_depth: default_max_delegation_depth(),
+            conversation_db: None,
         }
     }
 }
@@ -532,6 +548,7 @@ impl RuntimeConfig {
     /// - `agent.provider` (or a `subagent.provider`) names no provider
     /// - the agent has no effective model (`agent.model` and the provider's
     ///   `model` are both unset)
+    /// - `runtime.metrics_addr` is set while `runtime.metrics_enabled = false`
     /// - a `[[bootstrap]]` entry has an empty `name` or `command`
     pub fn validate(&self) -> Result<(), RuntimeError> {
         if matches!(self.mode, Mode::Oneshot) && self.oneshot.is_none() {
@@ -569,8 +586,19 @@ impl RuntimeConfig {
                 "runtime.max_queue_depth must be at least 1".to_string(),
             ));
         }
+        if !self.runtime.metrics_enabled && self.runtime.metrics_addr.is_some() {
+            return Err(RuntimeError::Config(
+                "runtime.metrics_addr cannot be set with runtime.metrics_enabled = false"
+                    .to_string(),
+            ));
+        }
 
         if let Some(database) = &self.database {
+            if self.runtime.conversation_db.is_some() {
+                return Err(RuntimeError::Config(
+                    "runtime.conversation_db cannot be combined with [database]".to_string(),
+                ));
+            }
             if database.url_env.trim().is_empty() {
                 return Err(RuntimeError::Config(
                     "database.url_env must not be empty".to_string(),
@@ -1547,6 +1575,55 @@ mod tests {
         assert_eq!(database.acquire_timeout_seconds, 30);
     }
 
+    #[test]
+    fn test_conversation_db_excludes_database_section() {
+        let config = serve_config(
+            r#"
+            [runtime]
+            conversation_db = "/var/lib/neuromance/conversations.db"
+        "#,
+        );
+        config.validate().unwrap();
+        assert_eq!(
+            config.runtime.conversation_db.as_deref(),
+            Some(Path::new("/var/lib/neuromance/conversations.db"))
+        );
+
+        let config = serve_config(
+            r#"
+            [runtime]
+            conversation_db = "/var/lib/neuromance/conversations.db"
+
+            [database]
+            url_env = "DATABASE_URL"
+        "#,
+        );
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("runtime.conversation_db"));
+    }
+
+    #[test]
+    fn test_metrics_addr_requires_metrics_enabled() {
+        let config = serve_config(
+            r#"
+            [runtime]
+            metrics_addr = "0.0.0.0:9090"
+        "#,
+        );
+        config.validate().unwrap();
+        assert!(config.runtime.metrics_enabled);
+
+        let config = serve_config(
+            r#"
+            [runtime]
+            metrics_enabled = false
+            metrics_addr = "0.0.0.0:9090"
+        "#,
+        );
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("runtime.metrics_addr"));
+    }
+
     #[test]
     fn test_rules_section_round_trips_custom_values() {
         let config = serve_config(
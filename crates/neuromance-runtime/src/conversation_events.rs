@@ -0,0 +1,163 @@
+//! Live message feed for watching a conversation as it runs.
+//!
+//! [`ConversationEvents`] is a [`Hook`]: registered on a `Core`, it sees the
+//! history each time it advances and broadcasts every message it has not sent
+//! before — user, assistant, and tool alike — to that conversation's
+//! subscribers. A second client can [`subscribe`](ConversationEvents::subscribe)
+//! to tail an agent's progress while another drives it.
+//!
+//! Channels exist only while someone listens: subscribing creates one, and the
+//! next update after the last receiver drops removes it. The first update on a
+//! new channel sends the full history; a subscriber joining a channel that is
+//! already open only sees messages from then on, so a client that needs the
+//! backlog reads the history itself and skips ids it has seen. Serve mode does
+//! exactly that behind `GET /conversations/{id}/events`.
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use neuromance_common::chat::Message;
+use neuromance_common::hook::{Hook, HookContext};
+
+/// Messages buffered per subscriber before the slowest one starts missing
+/// them (its next `recv` reports [`broadcast::error::RecvError::Lagged`]).
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// A change to a watched conversation.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ConversationEvent {
+    /// A message joined the history.
+    MessageAppended(Message),
+}
+
+/// One conversation's channel and the messages already sent on it.
+struct Channel {
+    sender: broadcast::Sender<ConversationEvent>,
+    sent: HashSet<Uuid>,
+}
+
+/// Per-conversation broadcast of appended messages.
+#[derive(Default)]
+pub struct ConversationEvents {
+    channels: DashMap<Uuid, Channel>,
+}
+
+impl ConversationEvents {
+    /// A feed with no conversations watched.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive the messages appended to `conversation_id` from now on, plus
+    /// the full history if this opens the conversation's channel.
+    #[must_use]
+    pub fn subscribe(&self, conversation_id: Uuid) -> broadcast::Receiver<ConversationEvent> {
+        self.channels
+            .entry(conversation_id)
+            .or_insert_with(|| Channel {
+                sender: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+                sent: HashSet::new(),
+            })
+            .sender
+            .subscribe()
+    }
+
+    /// Number of conversations with at least one channel open.
+    #[must_use]
+    pub fn watched_conversations(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Drop `conversation_id`'s channel if no receiver is left, e.g. after a
+    /// subscriber gives up before any update arrives.
+    pub fn close_if_unwatched(&self, conversation_id: Uuid) {
+        self.channels
+            .remove_if(&conversation_id, |_, c| c.sender.receiver_count() == 0);
+    }
+
+    /// Send the messages in `history` not yet sent for `conversation_id`, in
+    /// order. Does nothing if no one is subscribed.
+    pub fn publish(&self, conversation_id: Uuid, history: &[Message]) {
+        let Some(mut channel) = self.channels.get_mut(&conversation_id) else {
+            return;
+        };
+        if channel.sender.receiver_count() == 0 {
+            drop(channel);
+            self.close_if_unwatched(conversation_id);
+            return;
+        }
+        for message in history {
+            if channel.sent.insert(message.id) {
+                // Receivers may all drop mid-loop; the next update cleans up.
+                let _ = channel
+                    .sender
+                    .send(ConversationEvent::MessageAppended(message.clone()));
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Hook for ConversationEvents {
+    fn name(&self) -> &'static str {
+        "conversation_events"
+    }
+
+    async fn on_messages(&self, ctx: &HookContext, messages: &[Message]) -> anyhow::Result<()> {
+        self.publish(ctx.conversation_id, messages);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn content(event: ConversationEvent) -> String {
+        match event {
+            ConversationEvent::MessageAppended(message) => message.content,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_receives_appended_messages_in_order() {
+        let events = ConversationEvents::new();
+        let conversation_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+        let mut rx = events.subscribe(conversation_id);
+        let ctx = HookContext::new(conversation_id, 0);
+
+        let mut history = vec![Message::user(conversation_id, "list files")];
+        events.on_messages(&ctx, &history).await.unwrap();
+        history.push(Message::assistant(conversation_id, "calling ls"));
+        history.push(
+            Message::tool(
+                conversation_id,
+                "a.txt",
+                "call_1".to_string(),
+                "ls".to_string(),
+            )
+            .unwrap(),
+        );
+        events.on_messages(&ctx, &history).await.unwrap();
+        // Unwatched conversations are ignored.
+        events.publish(other_id, &[Message::user(other_id, "elsewhere")]);
+
+        let received: Vec<String> = (0..3).map(|_| content(rx.try_recv().unwrap())).collect();
+        assert_eq!(received, ["list files", "calling ls", "a.txt"]);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(events.watched_conversations(), 1);
+
+        drop(rx);
+        events.publish(conversation_id, &history);
+        assert_eq!(events.watched_conversations(), 0);
+    }
+}
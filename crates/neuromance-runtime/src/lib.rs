@@ -22,6 +22,7 @@
 pub mod approval;
 pub mod bootstrap;
 pub mod config;
+pub mod conversation_events;
 pub mod error;
 pub mod factory;
 pub mod health;
@@ -87,7 +87,11 @@ async fn main() -> Result<()> {
 /// Run the orchestrator role: metrics, health server, agent, and the
 /// configured serving mode.
 async fn run_orchestrator(config: &RuntimeConfig, cancel: CancellationToken) -> Result<()> {
-    let prometheus_handle = runtime_metrics::init().map_err(anyhow::Error::from)?;
+    let prometheus_handle = if config.runtime.metrics_enabled {
+        Some(runtime_metrics::init().map_err(anyhow::Error::from)?)
+    } else {
+        None
+    };
     info!(
         mode = ?config.mode,
         agent_id = %config.agent.id,
@@ -96,11 +100,15 @@ async fn run_orchestrator(config: &RuntimeConfig, cancel: CancellationToken) ->
         "neuromance-runtime starting"
     );
 
+    // `/metrics` shares the health server unless it has an address of its own.
+    let (health_metrics, metrics_handle) =
+        spawn_metrics_server(config, prometheus_handle, cancel.clone()).await?;
+
     let readiness = Arc::new(ReadinessGate::new());
     let health_handle = spawn_health_server(
         config,
         Arc::clone(&readiness),
-        prometheus_handle,
+        health_metrics,
         cancel.clone(),
     )
     .await
@@ -185,6 +193,15 @@ async fn run_orchestrator(config: &RuntimeConfig, cancel: CancellationToken) ->
     {
         warn!(error=%e, "health server did not shut down within grace period");
     }
+    if let Some(metrics_handle) = metrics_handle
+        && let Err(e) = tokio::time::timeout(
+            Duration::from_secs(config.runtime.shutdown_grace_seconds),
+            metrics_handle,
+        )
+        .await
+    {
+        warn!(error=%e, "metrics server did not shut down within grace period");
+    }
 
     result
 }
@@ -241,29 +258,64 @@ fn init_tracing(extra_layer: Option<BoxedLayer<Registry>>) {
     }
 }
 
+/// Serve the health probes on `runtime.health_addr`, plus `/metrics` when
+/// `prometheus_handle` is given.
 async fn spawn_health_server(
     config: &RuntimeConfig,
     readiness: Arc<ReadinessGate>,
-    prometheus_handle: metrics_exporter_prometheus::PrometheusHandle,
+    prometheus_handle: Option<metrics_exporter_prometheus::PrometheusHandle>,
+    cancel: CancellationToken,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let mut app = health_router(readiness);
+    if let Some(handle) = prometheus_handle {
+        app = app.merge(runtime_metrics::router(handle));
+    }
+    spawn_server("health", &config.runtime.health_addr, app, cancel).await
+}
+
+/// Serve `/metrics` on `runtime.metrics_addr` when one is set. Otherwise the
+/// handle is passed back for the health server to serve.
+async fn spawn_metrics_server(
+    config: &RuntimeConfig,
+    prometheus_handle: Option<metrics_exporter_prometheus::PrometheusHandle>,
+    cancel: CancellationToken,
+) -> Result<(
+    Option<metrics_exporter_prometheus::PrometheusHandle>,
+    Option<tokio::task::JoinHandle<()>>,
+)> {
+    match (&config.runtime.metrics_addr, prometheus_handle) {
+        (Some(addr), Some(handle)) => {
+            let server = spawn_server("metrics", addr, runtime_metrics::router(handle), cancel)
+                .await
+                .context("start metrics server")?;
+            Ok((None, Some(server)))
+        }
+        (_, handle) => Ok((handle, None)),
+    }
+}
+
+/// Bind `addr` and serve `app` on it until `cancel` fires. `name` labels the
+/// server in errors and logs.
+async fn spawn_server(
+    name: &'static str,
+    addr: &str,
+    app: axum::Router,
     cancel: CancellationToken,
 ) -> Result<tokio::task::JoinHandle<()>> {
-    let addr: std::net::SocketAddr = config
-        .runtime
-        .health_addr
+    let addr: std::net::SocketAddr = addr
         .parse()
-        .with_context(|| format!("invalid health_addr: {}", config.runtime.health_addr))?;
+        .with_context(|| format!("invalid {name} address: {addr}"))?;
     let listener = tokio::net::TcpListener::bind(addr)
         .await
-        .with_context(|| format!("bind health server to {addr}"))?;
-    info!(%addr, "health server listening");
+        .with_context(|| format!("bind {name} server to {addr}"))?;
+    info!(%addr, "{name} server listening");
 
-    let app = health_router(readiness).merge(runtime_metrics::router(prometheus_handle));
     let handle = tokio::spawn(async move {
         let result = axum::serve(listener, app)
             .with_graceful_shutdown(async move { cancel.cancelled().await })
             .await;
         if let Err(e) = result {
-            warn!(error=%e, "health server exited with error");
+            warn!(error=%e, "{name} server exited with error");
         }
     });
     Ok(handle)
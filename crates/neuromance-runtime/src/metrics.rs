@@ -5,7 +5,8 @@
 //! a `GET /metrics` route that renders the current snapshot in Prometheus
 //! exposition format. The route is mounted on the management port
 //! (`runtime.health_addr`), not the task port, so scraping never competes
-//! with task traffic.
+//! with task traffic. Set `runtime.metrics_addr` to serve it on a listener of
+//! its own, or `runtime.metrics_enabled = false` to turn metrics off.
 //!
 //! See [`crate::serve`] and `crates/neuromance/src/core.rs` for the
 //! instrumentation sites that emit values into the recorder.
@@ -5,6 +5,9 @@
 //! - `GET /tasks` lists the active queue (pending + running), sorted by
 //!   submit time — a caller's index in the array is their queue position.
 //! - `GET /tasks/{id}` returns the current state of a single task.
+//! - `GET /conversations/{id}/events` streams a conversation's messages as
+//!   server-sent events: the history so far, then each message as the agent
+//!   appends it.
 //!
 //! Liveness/readiness live on a separate server at `runtime.health_addr`
 //! (default `127.0.0.1:8081`) — see `health.rs`. Do not point readiness
@@ -30,6 +33,7 @@
 //! reads the turn's history from postgres, so a conversation continues
 //! correctly on any replica regardless of which one accepted earlier turns.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -38,13 +42,17 @@ use axum::{
     Json, Router,
     extract::{DefaultBodyLimit, Path, State},
     http::StatusCode,
-    response::{IntoResponse, Response},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
     routing::{get, post},
 };
 use chrono::Utc;
+use futures::StreamExt;
 use metrics::{counter, gauge, histogram};
 use serde::{Deserialize, Serialize};
-use tokio::sync::{Mutex, mpsc};
+use tokio::sync::{Mutex, broadcast, mpsc};
 use tokio_util::sync::CancellationToken;
 use tower_http::trace::TraceLayer;
 use tracing::{Level, Span, error, field, info, info_span, warn};
@@ -55,11 +63,13 @@ use neuromance_agent::{Agent, AgentResponse};
 use neuromance_client::LLMClient;
 use neuromance_common::chat::{Message, TaskStatus};
 use neuromance_common::client::Config;
-use neuromance_db::PgConversationStore;
+use neuromance_common::hook::Hook;
+use neuromance_db::{PgConversationStore, SqliteConversationStore};
 
 use crate::AgentBuilder;
 use crate::SessionReset;
 use crate::config::RuntimeConfig;
+use crate::conversation_events::{ConversationEvent, ConversationEvents};
 use crate::sandbox::{EXECUTE_PYTHON, SandboxClient};
 use crate::task_store::{
     ConversationRecord, InMemoryTaskStore, PostgresTaskStore, TaskRecord, TaskStore,
@@ -229,6 +239,9 @@ pub struct ServeState {
     /// per-task `provider` override at enqueue time (400) rather than failing the
     /// task mid-run.
     provider_names: Arc<[String]>,
+    /// Live message feed registered as a hook on every agent the worker runs,
+    /// tailed by `GET /conversations/{id}/events`.
+    events: Arc<ConversationEvents>,
 }
 
 /// Cap on `POST /tasks` request bodies. Task input is a single user prompt;
@@ -278,6 +291,7 @@ pub fn router(state: ServeState) -> Router {
             "/conversations/{id}/children",
             get(list_conversation_children),
         )
+        .route("/conversations/{id}/events", get(conversation_events))
         .layer(DefaultBodyLimit::max(MAX_TASK_BODY_BYTES))
         .layer(trace_layer)
         .with_state(state)
@@ -540,6 +554,61 @@ async fn get_conversation(
     }
 }
 
+/// Streams a conversation's messages as server-sent `message` events, each a
+/// JSON [`Message`]: the history so far, then every message the agent appends.
+///
+/// The subscription is opened before the history is read, so a message landing
+/// in between arrives on the feed; ids already sent are skipped. A subscriber
+/// that falls more than
+/// [`EVENT_CHANNEL_CAPACITY`](crate::conversation_events::EVENT_CHANNEL_CAPACITY)
+/// messages behind misses the overflow and can re-read the history from
+/// `GET /conversations/{id}`.
+async fn conversation_events(State(state): State<ServeState>, Path(id): Path<Uuid>) -> Response {
+    let rx = state.events.subscribe(id);
+    let history = match state.task_store.get_conversation_view(id).await {
+        Ok(Some(record)) => record.messages,
+        lookup => {
+            drop(rx);
+            state.events.close_if_unwatched(id);
+            return match lookup {
+                Err(e) => {
+                    warn!(conversation_id = %id, error = %e, "failed to load conversation");
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(serde_json::json!({"error": "failed to load conversation"})),
+                    )
+                        .into_response()
+                }
+                _ => (
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({"error": "conversation not found"})),
+                )
+                    .into_response(),
+            };
+        }
+    };
+
+    let live = futures::stream::unfold(rx, move |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(ConversationEvent::MessageAppended(message)) => return Some((message, rx)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(conversation_id = %id, skipped, "event subscriber lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    let mut sent = HashSet::new();
+    let messages = futures::stream::iter(history)
+        .chain(live)
+        .filter(move |message| std::future::ready(sent.insert(message.id)))
+        .map(|message| Event::default().event("message").json_data(&message));
+    Sse::new(messages)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
 /// Lists the child conversations (e.g. subagent delegations) of a conversation.
 ///
 /// Lineage is only durable in postgres — subagent conversations never enter the
@@ -592,6 +661,9 @@ struct WorkerCtx {
     /// the next. `Some` only when `execute_python` runs locally (not in the
     /// sandbox) and the `python-repl` feature is built.
     local_python: Option<SessionReset>,
+    /// The live message feed, registered on per-task override agents so their
+    /// turns are streamed like the shared agent's.
+    events: Arc<ConversationEvents>,
 }
 
 async fn worker_loop(mut rx: mpsc::Receiver<WorkerJob>, ctx: WorkerCtx, cancel: CancellationToken) {
@@ -659,6 +731,23 @@ async fn fail_task(ctx: &WorkerCtx, task_id: Uuid, reason: &str) {
     counter!("neuromance_tasks_total", "outcome" => "failed").increment(1);
 }
 
+/// Holds the `neuromance_active_conversations` gauge up while a task's
+/// conversation is being worked on, releasing it on every exit path.
+struct ActiveConversation;
+
+impl ActiveConversation {
+    fn enter() -> Self {
+        gauge!("neuromance_active_conversations").increment(1.0);
+        Self
+    }
+}
+
+impl Drop for ActiveConversation {
+    fn drop(&mut self) {
+        gauge!("neuromance_active_conversations").decrement(1.0);
+    }
+}
+
 /// Drives one agent turn over `input_messages`, racing it against `cancel` so a
 /// shutdown abandons the run rather than stalling the drain. Records the running
 /// agent's id on the current task span.
@@ -693,6 +782,7 @@ async fn run_turn(
     ),
 )]
 async fn process_job(ctx: &WorkerCtx, job: WorkerJob, cancel: CancellationToken) -> JobOutcome {
+    let _active = ActiveConversation::enter();
     let dequeued_at = Utc::now();
     let run_start = Instant::now();
     let (created_at, depth_at_enqueue) = ctx
@@ -739,6 +829,9 @@ async fn process_job(ctx: &WorkerCtx, job: WorkerJob, cancel: CancellationToken)
         let model = job.model.as_deref();
         match ctx.builder.build(provider, model).await {
             Ok((mut agent, _local_python)) => {
+                agent
+                    .core
+                    .add_hook(Arc::clone(&ctx.events) as Arc<dyn Hook>);
                 info!(
                     provider,
                     model, "task running on per-task provider/model override"
@@ -816,11 +909,28 @@ pub async fn run(
     // One storage instance, shared by the worker and the handlers: a conversation
     // a handler seeds must be visible to the run that continues it. Postgres
     // deployment writes through and reads authoritatively; otherwise the working
-    // set alone is authoritative.
-    let task_store: Arc<dyn TaskStore> = match store {
-        Some(store) => Arc::new(PostgresTaskStore::new(store)),
-        None => Arc::new(InMemoryTaskStore::new()),
+    // set alone is authoritative, optionally snapshotted to a local file.
+    let task_store: Arc<dyn TaskStore> = match (store, &config.runtime.conversation_db) {
+        (Some(store), _) => Arc::new(PostgresTaskStore::new(store)),
+        (None, Some(path)) => {
+            let snapshots = SqliteConversationStore::open(path)
+                .await
+                .with_context(|| format!("open conversation_db {}", path.display()))?;
+            let store = InMemoryTaskStore::with_snapshots(snapshots)
+                .await
+                .context("recover conversations from conversation_db")?;
+            info!(
+                path = %path.display(),
+                recovered = store.conversation_count(),
+                "conversation snapshots enabled"
+            );
+            Arc::new(store)
+        }
+        (None, None) => Arc::new(InMemoryTaskStore::new()),
     };
+    let events = Arc::new(ConversationEvents::new());
+    let mut agent = agent;
+    agent.core.add_hook(Arc::clone(&events) as Arc<dyn Hook>);
     let agent = Arc::new(Mutex::new(agent));
     let (work_tx, work_rx) = mpsc::channel::<WorkerJob>(config.runtime.max_queue_depth);
     let system_prompt: Arc<str> = Arc::from(config.agent.system_prompt.as_str());
@@ -838,6 +948,7 @@ pub async fn run(
             builder,
             sandbox: session_closer,
             local_python,
+            events: Arc::clone(&events),
         },
         cancel.clone(),
     ));
@@ -849,6 +960,7 @@ pub async fn run(
         system_prompt,
         skills_menu,
         provider_names,
+        events,
     };
     let app = router(state);
     let addr: std::net::SocketAddr = config
@@ -984,6 +1096,7 @@ mod tests {
                 created_at: Utc::now(),
                 response_id: Some("test-response".to_string()),
                 metadata: std::collections::HashMap::new(),
+                additional_choices: Vec::new(),
             })
         }
 
@@ -1045,6 +1158,7 @@ mod tests {
                 builder: stub_builder(),
                 sandbox: None,
                 local_python: None,
+                events: Arc::default(),
             },
             cancel.clone(),
         ));
@@ -1091,6 +1205,7 @@ mod tests {
                 system_prompt: Arc::from("system"),
                 skills_menu: None,
                 provider_names: ["primary".to_owned(), "secondary".to_owned()].into(),
+                events: Arc::default(),
             },
             store,
             work_rx,
@@ -1171,6 +1286,7 @@ mod tests {
             builder: stub_builder(),
             sandbox: None,
             local_python: None,
+            events: Arc::clone(&state.events),
         }
     }
 
@@ -1451,6 +1567,65 @@ mod tests {
         assert_eq!(store.conversation_turn_count(conv_id), Some(2));
     }
 
+    /// The process-wide Prometheus recorder, installed by whichever test asks
+    /// first.
+    fn prometheus() -> metrics_exporter_prometheus::PrometheusHandle {
+        static HANDLE: std::sync::OnceLock<metrics_exporter_prometheus::PrometheusHandle> =
+            std::sync::OnceLock::new();
+        HANDLE
+            .get_or_init(|| crate::metrics::init().expect("install recorder"))
+            .clone()
+    }
+
+    /// The value of `series` in a Prometheus scrape, or 0 before it is recorded.
+    fn sample(scrape: &str, series: &str) -> f64 {
+        scrape
+            .lines()
+            .find_map(|line| line.strip_prefix(series)?.trim().parse().ok())
+            .unwrap_or(0.0)
+    }
+
+    async fn scrape(url: &str) -> String {
+        reqwest::get(url).await.unwrap().text().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn metrics_endpoint_counts_processed_task() {
+        const SUCCEEDED: &str = r#"neuromance_tasks_total{outcome="succeeded"} "#;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/metrics", listener.local_addr().unwrap());
+        tokio::spawn(axum::serve(listener, crate::metrics::router(prometheus())).into_future());
+
+        let before = scrape(&url).await;
+        let (state, _store, _rx) = fresh_state(4);
+        let task = try_enqueue(&state, "hello".to_string(), None, None, None, None)
+            .await
+            .unwrap();
+        let outcome = process_job(
+            &worker_ctx(&state, boxed_agent(EchoClient::new("hi"))),
+            WorkerJob {
+                task_id: task.id,
+                conversation_id: task.conversation_id,
+                user: "hello".to_string(),
+                seeded: true,
+                provider: None,
+                model: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+        assert!(matches!(outcome, JobOutcome::Succeeded));
+        let after = scrape(&url).await;
+
+        // Other tests share the recorder and may run concurrently, so counters
+        // can only be checked for growth.
+        assert!(sample(&after, SUCCEEDED) >= sample(&before, SUCCEEDED) + 1.0);
+        assert!(after.contains("neuromance_chat_turns_total"));
+        assert!(after.contains("neuromance_active_conversations"));
+        assert!(after.contains("neuromance_task_duration_seconds"));
+    }
+
     #[tokio::test]
     async fn process_job_fails_cleanly_when_conversation_record_missing() {
         let (state, store, _rx) = fresh_state(4);
@@ -1574,6 +1749,7 @@ mod tests {
             }),
             sandbox: None,
             local_python: None,
+            events: Arc::clone(&state.events),
         };
         process_job(
             &ctx,
@@ -1608,6 +1784,85 @@ mod tests {
         );
     }
 
+    /// A subscriber to `/conversations/{id}/events` gets the history so far,
+    /// then each message the run appends, each exactly once.
+    #[tokio::test]
+    async fn conversation_events_streams_history_then_new_messages() {
+        let (state, _store, _rx) = fresh_state(4);
+        let task = try_enqueue(
+            &state,
+            "hi".to_string(),
+            None,
+            None,
+            Some("secondary"),
+            None,
+        )
+        .await
+        .unwrap();
+        let conv_id = task.conversation_id;
+        let ctx = WorkerCtx {
+            task_store: Arc::clone(&state.task_store),
+            agent: boxed_agent(EchoClient::new("from-shared")),
+            builder: Arc::new(TestBuilder {
+                reply: "from-override".to_string(),
+            }),
+            sandbox: None,
+            local_python: None,
+            events: Arc::clone(&state.events),
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let events = Arc::clone(&state.events);
+        tokio::spawn(axum::serve(listener, router(state)).into_future());
+
+        let missing = reqwest::get(format!("{base}/conversations/{}/events", Uuid::new_v4()))
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            events.watched_conversations(),
+            0,
+            "a rejected subscriber leaves no channel"
+        );
+
+        let mut response = reqwest::get(format!("{base}/conversations/{conv_id}/events"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        process_job(
+            &ctx,
+            WorkerJob {
+                task_id: task.id,
+                conversation_id: conv_id,
+                user: "hi".to_string(),
+                seeded: true,
+                provider: Some("secondary".to_string()),
+                model: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let mut contents = Vec::new();
+        let mut buffer = String::new();
+        while contents.len() < 3 {
+            let chunk = timeout(Duration::from_secs(2), response.chunk())
+                .await
+                .expect("event stream stalled")
+                .unwrap()
+                .expect("event stream ended");
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+            while let Some(end) = buffer.find("\n\n") {
+                let event: String = buffer.drain(..end + 2).collect();
+                if let Some(data) = event.lines().find_map(|l| l.strip_prefix("data: ")) {
+                    let message: Message = serde_json::from_str(data).unwrap();
+                    contents.push(message.content);
+                }
+            }
+        }
+        assert_eq!(contents, ["system", "hi", "from-override"]);
+    }
+
     #[tokio::test]
     async fn process_job_with_model_override_runs_built_agent() {
         let (state, store, _rx) = fresh_state(4);
@@ -1626,6 +1881,7 @@ mod tests {
             }),
             sandbox: None,
             local_python: None,
+            events: Arc::clone(&state.events),
         };
         process_job(
             &ctx,
@@ -1693,6 +1949,7 @@ mod tests {
                 system_prompt: Arc::from("system"),
                 skills_menu: None,
                 provider_names: ["primary".to_owned(), "secondary".to_owned()].into(),
+                events: Arc::default(),
             },
             store,
             work_rx,
@@ -1707,6 +1964,7 @@ mod tests {
             builder: stub_builder(),
             sandbox: None,
             local_python: None,
+            events: Arc::clone(&state.events),
         }
     }
 
@@ -2006,6 +2264,7 @@ mod tests {
             system_prompt: Arc::from("system"),
             skills_menu: None,
             provider_names: ["primary".to_owned()].into(),
+            events: Arc::default(),
         };
 
         let err = try_enqueue(&state, "hi".to_string(), None, None, None, None)
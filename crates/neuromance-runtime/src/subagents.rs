@@ -33,7 +33,9 @@ use neuromance::Core;
 use neuromance_agent::{Agent, LocalSubagent, Subagent, SubagentError, SubagentTool};
 use neuromance_client::{LLMClient, build_client};
 use neuromance_db::{PersistenceHook, PgConversationStore};
-use neuromance_tools::{ToolConfig, ToolFactoryRegistry, ToolImplementation, ToolRegistry};
+use neuromance_tools::{
+    Registration, ToolConfig, ToolFactoryRegistry, ToolImplementation, ToolRegistry,
+};
 
 use crate::config::RuntimeConfig;
 use crate::error::RuntimeError;
@@ -221,7 +223,12 @@ fn assemble_toolset(
     let staged = if let Some(remote) = remote_capabilities {
         let registry = ToolRegistry::new();
         for tool in remote {
-            registry.register(Arc::clone(tool));
+            if registry.register(Arc::clone(tool)) == Registration::Replaced {
+                tracing::warn!(
+                    tool = %tool.get_definition().function.name,
+                    "sandbox exposes more than one tool with this name; the last one wins"
+                );
+            }
         }
         registry
     } else {
@@ -281,18 +288,17 @@ fn register_child_delegates(
         let Some(inner) = children.get(&sub.id).map(Arc::clone) else {
             continue;
         };
-        if staged.contains(&sub.id) {
-            return Err(RuntimeError::Config(format!(
-                "subagent id '{}' collides with a configured tool of the same name",
-                sub.id
-            )));
-        }
         let description = sub
             .description
             .clone()
             .unwrap_or_else(|| format!("Delegate a task to the '{}' subagent.", sub.id));
         let tool = SubagentTool::new(inner, sub.id.clone(), description, cancel.clone());
-        staged.register(Arc::new(tool));
+        staged.register_unique(Arc::new(tool)).map_err(|_| {
+            RuntimeError::Config(format!(
+                "subagent id '{}' collides with a configured tool of the same name",
+                sub.id
+            ))
+        })?;
     }
     Ok(())
 }
@@ -6,7 +6,9 @@
 //! branch on `Option<store>` at every handler and worker step, both speak to one
 //! [`TaskStore`] interface with two adapters:
 //!
-//! - [`InMemoryTaskStore`] — working set only (tests, no-database serve).
+//! - [`InMemoryTaskStore`] — working set only (tests, no-database serve),
+//!   optionally snapshotting conversations to a local SQLite file so a restart
+//!   recovers them.
 //! - [`PostgresTaskStore`] — the same working set, plus write-through to postgres
 //!   and postgres-authoritative reads for cross-replica polling.
 //!
@@ -25,10 +27,10 @@ use serde::Serialize;
 use tracing::warn;
 use uuid::Uuid;
 
-use neuromance_common::chat::{Message, MessageRole, TaskStatus};
+use neuromance_common::chat::{Conversation, ConversationStatus, Message, MessageRole, TaskStatus};
 use neuromance_db::{
-    ConversationSummary as DbConversationSummary, DbError, PgConversationStore, StoredTask,
-    TaskStatusUpdate,
+    ConversationSummary as DbConversationSummary, DbError, PgConversationStore,
+    SqliteConversationStore, StoredTask, TaskStatusUpdate,
 };
 
 /// A single task's in-memory working record.
@@ -91,6 +93,30 @@ impl From<&ConversationRecord> for ConversationSummary {
     }
 }
 
+impl From<Conversation> for ConversationRecord {
+    fn from(conversation: Conversation) -> Self {
+        let messages = Arc::unwrap_or_clone(conversation.messages);
+        Self {
+            id: conversation.id,
+            created_at: conversation.created_at,
+            updated_at: conversation.updated_at,
+            turn_count: turn_count_of(&messages),
+            messages,
+        }
+    }
+}
+
+impl From<&ConversationRecord> for Conversation {
+    fn from(record: &ConversationRecord) -> Self {
+        let mut conversation = Self::new();
+        conversation.id = record.id;
+        conversation.created_at = record.created_at;
+        conversation.updated_at = record.updated_at;
+        conversation.messages = Arc::new(record.messages.clone());
+        conversation
+    }
+}
+
 impl From<&TaskRecord> for StoredTask {
     fn from(record: &TaskRecord) -> Self {
         Self {
@@ -358,9 +384,17 @@ pub trait TaskStore: Send + Sync {
 
 /// Working-set-only adapter: authoritative for a no-database deployment and used
 /// by tests. Every method operates on the in-memory maps and never fails.
+///
+/// With a snapshot store attached ([`Self::with_snapshots`]), conversations are
+/// also written to a local SQLite file after each change, best-effort like the
+/// rest of the persistence layer. Tasks stay in memory only.
 #[derive(Default)]
 pub struct InMemoryTaskStore {
     state: WorkingState,
+    snapshots: Option<SqliteConversationStore>,
+    /// One lock per conversation, held across reading the working record and
+    /// writing its snapshot, so concurrent saves land in order.
+    snapshot_locks: DashMap<Uuid, Arc<tokio::sync::Mutex<()>>>,
 }
 
 impl InMemoryTaskStore {
@@ -369,6 +403,57 @@ impl InMemoryTaskStore {
         Self::default()
     }
 
+    /// Create a working set backed by `snapshots`, first loading every active
+    /// conversation it holds — the recovery path after a restart.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError`] if the stored conversations cannot be read.
+    pub async fn with_snapshots(snapshots: SqliteConversationStore) -> Result<Self, DbError> {
+        let state = WorkingState::default();
+        for summary in snapshots.list_conversations().await? {
+            if summary.status != ConversationStatus::Active {
+                continue;
+            }
+            if let Some(conversation) = snapshots.load_conversation(summary.id).await? {
+                state
+                    .conversations
+                    .insert(conversation.id, ConversationRecord::from(conversation));
+            }
+        }
+        Ok(Self {
+            state,
+            snapshots: Some(snapshots),
+            snapshot_locks: DashMap::new(),
+        })
+    }
+
+    /// Number of conversation records held.
+    #[must_use]
+    pub fn conversation_count(&self) -> usize {
+        self.state.conversations.len()
+    }
+
+    /// Write the working record for `id` to the snapshot store, if attached.
+    async fn save_snapshot(&self, id: Uuid) {
+        let Some(snapshots) = &self.snapshots else {
+            return;
+        };
+        let lock = Arc::clone(self.snapshot_locks.entry(id).or_default().value());
+        let _guard = lock.lock().await;
+        let Some(conversation) = self
+            .state
+            .conversations
+            .get(&id)
+            .map(|record| Conversation::from(&*record))
+        else {
+            return;
+        };
+        if let Err(e) = snapshots.save_conversation(&conversation).await {
+            warn!(conversation_id = %id, error = %e, "failed to snapshot conversation");
+        }
+    }
+
     /// Number of task records held.
     #[cfg(test)]
     #[must_use]
@@ -387,13 +472,6 @@ impl InMemoryTaskStore {
             .collect()
     }
 
-    /// Number of conversation records held.
-    #[cfg(test)]
-    #[must_use]
-    pub fn conversation_count(&self) -> usize {
-        self.state.conversations.len()
-    }
-
     /// Snapshot of a conversation's messages, or `None` if absent.
     #[cfg(test)]
     #[must_use]
@@ -469,6 +547,17 @@ impl TaskStore for InMemoryTaskStore {
 
     async fn remove_conversation(&self, id: Uuid) {
         self.state.conversations.remove(&id);
+        let Some(snapshots) = &self.snapshots else {
+            return;
+        };
+        let lock = self.snapshot_locks.remove(&id).map(|(_, lock)| lock);
+        let _guard = match &lock {
+            Some(lock) => Some(lock.lock().await),
+            None => None,
+        };
+        if let Err(e) = snapshots.delete_conversation(id).await {
+            warn!(conversation_id = %id, error = %e, "failed to delete conversation snapshot");
+        }
     }
 
     async fn build_turn_input(
@@ -477,11 +566,14 @@ impl TaskStore for InMemoryTaskStore {
         _seeded: bool,
         user_msg: Message,
     ) -> Result<Vec<Message>, TurnInputError> {
-        self.state.build_turn_input(conversation_id, user_msg)
+        let snapshot = self.state.build_turn_input(conversation_id, user_msg)?;
+        self.save_snapshot(conversation_id).await;
+        Ok(snapshot)
     }
 
     async fn refresh_conversation(&self, id: Uuid, full_history: Vec<Message>) {
         self.state.refresh_conversation(id, full_history);
+        self.save_snapshot(id).await;
     }
 
     async fn list_conversations(&self) -> Result<Vec<ConversationSummary>, DbError> {
@@ -903,6 +995,70 @@ mod tests {
         assert_eq!(store.conversation_messages(id).unwrap().len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_snapshots_recover_conversations_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conversations.db");
+        let open = || async {
+            InMemoryTaskStore::with_snapshots(SqliteConversationStore::open(&path).await.unwrap())
+                .await
+                .unwrap()
+        };
+
+        let kept = Uuid::new_v4();
+        let removed = Uuid::new_v4();
+        let store = open().await;
+        for id in [kept, removed] {
+            store.seed_conversation(seeded_conversation(id));
+            store
+                .build_turn_input(id, true, Message::user(id, "hi"))
+                .await
+                .unwrap();
+        }
+        let mut history = store.conversation_messages(kept).unwrap();
+        history.push(Message::assistant(kept, "hello"));
+        store.refresh_conversation(kept, history.clone()).await;
+        store.remove_conversation(removed).await;
+        drop(store);
+
+        let store = open().await;
+        assert_eq!(store.conversation_count(), 1);
+        assert!(!store.conversation_exists(removed).await.unwrap());
+        let recovered = store.conversation_messages(kept).unwrap();
+        let contents: Vec<&str> = recovered.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["system", "hi", "hello"]);
+        assert_eq!(store.conversation_turn_count(kept), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_snapshots_keep_the_latest_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conversations.db");
+        let open = || async {
+            InMemoryTaskStore::with_snapshots(SqliteConversationStore::open(&path).await.unwrap())
+                .await
+                .unwrap()
+        };
+
+        let id = Uuid::new_v4();
+        let store = open().await;
+        store.seed_conversation(seeded_conversation(id));
+        let base = store.conversation_messages(id).unwrap();
+        let refreshes = (1..=8).map(|turns| {
+            let mut history = base.clone();
+            history.extend((0..turns).map(|turn| Message::user(id, format!("turn {turn}"))));
+            store.refresh_conversation(id, history)
+        });
+        futures::future::join_all(refreshes).await;
+        let latest = store.conversation_messages(id).unwrap();
+        drop(store);
+
+        let store = open().await;
+        let recovered = store.conversation_messages(id).unwrap();
+        let ids = |messages: &[Message]| messages.iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids(&recovered), ids(&latest));
+    }
+
     #[tokio::test]
     async fn test_build_turn_input_missing_record_errors() {
         let store = InMemoryTaskStore::new();
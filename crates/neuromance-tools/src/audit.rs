@@ -0,0 +1,345 @@
+//! Structured audit records of tool executions.
+//!
+//! Unlike tracing output, which is free-form and filtered by level, an audit
+//! trail records every tool call with the same fields so it can be queried
+//! afterwards. Register an [`AuditSink`] with
+//! [`ToolExecutor::add_audit_sink`](crate::ToolExecutor::add_audit_sink) and
+//! the executor emits one [`ToolAuditEvent`] per call it runs. Calls a user
+//! denies never reach the executor; whoever decides approval reports them with
+//! [`ToolExecutor::audit_denied`](crate::ToolExecutor::audit_denied).
+//! Arguments, output, and errors are redacted before they reach a sink.
+//!
+//! [`JsonlAuditSink`] appends events to a file, one JSON object per line,
+//! ready for `jq` or a log pipeline.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use crate::truncate::truncate_to;
+
+/// Bytes of tool output kept in a [`ToolAuditEvent`]; longer output is cut
+/// with a notice of how much was kept.
+pub const MAX_AUDIT_OUTPUT_BYTES: usize = 2048;
+
+/// How a tool call came to run, or why it did not.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum AuditApproval {
+    /// The tool is auto-approved, so no one was asked.
+    AutoApproved,
+    /// The call was approved before it ran.
+    Approved,
+    /// The call was approved with arguments supplied by the approver; the
+    /// event's `arguments` are the ones that ran.
+    ApprovedWithArguments {
+        /// The arguments the model asked for.
+        original_arguments: String,
+    },
+    /// The call was denied and did not run.
+    Denied {
+        /// The reason given for the denial.
+        reason: String,
+    },
+}
+
+/// What happened when a tool call ran.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The tool returned output, cut to [`MAX_AUDIT_OUTPUT_BYTES`].
+    Success {
+        /// The tool's output.
+        output: String,
+    },
+    /// The tool failed, timed out, or could not be run.
+    Failure {
+        /// The error message.
+        error: String,
+    },
+    /// The tool was not run, e.g. because the call was denied.
+    Skipped,
+}
+
+/// One audited tool call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolAuditEvent {
+    /// When the call finished, or was denied.
+    pub timestamp: DateTime<Utc>,
+    /// The model's id for the call, when it came from a [`ToolCall`].
+    ///
+    /// [`ToolCall`]: neuromance_common::tools::ToolCall
+    pub tool_call_id: Option<String>,
+    /// The tool's name.
+    pub tool: String,
+    /// The JSON-encoded arguments the tool ran with, redacted with the
+    /// executor's [audit redaction patterns](crate::ToolExecutor::set_audit_redaction_patterns).
+    pub arguments: String,
+    /// The approval decision for the call.
+    pub approval: AuditApproval,
+    /// Wall-clock time spent executing, including any wait for a
+    /// concurrency slot. Zero for calls that did not run.
+    #[serde(with = "duration_millis")]
+    pub duration: Duration,
+    /// The result of the call.
+    pub outcome: AuditOutcome,
+}
+
+impl ToolAuditEvent {
+    /// Whether the tool ran and succeeded.
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        matches!(self.outcome, AuditOutcome::Success { .. })
+    }
+}
+
+impl AuditOutcome {
+    /// A success whose output is cut to [`MAX_AUDIT_OUTPUT_BYTES`].
+    #[must_use]
+    pub fn success(output: &str) -> Self {
+        Self::Success {
+            output: truncate_to(output.to_owned(), MAX_AUDIT_OUTPUT_BYTES),
+        }
+    }
+}
+
+/// Serialize a [`Duration`] as whole milliseconds so it can be compared in queries.
+mod duration_millis {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(u64::try_from(duration.as_millis()).unwrap_or(u64::MAX))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+/// A destination for [`ToolAuditEvent`]s.
+///
+/// A sink that fails does not fail the tool call; the executor logs the error
+/// and carries on.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    /// Store one event.
+    ///
+    /// # Errors
+    /// Returns an error if the event could not be stored.
+    async fn record(&self, event: &ToolAuditEvent) -> io::Result<()>;
+}
+
+/// An [`AuditSink`] appending each event to a file as one line of JSON.
+#[derive(Debug)]
+pub struct JsonlAuditSink {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl JsonlAuditSink {
+    /// Open `path` for appending, creating it if needed.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened.
+    pub async fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// The file events are appended to.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl AuditSink for JsonlAuditSink {
+    async fn record(&self, event: &ToolAuditEvent) -> io::Result<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        // One write per event keeps lines whole when calls finish concurrently.
+        let mut file = self.file.lock().await;
+        file.write_all(&line).await?;
+        file.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic)]
+
+    use std::sync::Arc;
+
+    use neuromance_common::tools::{Function, FunctionCall, Tool, ToolCall};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{ToolError, ToolExecutor, ToolImplementation};
+
+    /// Succeeds with a long output unless asked to fail.
+    struct ReportTool;
+
+    #[async_trait]
+    impl ToolImplementation for ReportTool {
+        fn get_definition(&self) -> Tool {
+            Tool::builder()
+                .function(Function {
+                    name: "report".to_string(),
+                    description: "report".to_string(),
+                    parameters: json!({}),
+                })
+                .build()
+        }
+
+        async fn execute(&self, args: &Value) -> Result<String, ToolError> {
+            if args["fail"] == true {
+                Err(ToolError::execution("disk full"))
+            } else {
+                Ok("x".repeat(MAX_AUDIT_OUTPUT_BYTES * 2))
+            }
+        }
+
+        fn is_auto_approved(&self) -> bool {
+            true
+        }
+    }
+
+    fn call(id: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            id: id.to_string(),
+            function: FunctionCall {
+                name: "report".to_string(),
+                arguments: arguments.to_string(),
+            },
+            call_type: "function".to_string(),
+            index: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_events_for_success_failure_and_denial() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = Arc::new(
+            JsonlAuditSink::open(dir.path().join("audit.jsonl"))
+                .await
+                .unwrap(),
+        );
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(ReportTool);
+        executor.add_audit_sink(Arc::clone(&sink) as Arc<dyn AuditSink>);
+
+        executor.execute_tool(&call("call_1", "{}")).await.unwrap();
+        executor
+            .execute_tool(&call("call_2", r#"{"fail": true}"#))
+            .await
+            .unwrap_err();
+        executor
+            .audit_denied(&call("call_3", r#"{"fail": false}"#), "not today")
+            .await;
+
+        let text = std::fs::read_to_string(sink.path()).unwrap();
+        let events: Vec<ToolAuditEvent> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 3);
+
+        let success = &events[0];
+        assert_eq!(success.tool_call_id.as_deref(), Some("call_1"));
+        assert_eq!(success.tool, "report");
+        assert_eq!(success.arguments, "{}");
+        assert_eq!(success.approval, AuditApproval::AutoApproved);
+        assert!(success.is_success());
+        let AuditOutcome::Success { output } = &success.outcome else {
+            panic!("expected success, got {:?}", success.outcome);
+        };
+        assert!(output.starts_with(&"x".repeat(MAX_AUDIT_OUTPUT_BYTES)));
+        assert!(output.contains("[truncated: showing 2048 of 4096 bytes]"));
+
+        let failure = &events[1];
+        assert_eq!(failure.tool_call_id.as_deref(), Some("call_2"));
+        assert_eq!(failure.arguments, r#"{"fail": true}"#);
+        assert_eq!(
+            failure.outcome,
+            AuditOutcome::Failure {
+                error: "disk full".to_string()
+            }
+        );
+
+        let denied = &events[2];
+        assert_eq!(
+            denied.approval,
+            AuditApproval::Denied {
+                reason: "not today".to_string()
+            }
+        );
+        assert_eq!(denied.outcome, AuditOutcome::Skipped);
+        assert_eq!(denied.duration, Duration::ZERO);
+
+        let raw: Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(raw["approval"], json!({"decision": "auto_approved"}));
+        assert_eq!(raw["outcome"]["status"], "success");
+        assert!(raw["duration"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn test_audit_records_given_approval_and_redacts_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = Arc::new(
+            JsonlAuditSink::open(dir.path().join("audit.jsonl"))
+                .await
+                .unwrap(),
+        );
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(ReportTool);
+        executor.add_audit_sink(Arc::clone(&sink) as Arc<dyn AuditSink>);
+
+        let secret = r#"{"token": "sk-abcdefghijklmnopqrstuv"}"#;
+        let approval = AuditApproval::ApprovedWithArguments {
+            original_arguments: r#"{"auth": "Bearer abc.def"}"#.to_string(),
+        };
+        executor
+            .execute_approved_tool(&call("call_1", secret), approval)
+            .await
+            .unwrap();
+        executor.set_audit_redaction_patterns(Vec::new());
+        executor
+            .execute_approved_tool(&call("call_2", secret), AuditApproval::Approved)
+            .await
+            .unwrap();
+
+        let events: Vec<ToolAuditEvent> = std::fs::read_to_string(sink.path())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events[0].arguments, r#"{"token": "[REDACTED]"}"#);
+        assert_eq!(
+            events[0].approval,
+            AuditApproval::ApprovedWithArguments {
+                original_arguments: r#"{"auth": "[REDACTED]"}"#.to_string()
+            }
+        );
+        assert_eq!(events[1].arguments, secret);
+        assert_eq!(events[1].approval, AuditApproval::Approved);
+    }
+}
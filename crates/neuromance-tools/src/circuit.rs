@@ -0,0 +1,84 @@
+//! Per-tool circuit breaker for [`ToolExecutor`](crate::ToolExecutor).
+//!
+//! A tool that keeps failing (a crashed MCP server, a revoked credential)
+//! wastes a model turn on every call. Once a tool has failed
+//! [`failure_threshold`](CircuitBreakerConfig::failure_threshold) times in a
+//! row its circuit opens and calls are rejected without running it until the
+//! cooldown passes. The first call after the cooldown runs normally: a success
+//! closes the circuit, a failure reopens it for another cooldown.
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+/// When to open a tool's circuit and how long to keep it open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures after which the tool is disabled.
+    pub failure_threshold: u32,
+    /// How long a disabled tool stays disabled.
+    pub cooldown: Duration,
+}
+
+impl CircuitBreakerConfig {
+    #[must_use]
+    pub const fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+        }
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self::new(3, Duration::from_secs(60))
+    }
+}
+
+#[derive(Debug, Default)]
+struct ToolHealth {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Failure counts per tool name, shared by concurrent calls.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    tools: DashMap<String, ToolHealth>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            tools: DashMap::new(),
+        }
+    }
+
+    pub const fn config(&self) -> CircuitBreakerConfig {
+        self.config
+    }
+
+    /// How much longer `name` stays disabled, or `None` if it may run.
+    pub fn remaining_cooldown(&self, name: &str) -> Option<Duration> {
+        let opened_at = self.tools.get(name)?.opened_at?;
+        self.config
+            .cooldown
+            .checked_sub(opened_at.elapsed())
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    pub fn record_success(&self, name: &str) {
+        self.tools.remove(name);
+    }
+
+    pub fn record_failure(&self, name: &str) {
+        let mut health = self.tools.entry(name.to_owned()).or_default();
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= self.config.failure_threshold {
+            health.opened_at = Some(Instant::now());
+        }
+    }
+}
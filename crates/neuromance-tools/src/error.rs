@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
 
 use thiserror::Error;
 
@@ -10,6 +11,26 @@ pub enum ToolError {
     #[error("{0}")]
     InvalidArguments(String),
 
+    /// Arguments that do not match the tool's parameter schema.
+    ///
+    /// Returned by [`ToolExecutor`](crate::ToolExecutor) before the tool runs
+    /// when [`set_validate_arguments`](crate::ToolExecutor::set_validate_arguments)
+    /// is enabled.
+    #[error("Arguments for tool '{tool}' do not match its schema: {}", .violations.join("; "))]
+    ArgumentSchema {
+        /// Name of the tool that was called.
+        tool: String,
+        /// One description per violation, prefixed with its JSON pointer
+        /// when it is not at the top level.
+        violations: Vec<String>,
+    },
+
+    /// A tool with this name is already registered.
+    ///
+    /// Returned by [`ToolRegistry::register_unique`](crate::ToolRegistry::register_unique).
+    #[error("Tool '{0}' is already registered")]
+    AlreadyRegistered(String),
+
     /// Runtime failure during tool construction or execution.
     ///
     /// Wraps the source error for downcasting (e.g., to `ReplError`).
@@ -46,4 +67,37 @@ pub enum ToolExecutorError {
     /// Forwarded from the tool's `execute()` method.
     #[error(transparent)]
     Tool(#[from] ToolError),
+
+    /// The tool did not finish within its execution timeout.
+    #[error("Tool '{name}' timed out after {duration:?}")]
+    Timeout {
+        /// Name of the tool that timed out.
+        name: String,
+        /// The timeout that elapsed.
+        duration: Duration,
+    },
+
+    /// The tool failed too many times in a row and is temporarily disabled.
+    ///
+    /// See [`ToolExecutor::set_circuit_breaker`](crate::ToolExecutor::set_circuit_breaker).
+    #[error(
+        "Tool '{name}' is temporarily disabled after repeated failures; retry in {retry_after:?}"
+    )]
+    CircuitOpen {
+        /// Name of the disabled tool.
+        name: String,
+        /// Time left until the tool may run again.
+        retry_after: Duration,
+    },
+
+    /// The tool is over its rate limit.
+    ///
+    /// See [`ToolExecutor::set_rate_limit`](crate::ToolExecutor::set_rate_limit).
+    #[error("Tool '{name}' is over its rate limit; retry in {retry_after:?}")]
+    RateLimited {
+        /// Name of the limited tool.
+        name: String,
+        /// Time until the tool may run again.
+        retry_after: Duration,
+    },
 }
@@ -1,11 +1,21 @@
 use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use serde_json::Value;
+use reqwest::redirect;
+use serde_json::{Value, json};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+use url::Url;
 
+use crate::truncate::{decode_capped, truncate_to};
 use crate::{ToolError, ToolImplementation};
-use neuromance_common::tools::{Function, Parameters, Tool};
+use neuromance_common::tools::{Function, Parameters, Property, Tool};
 
 pub struct CurrentTimeTool;
 #[async_trait]
@@ -33,3 +43,1187 @@ impl ToolImplementation for CurrentTimeTool {
         true // Time tool is safe and can be auto-approved
     }
 }
+
+/// A tool backed by an async closure, for ad-hoc tools that don't warrant
+/// their own type.
+///
+/// The closure receives the call's arguments and returns the tool output.
+/// Not auto-approved unless [`with_auto_approved`](Self::with_auto_approved)
+/// says otherwise.
+///
+/// ```
+/// use neuromance_common::tools::{Function, Tool};
+/// use neuromance_tools::generic::FnTool;
+///
+/// let definition = Tool::builder()
+///     .function(Function {
+///         name: "shout".to_string(),
+///         description: "Upper-case the given text.".to_string(),
+///         parameters: serde_json::json!({"type": "object"}),
+///     })
+///     .build();
+/// let tool = FnTool::new(definition, |args| async move {
+///     Ok(args["text"].as_str().unwrap_or_default().to_uppercase())
+/// });
+/// ```
+pub struct FnTool<F> {
+    definition: Tool,
+    handler: F,
+    auto_approved: bool,
+}
+
+impl<F, Fut> FnTool<F>
+where
+    F: Fn(Value) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<String, ToolError>> + Send,
+{
+    /// Creates a tool advertised as `definition` that runs `handler`.
+    pub const fn new(definition: Tool, handler: F) -> Self {
+        Self {
+            definition,
+            handler,
+            auto_approved: false,
+        }
+    }
+
+    /// Sets whether calls may run without user approval.
+    #[must_use]
+    pub const fn with_auto_approved(mut self, auto_approved: bool) -> Self {
+        self.auto_approved = auto_approved;
+        self
+    }
+}
+
+#[async_trait]
+impl<F, Fut> ToolImplementation for FnTool<F>
+where
+    F: Fn(Value) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<String, ToolError>> + Send,
+{
+    fn get_definition(&self) -> Tool {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, args: &Value) -> Result<String, ToolError> {
+        (self.handler)(args.clone()).await
+    }
+
+    fn is_auto_approved(&self) -> bool {
+        self.auto_approved
+    }
+}
+
+/// Decides the answer of a [`BooleanTool`] call from its arguments.
+pub type BooleanEvaluator = Arc<dyn Fn(&Value) -> bool + Send + Sync>;
+
+/// A tool that answers a yes/no question with `"true"` or `"false"`.
+///
+/// By default it evaluates a `{"condition": "..."}` argument with
+/// [`compare_condition`]; [`with_evaluator`](Self::with_evaluator) lets the
+/// caller decide instead. Auto-approved, as it only computes.
+pub struct BooleanTool {
+    evaluator: BooleanEvaluator,
+}
+
+impl BooleanTool {
+    /// Creates a tool that evaluates simple comparisons.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            evaluator: Arc::new(compare_condition),
+        }
+    }
+
+    /// Replaces the condition logic with `evaluator`.
+    #[must_use]
+    pub fn with_evaluator(mut self, evaluator: BooleanEvaluator) -> Self {
+        self.evaluator = evaluator;
+        self
+    }
+}
+
+impl Default for BooleanTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ToolImplementation for BooleanTool {
+    fn get_definition(&self) -> Tool {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "condition".to_string(),
+            Property::string(
+                "A comparison such as `3 >= 2` or `status == ready`. Operands that \
+                 parse as numbers compare numerically, others as text.",
+            ),
+        );
+
+        Tool::builder()
+            .function(Function {
+                name: "evaluate_condition".to_string(),
+                description: "Evaluate a condition and return true or false.".to_string(),
+                parameters: Parameters::new(properties, vec!["condition".into()]).into(),
+            })
+            .build()
+    }
+
+    async fn execute(&self, args: &Value) -> Result<String, ToolError> {
+        Ok((self.evaluator)(args).to_string())
+    }
+
+    fn is_auto_approved(&self) -> bool {
+        true
+    }
+}
+
+/// Comparison operators understood by [`compare_condition`], two-character
+/// ones first so `>=` is not read as `>`.
+const COMPARISON_OPERATORS: [&str; 6] = [">=", "<=", "==", "!=", ">", "<"];
+
+/// Evaluates `args["condition"]` as `<lhs> <op> <rhs>` with one of `==`,
+/// `!=`, `<`, `<=`, `>`, `>=`.
+///
+/// Operands are trimmed and may be quoted. When both parse as numbers they
+/// compare numerically, otherwise as strings. A bare `true` or `false` is
+/// taken as is; anything else, including a missing condition, is `false`.
+#[must_use]
+pub fn compare_condition(args: &Value) -> bool {
+    let Some(condition) = args.get("condition").and_then(Value::as_str) else {
+        return false;
+    };
+    let condition = condition.trim();
+    let operator = condition.char_indices().find_map(|(at, _)| {
+        COMPARISON_OPERATORS
+            .iter()
+            .find(|op| condition[at..].starts_with(**op))
+            .map(|op| (at, *op))
+    });
+    let Some((at, operator)) = operator else {
+        return condition.eq_ignore_ascii_case("true");
+    };
+
+    let operand = |text: &str| {
+        let text = text.trim();
+        text.strip_prefix('"')
+            .and_then(|t| t.strip_suffix('"'))
+            .unwrap_or(text)
+            .to_string()
+    };
+    let lhs = operand(&condition[..at]);
+    let rhs = operand(&condition[at + operator.len()..]);
+    let ordering = match (lhs.parse::<f64>(), rhs.parse::<f64>()) {
+        (Ok(l), Ok(r)) => l.partial_cmp(&r),
+        _ => Some(lhs.cmp(&rhs)),
+    };
+    let Some(ordering) = ordering else {
+        return false;
+    };
+    match operator {
+        "==" => ordering.is_eq(),
+        "!=" => ordering.is_ne(),
+        ">=" => ordering.is_ge(),
+        "<=" => ordering.is_le(),
+        ">" => ordering.is_gt(),
+        _ => ordering.is_lt(),
+    }
+}
+
+/// Default cap on the response body returned by [`HttpGetTool`].
+pub const DEFAULT_HTTP_MAX_BYTES: usize = 64 * 1024;
+
+/// Timeout for a single [`HttpGetTool`] request, including redirects.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An HTTP GET issued by a network tool.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// The URL to fetch, already checked against the tool's allowlist.
+    pub url: Url,
+    /// Request headers as name/value pairs.
+    pub headers: Vec<(String, String)>,
+    /// Most body bytes to read; the rest of a longer body is not downloaded.
+    pub max_bytes: usize,
+}
+
+/// The response to an [`HttpRequest`].
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response body decoded as text, at most the request's `max_bytes`.
+    pub body: String,
+    /// Whether the body was cut at `max_bytes`.
+    pub truncated: bool,
+    /// Full body length, when the server declared it.
+    pub total_bytes: Option<u64>,
+}
+
+impl HttpResponse {
+    /// Whether the status is in the 2xx range.
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        self.status >= 200 && self.status < 300
+    }
+}
+
+/// Performs the HTTP requests of network tools.
+///
+/// Tools take an `Arc<dyn HttpFetcher>` rather than building their own client,
+/// so tests can substitute canned responses for the network.
+#[async_trait]
+pub trait HttpFetcher: Send + Sync {
+    /// Sends `request` and returns the response, whatever its status.
+    ///
+    /// Reads at most `request.max_bytes` of the body, flagging the response
+    /// as truncated when there was more.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request cannot be sent or its body cannot be read.
+    async fn fetch(&self, request: HttpRequest) -> Result<HttpResponse, ToolError>;
+}
+
+/// [`HttpFetcher`] backed by a `reqwest` client.
+///
+/// Redirects are followed only to the hosts it was built with, and each request
+/// times out after 30 seconds.
+pub struct ReqwestFetcher {
+    client: reqwest::Client,
+}
+
+impl ReqwestFetcher {
+    /// Creates a fetcher that follows redirects only to `allowed_hosts`
+    /// (matched exactly, ignoring case).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying HTTP client fails to build.
+    pub fn new(
+        allowed_hosts: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Self, ToolError> {
+        let redirect_hosts = lowercase_hosts(allowed_hosts);
+        let client = reqwest::Client::builder()
+            .timeout(HTTP_TIMEOUT)
+            .redirect(redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() >= 10 {
+                    attempt.error("too many redirects")
+                } else if is_allowed(&redirect_hosts, attempt.url()) {
+                    attempt.follow()
+                } else {
+                    let message = format!("redirect to non-allowlisted URL {}", attempt.url());
+                    attempt.error(message)
+                }
+            }))
+            .build()
+            .map_err(|e| ToolError::execution(format!("failed to build HTTP client: {e}")))?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl HttpFetcher for ReqwestFetcher {
+    async fn fetch(&self, request: HttpRequest) -> Result<HttpResponse, ToolError> {
+        let url = request.url;
+        let mut builder = self.client.get(url.clone());
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        let mut response = builder
+            .send()
+            .await
+            .map_err(|e| ToolError::execution(format!("GET {url} failed: {e}")))?;
+        let status = response.status().as_u16();
+        let total_bytes = response.content_length();
+
+        // The body is read chunk by chunk and only one byte past the cap is
+        // kept, so a huge response is never held in memory.
+        let limit = request.max_bytes.saturating_add(1);
+        let mut bytes = Vec::new();
+        while bytes.len() < limit
+            && let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| ToolError::execution(format!("failed to read body of {url}: {e}")))?
+        {
+            let take = chunk.len().min(limit - bytes.len());
+            bytes.extend_from_slice(&chunk[..take]);
+        }
+        let (body, truncated) = decode_capped(bytes, request.max_bytes);
+        Ok(HttpResponse {
+            status,
+            body,
+            truncated,
+            total_bytes,
+        })
+    }
+}
+
+/// Fetches web content with an HTTP GET.
+///
+/// Only `http` and `https` URLs whose host is on the allowlist are fetched;
+/// redirects are followed only to allowlisted hosts. The body is returned as
+/// text, truncated to [`DEFAULT_HTTP_MAX_BYTES`] unless configured otherwise.
+/// Not auto-approved, since it reaches the network.
+pub struct HttpGetTool {
+    fetcher: Arc<dyn HttpFetcher>,
+    allowed_hosts: Arc<[String]>,
+    max_bytes: usize,
+}
+
+impl HttpGetTool {
+    /// Creates a tool that may fetch from `allowed_hosts` (matched exactly,
+    /// ignoring case), using a [`ReqwestFetcher`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying HTTP client fails to build.
+    pub fn new(
+        allowed_hosts: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Self, ToolError> {
+        let allowed_hosts = lowercase_hosts(allowed_hosts);
+        let fetcher = ReqwestFetcher::new(allowed_hosts.iter().cloned())?;
+        Ok(Self::with_fetcher(
+            allowed_hosts.iter().cloned(),
+            Arc::new(fetcher),
+        ))
+    }
+
+    /// Creates a tool that sends its requests through `fetcher`.
+    ///
+    /// The allowlist is checked before each request; keeping redirects within
+    /// it is up to the fetcher.
+    pub fn with_fetcher(
+        allowed_hosts: impl IntoIterator<Item = impl Into<String>>,
+        fetcher: Arc<dyn HttpFetcher>,
+    ) -> Self {
+        Self {
+            fetcher,
+            allowed_hosts: lowercase_hosts(allowed_hosts),
+            max_bytes: DEFAULT_HTTP_MAX_BYTES,
+        }
+    }
+
+    /// Sets the maximum number of body bytes returned.
+    #[must_use]
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Parses `url` and checks its scheme and host against the allowlist.
+    fn check_url(&self, url: &str) -> Result<Url, ToolError> {
+        let parsed = Url::parse(url)
+            .map_err(|e| ToolError::InvalidArguments(format!("invalid URL '{url}': {e}")))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(ToolError::InvalidArguments(format!(
+                "unsupported URL scheme '{}': only http and https are allowed",
+                parsed.scheme()
+            )));
+        }
+        if !is_allowed(&self.allowed_hosts, &parsed) {
+            return Err(ToolError::InvalidArguments(format!(
+                "host '{}' is not in the allowlist",
+                parsed.host_str().unwrap_or_default()
+            )));
+        }
+        Ok(parsed)
+    }
+}
+
+/// Normalizes an allowlist to lowercase host names.
+fn lowercase_hosts(hosts: impl IntoIterator<Item = impl Into<String>>) -> Arc<[String]> {
+    hosts
+        .into_iter()
+        .map(|host| host.into().to_ascii_lowercase())
+        .collect()
+}
+
+/// Whether `url` is http(s) and its host is in `allowed_hosts`.
+fn is_allowed(allowed_hosts: &[String], url: &Url) -> bool {
+    matches!(url.scheme(), "http" | "https")
+        && url.host_str().is_some_and(|host| {
+            let host = host.to_ascii_lowercase();
+            allowed_hosts.contains(&host)
+        })
+}
+
+#[async_trait]
+impl ToolImplementation for HttpGetTool {
+    fn get_definition(&self) -> Tool {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "url".to_string(),
+            Property::string("The http or https URL to fetch."),
+        );
+        properties.insert(
+            "headers".to_string(),
+            Property::object(
+                "Optional request headers as name/value strings.",
+                HashMap::new(),
+                vec![],
+            ),
+        );
+
+        Tool::builder()
+            .function(Function {
+                name: "http_get".to_string(),
+                description: format!(
+                    "Fetch a URL with an HTTP GET and return the response body as text. \
+                     Only these hosts are allowed: {}. Bodies longer than {} bytes are \
+                     truncated.",
+                    self.allowed_hosts.join(", "),
+                    self.max_bytes
+                ),
+                parameters: Parameters::new(properties, vec!["url".into()]).into(),
+            })
+            .build()
+    }
+
+    async fn execute(&self, args: &Value) -> Result<String, ToolError> {
+        let url = args
+            .get("url")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ToolError::InvalidArguments("missing 'url' parameter".into()))?;
+        let url = self.check_url(url)?;
+
+        let mut headers = Vec::new();
+        match args.get("headers") {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(map)) => {
+                for (name, value) in map {
+                    let value = value.as_str().ok_or_else(|| {
+                        ToolError::InvalidArguments(format!("header '{name}' must be a string"))
+                    })?;
+                    headers.push((name.clone(), value.to_string()));
+                }
+            }
+            Some(_) => {
+                return Err(ToolError::InvalidArguments(
+                    "'headers' must be an object".into(),
+                ));
+            }
+        }
+
+        let response = self
+            .fetcher
+            .fetch(HttpRequest {
+                url: url.clone(),
+                headers,
+                max_bytes: self.max_bytes,
+            })
+            .await?;
+        if !response.is_success() {
+            return Err(ToolError::execution(format!(
+                "GET {url} returned {}",
+                response.status
+            )));
+        }
+
+        if !response.truncated {
+            // Guards against a fetcher that ignored the requested cap.
+            return Ok(truncate_to(response.body, self.max_bytes));
+        }
+        let shown = response.body.len();
+        Ok(match response.total_bytes {
+            Some(total) => format!(
+                "{}\n[truncated: showing {shown} of {total} bytes]",
+                response.body
+            ),
+            None => format!(
+                "{}\n[truncated: showing the first {shown} bytes]",
+                response.body
+            ),
+        })
+    }
+
+    fn is_auto_approved(&self) -> bool {
+        false
+    }
+}
+
+/// Default cap on the file contents returned by [`ReadFileTool`].
+pub const DEFAULT_READ_FILE_MAX_BYTES: usize = 64 * 1024;
+
+/// Reads a text file confined to a root directory.
+///
+/// Paths are resolved against the root and canonicalized before reading, so
+/// `..` components and symlinks that lead outside the root are rejected.
+/// Contents are truncated to [`DEFAULT_READ_FILE_MAX_BYTES`] unless
+/// configured otherwise. Not auto-approved.
+pub struct ReadFileTool {
+    root: PathBuf,
+    max_bytes: usize,
+}
+
+impl ReadFileTool {
+    /// Creates a tool that may read files under `root`.
+    #[must_use]
+    pub const fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            max_bytes: DEFAULT_READ_FILE_MAX_BYTES,
+        }
+    }
+
+    /// Sets the maximum number of bytes returned.
+    #[must_use]
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Resolves `path` against the root and checks that the canonical result
+    /// stays inside the canonical root.
+    async fn resolve(&self, path: &str) -> Result<PathBuf, ToolError> {
+        let root = tokio::fs::canonicalize(&self.root).await.map_err(|e| {
+            ToolError::execution(format!(
+                "failed to resolve root {}: {e}",
+                self.root.display()
+            ))
+        })?;
+        let resolved = tokio::fs::canonicalize(root.join(path))
+            .await
+            .map_err(|e| ToolError::InvalidArguments(format!("cannot read '{path}': {e}")))?;
+        if !resolved.starts_with(&root) {
+            return Err(ToolError::InvalidArguments(format!(
+                "path '{path}' is outside the allowed root"
+            )));
+        }
+        Ok(resolved)
+    }
+}
+
+#[async_trait]
+impl ToolImplementation for ReadFileTool {
+    fn get_definition(&self) -> Tool {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "path".to_string(),
+            Property::string("Path of the file to read, relative to the root directory."),
+        );
+
+        Tool::builder()
+            .function(Function {
+                name: "read_file".to_string(),
+                description: format!(
+                    "Read a UTF-8 text file under {}. Paths outside this directory are \
+                     rejected. Files longer than {} bytes are truncated and binary files \
+                     are refused.",
+                    self.root.display(),
+                    self.max_bytes
+                ),
+                parameters: Parameters::new(properties, vec!["path".into()]).into(),
+            })
+            .build()
+    }
+
+    async fn execute(&self, args: &Value) -> Result<String, ToolError> {
+        let path = args
+            .get("path")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ToolError::InvalidArguments("missing 'path' parameter".into()))?;
+        let resolved = self.resolve(path).await?;
+
+        let read_error =
+            |e: std::io::Error| ToolError::execution(format!("failed to read '{path}': {e}"));
+        let file = tokio::fs::File::open(&resolved).await.map_err(read_error)?;
+        let total = file.metadata().await.map_err(read_error)?.len();
+
+        // Only one byte past the cap is read, enough to tell that the file
+        // was cut without pulling a large file into memory.
+        let limit = u64::try_from(self.max_bytes)
+            .unwrap_or(u64::MAX)
+            .saturating_add(1);
+        let mut bytes = Vec::new();
+        file.take(limit)
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(read_error)?;
+
+        if bytes.contains(&0) {
+            return Err(ToolError::execution(format!(
+                "'{path}' looks like a binary file"
+            )));
+        }
+        let (contents, truncated) = decode_capped(bytes, self.max_bytes);
+        if truncated {
+            Ok(format!(
+                "{contents}\n[truncated: showing {} of {total} bytes]",
+                contents.len()
+            ))
+        } else {
+            Ok(contents)
+        }
+    }
+
+    fn is_auto_approved(&self) -> bool {
+        false
+    }
+}
+
+/// Default cap on each of stdout and stderr returned by [`ShellCommandTool`].
+pub const DEFAULT_SHELL_OUTPUT_MAX_BYTES: usize = 64 * 1024;
+
+/// Default time a [`ShellCommandTool`] command may run before it is killed.
+pub const DEFAULT_SHELL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Runs a program from a fixed allowlist with literal arguments.
+///
+/// Unlike [`BashTool`](crate::BashTool), nothing goes through a shell: the
+/// program is spawned directly, so arguments are never expanded, split, or
+/// interpreted. The program name must match an allowlist entry exactly. The
+/// result is a JSON object with `exit_code`, `stdout`, and `stderr`. Each
+/// stream is read with a cap of [`DEFAULT_SHELL_OUTPUT_MAX_BYTES`] unless
+/// configured otherwise; past the cap the rest is discarded as it arrives and
+/// the stream ends with a truncation footer. Never auto-approved.
+pub struct ShellCommandTool {
+    allowed_commands: Arc<[String]>,
+    timeout: Duration,
+    max_bytes: usize,
+}
+
+impl ShellCommandTool {
+    /// Creates a tool that may run only the programs in `allowed_commands`.
+    pub fn new(allowed_commands: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowed_commands: allowed_commands.into_iter().map(Into::into).collect(),
+            timeout: DEFAULT_SHELL_TIMEOUT,
+            max_bytes: DEFAULT_SHELL_OUTPUT_MAX_BYTES,
+        }
+    }
+
+    /// Sets how long a command may run before it is killed.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum number of bytes returned for each of stdout and stderr.
+    #[must_use]
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+}
+
+/// Reads `pipe` to the end, keeping at most `max_bytes + 1` bytes and only
+/// counting the rest, so a chatty command neither fills memory nor blocks on
+/// a full pipe. Returns the kept bytes and the total read.
+async fn read_capped(
+    mut pipe: impl AsyncRead + Unpin,
+    max_bytes: usize,
+) -> std::io::Result<(Vec<u8>, u64)> {
+    let limit = max_bytes.saturating_add(1);
+    let mut kept = Vec::new();
+    let mut total = 0u64;
+    let mut buf = vec![0u8; 8 * 1024];
+    loop {
+        let n = pipe.read(&mut buf).await?;
+        if n == 0 {
+            return Ok((kept, total));
+        }
+        total += n as u64;
+        let room = limit.saturating_sub(kept.len()).min(n);
+        kept.extend_from_slice(&buf[..room]);
+    }
+}
+
+#[async_trait]
+impl ToolImplementation for ShellCommandTool {
+    fn get_definition(&self) -> Tool {
+        // `Property::array` only describes arrays of objects, so the schema
+        // is written out to give `args` string items.
+        let parameters = json!({
+            "type": "object",
+            "properties": {
+                "command": {
+                    "type": "string",
+                    "description": "The program to run, without arguments.",
+                },
+                "args": {
+                    "type": "array",
+                    "description": "Arguments passed to the program as-is, without shell interpretation.",
+                    "items": {"type": "string"},
+                },
+            },
+            "required": ["command"],
+        });
+
+        Tool::builder()
+            .function(Function {
+                name: "run_command".to_string(),
+                description: format!(
+                    "Run a program directly (no shell) and return its exit code, stdout, \
+                     and stderr as JSON. Only these programs are allowed: {}. Commands are \
+                     killed after {} seconds.",
+                    self.allowed_commands.join(", "),
+                    self.timeout.as_secs()
+                ),
+                parameters,
+            })
+            .build()
+    }
+
+    async fn execute(&self, args: &Value) -> Result<String, ToolError> {
+        let command = args
+            .get("command")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ToolError::InvalidArguments("missing 'command' parameter".into()))?;
+        if !self.allowed_commands.iter().any(|c| c == command) {
+            return Err(ToolError::InvalidArguments(format!(
+                "command '{command}' is not in the allowlist"
+            )));
+        }
+        let command_args = match args.get("args") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    item.as_str().ok_or_else(|| {
+                        ToolError::InvalidArguments("'args' must be an array of strings".into())
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_) => {
+                return Err(ToolError::InvalidArguments(
+                    "'args' must be an array of strings".into(),
+                ));
+            }
+        };
+
+        let mut child = Command::new(command)
+            .args(command_args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| ToolError::execution(format!("failed to run '{command}': {e}")))?;
+        let (Some(stdout), Some(stderr)) = (child.stdout.take(), child.stderr.take()) else {
+            return Err(ToolError::execution(format!(
+                "failed to capture output of '{command}'"
+            )));
+        };
+        let run = async {
+            tokio::try_join!(
+                read_capped(stdout, self.max_bytes),
+                read_capped(stderr, self.max_bytes),
+                child.wait(),
+            )
+        };
+        let (stdout, stderr, status) = tokio::time::timeout(self.timeout, run)
+            .await
+            .map_err(|_| {
+                ToolError::execution(format!(
+                    "'{command}' timed out after {}ms",
+                    self.timeout.as_millis()
+                ))
+            })?
+            .map_err(|e| ToolError::execution(format!("error running '{command}': {e}")))?;
+
+        let stream = |(bytes, total): (Vec<u8>, u64)| {
+            let (text, truncated) = decode_capped(bytes, self.max_bytes);
+            if truncated {
+                format!(
+                    "{text}\n[truncated: showing {} of {total} bytes]",
+                    text.len()
+                )
+            } else {
+                text
+            }
+        };
+        Ok(json!({
+            "exit_code": status.code(),
+            "stdout": stream(stdout),
+            "stderr": stream(stderr),
+        })
+        .to_string())
+    }
+
+    fn is_auto_approved(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use tempfile::tempdir;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::ToolExecutor;
+
+    #[tokio::test]
+    async fn fn_tool_registers_and_executes_closure() {
+        let definition = Tool::builder()
+            .function(Function {
+                name: "add".to_string(),
+                description: "Add two integers.".to_string(),
+                parameters: json!({"type": "object"}),
+            })
+            .build();
+        let offset = 10;
+        let tool = FnTool::new(definition, move |args| async move {
+            let sum = ["a", "b"]
+                .iter()
+                .map(|key| args.get(*key).and_then(Value::as_i64))
+                .sum::<Option<i64>>()
+                .ok_or_else(|| {
+                    ToolError::InvalidArguments("'a' and 'b' must be integers".into())
+                })?;
+            Ok((sum + offset).to_string())
+        })
+        .with_auto_approved(true);
+
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(tool);
+        assert!(executor.is_tool_auto_approved("add"));
+        assert_eq!(
+            executor
+                .execute_named("add", r#"{"a": 2, "b": 3}"#)
+                .await
+                .unwrap(),
+            "15"
+        );
+        let err = executor
+            .execute_named("add", r#"{"a": 2}"#)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("must be integers"),
+            "unexpected: {err}"
+        );
+    }
+
+    #[test]
+    fn compare_condition_handles_numbers_strings_and_literals() {
+        let eval = |condition: &str| compare_condition(&json!({ "condition": condition }));
+
+        assert!(eval("10 > 9"));
+        assert!(eval("2.5 <= 2.50"));
+        assert!(!eval("3 >= 4"));
+        assert!(eval("\"ready\" == ready"));
+        assert!(!eval("status == ready"));
+        assert!(eval("apple < banana"));
+        assert!(eval("a != b"));
+        assert!(eval("true"));
+        assert!(!eval("maybe"));
+        assert!(!compare_condition(&json!({})));
+    }
+
+    #[tokio::test]
+    async fn boolean_tool_returns_default_and_custom_results() {
+        let tool = BooleanTool::new();
+        assert!(tool.is_auto_approved());
+        assert_eq!(
+            tool.execute(&json!({"condition": "1 == 1"})).await.unwrap(),
+            "true"
+        );
+
+        let threshold = BooleanTool::new().with_evaluator(Arc::new(|args: &Value| {
+            args.get("score").and_then(Value::as_f64).unwrap_or(0.0) >= 0.8
+        }));
+        assert_eq!(
+            threshold.execute(&json!({"score": 0.9})).await.unwrap(),
+            "true"
+        );
+        assert_eq!(
+            threshold.execute(&json!({"score": 0.2})).await.unwrap(),
+            "false"
+        );
+    }
+
+    #[tokio::test]
+    async fn http_get_fetches_allowlisted_host_and_truncates() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .and(header("accept", "text/plain"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello world"))
+            .mount(&server)
+            .await;
+        let url = format!("{}/page", server.uri());
+        let args = json!({"url": url, "headers": {"accept": "text/plain"}});
+
+        let tool = HttpGetTool::new(["127.0.0.1"]).unwrap();
+        assert!(!tool.is_auto_approved());
+        assert_eq!(tool.execute(&args).await.unwrap(), "hello world");
+
+        let truncated = HttpGetTool::new(["127.0.0.1"]).unwrap().with_max_bytes(5);
+        assert_eq!(
+            truncated.execute(&args).await.unwrap(),
+            "hello\n[truncated: showing 5 of 11 bytes]"
+        );
+    }
+
+    #[tokio::test]
+    async fn http_get_stops_reading_a_large_body_at_the_cap() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/big"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(4 << 20)))
+            .mount(&server)
+            .await;
+        let fetcher = ReqwestFetcher::new(["127.0.0.1"]).unwrap();
+
+        let response = fetcher
+            .fetch(HttpRequest {
+                url: Url::parse(&format!("{}/big", server.uri())).unwrap(),
+                headers: Vec::new(),
+                max_bytes: 8,
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.body, "xxxxxxxx");
+        assert!(response.truncated);
+        assert_eq!(response.total_bytes, Some(4 << 20));
+    }
+
+    /// Answers every request with a fixed response and records what was asked.
+    struct CannedFetcher {
+        response: HttpResponse,
+        requests: std::sync::Mutex<Vec<HttpRequest>>,
+    }
+
+    impl CannedFetcher {
+        fn new(status: u16, body: &str) -> Arc<Self> {
+            Arc::new(Self {
+                response: HttpResponse {
+                    status,
+                    body: body.to_string(),
+                    truncated: false,
+                    total_bytes: None,
+                },
+                requests: std::sync::Mutex::default(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl HttpFetcher for CannedFetcher {
+        async fn fetch(&self, request: HttpRequest) -> Result<HttpResponse, ToolError> {
+            self.requests.lock().unwrap().push(request);
+            Ok(self.response.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn http_get_uses_injected_fetcher() {
+        let fetcher = CannedFetcher::new(200, r#"{"temp_c": 18}"#);
+        let tool = HttpGetTool::with_fetcher(["api.weather.test"], fetcher.clone());
+
+        let args = json!({
+            "url": "https://API.weather.test/v1/current?city=Oslo",
+            "headers": {"accept": "application/json"},
+        });
+        assert_eq!(tool.execute(&args).await.unwrap(), r#"{"temp_c": 18}"#);
+
+        {
+            let requests = fetcher.requests.lock().unwrap();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(
+                requests[0].url.as_str(),
+                "https://api.weather.test/v1/current?city=Oslo"
+            );
+            assert_eq!(
+                requests[0].headers,
+                [("accept".to_string(), "application/json".to_string())]
+            );
+            assert_eq!(requests[0].max_bytes, DEFAULT_HTTP_MAX_BYTES);
+        }
+
+        let err = HttpGetTool::with_fetcher(["api.weather.test"], CannedFetcher::new(503, ""))
+            .execute(&args)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("returned 503"),
+            "unexpected: {err}"
+        );
+
+        // Blocked hosts never reach the fetcher.
+        let blocked = tool
+            .execute(&json!({"url": "https://example.com/"}))
+            .await
+            .unwrap_err();
+        assert!(blocked.to_string().contains("not in the allowlist"));
+        assert_eq!(fetcher.requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn http_get_rejects_blocked_host_and_scheme() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let tool = HttpGetTool::new(["example.com"]).unwrap();
+        let err = tool
+            .execute(&json!({"url": server.uri()}))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("not in the allowlist"),
+            "unexpected error: {err}"
+        );
+
+        let err = tool
+            .execute(&json!({"url": "file:///etc/passwd"}))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("unsupported URL scheme 'file'"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn read_file_reads_inside_root_and_truncates() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("notes")).unwrap();
+        std::fs::write(dir.path().join("notes/todo.txt"), "hello world").unwrap();
+        let args = json!({"path": "notes/todo.txt"});
+
+        let tool = ReadFileTool::new(dir.path().to_path_buf());
+        assert!(!tool.is_auto_approved());
+        assert_eq!(tool.execute(&args).await.unwrap(), "hello world");
+
+        let truncated = ReadFileTool::new(dir.path().to_path_buf()).with_max_bytes(5);
+        assert_eq!(
+            truncated.execute(&args).await.unwrap(),
+            "hello\n[truncated: showing 5 of 11 bytes]"
+        );
+    }
+
+    #[tokio::test]
+    async fn read_file_cuts_on_char_boundary_and_refuses_binary() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("accent.txt"), "caf\u{e9}!").unwrap();
+        std::fs::write(dir.path().join("latin1.txt"), b"caf\xe9").unwrap();
+        std::fs::write(dir.path().join("blob.bin"), b"\x7fELF\0\0").unwrap();
+        let tool = ReadFileTool::new(dir.path().to_path_buf()).with_max_bytes(4);
+
+        assert_eq!(
+            tool.execute(&json!({"path": "accent.txt"})).await.unwrap(),
+            "caf\n[truncated: showing 3 of 6 bytes]"
+        );
+        assert_eq!(
+            tool.execute(&json!({"path": "latin1.txt"})).await.unwrap(),
+            "caf\u{fffd}"
+        );
+        let err = tool
+            .execute(&json!({"path": "blob.bin"}))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("binary"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn read_file_rejects_paths_escaping_root() {
+        let outer = tempdir().unwrap();
+        std::fs::write(outer.path().join("secret.txt"), "secret").unwrap();
+        let root = outer.path().join("root");
+        std::fs::create_dir(&root).unwrap();
+        let tool = ReadFileTool::new(root.clone());
+
+        let outside = outer.path().join("secret.txt");
+        for path in ["../secret.txt", outside.to_str().unwrap()] {
+            let err = tool.execute(&json!({"path": path})).await.unwrap_err();
+            assert!(
+                err.to_string().contains("outside the allowed root"),
+                "unexpected error for {path}: {err}"
+            );
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&outside, root.join("link.txt")).unwrap();
+            let err = tool
+                .execute(&json!({"path": "link.txt"}))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("outside the allowed root"));
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shell_command_runs_allowed_program_without_a_shell() {
+        let tool = ShellCommandTool::new(["echo", "sh"]);
+        assert!(!tool.is_auto_approved());
+
+        let output = tool
+            .execute(&json!({"command": "echo", "args": ["hello", "$HOME; rm -rf /"]}))
+            .await
+            .unwrap();
+        let output: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["exit_code"], 0);
+        assert_eq!(output["stdout"], "hello $HOME; rm -rf /\n");
+        assert_eq!(output["stderr"], "");
+
+        let output = tool
+            .execute(&json!({"command": "sh", "args": ["-c", "echo oops >&2; exit 3"]}))
+            .await
+            .unwrap();
+        let output: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["exit_code"], 3);
+        assert_eq!(output["stderr"], "oops\n");
+
+        let slow = ShellCommandTool::new(["sleep"]).with_timeout(Duration::from_millis(50));
+        let err = slow
+            .execute(&json!({"command": "sleep", "args": ["5"]}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shell_command_caps_each_stream_and_reports_truncation() {
+        let tool = ShellCommandTool::new(["sh"]).with_max_bytes(5);
+        let script = "head -c 4194304 /dev/zero | tr '\\0' a; echo oops >&2";
+
+        let output = tool
+            .execute(&json!({"command": "sh", "args": ["-c", script]}))
+            .await
+            .unwrap();
+        let output: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["exit_code"], 0);
+        assert_eq!(
+            output["stdout"],
+            "aaaaa\n[truncated: showing 5 of 4194304 bytes]"
+        );
+        assert_eq!(output["stderr"], "oops\n");
+    }
+
+    #[tokio::test]
+    async fn shell_command_rejects_programs_off_the_allowlist() {
+        let tool = ShellCommandTool::new(["echo"]);
+        for command in ["rm", "/bin/echo", "echo hello"] {
+            let err = tool
+                .execute(&json!({"command": command}))
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("not in the allowlist"),
+                "unexpected error for {command}: {err}"
+            );
+        }
+        let err = tool
+            .execute(&json!({"command": "echo", "args": "hello"}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::InvalidArguments(_)));
+    }
+}
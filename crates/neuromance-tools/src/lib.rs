@@ -11,6 +11,7 @@
 //! - [`ToolImplementation`]: Trait for defining custom tools with execution logic
 //! - [`ToolRegistry`]: Thread-safe registry for managing tool definitions
 //! - [`ToolExecutor`]: High-level interface for tool execution with argument parsing
+//! - [`audit`]: Structured records of every tool call for compliance trails
 //! - [`mcp`]: Model Context Protocol client and server integration
 //!
 //! ## Built-in Tools
@@ -104,6 +105,24 @@
 //! # }
 //! ```
 //!
+//! ## Errors
+//!
+//! Tools report failures as [`ToolError`] (bad arguments vs. runtime failure).
+//! [`ToolExecutor::execute_tool`] returns [`ToolExecutorError`], which adds the
+//! executor's own failure modes so callers can tell them apart:
+//!
+//! - [`ToolExecutorError::UnknownTool`]: no tool is registered under the name
+//! - [`ToolExecutorError::Tool`]: the tool itself failed, wrapping its [`ToolError`]
+//! - [`ToolExecutorError::Timeout`]: the tool outlived its execution timeout
+//! - [`ToolExecutorError::CircuitOpen`]: the tool failed too often in a row and
+//!   is disabled for a cooldown (only with [`ToolExecutor::set_circuit_breaker`])
+//! - [`ToolExecutorError::RateLimited`]: the tool is over its rate limit (only
+//!   with [`ToolExecutor::set_rate_limit`] and [`RateLimitPolicy::Error`])
+//!
+//! Arguments that are not valid JSON are passed to the tool as a JSON string
+//! rather than rejected, so argument problems surface as
+//! [`ToolError::InvalidArguments`] from the tool.
+//!
 //! ## Tool Auto-Approval
 //!
 //! Tools can be marked as "auto-approved" via the [`ToolImplementation::is_auto_approved`]
@@ -114,17 +133,34 @@
 //!
 //! The [`ToolRegistry`] uses `DashMap` for concurrent access, making it safe to use
 //! from multiple async tasks without additional synchronization.
+//! [`ToolExecutor::set_max_concurrent_tools`] caps how many tools run at once
+//! when calls are executed in parallel.
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
 use serde_json::Value;
+use tokio::sync::Semaphore;
+use tracing::warn;
 
+use neuromance_common::Embedder;
+use neuromance_common::chat::{default_redaction_patterns, redact_text};
 use neuromance_common::tools::{Tool, ToolCall};
+use regex::Regex;
+
+use crate::audit::{AuditApproval, AuditOutcome};
+use crate::circuit::CircuitBreaker;
+use crate::rate_limit::RateLimiter;
+use crate::relevance::ToolEmbeddingCache;
+use crate::schema::ArgumentValidators;
 
+pub mod audit;
 mod bash_tool;
+mod circuit;
 mod edit_tool;
 mod error;
 pub mod factory;
@@ -134,17 +170,23 @@ mod grep_tool;
 mod ls_tool;
 pub mod mcp;
 pub mod proxy;
+mod rate_limit;
 mod read_tool;
+mod relevance;
+mod schema;
 mod skill_tool;
 mod truncate;
 mod write_tool;
+pub use audit::{AuditSink, JsonlAuditSink, ToolAuditEvent};
 pub use bash_tool::{BashTool, BashToolFactory};
+pub use circuit::CircuitBreakerConfig;
 pub use edit_tool::{EditTool, EditToolFactory};
 pub use error::{ToolError, ToolExecutorError};
 pub use factory::{ToolConfig, ToolFactory, ToolFactoryRegistry};
 pub use find_tool::{FindTool, FindToolFactory};
 pub use grep_tool::{GrepTool, GrepToolFactory};
 pub use ls_tool::{LsTool, LsToolFactory};
+pub use rate_limit::{RateLimit, RateLimitPolicy};
 pub use read_tool::{ReadTool, ReadToolFactory};
 pub use skill_tool::SkillTool;
 pub use write_tool::{WriteTool, WriteToolFactory};
@@ -200,6 +242,15 @@ pub trait ToolImplementation: Send + Sync {
     }
 }
 
+/// Outcome of [`ToolRegistry::register`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Registration {
+    /// No tool with this name was registered before.
+    Inserted,
+    /// A tool with the same name was registered and has been replaced.
+    Replaced,
+}
+
 pub struct ToolRegistry {
     tools: Arc<DashMap<String, Arc<dyn ToolImplementation>>>,
 }
@@ -218,9 +269,30 @@ impl ToolRegistry {
         }
     }
 
-    pub fn register(&self, tool: Arc<dyn ToolImplementation>) {
+    /// Register `tool` under its definition's name, replacing any tool that
+    /// already uses that name.
+    pub fn register(&self, tool: Arc<dyn ToolImplementation>) -> Registration {
         let name = tool.get_definition().function.name;
-        self.tools.insert(name, tool);
+        match self.tools.insert(name, tool) {
+            Some(_) => Registration::Replaced,
+            None => Registration::Inserted,
+        }
+    }
+
+    /// Register `tool` only if no tool with the same name is registered.
+    ///
+    /// # Errors
+    /// Returns [`ToolError::AlreadyRegistered`] if the name is taken; the
+    /// existing tool is left in place.
+    pub fn register_unique(&self, tool: Arc<dyn ToolImplementation>) -> Result<(), ToolError> {
+        let name = tool.get_definition().function.name;
+        match self.tools.entry(name) {
+            Entry::Occupied(entry) => Err(ToolError::AlreadyRegistered(entry.key().clone())),
+            Entry::Vacant(entry) => {
+                entry.insert(tool);
+                Ok(())
+            }
+        }
     }
 
     #[must_use]
@@ -260,6 +332,18 @@ impl ToolRegistry {
 
 pub struct ToolExecutor {
     registry: ToolRegistry,
+    default_timeout: Option<Duration>,
+    circuit_breaker: Option<CircuitBreaker>,
+    rate_limiter: RateLimiter,
+    rate_limit_policy: RateLimitPolicy,
+    /// The limit set by `set_max_concurrent_tools` and the slots enforcing it.
+    concurrency_limit: Option<(usize, Semaphore)>,
+    validate_arguments: bool,
+    argument_validators: ArgumentValidators,
+    max_tool_result_bytes: Option<usize>,
+    audit_sinks: Vec<Arc<dyn AuditSink>>,
+    audit_redaction: Vec<Regex>,
+    tool_embeddings: ToolEmbeddingCache,
 }
 
 impl ToolExecutor {
@@ -267,14 +351,208 @@ impl ToolExecutor {
     pub fn new() -> Self {
         Self {
             registry: ToolRegistry::new(),
+            default_timeout: None,
+            circuit_breaker: None,
+            rate_limiter: RateLimiter::default(),
+            rate_limit_policy: RateLimitPolicy::Wait,
+            concurrency_limit: None,
+            validate_arguments: false,
+            argument_validators: ArgumentValidators::default(),
+            max_tool_result_bytes: None,
+            audit_sinks: Vec::new(),
+            audit_redaction: default_redaction_patterns().to_vec(),
+            tool_embeddings: ToolEmbeddingCache::default(),
         }
     }
 
     /// Wrap an already-populated [`ToolRegistry`], e.g. one returned by
     /// [`ToolFactoryRegistry::build_all`](crate::ToolFactoryRegistry::build_all).
     #[must_use]
-    pub const fn from_registry(registry: ToolRegistry) -> Self {
-        Self { registry }
+    pub fn from_registry(registry: ToolRegistry) -> Self {
+        Self {
+            registry,
+            default_timeout: None,
+            circuit_breaker: None,
+            rate_limiter: RateLimiter::default(),
+            rate_limit_policy: RateLimitPolicy::Wait,
+            concurrency_limit: None,
+            validate_arguments: false,
+            argument_validators: ArgumentValidators::default(),
+            max_tool_result_bytes: None,
+            audit_sinks: Vec::new(),
+            audit_redaction: default_redaction_patterns().to_vec(),
+            tool_embeddings: ToolEmbeddingCache::default(),
+        }
+    }
+
+    /// Bound every tool execution by `timeout` unless a call overrides it.
+    pub const fn set_default_timeout(&mut self, timeout: Duration) {
+        self.default_timeout = Some(timeout);
+    }
+
+    /// Remove the default execution timeout.
+    pub const fn clear_default_timeout(&mut self) {
+        self.default_timeout = None;
+    }
+
+    /// The default execution timeout, if one is set.
+    #[must_use]
+    pub const fn default_timeout(&self) -> Option<Duration> {
+        self.default_timeout
+    }
+
+    /// Disable a tool for `config.cooldown` once it has failed
+    /// `config.failure_threshold` times in a row. Failures and timeouts count;
+    /// a success resets the tool's count. Replaces any earlier breaker and its
+    /// failure history.
+    pub fn set_circuit_breaker(&mut self, config: CircuitBreakerConfig) {
+        self.circuit_breaker = Some(CircuitBreaker::new(config));
+    }
+
+    /// Remove the circuit breaker, re-enabling every tool.
+    pub fn clear_circuit_breaker(&mut self) {
+        self.circuit_breaker = None;
+    }
+
+    /// The circuit breaker settings, if one is set.
+    #[must_use]
+    pub fn circuit_breaker(&self) -> Option<CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref().map(CircuitBreaker::config)
+    }
+
+    /// Let `name` run at most `requests` times per `interval`, refilling
+    /// evenly across the interval. Calls past the limit wait or fail per the
+    /// [`rate_limit_policy`](Self::rate_limit_policy). Replaces any earlier
+    /// limit for the tool and its usage so far.
+    pub fn set_rate_limit(&mut self, name: impl Into<String>, requests: u32, interval: Duration) {
+        self.rate_limiter
+            .set(name.into(), RateLimit::new(requests, interval));
+    }
+
+    /// Remove `name`'s rate limit.
+    pub fn clear_rate_limit(&mut self, name: &str) {
+        self.rate_limiter.remove(name);
+    }
+
+    /// `name`'s rate limit, if one is set.
+    #[must_use]
+    pub fn rate_limit(&self, name: &str) -> Option<RateLimit> {
+        self.rate_limiter.get(name)
+    }
+
+    /// Choose whether calls over a tool's rate limit wait for it or fail with
+    /// [`ToolExecutorError::RateLimited`]. Waits by default.
+    pub const fn set_rate_limit_policy(&mut self, policy: RateLimitPolicy) {
+        self.rate_limit_policy = policy;
+    }
+
+    /// What calls over a tool's rate limit do.
+    #[must_use]
+    pub const fn rate_limit_policy(&self) -> RateLimitPolicy {
+        self.rate_limit_policy
+    }
+
+    /// Run at most `limit` tools at once; further calls wait for a slot.
+    ///
+    /// Matters when many calls from one assistant message run concurrently.
+    /// Waiting calls start in the order they asked for a slot, and each caller
+    /// still receives its own result. A `limit` of 0 is treated as 1.
+    pub fn set_max_concurrent_tools(&mut self, limit: usize) {
+        let limit = limit.max(1);
+        self.concurrency_limit = Some((limit, Semaphore::new(limit)));
+    }
+
+    /// Remove the concurrency limit, letting every call run immediately.
+    pub const fn clear_max_concurrent_tools(&mut self) {
+        self.concurrency_limit = None;
+    }
+
+    /// The concurrency limit, if one is set.
+    #[must_use]
+    pub fn max_concurrent_tools(&self) -> Option<usize> {
+        self.concurrency_limit.as_ref().map(|(limit, _)| *limit)
+    }
+
+    /// Check each call's arguments against the tool's `parameters` schema
+    /// before running it, failing with [`ToolError::ArgumentSchema`] instead.
+    /// Off by default.
+    pub const fn set_validate_arguments(&mut self, enabled: bool) {
+        self.validate_arguments = enabled;
+    }
+
+    /// Whether arguments are validated against the tool's schema.
+    #[must_use]
+    pub const fn validates_arguments(&self) -> bool {
+        self.validate_arguments
+    }
+
+    /// Cap tool output at `max_bytes`; [`limit_tool_result`](Self::limit_tool_result)
+    /// cuts longer results on a char boundary and appends a notice saying how
+    /// much was kept. Unlimited by default.
+    pub const fn set_max_tool_result_bytes(&mut self, max_bytes: usize) {
+        self.max_tool_result_bytes = Some(max_bytes);
+    }
+
+    /// Remove the tool output cap.
+    pub const fn clear_max_tool_result_bytes(&mut self) {
+        self.max_tool_result_bytes = None;
+    }
+
+    /// The tool output cap, if one is set.
+    #[must_use]
+    pub const fn max_tool_result_bytes(&self) -> Option<usize> {
+        self.max_tool_result_bytes
+    }
+
+    /// Apply the [`max_tool_result_bytes`](Self::max_tool_result_bytes) cap
+    /// to a tool's output before it becomes a tool message.
+    #[must_use]
+    pub fn limit_tool_result(&self, result: String) -> String {
+        match self.max_tool_result_bytes {
+            Some(max_bytes) => truncate::truncate_to(result, max_bytes),
+            None => result,
+        }
+    }
+
+    /// Emit a [`ToolAuditEvent`] to `sink` for every call this executor runs.
+    /// Sinks receive events in the order they were added.
+    pub fn add_audit_sink(&mut self, sink: Arc<dyn AuditSink>) {
+        self.audit_sinks.push(sink);
+    }
+
+    /// Remove every audit sink.
+    pub fn clear_audit_sinks(&mut self) {
+        self.audit_sinks.clear();
+    }
+
+    /// Replace the patterns redacted from audit events' arguments, output,
+    /// and errors. Defaults to [`default_redaction_patterns`]; pass an empty
+    /// list to record them verbatim.
+    pub fn set_audit_redaction_patterns(&mut self, patterns: Vec<Regex>) {
+        self.audit_redaction = patterns;
+    }
+
+    /// Record that `tool_call` was denied for `reason` and never ran.
+    ///
+    /// Approval happens before the executor sees a call, so the code that
+    /// denies it reports it here to keep the audit trail complete.
+    pub async fn audit_denied(&self, tool_call: &ToolCall, reason: &str) {
+        if self.audit_sinks.is_empty() {
+            return;
+        }
+        let function = &tool_call.function;
+        self.emit_audit(ToolAuditEvent {
+            timestamp: chrono::Utc::now(),
+            tool_call_id: Some(tool_call.id.clone()),
+            tool: function.name.clone(),
+            arguments: function.arguments_json().to_owned(),
+            approval: AuditApproval::Denied {
+                reason: reason.to_owned(),
+            },
+            duration: Duration::ZERO,
+            outcome: AuditOutcome::Skipped,
+        })
+        .await;
     }
 
     pub fn add_tool<T: ToolImplementation + 'static>(&mut self, tool: T) {
@@ -295,6 +573,31 @@ impl ToolExecutor {
         self.registry.get_all_definitions()
     }
 
+    /// The `top_k` registered tools whose descriptions are most similar to
+    /// `query`, best match first.
+    ///
+    /// Useful when many (e.g. MCP) tools are registered and sending every
+    /// schema would waste tokens. Tool descriptions are embedded once and
+    /// cached per tool until the description or the embedding model changes;
+    /// each call embeds `query`.
+    ///
+    /// # Errors
+    /// Returns the error from `embedder` if embedding fails.
+    pub async fn select_relevant_tools<E: Embedder + ?Sized>(
+        &self,
+        query: &str,
+        embedder: &E,
+        top_k: usize,
+    ) -> Result<Vec<Tool>, E::Error> {
+        let tools = self.get_all_tools();
+        if top_k == 0 || tools.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.tool_embeddings
+            .rank(tools, query, embedder, top_k)
+            .await
+    }
+
     #[must_use]
     pub fn is_tool_auto_approved(&self, name: &str) -> bool {
         self.registry.is_tool_auto_approved(name)
@@ -309,7 +612,7 @@ impl ToolExecutor {
         self.registry.clear();
     }
 
-    /// Execute a tool call.
+    /// Execute a tool call, bounded by the default timeout if one is set.
     ///
     /// Cancellation is the caller's responsibility — wrap this future in a
     /// `tokio::select!` against your `CancellationToken`. Dropping the future
@@ -317,11 +620,63 @@ impl ToolExecutor {
     ///
     /// # Errors
     /// Returns [`ToolExecutorError::UnknownTool`] if the tool is not found,
-    /// or [`ToolExecutorError::Tool`] if execution fails.
+    /// [`ToolExecutorError::CircuitOpen`] if its circuit breaker is open,
+    /// [`ToolExecutorError::RateLimited`] if it is over its rate limit under
+    /// [`RateLimitPolicy::Error`],
+    /// [`ToolExecutorError::Timeout`] if it outlives the timeout, or
+    /// [`ToolExecutorError::Tool`] if argument validation or execution fails.
     pub async fn execute_tool(&self, tool_call: &ToolCall) -> Result<String, ToolExecutorError> {
         let function = &tool_call.function;
-        self.execute_named(&function.name, function.arguments_json())
-            .await
+        self.audited(
+            Some(&tool_call.id),
+            &function.name,
+            function.arguments_json(),
+            self.default_timeout,
+            None,
+        )
+        .await
+    }
+
+    /// [`execute_tool`](Self::execute_tool), recording `approval` — the
+    /// decision that let the call run — in the audit trail instead of
+    /// inferring it from the tool's auto-approve flag.
+    ///
+    /// # Errors
+    /// Same as [`execute_tool`](Self::execute_tool).
+    pub async fn execute_approved_tool(
+        &self,
+        tool_call: &ToolCall,
+        approval: AuditApproval,
+    ) -> Result<String, ToolExecutorError> {
+        let function = &tool_call.function;
+        self.audited(
+            Some(&tool_call.id),
+            &function.name,
+            function.arguments_json(),
+            self.default_timeout,
+            Some(approval),
+        )
+        .await
+    }
+
+    /// Execute a tool call with `timeout` in place of the default timeout.
+    ///
+    /// # Errors
+    /// Same as [`execute_tool`](Self::execute_tool).
+    pub async fn execute_tool_with_timeout(
+        &self,
+        tool_call: &ToolCall,
+        timeout: Duration,
+    ) -> Result<String, ToolExecutorError> {
+        let function = &tool_call.function;
+        self.audited(
+            Some(&tool_call.id),
+            &function.name,
+            function.arguments_json(),
+            Some(timeout),
+            None,
+        )
+        .await
     }
 
     /// Execute a tool by name with raw JSON-encoded arguments.
@@ -336,11 +691,163 @@ impl ToolExecutor {
     ///
     /// # Errors
     /// Returns [`ToolExecutorError::UnknownTool`] if the tool is not found,
-    /// or [`ToolExecutorError::Tool`] if execution fails.
+    /// [`ToolExecutorError::CircuitOpen`] if its circuit breaker is open,
+    /// [`ToolExecutorError::RateLimited`] if it is over its rate limit under
+    /// [`RateLimitPolicy::Error`],
+    /// [`ToolExecutorError::Timeout`] if it outlives the default timeout, or
+    /// [`ToolExecutorError::Tool`] if argument validation or execution fails.
     pub async fn execute_named(
         &self,
         name: &str,
         arguments_json: &str,
+    ) -> Result<String, ToolExecutorError> {
+        self.audited(None, name, arguments_json, self.default_timeout, None)
+            .await
+    }
+
+    /// [`dispatch`](Self::dispatch), emitting a [`ToolAuditEvent`] to every
+    /// audit sink once the call finishes. Without a known `approval`, one is
+    /// inferred from the tool's auto-approve flag.
+    async fn audited(
+        &self,
+        tool_call_id: Option<&str>,
+        name: &str,
+        arguments_json: &str,
+        timeout: Option<Duration>,
+        approval: Option<AuditApproval>,
+    ) -> Result<String, ToolExecutorError> {
+        if self.audit_sinks.is_empty() {
+            return self.dispatch(name, arguments_json, timeout).await;
+        }
+
+        let started = Instant::now();
+        let result = self.dispatch(name, arguments_json, timeout).await;
+        let approval = approval.unwrap_or_else(|| {
+            if self.is_tool_auto_approved(name) {
+                AuditApproval::AutoApproved
+            } else {
+                AuditApproval::Approved
+            }
+        });
+        let outcome = match &result {
+            Ok(output) => AuditOutcome::success(output),
+            Err(error) => AuditOutcome::Failure {
+                error: error.to_string(),
+            },
+        };
+        self.emit_audit(ToolAuditEvent {
+            timestamp: chrono::Utc::now(),
+            tool_call_id: tool_call_id.map(str::to_owned),
+            tool: name.to_owned(),
+            arguments: arguments_json.to_owned(),
+            approval,
+            duration: started.elapsed(),
+            outcome,
+        })
+        .await;
+        result
+    }
+
+    async fn emit_audit(&self, mut event: ToolAuditEvent) {
+        let redact = |text: &mut String| *text = redact_text(text, &self.audit_redaction);
+        redact(&mut event.arguments);
+        if let AuditApproval::ApprovedWithArguments { original_arguments } = &mut event.approval {
+            redact(original_arguments);
+        }
+        match &mut event.outcome {
+            AuditOutcome::Success { output } => redact(output),
+            AuditOutcome::Failure { error } => redact(error),
+            AuditOutcome::Skipped => {}
+        }
+        for sink in &self.audit_sinks {
+            if let Err(error) = sink.record(&event).await {
+                warn!(tool = %event.tool, %error, "failed to record tool audit event");
+            }
+        }
+    }
+
+    /// Check the circuit breaker and rate limit, run the tool once a
+    /// concurrency slot is free, and record the outcome.
+    async fn dispatch(
+        &self,
+        name: &str,
+        arguments_json: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String, ToolExecutorError> {
+        let Some(breaker) = &self.circuit_breaker else {
+            self.acquire_rate_limit(name).await?;
+            return self.run_in_slot(name, arguments_json, timeout).await;
+        };
+        if let Some(retry_after) = breaker.remaining_cooldown(name) {
+            return Err(ToolExecutorError::CircuitOpen {
+                name: name.to_owned(),
+                retry_after,
+            });
+        }
+
+        self.acquire_rate_limit(name).await?;
+        let result = self.run_in_slot(name, arguments_json, timeout).await;
+        match &result {
+            Ok(_) => breaker.record_success(name),
+            // Bad arguments are the caller's fault, not a sign the tool is
+            // unhealthy, so they never trip the breaker.
+            Err(ToolExecutorError::Tool(
+                ToolError::ArgumentSchema { .. } | ToolError::InvalidArguments(_),
+            )) => {}
+            Err(ToolExecutorError::Tool(_) | ToolExecutorError::Timeout { .. }) => {
+                breaker.record_failure(name);
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Take a rate-limit token for `name`, waiting for one or failing per the
+    /// [`rate_limit_policy`](Self::rate_limit_policy).
+    async fn acquire_rate_limit(&self, name: &str) -> Result<(), ToolExecutorError> {
+        loop {
+            let Err(retry_after) = self.rate_limiter.try_acquire(name) else {
+                return Ok(());
+            };
+            match self.rate_limit_policy {
+                RateLimitPolicy::Wait => tokio::time::sleep(retry_after).await,
+                RateLimitPolicy::Error => {
+                    return Err(ToolExecutorError::RateLimited {
+                        name: name.to_owned(),
+                        retry_after,
+                    });
+                }
+            }
+        }
+    }
+
+    /// [`run`](Self::run) while holding a concurrency slot, if a limit is set.
+    ///
+    /// The timeout starts once the slot is acquired, so time spent queued
+    /// behind other tools does not count against it.
+    async fn run_in_slot(
+        &self,
+        name: &str,
+        arguments_json: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String, ToolExecutorError> {
+        // The semaphore is never closed, so `acquire` cannot fail.
+        let _slot = match &self.concurrency_limit {
+            Some((_, slots)) => slots.acquire().await.ok(),
+            None => None,
+        };
+        self.run(name, arguments_json, timeout).await
+    }
+
+    /// Look up, parse arguments for, and run a tool under an optional timeout.
+    ///
+    /// The tool's future is awaited in place rather than spawned, so on
+    /// timeout it is dropped and none of its work outlives this call.
+    async fn run(
+        &self,
+        name: &str,
+        arguments_json: &str,
+        timeout: Option<Duration>,
     ) -> Result<String, ToolExecutorError> {
         let tool = self
             .registry
@@ -348,8 +855,24 @@ impl ToolExecutor {
             .ok_or_else(|| ToolExecutorError::UnknownTool(name.to_owned()))?;
 
         let args = Self::parse_arguments(arguments_json);
+        if self.validate_arguments {
+            self.argument_validators.validate(
+                name,
+                &tool.get_definition().function.parameters,
+                &args,
+            )?;
+        }
 
-        Ok(tool.execute(&args).await?)
+        let Some(duration) = timeout else {
+            return Ok(tool.execute(&args).await?);
+        };
+        match tokio::time::timeout(duration, tool.execute(&args)).await {
+            Ok(result) => Ok(result?),
+            Err(_) => Err(ToolExecutorError::Timeout {
+                name: name.to_owned(),
+                duration,
+            }),
+        }
     }
 
     fn parse_arguments(arguments_json: &str) -> Value {
@@ -467,10 +990,561 @@ mod tests {
         assert_eq!(via_named, via_call);
     }
 
+    /// A tool that fails while `failing` is set, counting the calls that reach it.
+    struct FlakyTool {
+        failing: Arc<std::sync::atomic::AtomicBool>,
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl ToolImplementation for FlakyTool {
+        fn get_definition(&self) -> Tool {
+            Tool::builder()
+                .function(Function {
+                    name: "flaky".to_string(),
+                    description: "flaky".to_string(),
+                    parameters: json!({}),
+                })
+                .build()
+        }
+
+        async fn execute(&self, _args: &Value) -> Result<String, ToolError> {
+            use std::sync::atomic::Ordering;
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.failing.load(Ordering::SeqCst) {
+                Err(ToolError::execution("server unavailable"))
+            } else {
+                Ok("ok".to_string())
+            }
+        }
+    }
+
+    fn flaky_executor(
+        config: CircuitBreakerConfig,
+    ) -> (
+        ToolExecutor,
+        Arc<std::sync::atomic::AtomicBool>,
+        Arc<std::sync::atomic::AtomicUsize>,
+    ) {
+        let failing = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(FlakyTool {
+            failing: Arc::clone(&failing),
+            calls: Arc::clone(&calls),
+        });
+        executor.set_circuit_breaker(config);
+        (executor, failing, calls)
+    }
+
+    /// After the threshold the tool is short-circuited without running; once
+    /// the cooldown passes a successful call closes the circuit again.
+    #[tokio::test]
+    async fn test_circuit_breaker_opens_and_recovers_after_cooldown() {
+        use std::sync::atomic::Ordering;
+
+        let cooldown = Duration::from_millis(50);
+        let (executor, failing, calls) = flaky_executor(CircuitBreakerConfig::new(2, cooldown));
+
+        for _ in 0..2 {
+            let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+            assert!(matches!(err, ToolExecutorError::Tool(_)));
+        }
+        let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+        assert!(
+            matches!(err, ToolExecutorError::CircuitOpen { ref name, retry_after } if name == "flaky" && retry_after <= cooldown)
+        );
+        assert!(err.to_string().contains("temporarily disabled"));
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            2,
+            "open circuit must not run the tool"
+        );
+
+        tokio::time::sleep(cooldown * 2).await;
+        failing.store(false, Ordering::SeqCst);
+        assert_eq!(executor.execute_named("flaky", "{}").await.unwrap(), "ok");
+
+        // The success reset the count: one new failure does not reopen it.
+        failing.store(true, Ordering::SeqCst);
+        let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+        assert!(matches!(err, ToolExecutorError::Tool(_)));
+        let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+        assert!(matches!(err, ToolExecutorError::Tool(_)));
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    /// A success before the threshold resets the count, and a failure on the
+    /// first call after the cooldown reopens the circuit straight away.
+    #[tokio::test]
+    async fn test_circuit_breaker_counts_consecutive_failures_only() {
+        use std::sync::atomic::Ordering;
+
+        let cooldown = Duration::from_millis(50);
+        let (executor, failing, calls) = flaky_executor(CircuitBreakerConfig::new(2, cooldown));
+
+        executor.execute_named("flaky", "{}").await.unwrap_err();
+        failing.store(false, Ordering::SeqCst);
+        executor.execute_named("flaky", "{}").await.unwrap();
+        failing.store(true, Ordering::SeqCst);
+        executor.execute_named("flaky", "{}").await.unwrap_err();
+        let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+        assert!(matches!(err, ToolExecutorError::Tool(_)), "count was reset");
+
+        tokio::time::sleep(cooldown * 2).await;
+        let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+        assert!(
+            matches!(err, ToolExecutorError::Tool(_)),
+            "cooldown lets one call through"
+        );
+        let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+        assert!(matches!(err, ToolExecutorError::CircuitOpen { .. }));
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+        // Unknown tools still report as unknown with a breaker set.
+        let err = executor.execute_named("missing", "{}").await.unwrap_err();
+        assert!(matches!(err, ToolExecutorError::UnknownTool(_)));
+    }
+
+    fn rate_limited_executor(
+        policy: RateLimitPolicy,
+    ) -> (ToolExecutor, Arc<std::sync::atomic::AtomicUsize>) {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(FlakyTool {
+            failing: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            calls: Arc::clone(&calls),
+        });
+        executor.set_rate_limit("flaky", 2, Duration::from_millis(200));
+        executor.set_rate_limit_policy(policy);
+        (executor, calls)
+    }
+
+    /// Under the error policy, calls past the limit fail without running the
+    /// tool until a token has been earned back.
+    #[tokio::test]
+    async fn test_rate_limit_error_policy_rejects_excess_calls() {
+        use std::sync::atomic::Ordering;
+
+        let (mut executor, calls) = rate_limited_executor(RateLimitPolicy::Error);
+        assert_eq!(
+            executor.rate_limit("flaky"),
+            Some(RateLimit::new(2, Duration::from_millis(200)))
+        );
+
+        executor.execute_named("flaky", "{}").await.unwrap();
+        executor.execute_named("flaky", "{}").await.unwrap();
+        let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+        assert!(
+            matches!(err, ToolExecutorError::RateLimited { ref name, retry_after } if name == "flaky" && retry_after <= Duration::from_millis(100))
+        );
+        assert!(err.to_string().contains("rate limit"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // One token refills every 100ms.
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        executor.execute_named("flaky", "{}").await.unwrap();
+        let err = executor.execute_named("flaky", "{}").await.unwrap_err();
+        assert!(matches!(err, ToolExecutorError::RateLimited { .. }));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Other tools are not limited.
+        executor.add_tool(EchoTool);
+        for _ in 0..5 {
+            executor
+                .execute_named("echo", r#"{"value": "hi"}"#)
+                .await
+                .unwrap();
+        }
+    }
+
+    /// Under the wait policy, calls past the limit are delayed until a token
+    /// is available and then run.
+    #[tokio::test]
+    async fn test_rate_limit_wait_policy_delays_excess_calls() {
+        use std::sync::atomic::Ordering;
+
+        let (executor, calls) = rate_limited_executor(RateLimitPolicy::Wait);
+
+        let started = Instant::now();
+        for _ in 0..4 {
+            assert_eq!(executor.execute_named("flaky", "{}").await.unwrap(), "ok");
+        }
+        // Two calls fit the burst; the other two wait ~100ms each.
+        assert!(started.elapsed() >= Duration::from_millis(180));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        let mut executor = executor;
+        executor.clear_rate_limit("flaky");
+        assert_eq!(executor.rate_limit("flaky"), None);
+        let started = Instant::now();
+        for _ in 0..4 {
+            executor.execute_named("flaky", "{}").await.unwrap();
+        }
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+
+    /// A tool with a fixed name and description.
+    struct DescribedTool(&'static str, &'static str);
+
+    #[async_trait]
+    impl ToolImplementation for DescribedTool {
+        fn get_definition(&self) -> Tool {
+            Tool::builder()
+                .function(Function {
+                    name: self.0.to_string(),
+                    description: self.1.to_string(),
+                    parameters: json!({}),
+                })
+                .build()
+        }
+
+        async fn execute(&self, _args: &Value) -> Result<String, ToolError> {
+            Ok(String::new())
+        }
+    }
+
+    /// Embeds text as keyword presence over a tiny vocabulary and counts the
+    /// texts it has embedded.
+    #[derive(Default)]
+    struct KeywordEmbedder {
+        embedded: std::sync::atomic::AtomicUsize,
+    }
+
+    impl KeywordEmbedder {
+        const VOCABULARY: [&'static str; 4] = ["file", "weather", "email", "math"];
+
+        fn vector(text: &str) -> Vec<f32> {
+            let text = text.to_lowercase();
+            Self::VOCABULARY
+                .iter()
+                .map(|word| if text.contains(word) { 1.0 } else { 0.0 })
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl Embedder for KeywordEmbedder {
+        type Error = std::convert::Infallible;
+
+        fn embedding_model(&self) -> &'static str {
+            "keywords"
+        }
+
+        async fn embed_texts(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, Self::Error> {
+            self.embedded
+                .fetch_add(texts.len(), std::sync::atomic::Ordering::SeqCst);
+            Ok(texts.iter().map(|text| Self::vector(text)).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_select_relevant_tools_ranks_by_similarity_and_caches() {
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(DescribedTool("read_file", "Read a file from disk"));
+        executor.add_tool(DescribedTool("write_file", "Write a file to disk"));
+        executor.add_tool(DescribedTool("forecast", "Get the weather forecast"));
+        executor.add_tool(DescribedTool("send_email", "Send an email"));
+        executor.add_tool(DescribedTool("calculate", "Evaluate a math expression"));
+        let embedder = KeywordEmbedder::default();
+
+        let selected = executor
+            .select_relevant_tools("edit the config file", &embedder, 2)
+            .await
+            .unwrap();
+        let names: Vec<&str> = selected.iter().map(|t| t.function.name.as_str()).collect();
+        assert_eq!(names, vec!["read_file", "write_file"]);
+        // Five descriptions plus the query.
+        assert_eq!(
+            embedder.embedded.load(std::sync::atomic::Ordering::SeqCst),
+            6
+        );
+
+        let selected = executor
+            .select_relevant_tools("will the weather hold?", &embedder, 1)
+            .await
+            .unwrap();
+        assert_eq!(selected[0].function.name, "forecast");
+        // Descriptions came from the cache; only the query was embedded.
+        assert_eq!(
+            embedder.embedded.load(std::sync::atomic::Ordering::SeqCst),
+            7
+        );
+
+        assert!(
+            executor
+                .select_relevant_tools("anything", &embedder, 0)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_register_reports_insert_and_replace() {
+        let registry = ToolRegistry::new();
+        assert_eq!(
+            registry.register(Arc::new(EchoTool)),
+            Registration::Inserted
+        );
+        assert_eq!(
+            registry.register(Arc::new(EchoTool)),
+            Registration::Replaced
+        );
+        assert_eq!(registry.tool_names(), vec!["echo"]);
+    }
+
+    #[test]
+    fn test_register_unique_rejects_conflicts() {
+        let registry = ToolRegistry::new();
+        registry.register_unique(Arc::new(EchoTool)).unwrap();
+
+        let err = registry.register_unique(Arc::new(EchoTool)).unwrap_err();
+        assert!(matches!(err, ToolError::AlreadyRegistered(ref name) if name == "echo"));
+        assert_eq!(err.to_string(), "Tool 'echo' is already registered");
+        assert_eq!(registry.get_all_definitions().len(), 1);
+    }
+
+    /// A tool that sleeps, then records that it ran to completion.
+    struct SlowTool {
+        delay: Duration,
+        finished: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    #[async_trait]
+    impl ToolImplementation for SlowTool {
+        fn get_definition(&self) -> Tool {
+            Tool::builder()
+                .function(Function {
+                    name: "slow".to_string(),
+                    description: "slow".to_string(),
+                    parameters: json!({}),
+                })
+                .build()
+        }
+
+        async fn execute(&self, _args: &Value) -> Result<String, ToolError> {
+            tokio::time::sleep(self.delay).await;
+            self.finished
+                .store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok("done".to_string())
+        }
+    }
+
+    fn slow_executor(delay: Duration) -> (ToolExecutor, Arc<std::sync::atomic::AtomicBool>) {
+        let finished = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(SlowTool {
+            delay,
+            finished: Arc::clone(&finished),
+        });
+        (executor, finished)
+    }
+
+    /// The default timeout surfaces as `Timeout`, and the abandoned tool
+    /// future is dropped rather than left running.
+    #[tokio::test]
+    async fn test_default_timeout_elapses_and_drops_tool() {
+        let (mut executor, finished) = slow_executor(Duration::from_millis(200));
+        executor.set_default_timeout(Duration::from_millis(20));
+
+        let err = executor.execute_named("slow", "{}").await.unwrap_err();
+        assert!(matches!(
+            err,
+            ToolExecutorError::Timeout { ref name, duration }
+                if name == "slow" && duration == Duration::from_millis(20)
+        ));
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(!finished.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    /// A per-call timeout overrides the default in either direction.
+    #[tokio::test]
+    async fn test_per_call_timeout_overrides_default() {
+        let (mut executor, _) = slow_executor(Duration::from_millis(100));
+        let call = ToolCall::new("slow", "{}");
+
+        executor.set_default_timeout(Duration::from_millis(10));
+        let ok = executor
+            .execute_tool_with_timeout(&call, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(ok, "done");
+
+        executor.clear_default_timeout();
+        let err = executor
+            .execute_tool_with_timeout(&call, Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolExecutorError::Timeout { .. }));
+        assert_eq!(executor.execute_tool(&call).await.unwrap(), "done");
+    }
+
     #[tokio::test]
     async fn test_execute_named_unknown_tool() {
         let executor = ToolExecutor::new();
         let err = executor.execute_named("missing", "{}").await.unwrap_err();
         assert!(matches!(err, ToolExecutorError::UnknownTool(name) if name == "missing"));
     }
+    /// Records how many executions overlap, echoing its `id` argument.
+    struct ConcurrencyProbe {
+        running: Arc<std::sync::atomic::AtomicUsize>,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl ToolImplementation for ConcurrencyProbe {
+        fn get_definition(&self) -> Tool {
+            Tool::builder()
+                .function(Function {
+                    name: "probe".to_string(),
+                    description: "probe".to_string(),
+                    parameters: json!({}),
+                })
+                .build()
+        }
+
+        async fn execute(&self, args: &Value) -> Result<String, ToolError> {
+            use std::sync::atomic::Ordering;
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(args["id"].to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrent_tools_caps_overlapping_executions() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(ConcurrencyProbe {
+            running: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::clone(&peak),
+        });
+        executor.set_max_concurrent_tools(2);
+        assert_eq!(executor.max_concurrent_tools(), Some(2));
+        let executor = Arc::new(executor);
+
+        let handles: Vec<_> = (0..8)
+            .map(|id| {
+                let executor = Arc::clone(&executor);
+                tokio::spawn(async move {
+                    executor
+                        .execute_named("probe", &format!(r#"{{"id":{id}}}"#))
+                        .await
+                })
+            })
+            .collect();
+        let mut results = Vec::new();
+        for handle in handles {
+            results.push(handle.await.unwrap().unwrap());
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        let expected: Vec<String> = (0..8).map(|id| id.to_string()).collect();
+        assert_eq!(results, expected);
+    }
+
+    /// Requires a string `path`, counting the calls that reach it.
+    struct RequiresPath(Arc<std::sync::atomic::AtomicUsize>);
+
+    #[async_trait]
+    impl ToolImplementation for RequiresPath {
+        fn get_definition(&self) -> Tool {
+            Tool::builder()
+                .function(Function {
+                    name: "open".to_string(),
+                    description: "open a file".to_string(),
+                    parameters: json!({
+                        "type": "object",
+                        "properties": {"path": {"type": "string"}},
+                        "required": ["path"],
+                    }),
+                })
+                .build()
+        }
+
+        async fn execute(&self, _args: &Value) -> Result<String, ToolError> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok("opened".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_arguments_rejects_missing_required_field() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(RequiresPath(Arc::clone(&calls)));
+
+        // Off by default: the tool sees the bad arguments.
+        assert!(!executor.validates_arguments());
+        executor
+            .execute_named("open", r#"{"mode": "r"}"#)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        executor.set_validate_arguments(true);
+        let err = executor
+            .execute_named("open", r#"{"mode": "r"}"#)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                ToolExecutorError::Tool(ToolError::ArgumentSchema { tool, violations })
+                    if tool == "open" && violations.len() == 1 && violations[0].contains("\"path\"")
+            ),
+            "unexpected: {err:?}"
+        );
+
+        let err = executor
+            .execute_named("open", r#"{"path": 7}"#)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("/path: "), "unexpected: {err}");
+
+        assert_eq!(
+            executor
+                .execute_named("open", r#"{"path": "a.txt"}"#)
+                .await
+                .unwrap(),
+            "opened"
+        );
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    /// Calls rejected for their arguments are the model's mistake, not the
+    /// tool's, so they never open the circuit.
+    #[tokio::test]
+    async fn test_argument_errors_do_not_trip_circuit_breaker() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut executor = ToolExecutor::new();
+        executor.add_tool(RequiresPath(Arc::clone(&calls)));
+        executor.set_validate_arguments(true);
+        executor.set_circuit_breaker(CircuitBreakerConfig::new(1, Duration::from_secs(60)));
+
+        for _ in 0..3 {
+            let err = executor.execute_named("open", "{}").await.unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    ToolExecutorError::Tool(ToolError::ArgumentSchema { .. })
+                ),
+                "unexpected: {err:?}"
+            );
+        }
+        assert_eq!(
+            executor
+                .execute_named("open", r#"{"path": "a.txt"}"#)
+                .await
+                .unwrap(),
+            "opened"
+        );
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
 }
@@ -2,13 +2,33 @@ use async_trait::async_trait;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{ToolError, ToolImplementation};
 use neuromance_common::tools::{Function, ObjectSchema, Parameters, Property, Tool};
 use rmcp::model::Tool as McpTool;
 
-use super::client::McpClientWrapper;
+use super::client::{McpClientWrapper, is_transport_error};
+use super::manager::Connections;
+
+/// Separator between the server id and tool name in namespaced tool names.
+pub const TOOL_NAME_SEPARATOR: &str = "__";
+
+/// Join a server id and tool name into a namespaced name (`server__tool`).
+#[must_use]
+pub fn namespaced_name(server_id: &str, tool_name: &str) -> String {
+    format!("{server_id}{TOOL_NAME_SEPARATOR}{tool_name}")
+}
+
+/// Split a namespaced name into `(server_id, tool_name)`.
+///
+/// Splits at the first separator; server ids cannot contain it (see
+/// [`McpConfig`](super::McpConfig) validation), so tool names may.
+#[must_use]
+pub fn split_namespaced_name(name: &str) -> Option<(&str, &str)> {
+    name.split_once(TOOL_NAME_SEPARATOR)
+        .filter(|(server, tool)| !server.is_empty() && !tool.is_empty())
+}
 
 /// Adapter that wraps an MCP tool as a `ToolImplementation`
 pub struct McpToolAdapter {
@@ -17,6 +37,11 @@ pub struct McpToolAdapter {
     pub client: Arc<McpClientWrapper>,
     pub mcp_tool: McpTool,
     pub auto_approved: bool,
+    /// Whether the tool is exposed as `server__tool` rather than its bare name.
+    pub namespaced: bool,
+    /// Manager-owned connections; when set, calls go to the server's current
+    /// session instead of `client`, and transport errors trigger a reconnect.
+    connections: Option<Arc<Connections>>,
 }
 
 impl McpToolAdapter {
@@ -31,13 +56,39 @@ impl McpToolAdapter {
             client,
             mcp_tool,
             auto_approved,
+            namespaced: true,
+            connections: None,
         }
     }
 
-    /// Get the full tool name (`server_id.tool_name`)
+    pub(crate) fn with_connections(mut self, connections: Arc<Connections>) -> Self {
+        self.connections = Some(connections);
+        self
+    }
+
+    /// Expose the tool under its bare name instead of `server__tool`.
+    #[must_use]
+    pub const fn with_namespace(mut self, namespaced: bool) -> Self {
+        self.namespaced = namespaced;
+        self
+    }
+
+    /// Get the full tool name (`server_id__tool_name`)
     #[must_use]
     pub fn full_name(&self) -> String {
-        format!("{}.{}", self.server_id, self.tool_name)
+        namespaced_name(&self.server_id, &self.tool_name)
+    }
+
+    /// The name the LLM sees: [`Self::full_name`] when namespaced, otherwise
+    /// the bare tool name. Calls are always dispatched to the server under the
+    /// bare name.
+    #[must_use]
+    pub fn exposed_name(&self) -> String {
+        if self.namespaced {
+            self.full_name()
+        } else {
+            self.tool_name.clone()
+        }
     }
 }
 
@@ -117,7 +168,7 @@ impl ToolImplementation for McpToolAdapter {
 
         Tool::builder()
             .function(Function {
-                name: self.full_name(),
+                name: self.exposed_name(),
                 description: self.mcp_tool.description.as_ref().map_or_else(
                     || {
                         format!(
@@ -142,11 +193,22 @@ impl ToolImplementation for McpToolAdapter {
             "executing MCP tool",
         );
 
-        let result = self
-            .client
-            .call_tool(&self.tool_name, args.clone())
-            .await
-            .map_err(|e| ToolError::Execution(e.into()))?;
+        let client = match &self.connections {
+            Some(connections) => connections.client_for_call(&self.server_id).await?,
+            None => Arc::clone(&self.client),
+        };
+        let result = match client.call_tool(&self.tool_name, args.clone()).await {
+            Ok(result) => result,
+            Err(e) => {
+                if is_transport_error(&e)
+                    && let Some(connections) = &self.connections
+                {
+                    warn!(server = %self.server_id, error = %e, "MCP connection dropped");
+                    connections.spawn_reconnect(&self.server_id);
+                }
+                return Err(ToolError::Execution(e.into()));
+            }
+        };
 
         // Check if there was an error
         if result.is_error.unwrap_or(false) {
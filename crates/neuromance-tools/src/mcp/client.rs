@@ -2,7 +2,7 @@ use anyhow::Result;
 use rmcp::{
     RoleClient, ServiceExt,
     model::{CallToolRequestParams, CallToolResult, Tool as McpTool},
-    service::{RunningService, ServerSink},
+    service::{RunningService, ServerSink, ServiceError},
     transport::{ConfigureCommandExt, StreamableHttpClientTransport, TokioChildProcess},
 };
 use std::collections::HashMap;
@@ -12,6 +12,15 @@ use tracing::{debug, info};
 
 use super::config::{McpServerConfig, McpTransportConfig};
 
+/// Whether `error` means the session's transport is gone, as opposed to the
+/// server rejecting a request.
+pub(crate) fn is_transport_error(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<ServiceError>(),
+        Some(ServiceError::TransportClosed | ServiceError::TransportSend(_))
+    )
+}
+
 /// Wrapper around an MCP client connection
 pub struct McpClientWrapper {
     pub server_config: McpServerConfig,
@@ -48,6 +57,17 @@ impl McpClientWrapper {
             }
         };
 
+        Self::from_service(config, service).await
+    }
+
+    /// Wrap an already-initialized client session and fetch its tool list.
+    ///
+    /// # Errors
+    /// Returns an error if listing the server's tools fails.
+    pub(crate) async fn from_service(
+        config: McpServerConfig,
+        service: RunningService<RoleClient, ()>,
+    ) -> Result<Self> {
         // Get server info
         if let Some(peer_info) = service.peer_info() {
             info!(
@@ -13,7 +13,9 @@
 //! # Global settings
 //! [settings]
 //! max_retries = 3
+//! reconnect_delay_ms = 500
 //! debug = false
+//! bare_tool_names = false
 //!
 //! # Filesystem server via stdio
 //! [[servers]]
@@ -61,11 +63,19 @@
 //!
 //! Servers can be configured with `auto_approve = true` to automatically execute
 //! their tools without user confirmation. Use this carefully for trusted, read-only tools.
+//!
+//! ## Tool Names
+//!
+//! Tools are exposed to the LLM as `server_id__tool_name` (e.g.
+//! `filesystem__read_file`), so two servers can both offer a `search` tool.
+//! Set `bare_tool_names = true` under `[settings]` to expose bare names instead.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use super::adapter::TOOL_NAME_SEPARATOR;
+
 /// Configuration for MCP servers.
 ///
 /// Contains a list of server configurations and global settings for MCP client behavior.
@@ -118,15 +128,29 @@ pub struct McpSettings {
     /// Maximum retries for failed connections
     #[serde(default = "default_max_retries")]
     pub max_retries: usize,
+    /// Delay before the second reconnection attempt after a dropped
+    /// connection, doubled after each further failure (capped at 30s).
+    #[serde(default = "default_reconnect_delay_ms")]
+    pub reconnect_delay_ms: u64,
     /// Whether to enable debug logging for MCP
     #[serde(default)]
     pub debug: bool,
+    /// Expose tools under their bare names instead of `server__tool`.
+    ///
+    /// Bare names are shorter but collide when two servers expose a tool with
+    /// the same name; the later server's tool then shadows the earlier one.
+    #[serde(default)]
+    pub bare_tool_names: bool,
 }
 
 const fn default_max_retries() -> usize {
     3
 }
 
+const fn default_reconnect_delay_ms() -> u64 {
+    500
+}
+
 impl McpConfig {
     /// Load configuration from a YAML file
     ///
@@ -184,6 +208,12 @@ impl McpConfig {
             if !seen_ids.insert(&server.id) {
                 return Err(anyhow::anyhow!("Duplicate server ID found: {}", server.id));
             }
+            if server.id.contains(TOOL_NAME_SEPARATOR) {
+                return Err(anyhow::anyhow!(
+                    "Server ID '{}' must not contain '{TOOL_NAME_SEPARATOR}'",
+                    server.id
+                ));
+            }
         }
         Ok(())
     }
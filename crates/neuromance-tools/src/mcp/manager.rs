@@ -1,19 +1,155 @@
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::path::Path;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use super::adapter::McpToolAdapter;
+use super::adapter::{McpToolAdapter, split_namespaced_name};
 use super::client::McpClientWrapper;
-use super::config::McpConfig;
-use crate::ToolImplementation;
+use super::config::{McpConfig, McpServerConfig};
+use crate::{ToolError, ToolImplementation};
+
+/// Upper bound on the delay between reconnection attempts.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Opens a session to one server. Defaults to [`McpClientWrapper::connect`];
+/// tests substitute in-process servers.
+pub(crate) type Connector = Arc<
+    dyn Fn(McpServerConfig) -> Pin<Box<dyn Future<Output = Result<McpClientWrapper>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Connection state shared by the manager and the tool adapters it hands out,
+/// so adapters created before a reconnect dispatch to the new session.
+pub(crate) struct Connections {
+    config: McpConfig,
+    clients: RwLock<HashMap<String, Arc<McpClientWrapper>>>,
+    /// Servers with a reconnect in flight, mapped to the current attempt.
+    reconnecting: RwLock<HashMap<String, usize>>,
+    connector: Connector,
+}
+
+impl Connections {
+    fn new(config: McpConfig, connector: Connector) -> Self {
+        Self {
+            config,
+            clients: RwLock::new(HashMap::new()),
+            reconnecting: RwLock::new(HashMap::new()),
+            connector,
+        }
+    }
+
+    fn server_config(&self, server_id: &str) -> Result<McpServerConfig> {
+        self.config
+            .servers
+            .iter()
+            .find(|s| s.id == server_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Server '{server_id}' not found in configuration"))
+    }
+
+    /// The live client for `server_id`, failing fast while it reconnects.
+    pub(crate) async fn client_for_call(
+        &self,
+        server_id: &str,
+    ) -> Result<Arc<McpClientWrapper>, ToolError> {
+        if let Some(attempt) = self.reconnecting.read().await.get(server_id) {
+            return Err(ToolError::execution(format!(
+                "MCP server '{server_id}' is reconnecting (attempt {attempt}); try again shortly"
+            )));
+        }
+        self.clients
+            .read()
+            .await
+            .get(server_id)
+            .cloned()
+            .ok_or_else(|| {
+                ToolError::execution(format!("MCP server '{server_id}' is not connected"))
+            })
+    }
+
+    /// Start reconnecting `server_id` in the background unless a reconnect is
+    /// already in flight.
+    pub(crate) fn spawn_reconnect(self: &Arc<Self>, server_id: &str) {
+        let connections = Arc::clone(self);
+        let server_id = server_id.to_string();
+        tokio::spawn(async move {
+            let Ok(server_config) = connections.server_config(&server_id) else {
+                return;
+            };
+            if !connections.begin_reconnect(&server_id).await {
+                return;
+            }
+            if let Err(e) = connections.run_reconnect(server_config).await {
+                error!(server = %server_id, error = %e, "MCP server reconnection failed");
+            }
+        });
+    }
+
+    /// Mark `server_id` as reconnecting and drop its dead session. Returns
+    /// `false` if a reconnect is already in flight.
+    async fn begin_reconnect(&self, server_id: &str) -> bool {
+        {
+            let mut reconnecting = self.reconnecting.write().await;
+            if reconnecting.contains_key(server_id) {
+                return false;
+            }
+            reconnecting.insert(server_id.to_string(), 1);
+        }
+        self.clients.write().await.remove(server_id);
+        true
+    }
+
+    /// Retry the connection with exponential backoff, starting at
+    /// [`McpSettings::reconnect_delay_ms`](super::McpSettings::reconnect_delay_ms)
+    /// and doubling up to 30s, for at most `max_retries` attempts.
+    async fn run_reconnect(&self, server_config: McpServerConfig) -> Result<()> {
+        let server_id = server_config.id.clone();
+        let attempts = self.config.settings.max_retries.max(1);
+        let mut delay = Duration::from_millis(self.config.settings.reconnect_delay_ms);
+
+        for attempt in 1..=attempts {
+            if attempt > 1 {
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+            self.reconnecting
+                .write()
+                .await
+                .insert(server_id.clone(), attempt);
+            info!(server = %server_id, attempt, "reconnecting to MCP server");
+
+            match (self.connector)(server_config.clone()).await {
+                Ok(client) => {
+                    self.clients
+                        .write()
+                        .await
+                        .insert(server_id.clone(), Arc::new(client));
+                    self.reconnecting.write().await.remove(&server_id);
+                    info!(server = %server_id, attempt, "MCP server reconnected");
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!(server = %server_id, attempt, error = %e, "MCP reconnect attempt failed");
+                }
+            }
+        }
+
+        self.reconnecting.write().await.remove(&server_id);
+        Err(anyhow::anyhow!(
+            "Failed to reconnect to MCP server '{server_id}' after {attempts} attempts"
+        ))
+    }
+}
 
 /// Manages multiple MCP server connections
 pub struct McpManager {
-    config: McpConfig,
-    clients: Arc<RwLock<HashMap<String, Arc<McpClientWrapper>>>>,
+    connections: Arc<Connections>,
 }
 
 impl McpManager {
@@ -31,9 +167,9 @@ impl McpManager {
     /// # Errors
     /// Returns an error if connections fail.
     pub async fn new(config: McpConfig) -> Result<Self> {
+        let connector: Connector = Arc::new(|config| Box::pin(McpClientWrapper::connect(config)));
         let manager = Self {
-            config,
-            clients: Arc::new(RwLock::new(HashMap::new())),
+            connections: Arc::new(Connections::new(config, connector)),
         };
 
         // Connect to all configured servers
@@ -47,12 +183,12 @@ impl McpManager {
     /// # Errors
     /// Returns an error if no servers can be connected.
     pub async fn connect_all(&self) -> Result<()> {
-        let mut clients = self.clients.write().await;
+        let mut clients = self.connections.clients.write().await;
 
-        for server_config in &self.config.servers {
+        for server_config in &self.connections.config.servers {
             info!(server = %server_config.id, "connecting to MCP server");
 
-            match McpClientWrapper::connect(server_config.clone()).await {
+            match (self.connections.connector)(server_config.clone()).await {
                 Ok(client) => {
                     let tools_count = client.get_tools().await.len();
                     info!(
@@ -68,20 +204,20 @@ impl McpManager {
                         error = %e,
                         "MCP server connection failed",
                     );
-                    if self.config.settings.max_retries > 0 {
+                    if self.connections.config.settings.max_retries > 0 {
                         // Try reconnecting with retries
-                        for attempt in 1..=self.config.settings.max_retries {
+                        for attempt in 1..=self.connections.config.settings.max_retries {
                             info!(
                                 server = %server_config.id,
                                 attempt,
-                                max_retries = self.config.settings.max_retries,
+                                max_retries = self.connections.config.settings.max_retries,
                                 "retrying MCP server connection",
                             );
 
                             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
                             if let Ok(client) =
-                                McpClientWrapper::connect(server_config.clone()).await
+                                (self.connections.connector)(server_config.clone()).await
                             {
                                 let tools_count = client.get_tools().await.len();
                                 info!(
@@ -112,17 +248,11 @@ impl McpManager {
     /// # Errors
     /// Returns an error if the server is not found or connection fails.
     pub async fn connect_server(&self, server_id: &str) -> Result<()> {
-        let server_config = self
-            .config
-            .servers
-            .iter()
-            .find(|s| s.id == server_id)
-            .ok_or_else(|| anyhow::anyhow!("Server '{server_id}' not found in configuration"))?
-            .clone();
-
-        let client = McpClientWrapper::connect(server_config).await?;
+        let server_config = self.connections.server_config(server_id)?;
+        let client = (self.connections.connector)(server_config).await?;
 
-        self.clients
+        self.connections
+            .clients
             .write()
             .await
             .insert(server_id.to_string(), Arc::new(client));
@@ -135,7 +265,7 @@ impl McpManager {
     /// # Errors
     /// Returns an error if shutdown fails.
     pub async fn disconnect_server(&self, server_id: &str) -> Result<()> {
-        let client = self.clients.write().await.remove(server_id);
+        let client = self.connections.clients.write().await.remove(server_id);
         if let Some(client) = client {
             // Shut down the client gracefully
             if let Ok(client) = Arc::try_unwrap(client) {
@@ -147,16 +277,32 @@ impl McpManager {
 
     /// Get all available tools from all connected MCP servers
     ///
+    /// Tools are named `server_id__tool_name` so identically named tools on
+    /// different servers stay distinct, unless
+    /// [`McpSettings::bare_tool_names`](super::McpSettings::bare_tool_names)
+    /// is set; bare-name collisions are logged.
+    ///
     /// # Errors
     /// This function currently cannot fail but returns Result for API consistency.
     pub async fn get_all_tools(&self) -> Result<Vec<Arc<dyn ToolImplementation>>> {
+        let namespaced = !self.connections.config.settings.bare_tool_names;
         let mut tools: Vec<Arc<dyn ToolImplementation>> = Vec::new();
+        let mut seen = HashSet::new();
 
-        for (server_id, client) in self.clients.read().await.iter() {
+        for (server_id, client) in self.connections.clients.read().await.iter() {
             let mcp_tools = client.get_tools().await;
 
             for mcp_tool in mcp_tools {
-                let adapter = McpToolAdapter::new(server_id.clone(), client.clone(), mcp_tool);
+                let adapter = McpToolAdapter::new(server_id.clone(), client.clone(), mcp_tool)
+                    .with_namespace(namespaced)
+                    .with_connections(Arc::clone(&self.connections));
+                if !seen.insert(adapter.exposed_name()) {
+                    warn!(
+                        server = %server_id,
+                        tool = %adapter.tool_name,
+                        "MCP tool name is exposed by more than one server",
+                    );
+                }
                 tools.push(Arc::new(adapter) as Arc<dyn ToolImplementation>);
             }
         }
@@ -169,18 +315,12 @@ impl McpManager {
     /// # Errors
     /// Returns an error if the tool name format is invalid, server is not connected, or tool not found.
     pub async fn get_tool(&self, full_name: &str) -> Result<Arc<dyn ToolImplementation>> {
-        // Parse full name (format: server_id.tool_name)
-        let parts: Vec<&str> = full_name.splitn(2, '.').collect();
-        if parts.len() != 2 {
-            return Err(anyhow::anyhow!(
-                "Invalid tool name format. Expected: server_id.tool_name"
-            ));
-        }
-
-        let server_id = parts[0];
-        let tool_name = parts[1];
+        let (server_id, tool_name) = split_namespaced_name(full_name).ok_or_else(|| {
+            anyhow::anyhow!("Invalid tool name format. Expected: server_id__tool_name")
+        })?;
 
         let client = self
+            .connections
             .clients
             .read()
             .await
@@ -196,17 +336,41 @@ impl McpManager {
             .ok_or_else(|| anyhow::anyhow!("Tool '{tool_name}' not found on server '{server_id}'"))?
             .clone();
 
-        let adapter = McpToolAdapter::new(server_id.to_string(), client, mcp_tool);
+        let adapter = McpToolAdapter::new(server_id.to_string(), client, mcp_tool)
+            .with_namespace(!self.connections.config.settings.bare_tool_names)
+            .with_connections(Arc::clone(&self.connections));
 
         Ok(Arc::new(adapter) as Arc<dyn ToolImplementation>)
     }
 
+    /// Reconnect to `server_name`, replacing its current session.
+    ///
+    /// Retries with exponential backoff (see
+    /// [`McpSettings::reconnect_delay_ms`](super::McpSettings::reconnect_delay_ms)).
+    /// While this runs the server reports [`ServerStatus::Reconnecting`] and
+    /// calls to its tools fail fast. Adapters obtained earlier pick up the new
+    /// session. Dropped connections are also reconnected automatically when a
+    /// tool call hits a transport error.
+    ///
+    /// # Errors
+    /// Returns an error if the server is not configured, a reconnect is already
+    /// in flight, or every attempt fails.
+    pub async fn reconnect(&self, server_name: &str) -> Result<()> {
+        let server_config = self.connections.server_config(server_name)?;
+        if !self.connections.begin_reconnect(server_name).await {
+            return Err(anyhow::anyhow!(
+                "Server '{server_name}' is already reconnecting"
+            ));
+        }
+        self.connections.run_reconnect(server_config).await
+    }
+
     /// Refresh tools for all connected servers
     ///
     /// # Errors
     /// Returns an error if refreshing tools fails on any server.
     pub async fn refresh_all_tools(&self) -> Result<()> {
-        for (server_id, client) in self.clients.read().await.iter() {
+        for (server_id, client) in self.connections.clients.read().await.iter() {
             info!(server = %server_id, "refreshing MCP server tools");
             client.refresh_tools().await?;
         }
@@ -218,8 +382,19 @@ impl McpManager {
     pub async fn get_status(&self) -> HashMap<String, ServerStatus> {
         let mut status_map = HashMap::new();
 
-        for server_config in &self.config.servers {
-            let status = if let Some(client) = self.clients.read().await.get(&server_config.id) {
+        for server_config in &self.connections.config.servers {
+            let reconnecting = self
+                .connections
+                .reconnecting
+                .read()
+                .await
+                .get(&server_config.id)
+                .copied();
+            let status = if let Some(attempt) = reconnecting {
+                ServerStatus::Reconnecting { attempt }
+            } else if let Some(client) =
+                self.connections.clients.read().await.get(&server_config.id)
+            {
                 let tools_count = client.get_tools().await.len();
                 let server_info = client.service.peer_info().map_or_else(
                     || "Unknown".to_string(),
@@ -243,7 +418,7 @@ impl McpManager {
     /// # Errors
     /// Returns an error if shutting down a client fails.
     pub async fn shutdown(self) -> Result<()> {
-        for (server_id, client) in self.clients.write().await.drain() {
+        for (server_id, client) in self.connections.clients.write().await.drain() {
             info!(server = %server_id, "shutting down MCP server connection");
             if let Ok(client) = Arc::try_unwrap(client) {
                 client.shutdown().await?;
@@ -259,5 +434,224 @@ pub enum ServerStatus {
         tools_count: usize,
         server_name: String,
     },
+    /// The connection dropped and a reconnect is in progress.
+    Reconnecting {
+        attempt: usize,
+    },
     Disconnected,
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic)]
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde_json::json;
+    use tokio::sync::Semaphore;
+
+    use super::*;
+    use crate::mcp::McpSettings;
+    use crate::mcp::test_support::{connect_mock, server_config};
+
+    /// A manager over already-connected `clients`, reconnecting via `connector`.
+    async fn manager_with(
+        clients: Vec<(&str, McpClientWrapper)>,
+        settings: McpSettings,
+        connector: Connector,
+    ) -> McpManager {
+        let config = McpConfig {
+            servers: clients.iter().map(|(id, _)| server_config(id)).collect(),
+            settings,
+        };
+        let connections = Connections::new(config, connector);
+        for (id, client) in clients {
+            connections
+                .clients
+                .write()
+                .await
+                .insert(id.to_string(), Arc::new(client));
+        }
+        McpManager {
+            connections: Arc::new(connections),
+        }
+    }
+
+    /// A connector that reopens the mock server, counting each attempt.
+    fn mock_connector(tools: &'static [&'static str], attempts: Arc<AtomicUsize>) -> Connector {
+        Arc::new(move |config: McpServerConfig| {
+            let attempts = Arc::clone(&attempts);
+            Box::pin(async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Ok(connect_mock(&config.id, tools).await)
+            })
+        })
+    }
+
+    /// A manager over two in-process servers that both expose `search`.
+    async fn two_server_manager(bare_tool_names: bool) -> McpManager {
+        let settings = McpSettings {
+            bare_tool_names,
+            ..McpSettings::default()
+        };
+        let connector = mock_connector(&["search"], Arc::default());
+        manager_with(
+            vec![
+                ("docs", connect_mock("docs", &["search"]).await),
+                ("web", connect_mock("web", &["search"]).await),
+            ],
+            settings,
+            connector,
+        )
+        .await
+    }
+
+    async fn wait_for_status(manager: &McpManager, server: &str, want: fn(&ServerStatus) -> bool) {
+        for _ in 0..200 {
+            if want(&manager.get_status().await[server]) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("server '{server}' never reached the expected status");
+    }
+    #[tokio::test]
+    async fn namespaced_tools_dispatch_to_their_own_server() {
+        let manager = two_server_manager(false).await;
+
+        let tools = manager.get_all_tools().await.unwrap();
+        let mut names: Vec<String> = tools
+            .iter()
+            .map(|t| t.get_definition().function.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["docs__search", "web__search"]);
+
+        for tool in &tools {
+            let name = tool.get_definition().function.name;
+            let server = name.split("__").next().unwrap();
+            let output = tool.execute(&json!({})).await.unwrap();
+            assert_eq!(output, format!("{server}:search"));
+        }
+
+        let web = manager.get_tool("web__search").await.unwrap();
+        assert_eq!(web.execute(&json!({})).await.unwrap(), "web:search");
+        assert!(manager.get_tool("web.search").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn bare_tool_names_collide_across_servers() {
+        let manager = two_server_manager(true).await;
+
+        let names: Vec<String> = manager
+            .get_all_tools()
+            .await
+            .unwrap()
+            .iter()
+            .map(|t| t.get_definition().function.name)
+            .collect();
+        assert_eq!(names, vec!["search", "search"]);
+
+        let docs = manager.get_tool("docs__search").await.unwrap();
+        assert_eq!(docs.get_definition().function.name, "search");
+        assert_eq!(docs.execute(&json!({})).await.unwrap(), "docs:search");
+    }
+
+    #[tokio::test]
+    async fn dropped_connection_reconnects_with_backoff() {
+        const TOOLS: &[&str] = &["echo", "crash"];
+        let gate = Arc::new(Semaphore::new(0));
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let connector: Connector = {
+            let gate = Arc::clone(&gate);
+            let attempts = Arc::clone(&attempts);
+            Arc::new(move |config: McpServerConfig| {
+                let gate = Arc::clone(&gate);
+                let attempts = Arc::clone(&attempts);
+                Box::pin(async move {
+                    gate.acquire().await.unwrap().forget();
+                    // The restarted server is not up on the first attempt.
+                    if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                        anyhow::bail!("connection refused");
+                    }
+                    Ok(connect_mock(&config.id, TOOLS).await)
+                })
+            })
+        };
+        let settings = McpSettings {
+            max_retries: 3,
+            reconnect_delay_ms: 10,
+            ..McpSettings::default()
+        };
+        let manager = manager_with(
+            vec![("fs", connect_mock("fs", TOOLS).await)],
+            settings,
+            connector,
+        )
+        .await;
+
+        let tools = manager.get_all_tools().await.unwrap();
+        let tool = |name: &str| {
+            tools
+                .iter()
+                .find(|t| t.get_definition().function.name == name)
+                .unwrap()
+        };
+        assert_eq!(
+            tool("fs__echo").execute(&json!({})).await.unwrap(),
+            "fs:echo"
+        );
+
+        // The server drops the connection mid-call.
+        assert!(tool("fs__crash").execute(&json!({})).await.is_err());
+        wait_for_status(&manager, "fs", |s| {
+            matches!(s, ServerStatus::Reconnecting { attempt: 1 })
+        })
+        .await;
+
+        // Calls during reconnection fail fast instead of hanging.
+        let err =
+            tokio::time::timeout(Duration::from_secs(1), tool("fs__echo").execute(&json!({})))
+                .await
+                .unwrap()
+                .unwrap_err();
+        assert!(err.to_string().contains("is reconnecting (attempt 1)"));
+
+        gate.add_permits(2);
+        wait_for_status(&manager, "fs", |s| {
+            matches!(s, ServerStatus::Connected { .. })
+        })
+        .await;
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        // Adapters handed out before the drop use the new session.
+        assert_eq!(
+            tool("fs__echo").execute(&json!({})).await.unwrap(),
+            "fs:echo"
+        );
+    }
+
+    #[tokio::test]
+    async fn manual_reconnect_replaces_session() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let connector = mock_connector(&["echo"], Arc::clone(&attempts));
+        let manager = manager_with(
+            vec![("fs", connect_mock("fs", &["echo"]).await)],
+            McpSettings::default(),
+            connector,
+        )
+        .await;
+        let echo = manager.get_tool("fs__echo").await.unwrap();
+
+        manager.reconnect("fs").await.unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert!(matches!(
+            manager.get_status().await["fs"],
+            ServerStatus::Connected { .. }
+        ));
+        assert_eq!(echo.execute(&json!({})).await.unwrap(), "fs:echo");
+
+        assert!(manager.reconnect("missing").await.is_err());
+    }
+}
@@ -5,3 +5,94 @@ pub mod manager;
 
 pub use config::{McpConfig, McpServerConfig, McpSettings, McpTransportConfig};
 pub use manager::{McpManager, ServerStatus};
+
+/// In-process MCP servers for exercising the client side in unit tests.
+#[cfg(test)]
+pub(crate) mod test_support {
+    #![allow(clippy::unwrap_used)]
+
+    use rmcp::ServiceExt;
+    use serde_json::{Value, json};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream};
+
+    use super::client::McpClientWrapper;
+    use super::config::{McpServerConfig, McpTransportConfig};
+
+    /// A stdio-style server config that is never spawned; only its id and
+    /// approval flag are read once the client is connected in-process.
+    pub fn server_config(id: &str) -> McpServerConfig {
+        McpServerConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            transport: McpTransportConfig::Stdio {
+                command: "unused".to_string(),
+                args: Vec::new(),
+                env: std::collections::HashMap::new(),
+            },
+            description: None,
+            auto_approve: false,
+            working_directory: None,
+        }
+    }
+
+    /// Connect a client to an in-process server named `id` that exposes
+    /// `tools`. Every tool call answers with `"{id}:{tool}"`, except `crash`,
+    /// which closes the connection without replying.
+    pub async fn connect_mock(id: &str, tools: &[&str]) -> McpClientWrapper {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let tools: Vec<String> = tools.iter().map(ToString::to_string).collect();
+        tokio::spawn(serve_mock(id.to_string(), tools, server_io));
+
+        let service = ().serve(client_io).await.unwrap();
+        McpClientWrapper::from_service(server_config(id), service)
+            .await
+            .unwrap()
+    }
+
+    /// Minimal newline-delimited JSON-RPC server covering the handshake,
+    /// `tools/list`, and `tools/call`. Returns when the client hangs up.
+    async fn serve_mock(id: String, tools: Vec<String>, io: DuplexStream) {
+        let (read, mut write) = tokio::io::split(io);
+        let mut lines = BufReader::new(read).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let request: Value = serde_json::from_str(&line).unwrap();
+            let Some(request_id) = request.get("id").cloned() else {
+                continue; // notifications need no reply
+            };
+            if request["params"]["name"] == "crash" {
+                break;
+            }
+            let result = match request["method"].as_str().unwrap_or_default() {
+                "initialize" => json!({
+                    "protocolVersion": request["params"]["protocolVersion"],
+                    "capabilities": { "tools": {} },
+                    "serverInfo": { "name": id, "version": "0.0.0" },
+                }),
+                "tools/list" => json!({
+                    "tools": tools
+                        .iter()
+                        .map(|name| json!({
+                            "name": name,
+                            "description": format!("{name} on {id}"),
+                            "inputSchema": { "type": "object", "properties": {} },
+                        }))
+                        .collect::<Vec<_>>(),
+                }),
+                "tools/call" => json!({
+                    "content": [{
+                        "type": "text",
+                        "text": format!("{id}:{}", request["params"]["name"].as_str().unwrap()),
+                    }],
+                    "isError": false,
+                }),
+                _ => json!({}),
+            };
+            let reply = json!({ "jsonrpc": "2.0", "id": request_id, "result": result });
+            let mut bytes = serde_json::to_vec(&reply).unwrap();
+            bytes.push(b'\n');
+            if write.write_all(&bytes).await.is_err() {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Per-tool rate limiting for [`ToolExecutor`](crate::ToolExecutor).
+//!
+//! Each limited tool gets a token bucket holding up to
+//! [`requests`](RateLimit::requests) tokens that refills evenly over
+//! [`interval`](RateLimit::interval). A call takes one token; with the bucket
+//! empty the call either waits for the next token or fails, depending on the
+//! executor's [`RateLimitPolicy`]. Bursts up to the full limit are allowed
+//! after a quiet period.
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+/// How many calls a tool may receive per interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Calls allowed per interval. A value of 0 is treated as 1.
+    pub requests: u32,
+    /// The window the calls are spread over.
+    pub interval: Duration,
+}
+
+impl RateLimit {
+    #[must_use]
+    pub const fn new(requests: u32, interval: Duration) -> Self {
+        Self { requests, interval }
+    }
+}
+
+/// What a call does when its tool is over its rate limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitPolicy {
+    /// Sleep until the tool may run again.
+    #[default]
+    Wait,
+    /// Fail with [`ToolExecutorError::RateLimited`](crate::ToolExecutorError::RateLimited).
+    Error,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    capacity: f64,
+    /// Time to earn back one token.
+    per_token: Duration,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        let requests = limit.requests.max(1);
+        Self {
+            limit,
+            capacity: f64::from(requests),
+            per_token: limit.interval / requests,
+            tokens: f64::from(requests),
+            refilled_at: Instant::now(),
+        }
+    }
+
+    /// Take a token, or return how long until one is available.
+    fn try_take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        if self.per_token.is_zero() {
+            self.tokens = self.capacity;
+        } else {
+            let earned =
+                now.duration_since(self.refilled_at).as_secs_f64() / self.per_token.as_secs_f64();
+            self.tokens = (self.tokens + earned).min(self.capacity);
+        }
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(self.per_token.mul_f64(1.0 - self.tokens))
+        }
+    }
+}
+
+/// Token buckets per tool name, shared by concurrent calls.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    tools: DashMap<String, TokenBucket>,
+}
+
+impl RateLimiter {
+    /// Limit `name` to `limit`, starting from a full bucket.
+    pub fn set(&self, name: String, limit: RateLimit) {
+        self.tools.insert(name, TokenBucket::new(limit));
+    }
+
+    pub fn remove(&self, name: &str) {
+        self.tools.remove(name);
+    }
+
+    pub fn get(&self, name: &str) -> Option<RateLimit> {
+        self.tools.get(name).map(|bucket| bucket.limit)
+    }
+
+    /// Take a token for `name`, or return how long until one is available.
+    /// Tools without a limit always succeed.
+    pub fn try_acquire(&self, name: &str) -> Result<(), Duration> {
+        self.tools
+            .get_mut(name)
+            .map_or(Ok(()), |mut bucket| bucket.try_take())
+    }
+}
@@ -0,0 +1,102 @@
+//! Embedding-based ranking behind
+//! [`ToolExecutor::select_relevant_tools`](crate::ToolExecutor::select_relevant_tools).
+
+use dashmap::DashMap;
+use neuromance_common::Embedder;
+use neuromance_common::tools::Tool;
+
+/// A tool's embedding along with what produced it, so a changed description
+/// or a different embedding model is re-embedded rather than reused.
+#[derive(Debug)]
+struct CachedEmbedding {
+    model: String,
+    text: String,
+    vector: Vec<f32>,
+}
+
+/// Tool description embeddings keyed by tool name.
+#[derive(Debug, Default)]
+pub struct ToolEmbeddingCache {
+    entries: DashMap<String, CachedEmbedding>,
+}
+
+impl ToolEmbeddingCache {
+    /// Returns the `top_k` tools most similar to `query`, best first.
+    ///
+    /// Only tools missing from the cache (or whose description changed) are
+    /// embedded, all in one batch.
+    pub async fn rank<E: Embedder + ?Sized>(
+        &self,
+        tools: Vec<Tool>,
+        query: &str,
+        embedder: &E,
+        top_k: usize,
+    ) -> Result<Vec<Tool>, E::Error> {
+        let model = embedder.embedding_model();
+        let stale: Vec<(String, String)> = tools
+            .iter()
+            .map(|tool| (tool.function.name.clone(), embedding_text(tool)))
+            .filter(|(name, text)| {
+                self.entries
+                    .get(name)
+                    .is_none_or(|cached| cached.model != model || &cached.text != text)
+            })
+            .collect();
+        if !stale.is_empty() {
+            let texts: Vec<&str> = stale.iter().map(|(_, text)| text.as_str()).collect();
+            let vectors = embedder.embed_texts(&texts).await?;
+            for ((name, text), vector) in stale.into_iter().zip(vectors) {
+                self.entries.insert(
+                    name,
+                    CachedEmbedding {
+                        model: model.to_owned(),
+                        text,
+                        vector,
+                    },
+                );
+            }
+        }
+
+        let query = embedder
+            .embed_texts(&[query])
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        let mut scored: Vec<(f32, Tool)> = tools
+            .into_iter()
+            .map(|tool| {
+                let score = self
+                    .entries
+                    .get(&tool.function.name)
+                    .map_or(f32::MIN, |cached| cosine_similarity(&query, &cached.vector));
+                (score, tool)
+            })
+            .collect();
+        scored.sort_by(|(a, a_tool), (b, b_tool)| {
+            b.total_cmp(a)
+                .then_with(|| a_tool.function.name.cmp(&b_tool.function.name))
+        });
+        Ok(scored
+            .into_iter()
+            .take(top_k)
+            .map(|(_, tool)| tool)
+            .collect())
+    }
+}
+
+fn embedding_text(tool: &Tool) -> String {
+    format!("{}: {}", tool.function.name, tool.function.description)
+}
+
+/// Cosine similarity of two vectors; `0.0` if either has zero length or
+/// magnitude.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
@@ -0,0 +1,85 @@
+//! Checking tool arguments against a tool's JSON Schema `parameters`.
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use jsonschema::Validator;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::ToolError;
+
+/// A compiled validator along with the schema it came from, so a tool
+/// re-registered with different parameters is recompiled rather than reused.
+/// `validator` is `None` for a schema that failed to compile.
+struct CachedValidator {
+    schema: Value,
+    validator: Option<Arc<Validator>>,
+}
+
+/// Compiled argument validators keyed by tool name.
+#[derive(Default)]
+pub struct ArgumentValidators {
+    entries: DashMap<String, CachedValidator>,
+}
+
+impl ArgumentValidators {
+    /// Checks `args` against `schema`, collecting every violation.
+    ///
+    /// The schema is compiled on first use and again only if it changes. A
+    /// schema that does not compile is logged once and skipped, so a tool
+    /// with a sloppy definition still runs as it did without validation.
+    ///
+    /// # Errors
+    /// Returns [`ToolError::ArgumentSchema`] if `args` violates `schema`.
+    pub fn validate(&self, tool: &str, schema: &Value, args: &Value) -> Result<(), ToolError> {
+        let Some(validator) = self.validator(tool, schema) else {
+            return Ok(());
+        };
+
+        let violations: Vec<String> = validator
+            .iter_errors(args)
+            .map(|error| match error.instance_path.as_str() {
+                "" => error.to_string(),
+                path => format!("{path}: {error}"),
+            })
+            .collect();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ToolError::ArgumentSchema {
+                tool: tool.to_owned(),
+                violations,
+            })
+        }
+    }
+
+    /// The compiled validator for `tool`, compiling `schema` if it is not
+    /// cached or has changed since it was.
+    fn validator(&self, tool: &str, schema: &Value) -> Option<Arc<Validator>> {
+        if let Some(cached) = self.entries.get(tool)
+            && &cached.schema == schema
+        {
+            return cached.validator.clone();
+        }
+
+        let validator = match jsonschema::validator_for(schema) {
+            Ok(validator) => Some(Arc::new(validator)),
+            Err(e) => {
+                warn!(
+                    tool,
+                    "Skipping argument validation, invalid parameter schema: {e}"
+                );
+                None
+            }
+        };
+        self.entries.insert(
+            tool.to_owned(),
+            CachedValidator {
+                schema: schema.clone(),
+                validator: validator.clone(),
+            },
+        );
+        validator
+    }
+}
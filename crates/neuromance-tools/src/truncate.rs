@@ -109,6 +109,44 @@ pub fn truncate_tail(text: &str, max_lines: usize, max_bytes: usize) -> Truncati
     }
 }
 
+/// Cuts `text` to at most `max_bytes` on a char boundary, noting how much
+/// was kept.
+#[must_use]
+pub fn truncate_to(text: String, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let end = text.floor_char_boundary(max_bytes);
+    format!(
+        "{}\n[truncated: showing {end} of {} bytes]",
+        &text[..end],
+        text.len()
+    )
+}
+
+/// Decodes the first `max_bytes` of `bytes` as text, cutting on a char
+/// boundary. Returns the text and whether anything was cut.
+///
+/// Callers read at most `max_bytes + 1` bytes of a file, body, or pipe, so a
+/// longer input shows up as one extra byte without being held in memory. A
+/// multi-byte character split by the cut is dropped rather than rendered as a
+/// replacement character; other invalid UTF-8 is replaced.
+#[must_use]
+pub fn decode_capped(mut bytes: Vec<u8>, max_bytes: usize) -> (String, bool) {
+    let truncated = bytes.len() > max_bytes;
+    if truncated {
+        bytes.truncate(max_bytes);
+        if let Err(e) = std::str::from_utf8(&bytes)
+            && e.error_len().is_none()
+        {
+            bytes.truncate(e.valid_up_to());
+        }
+    }
+    let text = String::from_utf8(bytes)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+    (text, truncated)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +199,16 @@ mod tests {
         assert_eq!(t.content, "a\nb\nc");
     }
 
+    #[test]
+    fn test_truncate_to_cuts_on_char_boundary() {
+        assert_eq!(truncate_to("short".to_string(), 10), "short");
+        // "é" is two bytes; a 4-byte cut would split the second one.
+        assert_eq!(
+            truncate_to("aéé".to_string(), 4),
+            "aé\n[truncated: showing 3 of 5 bytes]"
+        );
+    }
+
     #[test]
     fn test_empty_input() {
         let t = truncate_head("", DEFAULT_MAX_LINES, DEFAULT_MAX_BYTES);
@@ -168,4 +216,20 @@ mod tests {
         assert!(!t.is_truncated());
         assert_eq!(t.total_lines, 0);
     }
+
+    #[test]
+    fn test_decode_capped_cuts_on_char_boundary() {
+        assert_eq!(
+            decode_capped(b"hello".to_vec(), 5),
+            ("hello".to_string(), false)
+        );
+        assert_eq!(
+            decode_capped("caf\u{e9}!".as_bytes().to_vec(), 4),
+            ("caf".to_string(), true)
+        );
+        assert_eq!(
+            decode_capped(b"caf\xe9".to_vec(), 8),
+            ("caf\u{fffd}".to_string(), false)
+        );
+    }
 }
@@ -117,6 +117,9 @@ async fn main() -> Result<()> {
                     std::io::stdout().flush().unwrap();
                 }
             }
+            CoreEvent::ToolCallRequested(call) => {
+                info!("Calling tool '{}'", call.function.name);
+            }
             CoreEvent::ToolResult {
                 name,
                 result,
@@ -134,8 +137,9 @@ async fn main() -> Result<()> {
             } => {
                 info!("Context compacted: {original_tokens} -> {compacted_tokens} tokens");
             }
-            CoreEvent::ApprovalRequest { .. } => {
-                // Auto-approve is on, so this shouldn't fire in this example.
+            CoreEvent::ReasoningDelta(_) | CoreEvent::ApprovalRequest { .. } => {
+                // Reasoning isn't shown, and auto-approve is on so approval
+                // requests shouldn't fire in this example.
             }
             CoreEvent::Completed(msgs) => {
                 final_messages = msgs;
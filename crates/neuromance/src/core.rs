@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+use std::fmt::Write as _;
 use std::future::Future;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -5,41 +7,130 @@ use std::time::{Duration, Instant};
 use anyhow::Result;
 use async_stream::try_stream;
 use chrono::Utc;
+use futures::future::join_all;
 use futures::{Stream, StreamExt};
 use metrics::{counter, histogram};
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, info, info_span, trace};
+use tracing::{Instrument, debug, info, info_span, trace};
 
 /// How often to emit an info-level "still streaming" progress log while a
 /// single turn is in flight. Keeps long completions visible without flooding.
 const STREAM_PROGRESS_INTERVAL: Duration = Duration::from_secs(30);
 
-use neuromance_client::LLMClient;
+use neuromance_client::{ClientError, LLMClient};
 use neuromance_common::chat::{Conversation, Message, MessageRole};
-use neuromance_common::client::{ChatRequest, ChatResponse, ToolChoice, Usage};
+use neuromance_common::client::{ChatRequest, ChatResponse, FinishReason, ToolChoice, Usage};
 use neuromance_common::context::{ContextLedger, EditSource};
 use neuromance_common::features::ThinkingMode;
-use neuromance_common::hook::{CompactionStats, Hook, HookContext};
+use neuromance_common::hook::{CompactionStats, FnReviewHook, Hook, HookContext};
+use neuromance_common::tokens::{HeuristicTokenCounter, TokenCounter};
 use neuromance_common::tools::{ToolApproval, ToolCall};
+use neuromance_tools::audit::AuditApproval;
 use neuromance_tools::{ToolExecutor, ToolExecutorError};
 
 use crate::error::CoreError;
 use crate::events::CoreEvent;
 use crate::stats::RunStats;
 
+/// Metadata key under which a [dry run](Core::dry_run) records the
+/// serialized [`ChatRequest`] on its canned assistant message.
+pub const DRY_RUN_REQUEST_KEY: &str = "dry_run_request";
+
+/// Metadata key under which a [dry run](Core::dry_run) records the provider
+/// payload from [`LLMClient::request_payload`], when the client offers one.
+pub const DRY_RUN_PAYLOAD_KEY: &str = "dry_run_payload";
+
+/// Metadata key under which a run cut short by the caller records
+/// [`FinishReason::Cancelled`]: on the partial reply of a cancelled stream,
+/// or on the note appended when the token budget runs out.
+pub const FINISH_REASON_KEY: &str = "finish_reason";
+
+/// User nudge sent after a reply cut off by the length limit. See
+/// [`Core::with_max_continuations`].
+pub const CONTINUE_PROMPT: &str =
+    "Continue exactly where you left off. Do not repeat anything you already wrote.";
+
+/// Instructions [`Core::compact_conversation`] sends ahead of the transcript
+/// it asks the model to summarize.
+pub const COMPACTION_PROMPT: &str = "Summarize the conversation below so it can replace the \
+original messages. Keep decisions, requirements, user preferences, tool results that still \
+matter, and any facts that may be referenced later. Omit pleasantries and repetition. Reply \
+with the summary only.";
+
+/// System message [`SystemPromptStrategy::Appended`] adds to the end of each
+/// request.
+pub const SYSTEM_PROMPT_REMINDER: &str =
+    "Reminder: keep following the instructions given at the start of this conversation.";
+
+/// Where the system prompt goes in each request [`Core`] sends.
+///
+/// Only the outgoing request changes; the conversation history keeps its
+/// messages as given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SystemPromptStrategy {
+    /// Send system messages where they are in the history.
+    #[default]
+    First,
+    /// Also end the request with a short system message,
+    /// [`SYSTEM_PROMPT_REMINDER`], pointing back at the instructions, for
+    /// models that lose track of them in long contexts.
+    Appended,
+    /// Leave system and developer messages out of the request.
+    None,
+}
+
+impl SystemPromptStrategy {
+    /// Shape `messages` for a request under this strategy.
+    fn apply(self, messages: Arc<[Message]>) -> Arc<[Message]> {
+        match self {
+            Self::First => messages,
+            Self::Appended => {
+                let Some(instruction) = messages.iter().find(|m| m.role.is_instruction()) else {
+                    return messages;
+                };
+                let reminder = Message::system(instruction.conversation_id, SYSTEM_PROMPT_REMINDER);
+                messages.iter().cloned().chain([reminder]).collect()
+            }
+            Self::None => messages
+                .iter()
+                .filter(|m| !m.role.is_instruction())
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// What [`Core::send_guarded`] does with a request estimated to exceed the
+/// model's context window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContextOverflow {
+    /// Drop the oldest non-system messages until the request fits, using
+    /// [`Conversation::trim_to_token_budget`].
+    #[default]
+    Trim,
+    /// Fail with [`CoreError::ContextWindowExceeded`] without sending.
+    Error,
+}
+
 /// Core orchestration layer for LLM conversations with tool execution.
 ///
 /// [`Core::run`] returns a [`Stream`] of [`CoreEvent`]s. The stream borrows
 /// `&mut Core` for its lifetime and terminates with [`CoreEvent::Completed`].
+#[allow(clippy::struct_excessive_bools)] // independent run switches, not a state machine
 pub struct Core<C: LLMClient> {
     pub client: C,
     /// Enable streaming mode for chat responses.
     pub streaming: bool,
     /// Total number of tool calls the LLM can make before returning to the user.
     pub max_turns: Option<u32>,
+    /// Total tokens (summed `Usage.total_tokens`) the run may spend before it
+    /// stops after the current turn's tool calls.
+    pub token_budget: Option<u32>,
     /// Execute all tools regardless of their `auto_approve` value.
     pub auto_approve_tools: bool,
+    /// Run all tool calls from one assistant message concurrently.
+    pub parallel_tool_execution: bool,
     /// How the model selects which tool to call, if any.
     pub tool_choice: ToolChoice,
     /// Holds tools in `ToolRegistry` and executes tools.
@@ -48,6 +139,20 @@ pub struct Core<C: LLMClient> {
     pub hooks: Vec<Arc<dyn Hook>>,
     /// Thinking/reasoning mode configuration.
     pub thinking: ThinkingMode,
+    /// Build each request but answer it with a canned reply instead of
+    /// sending it. See [`Core::with_dry_run`].
+    pub dry_run: bool,
+    /// Where the system prompt goes in each request.
+    pub system_prompt_strategy: SystemPromptStrategy,
+    /// How many times a reply cut off by the length limit is continued.
+    /// See [`Core::with_max_continuations`].
+    pub max_continuations: u32,
+    /// What [`Core::send_guarded`] does with a request that would overflow
+    /// the model's context window.
+    pub context_overflow: ContextOverflow,
+    /// Text that ends the run as soon as an assistant message contains it.
+    /// See [`Core::with_completion_marker`].
+    pub completion_marker: Option<String>,
 }
 
 impl<C: LLMClient> Core<C> {
@@ -57,11 +162,18 @@ impl<C: LLMClient> Core<C> {
             client,
             streaming: false,
             max_turns: None,
+            token_budget: None,
             auto_approve_tools: false,
+            parallel_tool_execution: false,
             tool_choice: ToolChoice::Auto,
             tool_executor: ToolExecutor::new(),
             hooks: Vec::new(),
             thinking: ThinkingMode::Default,
+            dry_run: false,
+            system_prompt_strategy: SystemPromptStrategy::First,
+            max_continuations: 0,
+            context_overflow: ContextOverflow::Trim,
+            completion_marker: None,
         }
     }
 
@@ -75,6 +187,34 @@ impl<C: LLMClient> Core<C> {
         self
     }
 
+    /// Decide approval for each non-auto-approved tool call with `handler`.
+    ///
+    /// The handler is registered as a review [`Hook`], so it answers approvals
+    /// in-process instead of yielding [`CoreEvent::ApprovalRequest`]. Returning
+    /// [`ToolApproval::Denied`] records the reason as the tool's result, and
+    /// [`ToolApproval::ApprovedWithArguments`] runs the tool with the given
+    /// arguments. `auto_approve_tools` still short-circuits it when enabled.
+    #[must_use]
+    pub fn with_approval_handler<F>(self, handler: F) -> Self
+    where
+        F: Fn(&ToolCall) -> ToolApproval + Send + Sync + 'static,
+    {
+        self.with_async_approval_handler(move |call: &ToolCall| std::future::ready(handler(call)))
+    }
+
+    /// Like [`Core::with_approval_handler`], but the decision is awaited, so
+    /// the handler can prompt a user or call out to a service.
+    ///
+    /// Calls are reviewed one at a time, in the order the model requested them.
+    #[must_use]
+    pub fn with_async_approval_handler<F, Fut>(self, handler: F) -> Self
+    where
+        F: Fn(&ToolCall) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ToolApproval> + Send + 'static,
+    {
+        self.with_hook(Arc::new(FnReviewHook::new(handler)))
+    }
+
     /// Register a lifecycle [`Hook`] in place.
     pub fn add_hook(&mut self, hook: Arc<dyn Hook>) {
         self.hooks.push(hook);
@@ -87,6 +227,16 @@ impl<C: LLMClient> Core<C> {
         self
     }
 
+    /// Run all tool calls from one assistant message concurrently.
+    ///
+    /// Every call is reviewed before any runs; results are still recorded in
+    /// the order the model requested them.
+    #[must_use]
+    pub const fn with_parallel_tool_execution(mut self) -> Self {
+        self.parallel_tool_execution = true;
+        self
+    }
+
     /// Set extended thinking budget (Anthropic Claude models).
     #[must_use]
     pub const fn with_thinking_budget(mut self, budget: u32) -> Self {
@@ -105,6 +255,30 @@ impl<C: LLMClient> Core<C> {
         self
     }
 
+    /// Stop the run once the tokens reported across its turns exceed `budget`.
+    ///
+    /// The check runs at the end of each tool turn, so the history handed back
+    /// never ends on unanswered tool calls. Instead of another LLM call, the run
+    /// completes with a synthesized assistant note explaining why it stopped.
+    #[must_use]
+    pub const fn with_token_budget(mut self, budget: u32) -> Self {
+        self.token_budget = Some(budget);
+        self
+    }
+
+    /// Never call the provider; answer each turn with a canned assistant
+    /// message instead.
+    ///
+    /// The message's metadata carries the request that would have been sent
+    /// under [`DRY_RUN_REQUEST_KEY`] and, if the client can build it, the
+    /// provider's wire body under [`DRY_RUN_PAYLOAD_KEY`]. The reply has no
+    /// tool calls, so the run completes after one turn.
+    #[must_use]
+    pub const fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
     /// Set the thinking mode directly.
     #[must_use]
     pub const fn with_thinking_mode(mut self, mode: ThinkingMode) -> Self {
@@ -112,36 +286,272 @@ impl<C: LLMClient> Core<C> {
         self
     }
 
+    /// Continue replies that stop at the length limit, up to `max` times.
+    ///
+    /// A reply finishing with [`FinishReason::Length`] and no tool calls is
+    /// sent back with a [`CONTINUE_PROMPT`] nudge, and the pieces are joined
+    /// into a single assistant message. The partial replies and nudges are
+    /// never added to the history. Off (0) by default.
+    #[must_use]
+    pub const fn with_max_continuations(mut self, max: u32) -> Self {
+        self.max_continuations = max;
+        self
+    }
+
+    /// Set where the system prompt goes in each request.
+    #[must_use]
+    pub const fn with_system_prompt_strategy(mut self, strategy: SystemPromptStrategy) -> Self {
+        self.system_prompt_strategy = strategy;
+        self
+    }
+
+    /// End the run as soon as an assistant message contains `marker`
+    /// (e.g. `TASK_COMPLETE`), even if the message also requests tools.
+    ///
+    /// The marker is removed from the message, which becomes the final reply;
+    /// any tool calls it carried are dropped without running.
+    #[must_use]
+    pub fn with_completion_marker(mut self, marker: impl Into<String>) -> Self {
+        self.completion_marker = Some(marker.into());
+        self
+    }
+
+    /// Set what [`Core::send_guarded`] does with an oversized request.
+    #[must_use]
+    pub const fn with_context_overflow(mut self, overflow: ContextOverflow) -> Self {
+        self.context_overflow = overflow;
+        self
+    }
+
+    /// Send `conversation` as a single request after checking its estimated
+    /// size against the context window from [`LLMClient::capabilities`].
+    ///
+    /// Tokens are estimated with [`HeuristicTokenCounter`], counting the
+    /// registered tools as well as the messages. Under
+    /// [`ContextOverflow::Trim`] the oldest non-system messages are removed
+    /// from `conversation` until the request fits; under
+    /// [`ContextOverflow::Error`] the conversation is left untouched. A model
+    /// whose context window is unknown is sent unchecked. The reply is
+    /// returned, not appended to `conversation`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::ContextWindowExceeded`] if the request is over
+    /// the limit and overflow is [`ContextOverflow::Error`], or if it is still
+    /// over after trimming (the system messages alone are too large). Returns
+    /// any error from the client after retries.
+    pub async fn send_guarded(
+        &self,
+        conversation: &mut Conversation,
+    ) -> Result<ChatResponse, CoreError> {
+        let build = |messages: &[Message]| {
+            ChatRequest::from((self.client.config(), messages.to_vec()))
+                .with_tools(self.tool_executor.get_all_tools())
+                .with_tool_choice(self.tool_choice.clone())
+                .with_thinking_mode(self.thinking)
+        };
+        let counter = HeuristicTokenCounter;
+        let mut request = build(conversation.get_messages());
+
+        if let Some(limit) = self.client.capabilities().context_window {
+            let mut estimated = counter.count_request(&request);
+            if estimated > limit && self.context_overflow == ContextOverflow::Trim {
+                let fixed = estimated
+                    - request
+                        .messages
+                        .iter()
+                        .map(|m| counter.count_message(m))
+                        .sum::<usize>();
+                let removed = conversation.trim_to_token_budget(limit.saturating_sub(fixed), |m| {
+                    counter.count_message(m)
+                });
+                debug!(
+                    removed = removed.len(),
+                    estimated, limit, "trimmed conversation to fit context window"
+                );
+                request = build(conversation.get_messages());
+                estimated = counter.count_request(&request);
+            }
+            if estimated > limit {
+                return Err(CoreError::ContextWindowExceeded { estimated, limit });
+            }
+        }
+
+        self.chat_with_retry(&request).await
+    }
+
+    /// Replace all but the last `keep_last` messages of `conversation` with a
+    /// single system message summarizing them, returning how many were
+    /// replaced.
+    ///
+    /// The leading system and developer messages (the system prompt) are kept
+    /// as they are. If the boundary would split tool results from the
+    /// assistant message that requested them, it moves earlier so the whole
+    /// exchange is kept. Nothing is sent and 0 is returned when there is
+    /// nothing older than the kept messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the client after retries, or
+    /// [`CoreError::NoResponse`] if the model replies with an empty summary.
+    pub async fn compact_conversation(
+        &self,
+        conversation: &mut Conversation,
+        keep_last: usize,
+    ) -> Result<usize, CoreError> {
+        let messages = conversation.get_messages();
+        let start = messages
+            .iter()
+            .take_while(|m| m.role.is_instruction())
+            .count();
+        let mut boundary = messages.len().saturating_sub(keep_last).max(start);
+        while boundary > start
+            && messages
+                .get(boundary)
+                .is_some_and(|m| m.role == MessageRole::Tool)
+        {
+            boundary -= 1;
+        }
+        if boundary == start {
+            return Ok(0);
+        }
+
+        let transcript = messages[start..boundary]
+            .iter()
+            .map(|m| {
+                let mut line = format!("[{:?}]: {}", m.role, m.content);
+                for call in &m.tool_calls {
+                    let _ = write!(
+                        line,
+                        "\n  (called {} with {})",
+                        call.function.name,
+                        call.function.arguments_json()
+                    );
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let request = ChatRequest::from((
+            self.client.config(),
+            vec![Message::user(
+                conversation.id,
+                format!("{COMPACTION_PROMPT}\n\n{transcript}"),
+            )],
+        ));
+        let summary = self.chat_with_retry(&request).await?.message.content;
+        if summary.trim().is_empty() {
+            return Err(CoreError::NoResponse(
+                "model returned an empty summary".to_string(),
+            ));
+        }
+
+        let replaced = boundary - start;
+        let summary = Message::system(
+            conversation.id,
+            format!("Summary of the earlier conversation:\n{summary}"),
+        );
+        Arc::make_mut(&mut conversation.messages).splice(start..boundary, [summary]);
+        conversation.touch();
+        info!(replaced, "compacted conversation");
+        Ok(replaced)
+    }
+
+    /// The canned response a dry run returns in place of calling the client.
+    fn dry_run_response(&self, request: &ChatRequest) -> Result<ChatResponse, CoreError> {
+        let conversation_id = request
+            .messages
+            .first()
+            .map_or_else(uuid::Uuid::new_v4, |m| m.conversation_id);
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert(
+            DRY_RUN_REQUEST_KEY.to_string(),
+            serde_json::to_value(request)?,
+        );
+        if let Some(payload) = self.client.request_payload(request) {
+            metadata.insert(DRY_RUN_PAYLOAD_KEY.to_string(), payload);
+        }
+        let mut message = Message::assistant(conversation_id, "[dry run] request not sent");
+        message.metadata.clone_from(&metadata);
+        info!("dry run: request captured, not sent");
+
+        Ok(ChatResponse {
+            message,
+            model: request
+                .model
+                .clone()
+                .unwrap_or_else(|| self.client.config().model.clone()),
+            usage: None,
+            finish_reason: Some(FinishReason::Stop),
+            created_at: Utc::now(),
+            response_id: None,
+            metadata,
+            additional_choices: Vec::new(),
+        })
+    }
+
     /// Send a chat request with retry logic for transient failures.
+    ///
+    /// Delays come from the config's
+    /// [`RetryConfig::backoff`](neuromance_common::client::RetryConfig::backoff)
+    /// strategy, which also decides when to give up.
     async fn chat_with_retry(&self, request: &ChatRequest) -> Result<ChatResponse, CoreError> {
-        let mut last_error = None;
-        let config = self.client.config();
+        let backoff = self.client.config().retry_config.backoff();
 
-        for attempt in 0..=config.retry_config.max_retries {
-            match self.client.chat(request).await {
+        let mut attempt: u32 = 0;
+        loop {
+            let e = match self.client.chat(request).await {
                 Ok(response) => return Ok(response),
-                Err(e) => {
-                    if attempt < config.retry_config.max_retries && e.is_retryable() {
-                        debug!(
-                            "Request failed (attempt {}), retrying in {:?}: {}",
-                            attempt + 1,
-                            config.retry_config.initial_delay,
-                            e
-                        );
-                        last_error = Some(e);
-                        tokio::time::sleep(config.retry_config.initial_delay).await;
-                        continue;
-                    }
-                    last_error = Some(e);
-                    break;
-                }
-            }
+                Err(e) => e,
+            };
+            let delay = if e.is_retryable() {
+                backoff.next_delay(attempt, e.retry_after())
+            } else {
+                None
+            };
+            let Some(delay) = delay else {
+                return Err(CoreError::Client(e));
+            };
+            attempt += 1;
+            debug!(
+                "Request failed (attempt {}), retrying in {:?}: {}",
+                attempt, delay, e
+            );
+            tokio::time::sleep(delay).await;
         }
+    }
 
-        Err(last_error.map_or_else(
-            || CoreError::NoResponse("No response received after retries".to_string()),
-            CoreError::Client,
-        ))
+    /// Wait out the backoff before reconnecting a stream that failed before
+    /// producing any content, or return `error` once it is not retryable or
+    /// the retries are spent.
+    async fn stream_retry_backoff(
+        &self,
+        error: ClientError,
+        attempt: &mut u32,
+        cancel: &CancellationToken,
+    ) -> Result<(), CoreError> {
+        let delay = if error.is_retryable() {
+            self.client
+                .config()
+                .retry_config
+                .backoff()
+                .next_delay(*attempt, error.retry_after())
+        } else {
+            None
+        };
+        let Some(delay) = delay else {
+            return Err(CoreError::Client(error));
+        };
+        *attempt += 1;
+        debug!(
+            "Stream failed before content (attempt {}), reconnecting in {:?}: {}",
+            attempt, delay, error
+        );
+        tokio::select! {
+            biased;
+            () = cancel.cancelled() => Err(CoreError::Cancelled("stream retry".to_string())),
+            () = tokio::time::sleep(delay) => Ok(()),
+        }
     }
 
     /// Run every hook's `on_conversation_start`, recording each hook's injected
@@ -268,6 +678,48 @@ impl<C: LLMClient> Core<C> {
         Ok(stats)
     }
 
+    /// Execute the approved calls of a batch concurrently.
+    ///
+    /// Returns one entry per batch element, in order: the tool's result and
+    /// elapsed time for approved calls, `None` for the rest. A failing tool
+    /// yields its own `Err` entry and does not affect its siblings.
+    async fn execute_tool_batch(
+        &self,
+        batch: &[PendingToolCall<'_>],
+        assistant_message_id: uuid::Uuid,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<Option<ToolExecution>>, CoreError> {
+        let runs = batch.iter().map(|entry| {
+            async move {
+                if entry.approval != ToolApproval::Approved {
+                    return None;
+                }
+                info!(tool = %entry.call.function.name, "executing tool");
+                let tool_start = Instant::now();
+                // Carry the launch site down to any subagent this tool spawns:
+                // the assistant message and the specific tool call.
+                // Conversation/task ids are preserved from the enclosing scope.
+                let mut child_ctx = neuromance_common::delegation::current();
+                child_ctx.parent_message_id = Some(assistant_message_id);
+                child_ctx.parent_tool_call_id = Some(entry.call.id.clone());
+                let result = neuromance_common::delegation::scope(
+                    child_ctx,
+                    self.tool_executor
+                        .execute_approved_tool(&entry.call, entry.audit.clone()),
+                )
+                .await;
+                Some((result, tool_start.elapsed()))
+            }
+            .instrument(entry.span.clone())
+        });
+
+        tokio::select! {
+            biased;
+            () = cancel.cancelled() => Err(CoreError::Cancelled("tool execution".to_string())),
+            results = join_all(runs) => Ok(results),
+        }
+    }
+
     /// Run every hook's `on_completion` observer.
     async fn hooks_completion(
         &self,
@@ -287,6 +739,11 @@ impl<C: LLMClient> Core<C> {
     /// carrying the final message history. Errors surface inline as `Err`
     /// items and terminate the stream.
     ///
+    /// Cancelling `cancel` while a streamed reply is arriving stops reading
+    /// it: the text so far becomes the last assistant message, marked with
+    /// [`FINISH_REASON_KEY`], and the stream completes. Cancelling at any
+    /// other point ends the stream with [`CoreError::Cancelled`].
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -319,6 +776,11 @@ impl<C: LLMClient> Core<C> {
             // `prompt_tokens` from each turn is the whole context sent that turn,
             // so the turn-over-turn delta shows how fast the conversation grows.
             let mut prev_prompt_tokens: u32 = 0;
+            // Summed `total_tokens` across turns, checked against `token_budget`.
+            let mut tokens_used: u32 = 0;
+            // Reply text so far while continuing a length-truncated response.
+            let mut continuations: u32 = 0;
+            let mut partial_reply = String::new();
             let start_time = Instant::now();
 
             // The conversation id is stable across the run; derive it from the
@@ -352,7 +814,18 @@ impl<C: LLMClient> Core<C> {
 
                 let turn_ctx = HookContext::new(conversation_id, turn_count);
 
-                let mut request = ChatRequest::from((self.client.config(), ledger.snapshot()))
+                let mut messages = self.system_prompt_strategy.apply(ledger.snapshot());
+                if continuations > 0 {
+                    messages = messages
+                        .iter()
+                        .cloned()
+                        .chain([
+                            Message::assistant(conversation_id, partial_reply.clone()),
+                            Message::user(conversation_id, CONTINUE_PROMPT),
+                        ])
+                        .collect();
+                }
+                let mut request = ChatRequest::from((self.client.config(), messages))
                     .with_tools(self.tool_executor.get_all_tools())
                     .with_tool_choice(self.tool_choice.clone());
                 request = request.with_thinking_mode(self.thinking);
@@ -382,8 +855,15 @@ impl<C: LLMClient> Core<C> {
                     trace!(target: "neuromance::wire", %body, "chat request body");
                 }
 
-                let response = if self.streaming {
-                    let mut inner = self.client.chat_stream(&request).await?;
+                let mut response = if self.dry_run {
+                    self.dry_run_response(&request)?
+                } else if self.streaming {
+                    // Reconnect on retryable errors until the first content
+                    // arrives; after that a retry would repeat output the
+                    // caller has already seen.
+                    let mut inner = None;
+                    let mut attempt: u32 = 0;
+                    let mut emitted = false;
                     let mut accumulated_content = String::with_capacity(1024);
                     let mut response_metadata = None;
                     let mut role = None;
@@ -397,13 +877,45 @@ impl<C: LLMClient> Core<C> {
                     let mut chunks_seen: u32 = 0;
 
                     loop {
-                        let next_chunk: Result<_, CoreError> = tokio::select! {
+                        if inner.is_none() {
+                            match self.client.chat_stream(&request).await {
+                                Ok(stream) => inner = Some(stream),
+                                Err(e) => {
+                                    self.stream_retry_backoff(e, &mut attempt, &cancel).await?;
+                                    continue;
+                                }
+                            }
+                        }
+                        let Some(stream) = inner.as_mut() else { continue };
+                        let next_chunk = tokio::select! {
                             biased;
-                            () = cancel.cancelled() => Err(CoreError::Cancelled("stream chunk".to_string())),
-                            next = inner.next() => Ok(next),
+                            () = cancel.cancelled() => None,
+                            next = stream.next() => Some(next),
+                        };
+                        // Once output has streamed, cancelling keeps it as a
+                        // partial reply instead of discarding the turn.
+                        let Some(next_chunk) = next_chunk else {
+                            if !emitted {
+                                Err(CoreError::Cancelled("stream chunk".to_string()))?;
+                            }
+                            info!(turn = turn_number, "stream cancelled, keeping partial reply");
+                            finish_reason = Some(FinishReason::Cancelled);
+                            break;
+                        };
+                        let Some(chunk_result) = next_chunk else { break };
+                        let chunk = match chunk_result {
+                            Ok(chunk) => chunk,
+                            Err(e) if !emitted => {
+                                self.stream_retry_backoff(e, &mut attempt, &cancel).await?;
+                                inner = None;
+                                role = None;
+                                finish_reason = None;
+                                accumulated_usage = None;
+                                response_metadata = None;
+                                continue;
+                            }
+                            Err(e) => Err(e)?,
                         };
-                        let Some(chunk_result) = next_chunk? else { break };
-                        let chunk = chunk_result?;
                         chunks_seen = chunks_seen.saturating_add(1);
 
                         if first_chunk_at.is_none() {
@@ -421,12 +933,15 @@ impl<C: LLMClient> Core<C> {
                         }
 
                         if let Some(ref content) = chunk.delta_content {
+                            emitted = true;
                             accumulated_content.push_str(content);
                             yield CoreEvent::Delta(content.clone());
                         }
 
                         if let Some(ref reasoning) = chunk.delta_reasoning_content {
+                            emitted = true;
                             reasoning_bytes = reasoning_bytes.saturating_add(reasoning.len());
+                            yield CoreEvent::ReasoningDelta(reasoning.clone());
                         }
 
                         if role.is_none() {
@@ -434,6 +949,7 @@ impl<C: LLMClient> Core<C> {
                         }
 
                         if let Some(ref delta_tool_calls) = chunk.delta_tool_calls {
+                            emitted = true;
                             let delta_count = delta_tool_calls.len();
                             tool_call_deltas_seen = tool_call_deltas_seen
                                 .saturating_add(u32::try_from(delta_count).unwrap_or(u32::MAX));
@@ -514,6 +1030,8 @@ impl<C: LLMClient> Core<C> {
                         model: None,
                         provider: None,
                         usage: None,
+                        parent_id: None,
+                        content_parts: Vec::new(),
                     };
 
                     ChatResponse {
@@ -524,6 +1042,7 @@ impl<C: LLMClient> Core<C> {
                         created_at: last_chunk.created_at,
                         response_id: last_chunk.response_id,
                         metadata: std::collections::HashMap::new(),
+                        additional_choices: Vec::new(),
                     }
                 } else {
                     let outcome: Result<ChatResponse, CoreError> = tokio::select! {
@@ -534,12 +1053,23 @@ impl<C: LLMClient> Core<C> {
                     outcome?
                 };
 
+                // Tool calls cut off mid-stream cannot be run.
+                let cancelled = response.finish_reason == Some(FinishReason::Cancelled);
+                if cancelled {
+                    response.message.tool_calls.clear();
+                    response.message.metadata.insert(
+                        FINISH_REASON_KEY.to_string(),
+                        serde_json::json!(FinishReason::Cancelled),
+                    );
+                }
+
                 if tracing::enabled!(target: "neuromance::wire", tracing::Level::TRACE) {
                     let body = serde_json::to_string(&response)?;
                     trace!(target: "neuromance::wire", %body, "assistant response body");
                 }
 
                 if let Some(ref usage) = response.usage {
+                    tokens_used = tokens_used.saturating_add(usage.total_tokens);
                     yield CoreEvent::Usage(usage.clone());
                     self.hooks_usage(&turn_ctx, usage, &cancel).await?;
                 }
@@ -548,7 +1078,7 @@ impl<C: LLMClient> Core<C> {
                 let turn_duration_ms =
                     u64::try_from(turn_duration.as_millis()).unwrap_or(u64::MAX);
                 let conversation_id = response.message.conversation_id;
-                let tool_calls = response.message.tool_calls.clone();
+                let mut tool_calls = response.message.tool_calls.clone();
                 let tool_calls_count = tool_calls.len();
                 let finish_label = response
                     .finish_reason
@@ -610,7 +1140,33 @@ impl<C: LLMClient> Core<C> {
                     "model" => model_label,
                 )
                 .increment(u64::from(completion_tokens));
+
+                if tool_calls.is_empty()
+                    && response.finish_reason == Some(FinishReason::Length)
+                    && continuations < self.max_continuations
+                {
+                    continuations += 1;
+                    partial_reply.push_str(&response.message.content);
+                    info!(continuation = continuations, "reply hit the length limit, continuing");
+                    continue;
+                }
+                if continuations > 0 {
+                    partial_reply.push_str(&response.message.content);
+                    response.message.content = std::mem::take(&mut partial_reply);
+                    continuations = 0;
+                }
+
                 let mut assistant_message = response.message;
+                if let Some(marker) = self.completion_marker.as_deref()
+                    && strip_completion_marker(&mut assistant_message, marker)
+                {
+                    info!(
+                        turn = turn_number,
+                        dropped_tool_calls = tool_calls_count,
+                        "completion marker found; stopping",
+                    );
+                    tool_calls.clear();
+                }
                 assistant_message.model = Some(response.model);
                 assistant_message.provider = Some(self.client.config().provider.clone());
                 assistant_message.usage = response.usage;
@@ -619,6 +1175,13 @@ impl<C: LLMClient> Core<C> {
                 let assistant_message_id = assistant_message.id;
                 ledger.append(EditSource::model(), [assistant_message]);
 
+                if cancelled {
+                    // Hooks share the cancelled token, so none run for this turn.
+                    info!(turns = turn_number, "chat cancelled");
+                    yield CoreEvent::Completed(ledger.into_messages());
+                    return;
+                }
+
                 // Hooks observe the assistant message before tool execution
                 // (e.g. persistence records it so a crashed run keeps its prefix).
                 self.hooks_messages(&turn_ctx, ledger.messages(), &cancel).await?;
@@ -653,7 +1216,12 @@ impl<C: LLMClient> Core<C> {
                     return;
                 }
 
-                for tool_call in &tool_calls {
+                // In parallel mode every call is reviewed up front and the
+                // approved ones run as a single concurrent batch; otherwise each
+                // call is settled before the next is reviewed, so an interactive
+                // approver sees earlier results first.
+                let mut pending: Vec<PendingToolCall<'_>> = Vec::with_capacity(tool_calls_count);
+                for (position, tool_call) in tool_calls.iter().enumerate() {
                     let tool_name = &tool_call.function.name;
                     let call_id = &tool_call.id;
                     let tool_span = info_span!(
@@ -661,9 +1229,10 @@ impl<C: LLMClient> Core<C> {
                         tool = %tool_name,
                         call_id = %call_id,
                     );
-                    let _tool_enter = tool_span.enter();
+                    let tool_enter = tool_span.enter();
                     info!(tool = %tool_name, call_id = %call_id, "tool call requested");
                     debug!(arguments = ?tool_call.function.arguments, "tool arguments");
+                    yield CoreEvent::ToolCallRequested(tool_call.clone());
 
                     let is_auto_approved = self.auto_approve_tools
                         || self.tool_executor.is_tool_auto_approved(tool_name);
@@ -693,125 +1262,182 @@ impl<C: LLMClient> Core<C> {
 
                     debug!(approval = ?approval, "tool approval decided");
 
-                    // Captured from an executed tool so `after_tool` hooks can
-                    // inject follow-on context once the result message is in place.
-                    let mut tool_outcome: Option<(String, bool)> = None;
-
-                    match approval {
-                        ToolApproval::Approved => {
-                            info!(tool = %tool_name, "executing tool");
-                            let tool_start = Instant::now();
-                            // Carry the launch site down to any subagent this tool
-                            // spawns: the assistant message and the specific tool
-                            // call. Conversation/task ids are preserved from the
-                            // enclosing scope.
-                            let mut child_ctx = neuromance_common::delegation::current();
-                            child_ctx.parent_message_id = Some(assistant_message_id);
-                            child_ctx.parent_tool_call_id = Some(tool_call.id.clone());
-                            let exec_outcome: Result<Result<String, ToolExecutorError>, CoreError> = tokio::select! {
-                                biased;
-                                () = cancel.cancelled() => Err(CoreError::Cancelled("tool execution".to_string())),
-                                r = neuromance_common::delegation::scope(
-                                    child_ctx,
-                                    self.tool_executor.execute_tool(tool_call),
-                                ) => Ok(r),
+                    if approval == ToolApproval::Quit {
+                        debug!("user quit during tool approval");
+                        Err(CoreError::UserQuit(
+                            "User quit during tool approval".to_string(),
+                        ))?;
+                    }
+
+                    // Rewritten arguments are applied here so execution and
+                    // after-tool hooks see the call that actually runs.
+                    let (call, approval, audit) = match approval {
+                        ToolApproval::ApprovedWithArguments(arguments) => {
+                            debug!("tool arguments rewritten by approver");
+                            // The assistant message is rewritten too, so the
+                            // history sent on the next turn matches what ran.
+                            ledger.update(
+                                EditSource::core(),
+                                assistant_message_id,
+                                Some(serde_json::json!({
+                                    "tool_call_id": call_id,
+                                    "original_arguments": tool_call.function.arguments,
+                                })),
+                                |message| {
+                                    if let Some(call) =
+                                        message.tool_calls.iter_mut().find(|c| &c.id == call_id)
+                                    {
+                                        call.function.arguments.clone_from(&arguments);
+                                    }
+                                },
+                            );
+                            let mut modified = tool_call.clone();
+                            modified.function.arguments = arguments;
+                            let audit = AuditApproval::ApprovedWithArguments {
+                                original_arguments: tool_call.function.arguments_json().to_owned(),
                             };
-                            let tool_elapsed = tool_start.elapsed();
-                            let tool_duration_ms =
-                                u64::try_from(tool_elapsed.as_millis()).unwrap_or(u64::MAX);
-                            histogram!(
-                                "neuromance_tool_duration_seconds",
-                                "tool" => tool_name.clone(),
-                            )
-                            .record(tool_elapsed.as_secs_f64());
-                            match exec_outcome? {
-                                Ok(result) => {
-                                    let bytes = result.len();
-                                    info!(
-                                        tool = %tool_name,
-                                        duration_ms = tool_duration_ms,
-                                        bytes,
-                                        "tool call succeeded",
-                                    );
-                                    counter!(
-                                        "neuromance_tool_calls_total",
-                                        "tool" => tool_name.clone(),
-                                        "outcome" => "success",
-                                    )
-                                    .increment(1);
-                                    yield CoreEvent::ToolResult {
-                                        name: tool_name.clone(),
-                                        result: result.clone(),
-                                        success: true,
-                                    };
-                                    let tool_message = Message::tool(
-                                        conversation_id,
-                                        result.clone(),
-                                        tool_call.id.clone(),
-                                        tool_call.function.name.clone(),
-                                    )
-                                    .map_err(|e| CoreError::ToolError(e.to_string()))?;
-                                    ledger.append(EditSource::tool(), [tool_message]);
-                                    tool_outcome = Some((result, true));
-                                }
-                                Err(e) => {
-                                    info!(
-                                        tool = %tool_name,
-                                        duration_ms = tool_duration_ms,
-                                        error = %e,
-                                        "tool call failed",
-                                    );
-                                    counter!(
-                                        "neuromance_tool_calls_total",
-                                        "tool" => tool_name.clone(),
-                                        "outcome" => "failure",
-                                    )
-                                    .increment(1);
-                                    let error_msg = format!("Tool execution failed: {e}");
-                                    yield CoreEvent::ToolResult {
-                                        name: tool_name.clone(),
-                                        result: error_msg.clone(),
-                                        success: false,
-                                    };
-                                    let error_message = Message::tool(
-                                        conversation_id,
-                                        error_msg.clone(),
-                                        tool_call.id.clone(),
-                                        tool_call.function.name.clone(),
-                                    )
-                                    .map_err(|e| CoreError::ToolError(e.to_string()))?;
-                                    ledger.append(EditSource::tool(), [error_message]);
-                                    tool_outcome = Some((error_msg, false));
-                                }
-                            }
-                        }
-                        ToolApproval::Denied(reason) => {
-                            info!(tool = %tool_name, reason = %reason, "tool call denied");
-                            let denial_message = Message::tool(
-                                conversation_id,
-                                format!("Tool execution denied: {reason}"),
-                                tool_call.id.clone(),
-                                tool_call.function.name.clone(),
-                            )
-                            .map_err(|e| CoreError::ToolError(e.to_string()))?;
-                            ledger.append(EditSource::core(), [denial_message]);
+                            (Cow::Owned(modified), ToolApproval::Approved, audit)
                         }
-                        ToolApproval::Quit => {
-                            debug!("user quit during tool approval");
-                            Err(CoreError::UserQuit(
-                                "User quit during tool approval".to_string(),
-                            ))?;
+                        approval => {
+                            let audit = if is_auto_approved {
+                                AuditApproval::AutoApproved
+                            } else {
+                                AuditApproval::Approved
+                            };
+                            (Cow::Borrowed(tool_call), approval, audit)
                         }
+                    };
+
+                    drop(tool_enter);
+                    pending.push(PendingToolCall {
+                        call,
+                        approval,
+                        audit,
+                        span: tool_span,
+                    });
+                    if self.parallel_tool_execution && position + 1 < tool_calls_count {
+                        continue;
                     }
 
-                    // After-tool hooks inject follow-on context (e.g. a rule
-                    // file keyed to the touched path) right after the result.
-                    if let Some((result, success)) = tool_outcome {
-                        let injected = self
-                            .hooks_after_tool(&turn_ctx, tool_call, &result, success, &cancel)
-                            .await?;
-                        for (source, msgs) in injected {
-                            ledger.append(source, msgs);
+                    let batch = std::mem::take(&mut pending);
+                    let executed = self
+                        .execute_tool_batch(&batch, assistant_message_id, &cancel)
+                        .await?;
+
+                    for (entry, execution) in batch.into_iter().zip(executed) {
+                        let tool_call = entry.call;
+                        let tool_name = &tool_call.function.name;
+                        let _tool_enter = entry.span.enter();
+
+                        // Captured from an executed tool so `after_tool` hooks can
+                        // inject follow-on context once the result message is in place.
+                        let mut tool_outcome: Option<(String, bool)> = None;
+
+                        match (entry.approval, execution) {
+                            (ToolApproval::Approved, Some((result, tool_elapsed))) => {
+                                let tool_duration_ms =
+                                    u64::try_from(tool_elapsed.as_millis()).unwrap_or(u64::MAX);
+                                histogram!(
+                                    "neuromance_tool_duration_seconds",
+                                    "tool" => tool_name.clone(),
+                                )
+                                .record(tool_elapsed.as_secs_f64());
+                                match result {
+                                    Ok(result) => {
+                                        let bytes = result.len();
+                                        info!(
+                                            tool = %tool_name,
+                                            duration_ms = tool_duration_ms,
+                                            bytes,
+                                            "tool call succeeded",
+                                        );
+                                        let result = self.tool_executor.limit_tool_result(result);
+                                        counter!(
+                                            "neuromance_tool_calls_total",
+                                            "tool" => tool_name.clone(),
+                                            "outcome" => "success",
+                                        )
+                                        .increment(1);
+                                        yield CoreEvent::ToolResult {
+                                            name: tool_name.clone(),
+                                            result: result.clone(),
+                                            success: true,
+                                        };
+                                        let tool_message = Message::tool(
+                                            conversation_id,
+                                            result.clone(),
+                                            tool_call.id.clone(),
+                                            tool_call.function.name.clone(),
+                                        )
+                                        .map_err(|e| CoreError::ToolError(e.to_string()))?;
+                                        ledger.append(EditSource::tool(), [tool_message]);
+                                        tool_outcome = Some((result, true));
+                                    }
+                                    Err(e) => {
+                                        info!(
+                                            tool = %tool_name,
+                                            duration_ms = tool_duration_ms,
+                                            error = %e,
+                                            "tool call failed",
+                                        );
+                                        counter!(
+                                            "neuromance_tool_calls_total",
+                                            "tool" => tool_name.clone(),
+                                            "outcome" => "failure",
+                                        )
+                                        .increment(1);
+                                        let error_msg = self
+                                            .tool_executor
+                                            .limit_tool_result(format!("Tool execution failed: {e}"));
+                                        yield CoreEvent::ToolResult {
+                                            name: tool_name.clone(),
+                                            result: error_msg.clone(),
+                                            success: false,
+                                        };
+                                        let error_message = Message::tool(
+                                            conversation_id,
+                                            error_msg.clone(),
+                                            tool_call.id.clone(),
+                                            tool_call.function.name.clone(),
+                                        )
+                                        .map_err(|e| CoreError::ToolError(e.to_string()))?;
+                                        ledger.append(EditSource::tool(), [error_message]);
+                                        tool_outcome = Some((error_msg, false));
+                                    }
+                                }
+                            }
+                            (ToolApproval::Denied(reason), _) => {
+                                info!(tool = %tool_name, reason = %reason, "tool call denied");
+                                self.tool_executor.audit_denied(&tool_call, &reason).await;
+                                let denial_message = Message::tool(
+                                    conversation_id,
+                                    format!("Tool execution denied: {reason}"),
+                                    tool_call.id.clone(),
+                                    tool_call.function.name.clone(),
+                                )
+                                .map_err(|e| CoreError::ToolError(e.to_string()))?;
+                                ledger.append(EditSource::core(), [denial_message]);
+                            }
+                            // Quit is rejected and rewritten arguments are applied
+                            // before batching, and every approved call in a batch
+                            // is executed.
+                            (
+                                ToolApproval::Approved
+                                | ToolApproval::ApprovedWithArguments(_)
+                                | ToolApproval::Quit,
+                                _,
+                            ) => {}
+                        }
+
+                        // After-tool hooks inject follow-on context (e.g. a rule
+                        // file keyed to the touched path) right after the result.
+                        if let Some((result, success)) = tool_outcome {
+                            let injected = self
+                                .hooks_after_tool(&turn_ctx, &tool_call, &result, success, &cancel)
+                                .await?;
+                            for (source, msgs) in injected {
+                                ledger.append(source, msgs);
+                            }
                         }
                     }
                 }
@@ -840,6 +1466,33 @@ impl<C: LLMClient> Core<C> {
 
                 turn_count += 1;
 
+                if let Some(budget) = self.token_budget
+                    && tokens_used > budget
+                {
+                    info!(
+                        turns = turn_count,
+                        tokens_used,
+                        token_budget = budget,
+                        "token budget exceeded; stopping",
+                    );
+                    let mut note = Message::assistant(
+                        conversation_id,
+                        format!(
+                            "Stopped: token budget exceeded ({tokens_used} tokens used, \
+                             budget {budget})."
+                        ),
+                    );
+                    note.metadata.insert(
+                        FINISH_REASON_KEY.to_string(),
+                        serde_json::json!(FinishReason::Cancelled),
+                    );
+                    ledger.append(EditSource::core(), [note]);
+                    self.hooks_messages(&turn_ctx, ledger.messages(), &cancel).await?;
+                    self.hooks_completion(&turn_ctx, ledger.messages(), &cancel).await?;
+                    yield CoreEvent::Completed(ledger.into_messages());
+                    return;
+                }
+
                 if let Some(max) = self.max_turns
                     && turn_count >= max
                 {
@@ -855,7 +1508,7 @@ impl<C: LLMClient> Core<C> {
     /// returns the final message history along with [`RunStats`] aggregated
     /// over the run.
     ///
-    /// When no [`Core::with_tool_approval_callback`] is set and a non-auto-approved
+    /// When no [`Core::with_approval_handler`] is set and a non-auto-approved
     /// tool is requested, the yielded [`CoreEvent::ApprovalRequest`] is answered
     /// with `Denied("No approval mechanism configured")`.
     ///
@@ -881,6 +1534,8 @@ impl<C: LLMClient> Core<C> {
                     ));
                 }
                 CoreEvent::Delta(_)
+                | CoreEvent::ReasoningDelta(_)
+                | CoreEvent::ToolCallRequested(_)
                 | CoreEvent::ToolResult { .. }
                 | CoreEvent::Usage(_)
                 | CoreEvent::Compaction { .. } => {}
@@ -892,6 +1547,21 @@ impl<C: LLMClient> Core<C> {
     }
 }
 
+/// A reviewed tool call waiting to be executed and recorded.
+struct PendingToolCall<'a> {
+    /// The call to run: the model's own, or a copy with approver-supplied
+    /// arguments.
+    call: Cow<'a, ToolCall>,
+    approval: ToolApproval,
+    /// How an approved call came to run, for the audit trail. Denials are
+    /// audited separately through `ToolExecutor::audit_denied`.
+    audit: AuditApproval,
+    span: tracing::Span,
+}
+
+/// Result of executing one tool call, with its wall-clock duration.
+type ToolExecution = (Result<String, ToolExecutorError>, Duration);
+
 /// Await a hook future under cancellation, mapping its error to
 /// [`CoreError::Hook`] with the hook's name for context.
 async fn run_hook<T>(
@@ -909,6 +1579,18 @@ async fn run_hook<T>(
     }
 }
 
+/// Remove every occurrence of `marker` from `message`'s content, trimming the
+/// whitespace around it, and drop its tool calls. Returns whether the marker
+/// was present; the message is untouched otherwise.
+fn strip_completion_marker(message: &mut Message, marker: &str) -> bool {
+    if marker.is_empty() || !message.content.contains(marker) {
+        return false;
+    }
+    message.content = message.content.replace(marker, "").trim().to_string();
+    message.tool_calls.clear();
+    true
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
@@ -917,7 +1599,7 @@ mod tests {
     use super::*;
     use async_trait::async_trait;
     use neuromance_client::chat_completions::ChatCompletionsClient;
-    use neuromance_common::client::Config;
+    use neuromance_common::client::{ChatChunk, Config};
     use neuromance_common::context::Operation;
     use neuromance_common::hook::{HookOutcome, TurnEnd};
 
@@ -1043,81 +1725,659 @@ mod tests {
         }
     }
 
-    /// Returns a plain assistant reply with no tool calls, so a run reaches the
-    /// no-tool completion path in a single turn.
-    struct HugeUsageClient {
+    /// [`LLMClient`] that answers each request with the next queued reply and
+    /// records every request it receives. The last reply repeats once the
+    /// queue runs dry, and replies are stamped with the request's conversation
+    /// id. Streaming requests take the next queued chunk script instead.
+    struct ScriptedClient {
         config: Config,
+        capabilities: neuromance_common::Capabilities,
+        replies: std::sync::Mutex<std::collections::VecDeque<ChatResponse>>,
+        streams: std::sync::Mutex<std::collections::VecDeque<Vec<Result<ChatChunk, ClientError>>>>,
+        requests: std::sync::Mutex<Vec<ChatRequest>>,
     }
 
-    #[async_trait::async_trait]
-    impl LLMClient for HugeUsageClient {
+    impl ScriptedClient {
+        fn new(replies: impl IntoIterator<Item = ChatResponse>) -> Self {
+            Self {
+                config: Config::new("mock", "mock-model"),
+                capabilities: neuromance_common::Capabilities::default(),
+                replies: std::sync::Mutex::new(replies.into_iter().collect()),
+                streams: std::sync::Mutex::default(),
+                requests: std::sync::Mutex::default(),
+            }
+        }
+
+        /// A client whose `n`th stream yields the `n`th chunk script.
+        fn streaming(
+            streams: impl IntoIterator<Item = Vec<Result<ChatChunk, ClientError>>>,
+        ) -> Self {
+            let client = Self::new([]);
+            *client.streams.lock().unwrap() = streams.into_iter().collect();
+            client
+        }
+
+        fn with_context_window(mut self, tokens: usize) -> Self {
+            self.capabilities.context_window = Some(tokens);
+            self
+        }
+
+        fn requests(&self) -> Vec<ChatRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl LLMClient for ScriptedClient {
         fn config(&self) -> &Config {
             &self.config
         }
 
-        async fn chat(
-            &self,
-            request: &ChatRequest,
-        ) -> Result<ChatResponse, neuromance_client::ClientError> {
-            let conv_id = request
-                .messages
-                .first()
-                .map_or_else(uuid::Uuid::new_v4, |m| m.conversation_id);
-            Ok(ChatResponse {
-                message: Message::assistant(conv_id, "summary or reply"),
-                model: "mock-model".to_string(),
-                usage: Some(neuromance_common::client::Usage {
-                    prompt_tokens: 200_000,
-                    completion_tokens: 10,
-                    total_tokens: 200_010,
-                    cost: None,
-                    input_tokens_details: None,
-                    output_tokens_details: None,
-                }),
-                finish_reason: None,
-                created_at: chrono::Utc::now(),
-                response_id: None,
-                metadata: std::collections::HashMap::new(),
-            })
+        async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
+            self.requests.lock().unwrap().push(request.clone());
+            let mut replies = self.replies.lock().unwrap();
+            let mut reply = if replies.len() > 1 {
+                replies.pop_front()
+            } else {
+                replies.front().cloned()
+            }
+            .ok_or_else(|| ClientError::ConfigurationError("no scripted reply".to_string()))?;
+            if let Some(first) = request.messages.first() {
+                reply.message.conversation_id = first.conversation_id;
+            }
+            Ok(reply)
         }
 
         async fn chat_stream(
             &self,
-            _request: &ChatRequest,
+            request: &ChatRequest,
         ) -> Result<
-            std::pin::Pin<
-                Box<
-                    dyn futures::Stream<
-                            Item = Result<
-                                neuromance_common::client::ChatChunk,
-                                neuromance_client::ClientError,
-                            >,
-                        > + Send,
-                >,
-            >,
-            neuromance_client::ClientError,
+            std::pin::Pin<Box<dyn Stream<Item = Result<ChatChunk, ClientError>> + Send>>,
+            ClientError,
         > {
-            Ok(Box::pin(futures::stream::pending()))
+            self.requests.lock().unwrap().push(request.clone());
+            let chunks = self.streams.lock().unwrap().pop_front();
+            Ok(chunks.map_or_else(
+                || Box::pin(futures::stream::pending()) as _,
+                |chunks| Box::pin(futures::stream::iter(chunks)) as _,
+            ))
         }
 
         fn supports_tools(&self) -> bool {
-            false
+            true
         }
 
         fn supports_streaming(&self) -> bool {
-            false
+            true
+        }
+
+        fn capabilities(&self) -> neuromance_common::Capabilities {
+            self.capabilities
         }
     }
 
+    /// A scripted reply; [`ScriptedClient`] fills in the conversation id.
+    fn response(
+        message: Message,
+        finish_reason: Option<FinishReason>,
+        usage: Option<Usage>,
+    ) -> ChatResponse {
+        ChatResponse {
+            message,
+            model: "mock-model".to_string(),
+            usage,
+            finish_reason,
+            created_at: chrono::Utc::now(),
+            response_id: None,
+            metadata: std::collections::HashMap::new(),
+            additional_choices: Vec::new(),
+        }
+    }
+
+    /// An assistant message for a scripted reply.
+    fn assistant(content: &str) -> Message {
+        Message::assistant(uuid::Uuid::nil(), content)
+    }
+
+    fn usage(prompt_tokens: u32, completion_tokens: u32) -> Usage {
+        Usage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+            cost: None,
+            input_tokens_details: None,
+            output_tokens_details: None,
+        }
+    }
+
+    /// Replies "ok" in a 100-token context window.
+    fn small_window_client() -> ScriptedClient {
+        ScriptedClient::new([response(assistant("ok"), Some(FinishReason::Stop), None)])
+            .with_context_window(100)
+    }
+
+    /// Replies in two pieces: the first stops at the length limit, the second
+    /// finishes.
+    fn truncating_client() -> ScriptedClient {
+        ScriptedClient::new([
+            response(assistant("The answer is"), Some(FinishReason::Length), None),
+            response(assistant(" forty-two."), Some(FinishReason::Stop), None),
+        ])
+    }
+
+    /// A system prompt followed by `turns` user messages of ~25 tokens each.
+    fn long_conversation(turns: usize) -> Conversation {
+        let mut conversation = Conversation::new();
+        let id = conversation.id;
+        conversation
+            .add_message(Message::system(id, "Be brief."))
+            .unwrap();
+        for i in 0..turns {
+            conversation
+                .add_message(Message::user(id, format!("{i:02} {}", "x".repeat(80))))
+                .unwrap();
+        }
+        conversation
+    }
+
+    #[tokio::test]
+    async fn test_send_guarded_trims_oldest_messages_to_fit() {
+        let core = Core::new(small_window_client());
+        let mut conversation = long_conversation(6);
+
+        let response = core.send_guarded(&mut conversation).await.unwrap();
+        assert_eq!(response.message.content, "ok");
+
+        let kept = conversation.get_messages();
+        assert_eq!(kept[0].role, MessageRole::System);
+        assert!(kept.len() < 7);
+        assert!(kept.last().unwrap().content.starts_with("05"));
+
+        let sent = core.client.requests();
+        assert_eq!(sent[0].messages.len(), kept.len());
+        assert!(HeuristicTokenCounter.count_request(&sent[0]) <= 100);
+    }
+
+    #[tokio::test]
+    async fn test_send_guarded_errors_without_sending_when_configured() {
+        let core = Core::new(small_window_client()).with_context_overflow(ContextOverflow::Error);
+        let mut conversation = long_conversation(6);
+
+        let result = core.send_guarded(&mut conversation).await;
+        assert!(matches!(
+            result,
+            Err(CoreError::ContextWindowExceeded { estimated, limit: 100 }) if estimated > 100
+        ));
+        assert_eq!(conversation.get_messages().len(), 7);
+        assert!(core.client.requests().is_empty());
+
+        // A request that fits is sent as-is.
+        let mut short = long_conversation(1);
+        core.send_guarded(&mut short).await.unwrap();
+        assert_eq!(core.client.requests()[0].messages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_compact_conversation_summarizes_older_messages() {
+        let core = Core::new(small_window_client());
+        let mut conversation = Conversation::new();
+        let id = conversation.id;
+        let lookup = ToolCall::new("lookup", r#"{"q":"rust"}"#);
+        let call_id = lookup.id.clone();
+        for message in [
+            Message::system(id, "Be brief."),
+            Message::user(id, "My name is Ada."),
+            Message::assistant(id, "Hello Ada."),
+            Message::user(id, "Look up rust."),
+            Message::assistant(id, "")
+                .with_tool_calls(vec![lookup])
+                .unwrap(),
+            Message::tool(id, "a language", call_id, "lookup".to_string()).unwrap(),
+            Message::assistant(id, "Rust is a language."),
+        ] {
+            conversation.add_message(message).unwrap();
+        }
+
+        // Keeping two would split the tool result from its call, so the call
+        // is kept too.
+        let replaced = core
+            .compact_conversation(&mut conversation, 2)
+            .await
+            .unwrap();
+        assert_eq!(replaced, 3);
+
+        let messages = conversation.get_messages();
+        let roles: Vec<MessageRole> = messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            [
+                MessageRole::System,
+                MessageRole::System,
+                MessageRole::Assistant,
+                MessageRole::Tool,
+                MessageRole::Assistant,
+            ]
+        );
+        assert_eq!(messages[0].content, "Be brief.");
+        assert!(messages[1].content.ends_with("ok"));
+        assert_eq!(
+            messages[2].tool_calls[0].id,
+            messages[3].tool_call_id.clone().unwrap()
+        );
+
+        let sent = core.client.requests();
+        let prompt = &sent[0].messages[0].content;
+        assert!(prompt.contains("My name is Ada.") && prompt.contains("Look up rust."));
+        assert!(!prompt.contains("Rust is a language."));
+
+        // Nothing older than the kept messages: no request is made.
+        assert_eq!(
+            core.compact_conversation(&mut conversation, 10)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(core.client.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_length_truncated_reply_is_continued_and_joined() {
+        let mut core = Core::new(truncating_client()).with_max_continuations(2);
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "What is the answer?")];
+        let (history, _) = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await
+            .unwrap();
+
+        let roles: Vec<MessageRole> = history.iter().map(|m| m.role).collect();
+        assert_eq!(roles, [MessageRole::User, MessageRole::Assistant]);
+        assert_eq!(history[1].content, "The answer is forty-two.");
+
+        let requests = core.client.requests();
+        assert_eq!(requests.len(), 2);
+        let continuation = &requests[1].messages;
+        assert_eq!(continuation.len(), 3);
+        assert_eq!(continuation[1].content, "The answer is");
+        assert_eq!(continuation[2].content, CONTINUE_PROMPT);
+    }
+
+    #[tokio::test]
+    async fn test_length_truncated_reply_kept_when_continuation_disabled() {
+        let mut core = Core::new(truncating_client());
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "What is the answer?")];
+        let (history, _) = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(history.last().unwrap().content, "The answer is");
+        assert_eq!(core.client.requests().len(), 1);
+    }
+
+    /// Auto-approved tool that sleeps, then echoes its `id` argument.
+    struct SleepTool(Duration);
+
+    #[async_trait]
+    impl neuromance_tools::ToolImplementation for SleepTool {
+        fn get_definition(&self) -> neuromance_common::tools::Tool {
+            neuromance_common::tools::Tool {
+                r#type: "function".to_string(),
+                function: neuromance_common::tools::Function {
+                    name: "sleep".to_string(),
+                    description: "Sleep, then echo id".to_string(),
+                    parameters: serde_json::json!({"type": "object"}),
+                },
+            }
+        }
+
+        async fn execute(
+            &self,
+            args: &serde_json::Value,
+        ) -> Result<String, neuromance_tools::ToolError> {
+            tokio::time::sleep(self.0).await;
+            Ok(args["id"].as_str().unwrap_or_default().to_string())
+        }
+
+        fn is_auto_approved(&self) -> bool {
+            true
+        }
+    }
+
+    /// With `parallel_tool_execution`, a turn's tool calls overlap in time, the
+    /// tool messages keep the requested order, and a failing call yields its
+    /// own error message without aborting its siblings.
+    #[tokio::test]
+    async fn test_parallel_tool_execution_overlaps_and_preserves_order() {
+        let delay = Duration::from_millis(200);
+        let calls = vec![
+            ToolCall::new("sleep", r#"{"id":"a"}"#),
+            ToolCall::new("missing", "{}"),
+            ToolCall::new("sleep", r#"{"id":"b"}"#),
+            ToolCall::new("sleep", r#"{"id":"c"}"#),
+        ];
+        let mut core = scripted_core(calls.clone()).with_parallel_tool_execution();
+        core.auto_approve_tools = true;
+        core.tool_executor.add_tool(SleepTool(delay));
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "go")];
+        let start = Instant::now();
+        let (history, _) = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+
+        assert!(
+            elapsed < delay * 3,
+            "parallel run took {elapsed:?}, serial sum is {:?}",
+            delay * 3
+        );
+        let tool_messages: Vec<&Message> = history
+            .iter()
+            .filter(|m| m.role == MessageRole::Tool)
+            .collect();
+        let ids: Vec<&str> = tool_messages
+            .iter()
+            .map(|m| m.tool_call_id.as_deref().unwrap())
+            .collect();
+        let expected: Vec<&str> = calls.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(tool_messages[0].content, "a");
+        assert!(tool_messages[1].content.contains("Unknown tool"));
+        assert_eq!(tool_messages[2].content, "b");
+        assert_eq!(tool_messages[3].content, "c");
+        assert_eq!(history.last().unwrap().content, "done");
+    }
+
+    /// Tool that needs approval and records the `id` of every call it runs.
+    #[derive(Default)]
+    struct RecordingTool(Arc<std::sync::Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl neuromance_tools::ToolImplementation for RecordingTool {
+        fn get_definition(&self) -> neuromance_common::tools::Tool {
+            neuromance_common::tools::Tool {
+                r#type: "function".to_string(),
+                function: neuromance_common::tools::Function {
+                    name: "record".to_string(),
+                    description: "Record id".to_string(),
+                    parameters: serde_json::json!({"type": "object"}),
+                },
+            }
+        }
+
+        async fn execute(
+            &self,
+            args: &serde_json::Value,
+        ) -> Result<String, neuromance_tools::ToolError> {
+            let id = args["id"].as_str().unwrap_or_default().to_string();
+            self.0.lock().unwrap().push(id.clone());
+            Ok(id)
+        }
+    }
+
+    /// With a 100-token budget and 60 tokens per turn, the run stops after the
+    /// second turn's tool results with a note, before a third LLM call.
+    #[tokio::test]
+    async fn test_token_budget_stops_loop_after_crossing_turn() {
+        // Every turn requests another `record` call, so only the budget ends
+        // the run.
+        let turns = (0..3).map(|turn| {
+            let call = ToolCall::new("record", format!(r#"{{"id":"{turn}"}}"#));
+            let message = assistant("").with_tool_calls(vec![call]).unwrap();
+            response(message, None, Some(usage(50, 10)))
+        });
+        let mut core = Core::new(ScriptedClient::new(turns)).with_token_budget(100);
+        core.auto_approve_tools = true;
+        let tool = RecordingTool::default();
+        let ran = Arc::clone(&tool.0);
+        core.tool_executor.add_tool(tool);
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "go")];
+        let (history, stats) = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(core.client.requests().len(), 2);
+        assert_eq!(*ran.lock().unwrap(), vec!["0", "1"]);
+        let roles: Vec<MessageRole> = history.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                MessageRole::User,
+                MessageRole::Assistant,
+                MessageRole::Tool,
+                MessageRole::Assistant,
+                MessageRole::Tool,
+                MessageRole::Assistant,
+            ]
+        );
+        assert_eq!(
+            history.last().unwrap().content,
+            "Stopped: token budget exceeded (120 tokens used, budget 100)."
+        );
+        assert_eq!(
+            history.last().unwrap().metadata[FINISH_REASON_KEY],
+            "cancelled"
+        );
+        assert_eq!(stats.cache_metrics.total_requests, 2);
+    }
+
+    /// Core scripted to request `calls` on its first turn, then reply "done".
+    fn scripted_core(calls: Vec<ToolCall>) -> Core<ScriptedClient> {
+        let first = assistant("").with_tool_calls(calls).unwrap();
+        Core::new(ScriptedClient::new([
+            response(first, None, None),
+            response(assistant("done"), None, None),
+        ]))
+    }
+
+    /// Runs `core` with a [`RecordingTool`], returning the tool messages and
+    /// the ids the tool actually ran with.
+    async fn run_recorded(mut core: Core<ScriptedClient>) -> (Vec<Message>, Vec<String>) {
+        let tool = RecordingTool::default();
+        let ran = Arc::clone(&tool.0);
+        core.tool_executor.add_tool(tool);
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "go")];
+        let (history, _) = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await
+            .unwrap();
+        let tool_messages = history
+            .into_iter()
+            .filter(|m| m.role == MessageRole::Tool)
+            .collect();
+        let ran = ran.lock().unwrap().clone();
+        (tool_messages, ran)
+    }
+
+    /// The approval handler denies one of two calls: only the approved one
+    /// runs, and the denied one gets a denial result instead.
+    #[tokio::test]
+    async fn test_approval_handler_denies_one_of_two_calls() {
+        let calls = vec![
+            ToolCall::new("record", r#"{"id":"keep"}"#),
+            ToolCall::new("record", r#"{"id":"drop"}"#),
+        ];
+        let core = scripted_core(calls).with_approval_handler(|call: &ToolCall| {
+            if call.function.arguments.contains("drop") {
+                ToolApproval::Denied("not allowed".to_string())
+            } else {
+                ToolApproval::Approved
+            }
+        });
+
+        let (tool_messages, ran) = run_recorded(core).await;
+
+        assert_eq!(ran, vec!["keep"]);
+        assert_eq!(tool_messages.len(), 2);
+        assert_eq!(tool_messages[0].content, "keep");
+        assert_eq!(
+            tool_messages[1].content,
+            "Tool execution denied: not allowed"
+        );
+    }
+
+    /// `ApprovedWithArguments` runs the tool with the approver's arguments and
+    /// rewrites the assistant's tool call to match.
+    #[tokio::test]
+    async fn test_approval_handler_can_rewrite_arguments() {
+        let calls = vec![ToolCall::new("record", r#"{"id":"original"}"#)];
+        let mut core = scripted_core(calls.clone()).with_approval_handler(|_call: &ToolCall| {
+            ToolApproval::ApprovedWithArguments(r#"{"id":"rewritten"}"#.to_string())
+        });
+        let tool = RecordingTool::default();
+        let ran = Arc::clone(&tool.0);
+        core.tool_executor.add_tool(tool);
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "go")];
+        let (history, _) = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await
+            .unwrap();
+        let ran = ran.lock().unwrap().clone();
+        let tool_messages: Vec<&Message> = history
+            .iter()
+            .filter(|m| m.role == MessageRole::Tool)
+            .collect();
+
+        let assistant = history.iter().find(|m| !m.tool_calls.is_empty()).unwrap();
+        assert_eq!(
+            assistant.tool_calls[0].function.arguments,
+            r#"{"id":"rewritten"}"#
+        );
+        let replayed = &core.client.requests()[1];
+        assert!(replayed.messages.iter().any(|m| {
+            m.tool_calls
+                .iter()
+                .any(|c| c.function.arguments == r#"{"id":"rewritten"}"#)
+        }));
+
+        assert_eq!(ran, vec!["rewritten"]);
+        assert_eq!(tool_messages[0].content, "rewritten");
+        assert_eq!(
+            tool_messages[0].tool_call_id.as_deref(),
+            Some(calls[0].id.as_str())
+        );
+    }
+
+    /// Collects audit events in memory.
+    #[derive(Default)]
+    struct CollectingSink(std::sync::Mutex<Vec<neuromance_tools::audit::ToolAuditEvent>>);
+
+    #[async_trait]
+    impl neuromance_tools::audit::AuditSink for CollectingSink {
+        async fn record(
+            &self,
+            event: &neuromance_tools::audit::ToolAuditEvent,
+        ) -> std::io::Result<()> {
+            self.0.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    /// The audit trail records the approver's actual decision: rewritten
+    /// arguments with the originals, and denials with their reason.
+    #[tokio::test]
+    async fn test_audit_records_the_approval_decision() {
+        let calls = vec![
+            ToolCall::new("record", r#"{"id":"original"}"#),
+            ToolCall::new("record", r#"{"id":"drop"}"#),
+        ];
+        let mut core = scripted_core(calls).with_approval_handler(|call: &ToolCall| {
+            if call.function.arguments.contains("drop") {
+                ToolApproval::Denied("not allowed".to_string())
+            } else {
+                ToolApproval::ApprovedWithArguments(r#"{"id":"rewritten"}"#.to_string())
+            }
+        });
+        let sink = Arc::new(CollectingSink::default());
+        core.tool_executor
+            .add_audit_sink(Arc::clone(&sink) as Arc<dyn neuromance_tools::audit::AuditSink>);
+
+        run_recorded(core).await;
+
+        let events = sink.0.lock().unwrap().clone();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].arguments, r#"{"id":"rewritten"}"#);
+        assert_eq!(
+            events[0].approval,
+            AuditApproval::ApprovedWithArguments {
+                original_arguments: r#"{"id":"original"}"#.to_string()
+            }
+        );
+        assert_eq!(
+            events[1].approval,
+            AuditApproval::Denied {
+                reason: "not allowed".to_string()
+            }
+        );
+    }
+
+    /// Oversized tool output is cut on a char boundary before it becomes the
+    /// tool message, with a notice saying how much was kept.
+    #[tokio::test]
+    async fn test_max_tool_result_bytes_truncates_tool_message() {
+        let id = "é".repeat(50);
+        let calls = vec![ToolCall::new("record", format!(r#"{{"id":"{id}"}}"#))];
+        let mut core =
+            scripted_core(calls).with_approval_handler(|_call: &ToolCall| ToolApproval::Approved);
+        core.tool_executor.set_max_tool_result_bytes(9);
+
+        let (tool_messages, ran) = run_recorded(core).await;
+
+        assert_eq!(ran, vec![id]);
+        assert_eq!(
+            tool_messages[0].content,
+            "éééé\n[truncated: showing 8 of 100 bytes]"
+        );
+    }
+
+    /// An async handler is awaited per call and can decide by tool name.
+    #[tokio::test]
+    async fn test_async_approval_handler_approves_by_tool_name() {
+        let calls = vec![
+            ToolCall::new("record", r#"{"id":"a"}"#),
+            ToolCall::new("shell", r#"{"cmd":"rm -rf /"}"#),
+            ToolCall::new("record", r#"{"id":"b"}"#),
+        ];
+        let core = scripted_core(calls).with_async_approval_handler(|call: &ToolCall| {
+            let name = call.function.name.clone();
+            async move {
+                // Stand-in for awaiting a user's answer.
+                tokio::task::yield_now().await;
+                if name == "record" {
+                    ToolApproval::Approved
+                } else {
+                    ToolApproval::Denied(format!("{name} needs review"))
+                }
+            }
+        });
+
+        let (tool_messages, ran) = run_recorded(core).await;
+
+        assert_eq!(ran, vec!["a", "b"]);
+        let contents: Vec<&str> = tool_messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec!["a", "Tool execution denied: shell needs review", "b"]
+        );
+    }
+
     /// A hook reporting compaction surfaces as a `CoreEvent::Compaction` on the
     /// no-tool completion path, before `Completed`.
     #[tokio::test]
     async fn test_run_emits_compaction_event_on_completion_path() {
-        let mock_config = Config::new("mock", "mock-model");
-        let mut core = Core::new(HugeUsageClient {
-            config: mock_config,
-        })
-        .with_hook(Arc::new(CompactingHook));
+        let reply = response(
+            assistant("summary or reply"),
+            None,
+            Some(usage(200_000, 10)),
+        );
+        let mut core = Core::new(ScriptedClient::new([reply])).with_hook(Arc::new(CompactingHook));
 
         let conv_id = uuid::Uuid::new_v4();
         let messages = vec![
@@ -1374,4 +2634,181 @@ mod tests {
         assert!(matches!(err, CoreError::Hook { .. }));
         assert!(err.to_string().contains("boom"));
     }
+
+    /// Each strategy reshapes only the outgoing request: `Appended` adds the
+    /// short reminder and `None` drops every instruction message.
+    #[tokio::test]
+    async fn test_system_prompt_strategy_shapes_request_messages() {
+        use MessageRole::{Assistant, Developer, System, User};
+
+        let config = Config::new("openai", "gpt-4o")
+            .with_api_key("test-key")
+            .with_base_url("http://127.0.0.1:9");
+        let conversation_id = uuid::Uuid::new_v4();
+        let messages = vec![
+            Message::system(conversation_id, "Be terse."),
+            Message::developer(conversation_id, "Answer in English."),
+            Message::user(conversation_id, "hi"),
+            Message::assistant(conversation_id, "hello"),
+            Message::user(conversation_id, "bye"),
+        ];
+
+        for (strategy, expected) in [
+            (
+                SystemPromptStrategy::First,
+                vec![System, Developer, User, Assistant, User],
+            ),
+            (
+                SystemPromptStrategy::Appended,
+                vec![System, Developer, User, Assistant, User, System],
+            ),
+            (SystemPromptStrategy::None, vec![User, Assistant, User]),
+        ] {
+            let client =
+                ChatCompletionsClient::new(config.clone()).expect("Failed to create client");
+            let mut core = Core::new(client)
+                .with_dry_run()
+                .with_system_prompt_strategy(strategy);
+
+            let (history, _) = core
+                .chat_with_tool_loop(messages.clone(), CancellationToken::new())
+                .await
+                .unwrap();
+
+            // The history itself is untouched.
+            assert_eq!(history[0].role, System);
+            let sent: ChatRequest = serde_json::from_value(
+                history.last().unwrap().metadata[DRY_RUN_REQUEST_KEY].clone(),
+            )
+            .unwrap();
+            let roles: Vec<MessageRole> = sent.messages.iter().map(|m| m.role).collect();
+            assert_eq!(roles, expected, "{strategy:?}");
+            if strategy == SystemPromptStrategy::Appended {
+                assert_eq!(sent.messages[5].content, SYSTEM_PROMPT_REMINDER);
+            }
+        }
+    }
+
+    /// A dry run never reaches the provider: the canned reply carries the
+    /// request and the client's wire payload instead.
+    #[tokio::test]
+    async fn test_dry_run_captures_request_without_sending() {
+        // Nothing listens on the discard port, so a real send would fail.
+        let config = Config::new("openai", "gpt-4o")
+            .with_api_key("test-key")
+            .with_base_url("http://127.0.0.1:9");
+        let client = ChatCompletionsClient::new(config).expect("Failed to create client");
+        let mut core = Core::new(client).with_dry_run();
+
+        let conversation_id = uuid::Uuid::new_v4();
+        let messages = vec![Message::user(conversation_id, "what would you send?")];
+        let (history, _) = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(history.len(), 2);
+        let reply = &history[1];
+        assert_eq!(reply.role, MessageRole::Assistant);
+        assert!(reply.tool_calls.is_empty());
+
+        let captured: ChatRequest =
+            serde_json::from_value(reply.metadata[DRY_RUN_REQUEST_KEY].clone()).unwrap();
+        assert_eq!(captured.messages.len(), 1);
+        assert_eq!(captured.messages[0].content, "what would you send?");
+
+        let payload = &reply.metadata[DRY_RUN_PAYLOAD_KEY];
+        assert_eq!(payload["model"], "gpt-4o");
+        assert_eq!(payload["messages"][0]["content"], "what would you send?");
+    }
+
+    fn content_chunk(text: &str, finish_reason: Option<FinishReason>) -> ChatChunk {
+        ChatChunk {
+            model: "mock-model".to_string(),
+            delta_content: Some(text.to_string()),
+            delta_reasoning_content: None,
+            delta_role: Some(MessageRole::Assistant),
+            delta_tool_calls: None,
+            finish_reason,
+            usage: None,
+            response_id: None,
+            created_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Streams "hel" + "lo". The first `fail_attempts` attempts fail with a
+    /// retryable error after `fail_after` chunks of the script.
+    fn flaky_stream_client(fail_attempts: usize, fail_after: usize) -> ScriptedClient {
+        let script = || {
+            vec![
+                Ok(content_chunk("hel", None)),
+                Ok(content_chunk("lo", Some(FinishReason::Stop))),
+            ]
+        };
+        let failing = (0..fail_attempts).map(|_| {
+            let mut chunks = script();
+            chunks.truncate(fail_after);
+            chunks.push(Err(ClientError::ServiceUnavailable(
+                "overloaded".to_string(),
+            )));
+            chunks
+        });
+        let mut client = ScriptedClient::streaming(failing.chain([script()]));
+        client.config.retry_config.initial_delay = Duration::from_millis(1);
+        client
+    }
+
+    #[tokio::test]
+    async fn test_stream_retries_when_failing_before_content() {
+        let mut core = Core::new(flaky_stream_client(1, 0)).with_streaming();
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "hi")];
+        let (history, _) = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(history.last().unwrap().content, "hello");
+        assert_eq!(core.client.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_does_not_retry_after_content() {
+        let mut core = Core::new(flaky_stream_client(1, 1)).with_streaming();
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "hi")];
+        let result = core
+            .chat_with_tool_loop(messages, CancellationToken::new())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(CoreError::Client(ClientError::ServiceUnavailable(_)))
+        ));
+        assert_eq!(core.client.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_mid_stream_keeps_partial_reply() {
+        let mut core = Core::new(flaky_stream_client(0, 0)).with_streaming();
+        let cancel = CancellationToken::new();
+
+        let messages = vec![Message::user(uuid::Uuid::new_v4(), "hi")];
+        let mut stream = Box::pin(core.run(messages, cancel.clone()));
+        let mut history = None;
+        while let Some(event) = stream.next().await {
+            match event.unwrap() {
+                CoreEvent::Delta(_) => cancel.cancel(),
+                CoreEvent::Completed(messages) => history = Some(messages),
+                _ => {}
+            }
+        }
+
+        let history = history.unwrap();
+        let reply = history.last().unwrap();
+        assert_eq!(reply.role, MessageRole::Assistant);
+        assert_eq!(reply.content, "hel");
+        assert_eq!(reply.metadata[FINISH_REASON_KEY], "cancelled");
+    }
 }
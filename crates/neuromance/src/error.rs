@@ -37,6 +37,14 @@ pub enum CoreError {
         source: anyhow::Error,
     },
 
+    #[error("Request of ~{estimated} tokens exceeds the {limit}-token context window")]
+    ContextWindowExceeded {
+        /// Estimated tokens in the request.
+        estimated: usize,
+        /// The model's context window in tokens.
+        limit: usize,
+    },
+
     #[error("Context compaction error: {0}")]
     CompactionError(String),
 }
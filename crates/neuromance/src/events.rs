@@ -29,6 +29,13 @@ pub enum CoreEvent {
     /// Streaming content chunk received from the LLM.
     Delta(String),
 
+    /// Streaming reasoning chunk received from a thinking model.
+    ReasoningDelta(String),
+
+    /// The model requested a tool call. Yielded before the call is reviewed,
+    /// so it precedes any [`CoreEvent::ApprovalRequest`] for the same call.
+    ToolCallRequested(ToolCall),
+
     /// A tool finished executing.
     ToolResult {
         /// Name of the tool that was executed.
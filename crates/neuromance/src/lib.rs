@@ -48,7 +48,7 @@ pub mod events;
 pub mod stats;
 
 // --- Orchestration ---
-pub use crate::core::Core;
+pub use crate::core::{ContextOverflow, Core, SystemPromptStrategy};
 pub use crate::error::CoreError;
 pub use crate::events::CoreEvent;
 pub use crate::stats::RunStats;
@@ -60,8 +60,9 @@ pub use neuromance_client::{
 
 // --- Config, request, response ---
 pub use neuromance_common::client::{
-    CacheMetrics, ChatChunk, ChatRequest, ChatResponse, Config, FinishReason, InputTokensDetails,
-    OutputTokensDetails, Provider, ProxyConfig, ReasoningEffort, RetryConfig, ToolChoice, Usage,
+    BackoffStrategy, CacheMetrics, ChatChunk, ChatRequest, ChatResponse, Config, FinishReason,
+    InputTokensDetails, OutputTokensDetails, Provider, ProxyConfig, ReasoningEffort, RetryConfig,
+    ToolChoice, Usage,
 };
 
 // --- Chat primitives ---
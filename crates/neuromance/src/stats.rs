@@ -51,6 +51,8 @@ impl RunStats {
                 self.compaction_tokens_saved += u64::try_from(saved).unwrap_or(u64::MAX);
             }
             CoreEvent::Delta(_)
+            | CoreEvent::ReasoningDelta(_)
+            | CoreEvent::ToolCallRequested(_)
             | CoreEvent::ApprovalRequest { .. }
             | CoreEvent::Completed(_)
             | CoreEvent::Compaction { .. } => {}